@@ -5,8 +5,8 @@
 #![allow(clippy::blocks_in_if_conditions)]
 #![allow(dead_code)]
 #![feature(proc_macro_hygiene, decl_macro, option_result_contains, vec_remove_item, test)]
-#![feature(with_options)]
 #![feature(box_patterns)]
+extern crate event_storage;
 extern crate language;
 extern crate mail;
 extern crate okapi;
@@ -40,6 +40,7 @@ use rocket_prometheus::PrometheusMetrics;
 
 use crate::modules::{account, armory, data, instance, live_data_processor, tooltip, utility};
 use rocket_contrib::databases::mysql::Opts;
+use std::sync::Arc;
 
 #[cfg(test)]
 mod tests;
@@ -50,6 +51,9 @@ mod modules;
 mod rocket_impl;
 mod util;
 
+// Used if UPLOAD_WORKER_COUNT is not set
+const DEFAULT_UPLOAD_WORKER_COUNT: usize = 2;
+
 #[database("main")]
 pub struct MainDb(mysql::Conn);
 
@@ -58,17 +62,28 @@ fn main() {
     let dns = std::env::var("MYSQL_URL").unwrap();
     let opts = Opts::from_url(&dns).unwrap();
     let mut conn = mysql::Conn::new(opts.clone()).unwrap();
-    let instance_conn = mysql::Conn::new(opts).unwrap();
+    let instance_conn = mysql::Conn::new(opts.clone()).unwrap();
 
     let account = account::Account::default().init(&mut conn);
-    let data = data::Data::default().init(&mut conn);
+    // Shared with the upload workers, which run outside of Rocket
+    let data = Arc::new(data::Data::default().init(&mut conn));
     let armory = armory::Armory::default().init(&mut conn);
     let tooltip = tooltip::Tooltip::default();
-    let live_data_processor = live_data_processor::LiveDataProcessor::default().init(&mut conn);
+    let upload_worker_count = std::env::var("UPLOAD_WORKER_COUNT")
+        .map(|count| count.parse::<usize>().expect("upload worker count must be a number"))
+        .unwrap_or(DEFAULT_UPLOAD_WORKER_COUNT);
+    let live_data_processor = live_data_processor::LiveDataProcessor::default()
+        .with_upload_storage(std::env::var("UPLOAD_STORAGE_PATH").expect("upload storage path must be set"))
+        .init(&mut conn)
+        .restore_upload_sessions()
+        .init_server_snapshots()
+        .init_server_metrics()
+        .init_upload_workers(opts, Arc::clone(&data), &armory, upload_worker_count);
     let instance = instance::Instance::default().init(instance_conn, &armory);
     let utility = utility::Utility::default();
 
     let prometheus = PrometheusMetrics::new();
+    live_data_processor.metrics.register(prometheus.registry());
 
     let mut swagger_ui_config = SwaggerUIConfig::default();
     swagger_ui_config.urls = Some(vec![
@@ -124,7 +139,10 @@ fn main() {
                 account::transfer::create::confirm,
                 account::transfer::create::resend_confirm,
                 account::transfer::get::get_account_information,
-                account::transfer::forgot::receive_confirmation,
+                account::transfer::role::grant_role,
+                account::transfer::role::revoke_role,
+                account::transfer::role::get_audit_log,
+                account::transfer::forgot::reset_password,
                 account::transfer::forgot::send_confirmation,
                 account::transfer::update::request_mail,
                 account::transfer::update::confirm_mail,
@@ -210,6 +228,10 @@ fn main() {
                 data::transfer::encounter::get_all_encounters_localized,
                 data::transfer::encounter_npc::get_encounter_npc,
                 data::transfer::encounter_npc::get_all_encounter_npcs,
+                data::transfer::encounter_rule::get_encounter_rules,
+                data::transfer::encounter_rule::get_all_encounter_rules,
+                data::transfer::ranking_bracket::get_ranking_bracket,
+                data::transfer::ranking_bracket::get_all_ranking_brackets,
             ],
         )
         .mount(
@@ -256,12 +278,20 @@ fn main() {
                 live_data_processor::transfer::package::get_package,
                 live_data_processor::transfer::instance_reset::set_instance_resets,
                 live_data_processor::transfer::upload::upload_log,
+                live_data_processor::transfer::upload::validate_log,
+                live_data_processor::transfer::upload::open_upload_session,
+                live_data_processor::transfer::upload::get_upload_session,
+                live_data_processor::transfer::upload::upload_chunk,
+                live_data_processor::transfer::upload::finalize_upload_session,
+                live_data_processor::transfer::upload::get_upload_jobs,
+                live_data_processor::transfer::upload::get_upload_job,
             ],
         )
         .mount(
             "/API/instance",
             routes_with_openapi![
                 instance::transfer::export::get_instance_event_type,
+                instance::transfer::export::get_instance_events,
                 instance::transfer::export::get_instance_meta,
                 instance::transfer::export::get_instance_participants,
                 instance::transfer::export::get_instance_attempts,
@@ -277,7 +307,16 @@ fn main() {
                 instance::transfer::ranking::get_instance_ranking_hps,
                 instance::transfer::ranking::get_instance_ranking_tps,
                 instance::transfer::ranking::get_character_ranking,
+                instance::transfer::ranking::search_rankings,
+                instance::transfer::ranking::get_character_ranking_history,
                 instance::transfer::delete::delete_instance,
+                instance::transfer::delete::moderate_instance,
+                instance::transfer::analytics::get_instance_meters,
+                instance::transfer::death_recap::get_death_recaps,
+                instance::transfer::aura_uptime::get_aura_uptimes,
+                instance::transfer::interrupt_report::get_interrupt_report,
+                instance::transfer::progression::get_guild_progressions,
+                instance::transfer::progression::get_speed_runs,
             ],
         )
         .mount("/API/utility", routes_with_openapi![utility::transfer::tiny_url::get_tiny_url, utility::transfer::tiny_url::set_tiny_url])
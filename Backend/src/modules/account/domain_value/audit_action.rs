@@ -0,0 +1,43 @@
+use crate::modules::account::domain_value::Role;
+use schemars::JsonSchema;
+
+/// Administrative and moderative actions that are recorded in the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum AuditAction {
+    GrantRole { member_id: u32, role: Role },
+    RevokeRole { member_id: u32, role: Role },
+    DeleteInstance { instance_meta_id: u32 },
+    DeleteCharacter { character_id: u32 },
+    DeleteCharacterHistory { character_history_id: u32 },
+    DeleteGuild { guild_id: u32 },
+    ReloadServers,
+}
+
+impl AuditAction {
+    /// (action, target_id, role) as they are stored in account_audit_log
+    pub fn to_row(&self) -> (u8, u64, Option<u8>) {
+        match *self {
+            AuditAction::GrantRole { member_id, role } => (0, member_id as u64, Some(role as u8)),
+            AuditAction::RevokeRole { member_id, role } => (1, member_id as u64, Some(role as u8)),
+            AuditAction::DeleteInstance { instance_meta_id } => (2, instance_meta_id as u64, None),
+            AuditAction::DeleteCharacter { character_id } => (3, character_id as u64, None),
+            AuditAction::DeleteCharacterHistory { character_history_id } => (4, character_history_id as u64, None),
+            AuditAction::DeleteGuild { guild_id } => (5, guild_id as u64, None),
+            AuditAction::ReloadServers => (6, 0, None),
+        }
+    }
+
+    pub fn from_row(action: u8, target_id: u64, role: Option<u8>) -> Option<AuditAction> {
+        let target_id = target_id as u32;
+        match action {
+            0 => role.and_then(Role::from_u8).map(|role| AuditAction::GrantRole { member_id: target_id, role }),
+            1 => role.and_then(Role::from_u8).map(|role| AuditAction::RevokeRole { member_id: target_id, role }),
+            2 => Some(AuditAction::DeleteInstance { instance_meta_id: target_id }),
+            3 => Some(AuditAction::DeleteCharacter { character_id: target_id }),
+            4 => Some(AuditAction::DeleteCharacterHistory { character_history_id: target_id }),
+            5 => Some(AuditAction::DeleteGuild { guild_id: target_id }),
+            6 => Some(AuditAction::ReloadServers),
+            _ => None,
+        }
+    }
+}
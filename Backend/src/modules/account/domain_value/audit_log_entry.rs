@@ -0,0 +1,11 @@
+use crate::modules::account::domain_value::AuditAction;
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct AuditLogEntry {
+    pub id: u32,
+    // The member that performed the action, None if it was deleted since
+    pub member_id: Option<u32>,
+    pub action: AuditAction,
+    pub timestamp: u64,
+}
@@ -1,3 +1,11 @@
 pub use self::account_information::AccountInformation;
+pub use self::audit_action::AuditAction;
+pub use self::audit_log_entry::AuditLogEntry;
+pub use self::role::Role;
+pub use self::token_scope::TokenScope;
 
 mod account_information;
+mod audit_action;
+mod audit_log_entry;
+mod role;
+mod token_scope;
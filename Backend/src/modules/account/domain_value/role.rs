@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+
+/// Roles of a member, stored as a bit mask of the discriminants in access_rights
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum Role {
+    // Implies every other role
+    Admin = 0,
+    Moderator = 1,
+    ServerOwner = 2,
+    Uploader = 3,
+}
+
+impl Role {
+    pub fn all() -> Vec<Role> {
+        vec![Role::Admin, Role::Moderator, Role::ServerOwner, Role::Uploader]
+    }
+
+    pub fn to_flag(self) -> u32 {
+        1 << (self as u32)
+    }
+
+    pub fn from_flags(flags: u32) -> Vec<Role> {
+        Role::all().into_iter().filter(|role| flags & role.to_flag() != 0).collect()
+    }
+
+    pub fn from_u8(role: u8) -> Option<Role> {
+        Role::all().into_iter().find(|entry| *entry as u8 == role)
+    }
+}
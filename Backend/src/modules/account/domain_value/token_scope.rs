@@ -0,0 +1,31 @@
+use schemars::JsonSchema;
+
+/// Permissions of an API token, stored as a bit mask of the discriminants
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum TokenScope {
+    // Managing the account itself, including its tokens
+    Account = 0,
+    ReadOnly = 1,
+    UploadLog = 2,
+    DeleteInstance = 3,
+    ManageArmory = 4,
+    ServerPackage = 5,
+}
+
+impl TokenScope {
+    pub fn all() -> Vec<TokenScope> {
+        vec![TokenScope::Account, TokenScope::ReadOnly, TokenScope::UploadLog, TokenScope::DeleteInstance, TokenScope::ManageArmory, TokenScope::ServerPackage]
+    }
+
+    pub fn to_flag(self) -> u32 {
+        1 << (self as u32)
+    }
+
+    pub fn to_flags(scopes: &[TokenScope]) -> u32 {
+        scopes.iter().fold(0, |flags, scope| flags | scope.to_flag())
+    }
+
+    pub fn from_flags(flags: u32) -> Vec<TokenScope> {
+        TokenScope::all().into_iter().filter(|scope| flags & scope.to_flag() != 0).collect()
+    }
+}
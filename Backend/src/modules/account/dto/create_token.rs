@@ -1,7 +1,10 @@
+use crate::modules::account::domain_value::TokenScope;
 use schemars::JsonSchema;
 
 #[derive(Deserialize, Serialize, Debug, JsonSchema)]
 pub struct CreateToken {
     pub purpose: String,
+    // All scopes if none are specified
+    pub scopes: Option<Vec<TokenScope>>,
     pub exp_date: u64,
 }
@@ -12,7 +12,6 @@ pub enum Failure {
     InvalidNickname,
     PwnedPassword(u64),
     PasswordTooShort,
-    InvalidPasswordCharacters,
     MailIsInUse,
     NicknameIsInUse,
     InvalidUrl,
@@ -22,6 +21,9 @@ pub enum Failure {
     TooManyDays,
     DateInThePast,
     TokenPurposeLength,
+    InvalidTokenScopes,
+    UnknownMember,
+    LastAdmin,
     Unknown,
 }
 
@@ -46,7 +48,9 @@ impl<'r> Responder<'r> for Failure {
             Failure::TooManyDays => Status::new(531, "TooManyDays"),
             Failure::DateInThePast => Status::new(532, "DateInThePast"),
             Failure::TokenPurposeLength => Status::new(533, "TokenPurposeLength"),
-            Failure::InvalidPasswordCharacters => Status::new(535, "InvalidPasswordCharacters"),
+            Failure::InvalidTokenScopes => Status::new(534, "InvalidTokenScopes"),
+            Failure::UnknownMember => Status::new(535, "UnknownMember"),
+            Failure::LastAdmin => Status::new(536, "LastAdmin"),
             Failure::Unknown => Status::new(599, "Unknown"),
         };
         Response::build().status(status).sized_body(Cursor::new(body)).ok()
@@ -71,7 +75,9 @@ impl<'r> OpenApiResponder<'r> for Failure {
         add_schema_response(&mut responses, 531, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 532, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 533, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 534, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 535, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 536, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 599, "text/plain", schema)?;
         Ok(responses)
     }
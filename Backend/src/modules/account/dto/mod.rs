@@ -1,7 +1,8 @@
-pub use self::{create_member::CreateMember, create_token::CreateToken, credentials::Credentials, failure::Failure, prolong_token::ProlongToken};
+pub use self::{create_member::CreateMember, create_token::CreateToken, credentials::Credentials, failure::Failure, prolong_token::ProlongToken, update_role::UpdateRole};
 
 mod create_member;
 mod create_token;
 mod credentials;
 mod failure;
 mod prolong_token;
+mod update_role;
@@ -0,0 +1,8 @@
+use crate::modules::account::domain_value::Role;
+use schemars::JsonSchema;
+
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
+pub struct UpdateRole {
+    pub member_id: u32,
+    pub role: Role,
+}
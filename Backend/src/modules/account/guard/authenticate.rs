@@ -7,13 +7,15 @@ use rocket::{
     Response,
 };
 use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder, util::add_schema_response};
+use std::marker::PhantomData;
 
-use crate::modules::account::{tools::Token, Account};
+use crate::modules::account::{guard::scope::RequiredScope, tools::Token, Account};
 use crate::MainDb;
 
-pub struct Authenticate(pub u32);
+/// Routes declare the scope that the API token must have, e.g. Authenticate<scope::UploadLog>
+pub struct Authenticate<S: RequiredScope>(pub u32, PhantomData<S>);
 
-impl<'a, 'r> FromRequest<'a, 'r> for Authenticate {
+impl<'a, 'r, S: RequiredScope> FromRequest<'a, 'r> for Authenticate<S> {
     type Error = ();
 
     fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
@@ -35,12 +37,12 @@ impl<'a, 'r> FromRequest<'a, 'r> for Authenticate {
 
         let acc_res = account.unwrap();
         let mut db_main = db_main.unwrap();
-        let validation = acc_res.validate_token(&mut *db_main, api_token);
+        let validation = acc_res.validate_token_scope(&mut *db_main, api_token, S::required_scope());
         if validation.is_none() {
             return Failure((Status::Unauthorized, ()));
         }
 
-        Success(Authenticate(validation.unwrap()))
+        Success(Authenticate(validation.unwrap(), PhantomData))
     }
 }
 
@@ -69,13 +71,13 @@ impl<'a, 'r> OpenApiFromRequest<'a, 'r> for Authenticate {
  */
 // This implementation is required from OpenAPI, it does nothing here
 // and is not supposed to be used!
-impl Responder<'static> for Authenticate {
+impl<S: RequiredScope> Responder<'static> for Authenticate<S> {
     fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
         Response::build().status(Status::Unauthorized).ok()
     }
 }
 
-impl OpenApiResponder<'static> for Authenticate {
+impl<S: RequiredScope> OpenApiResponder<'static> for Authenticate<S> {
     fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
         let mut responses = Responses::default();
         let schema = gen.json_schema::<String>();
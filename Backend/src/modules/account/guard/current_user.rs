@@ -8,7 +8,7 @@ use rocket::{
 };
 use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder};
 
-use crate::modules::account::{tools::Token, Account};
+use crate::modules::account::{domain_value::TokenScope, tools::Token, Account};
 use crate::MainDb;
 
 pub struct CurrentUser(pub Option<u32>);
@@ -35,7 +35,7 @@ impl<'a, 'r> FromRequest<'a, 'r> for CurrentUser {
 
         let acc_res = account.unwrap();
         let mut db_main = db_main.unwrap();
-        let validation = acc_res.validate_token(&mut *db_main, api_token);
+        let validation = acc_res.validate_token_scope(&mut *db_main, api_token, TokenScope::ReadOnly);
         if validation.is_none() {
             return Success(CurrentUser(None));
         }
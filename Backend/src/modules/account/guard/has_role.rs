@@ -0,0 +1,55 @@
+use okapi::openapi3::Responses;
+use rocket::{
+    http::Status,
+    outcome::Outcome::*,
+    request::{self, FromRequest, Request, State},
+    response::Responder,
+    Response,
+};
+use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder, util::add_schema_response};
+use std::marker::PhantomData;
+
+use crate::modules::account::{
+    guard::{role::RequiredRole, scope::RequiredScope, Authenticate},
+    tools::Roles,
+    Account,
+};
+
+/// Routes declare the role that the member must have, e.g. HasRole<role::Moderator, scope::DeleteInstance>
+pub struct HasRole<R: RequiredRole, S: RequiredScope>(pub u32, PhantomData<(R, S)>);
+
+impl<'a, 'r, R: RequiredRole, S: RequiredScope> FromRequest<'a, 'r> for HasRole<R, S> {
+    type Error = ();
+
+    fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        Authenticate::<S>::from_request(req).and_then(|authenticate| {
+            let account = req.guard::<State<'_, Account>>();
+            if account.is_failure() {
+                return Failure((Status::Unauthorized, ()));
+            }
+
+            if !account.unwrap().has_role(authenticate.0, R::required_role()) {
+                return Failure((Status::Unauthorized, ()));
+            }
+
+            Success(HasRole(authenticate.0, PhantomData))
+        })
+    }
+}
+
+// This implementation is required from OpenAPI, it does nothing here
+// and is not supposed to be used!
+impl<R: RequiredRole, S: RequiredScope> Responder<'static> for HasRole<R, S> {
+    fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
+        Response::build().status(Status::Unauthorized).ok()
+    }
+}
+
+impl<R: RequiredRole, S: RequiredScope> OpenApiResponder<'static> for HasRole<R, S> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = Responses::default();
+        let schema = gen.json_schema::<String>();
+        add_schema_response(&mut responses, 401, "text/plain", schema)?;
+        Ok(responses)
+    }
+}
@@ -1,5 +1,8 @@
-pub use self::{authenticate::Authenticate, server_owner::ServerOwner, current_user::CurrentUser};
+pub use self::{authenticate::Authenticate, has_role::HasRole, server_owner::ServerOwner, current_user::CurrentUser};
 
 mod authenticate;
+mod has_role;
 mod server_owner;
-mod current_user;
\ No newline at end of file
+mod current_user;
+pub mod role;
+pub mod scope;
@@ -0,0 +1,35 @@
+use crate::modules::account::domain_value::Role;
+
+/// The role that the member of a guarded route must have
+pub trait RequiredRole {
+    fn required_role() -> Role;
+}
+
+pub struct Admin;
+pub struct Moderator;
+pub struct ServerOwner;
+pub struct Uploader;
+
+impl RequiredRole for Admin {
+    fn required_role() -> Role {
+        Role::Admin
+    }
+}
+
+impl RequiredRole for Moderator {
+    fn required_role() -> Role {
+        Role::Moderator
+    }
+}
+
+impl RequiredRole for ServerOwner {
+    fn required_role() -> Role {
+        Role::ServerOwner
+    }
+}
+
+impl RequiredRole for Uploader {
+    fn required_role() -> Role {
+        Role::Uploader
+    }
+}
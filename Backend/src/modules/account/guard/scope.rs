@@ -0,0 +1,49 @@
+use crate::modules::account::domain_value::TokenScope;
+
+/// The scope that the API token of a guarded route must have
+pub trait RequiredScope {
+    fn required_scope() -> TokenScope;
+}
+
+pub struct Account;
+pub struct ReadOnly;
+pub struct UploadLog;
+pub struct DeleteInstance;
+pub struct ManageArmory;
+pub struct ServerPackage;
+
+impl RequiredScope for Account {
+    fn required_scope() -> TokenScope {
+        TokenScope::Account
+    }
+}
+
+impl RequiredScope for ReadOnly {
+    fn required_scope() -> TokenScope {
+        TokenScope::ReadOnly
+    }
+}
+
+impl RequiredScope for UploadLog {
+    fn required_scope() -> TokenScope {
+        TokenScope::UploadLog
+    }
+}
+
+impl RequiredScope for DeleteInstance {
+    fn required_scope() -> TokenScope {
+        TokenScope::DeleteInstance
+    }
+}
+
+impl RequiredScope for ManageArmory {
+    fn required_scope() -> TokenScope {
+        TokenScope::ManageArmory
+    }
+}
+
+impl RequiredScope for ServerPackage {
+    fn required_scope() -> TokenScope {
+        TokenScope::ServerPackage
+    }
+}
@@ -7,17 +7,23 @@ use rocket::{
     Response,
 };
 use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder, util::add_schema_response};
+use std::marker::PhantomData;
 
-use crate::modules::{account::guard::Authenticate, data::Data};
+use crate::modules::{
+    account::guard::{scope::RequiredScope, Authenticate},
+    data::Data,
+};
+use std::sync::Arc;
 
-pub struct ServerOwner(pub u32);
+/// Members that own a server, which implies the server owner role
+pub struct ServerOwner<S: RequiredScope>(pub u32, PhantomData<S>);
 
-impl<'a, 'r> FromRequest<'a, 'r> for ServerOwner {
+impl<'a, 'r, S: RequiredScope> FromRequest<'a, 'r> for ServerOwner<S> {
     type Error = ();
 
     fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
-        Authenticate::from_request(req).and_then(|authenticate| {
-            let data_req = req.guard::<State<'_, Data>>();
+        Authenticate::<S>::from_request(req).and_then(|authenticate| {
+            let data_req = req.guard::<State<'_, Arc<Data>>>();
             if data_req.is_failure() {
                 return Failure((Status::Unauthorized, ()));
             }
@@ -30,20 +36,20 @@ impl<'a, 'r> FromRequest<'a, 'r> for ServerOwner {
             }
 
             let (id, _) = server_res.unwrap();
-            Success(ServerOwner(*id))
+            Success(ServerOwner(*id, PhantomData))
         })
     }
 }
 
 // This implementation is required from OpenAPI, it does nothing here
 // and is not supposed to be used!
-impl Responder<'static> for ServerOwner {
+impl<S: RequiredScope> Responder<'static> for ServerOwner<S> {
     fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
         Response::build().status(Status::Unauthorized).ok()
     }
 }
 
-impl OpenApiResponder<'static> for ServerOwner {
+impl<S: RequiredScope> OpenApiResponder<'static> for ServerOwner<S> {
     fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
         let mut responses = Responses::default();
         let schema = gen.json_schema::<String>();
@@ -14,10 +14,14 @@ pub fn init(dictionary: &Dictionary) {
     dictionary.register(
         "forgot.confirmation.text",
         Language::English,
-        "Greetings!\nPlease click on the provided url in order to generate a new password.\n\n{HOST}/confirm/forgot/{0}\n\nCheers!",
+        "Greetings!\nPlease click on the provided url in order to choose a new password. The url expires in one hour and can only be used once.\n\n{HOST}/confirm/forgot/{0}\n\nIf you did not request this, you can ignore this mail.\n\nCheers!",
+    );
+    dictionary.register("forgot.information.subject", Language::English, "Your password has been changed!");
+    dictionary.register(
+        "forgot.information.text",
+        Language::English,
+        "Greetings!\n\nThe password of your account has been changed and all of your sessions have been signed out.\n\nCheers!",
     );
-    dictionary.register("forgot.information.subject", Language::English, "Your new password!");
-    dictionary.register("forgot.information.text", Language::English, "Greetings!\n\nThis is your new Password: {0}\n\nPlease change it immediately!\n\nCheers!");
 
     dictionary.register("delete.confirmation.subject", Language::English, "Confirm the deletion of your account!");
     dictionary.register(
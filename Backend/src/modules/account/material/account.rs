@@ -5,8 +5,9 @@ use language::material::Dictionary;
 use str_util::sha3;
 
 use crate::modules::account::{
+    domain_value::TokenScope,
     language::init::Init,
-    material::{APIToken, Member},
+    material::{APIToken, Member, PasswordReset},
 };
 
 #[derive(Debug)]
@@ -16,6 +17,8 @@ pub struct Account {
     pub api_token_to_member_id: RwLock<HashMap<String, u32>>,
     pub api_tokens: RwLock<HashMap<u32, Vec<APIToken>>>,
     pub requires_mail_confirmation: RwLock<HashMap<String, u32>>,
+    // Hashed reset token => PasswordReset
+    pub password_resets: RwLock<HashMap<String, PasswordReset>>,
 }
 
 // Important: Always lock resources bottom to too, in order to prevent running into a deadlock
@@ -30,6 +33,7 @@ impl Default for Account {
             api_tokens: RwLock::new(HashMap::new()),
             api_token_to_member_id: RwLock::new(HashMap::new()),
             requires_mail_confirmation: RwLock::new(HashMap::new()),
+            password_resets: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -37,6 +41,7 @@ impl Default for Account {
 impl Account {
     pub fn init(self, db_main: &mut (impl Select + Execute)) -> Self {
         {
+            let mut password_resets = self.password_resets.write().unwrap();
             let mut requires_mail_confirmation = self.requires_mail_confirmation.write().unwrap();
             let mut api_token_to_member_id = self.api_token_to_member_id.write().unwrap();
             let mut api_token = self.api_tokens.write().unwrap();
@@ -44,23 +49,20 @@ impl Account {
 
             // Cleaning first
             db_main.execute_one("DELETE FROM account_api_token WHERE exp_date < UNIX_TIMESTAMP()");
+            db_main.execute_one("DELETE FROM account_password_reset WHERE exp_date < UNIX_TIMESTAMP()");
 
             // We are a little wasteful here because we do not insert it directly but rather create a vector first and then copy it over
-            for entry in db_main.select(
-                "SELECT id, nickname, mail, password, salt, mail_confirmed, forgot_password, delete_account, new_mail, access_rights FROM account_member",
-                |mut row| Member {
-                    id: row.take(0).unwrap(),
-                    nickname: row.take(1).unwrap(),
-                    mail: row.take(2).unwrap(),
-                    password: row.take(3).unwrap(),
-                    salt: row.take(4).unwrap(),
-                    mail_confirmed: row.take(5).unwrap(),
-                    forgot_password: row.take(6).unwrap(),
-                    delete_account: row.take(7).unwrap(),
-                    new_mail: row.take(8).unwrap(),
-                    access_rights: row.take(9).unwrap(),
-                },
-            ) {
+            for entry in db_main.select("SELECT id, nickname, mail, password, salt, mail_confirmed, delete_account, new_mail, access_rights FROM account_member", |mut row| Member {
+                id: row.take(0).unwrap(),
+                nickname: row.take(1).unwrap(),
+                mail: row.take(2).unwrap(),
+                password: row.take(3).unwrap(),
+                salt: row.take(4).unwrap(),
+                mail_confirmed: row.take(5).unwrap(),
+                delete_account: row.take(6).unwrap(),
+                new_mail: row.take(7).unwrap(),
+                access_rights: row.take(8).unwrap(),
+            }) {
                 // Prepping api_token map
                 api_token.insert(entry.id, vec![]);
 
@@ -68,10 +70,6 @@ impl Account {
                 if !entry.mail_confirmed {
                     requires_mail_confirmation.insert(sha3::hash(&[&entry.id.to_string(), "mail", &entry.salt]), entry.id);
                 }
-                // Init remaining forgot password mails
-                if entry.forgot_password {
-                    requires_mail_confirmation.insert(sha3::hash(&[&entry.id.to_string(), "forgot", &entry.salt]), entry.id);
-                }
                 // Init remaining delete mails
                 if entry.delete_account {
                     requires_mail_confirmation.insert(sha3::hash(&[&entry.id.to_string(), "delete", &entry.salt]), entry.id);
@@ -84,16 +82,28 @@ impl Account {
                 member.insert(entry.id, entry);
             }
 
-            for entry in db_main.select("SELECT id, member_id, token, purpose, exp_date FROM account_api_token", |mut row| APIToken {
+            for entry in db_main.select("SELECT id, member_id, token, purpose, exp_date, scopes FROM account_api_token", |mut row| APIToken {
                 id: row.take(0).unwrap(),
                 member_id: row.take(1).unwrap(),
                 token: Some(row.take(2).unwrap()),
                 purpose: row.take(3).unwrap(),
+                scopes: TokenScope::from_flags(row.take(5).unwrap()),
                 exp_date: row.take(4).unwrap(),
             }) {
                 api_token_to_member_id.insert(entry.token.as_ref().unwrap().clone(), entry.member_id);
                 api_token.get_mut(&entry.member_id).unwrap().push(entry);
             }
+
+            for (token, entry) in db_main.select("SELECT member_id, token, exp_date FROM account_password_reset", |mut row| {
+                let token: String = row.take(1).unwrap();
+                let entry = PasswordReset {
+                    member_id: row.take(0).unwrap(),
+                    exp_date: row.take(2).unwrap(),
+                };
+                (token, entry)
+            }) {
+                password_resets.insert(token, entry);
+            }
         }
 
         self
@@ -1,3 +1,4 @@
+use crate::modules::account::domain_value::TokenScope;
 use schemars::JsonSchema;
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
@@ -6,5 +7,6 @@ pub struct APIToken {
     pub member_id: u32,
     pub token: Option<String>,
     pub purpose: String,
+    pub scopes: Vec<TokenScope>,
     pub exp_date: u64,
 }
@@ -8,7 +8,6 @@ pub struct Member {
     pub password: String,
     pub salt: String,
     pub mail_confirmed: bool,
-    pub forgot_password: bool,
     pub delete_account: bool,
     pub new_mail: String, // Non-Empty means that a change was requested
     pub access_rights: u32,
@@ -1,5 +1,6 @@
-pub use self::{account::Account, api_token::APIToken, member::Member};
+pub use self::{account::Account, api_token::APIToken, member::Member, password_reset::PasswordReset};
 
 mod account;
 mod api_token;
 mod member;
+mod password_reset;
@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct PasswordReset {
+    pub member_id: u32,
+    pub exp_date: u64,
+}
@@ -1,4 +1,4 @@
-pub use self::material::Account;
+pub use self::{domain_value::AuditAction, material::Account, tools::AuditLog};
 
 #[cfg(test)]
 mod tests;
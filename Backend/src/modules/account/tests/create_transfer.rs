@@ -253,7 +253,7 @@ fn password_pwned() {
 
 #[test]
 #[ignore]
-fn invalid_password_characters() {
+fn special_password_characters() {
     let container = TestContainer::new(false);
     let (mut conn, dns, _node) = container.run();
 
@@ -264,7 +264,7 @@ fn invalid_password_characters() {
         nickname: "someNickname".to_string(),
         credentials: Credentials {
             mail: "someEmail@someDomain.test".to_string(),
-            password: "%$&§%".to_string(),
+            password: "%$&§%someExtremelySecurePassword%$&§%".to_string(),
         },
     };
     // Serialize the object to the json format
@@ -279,7 +279,7 @@ fn invalid_password_characters() {
 
     // Then
     // Verify the status code of the response
-    assert_eq!(response.status(), Status::new(535, "InvalidPasswordCharacters"));
-    // Verify that the user has not been created in the database
-    assert!(!has_existing_entry(&mut conn, &post_obj.credentials.mail));
+    assert_eq!(response.status(), Status::Ok);
+    // Verify that the user has been created in the database
+    assert!(has_existing_entry(&mut conn, &post_obj.credentials.mail));
 }
@@ -2,8 +2,8 @@ use str_util::sha3;
 
 use crate::modules::account::tests::helper::get_create_member;
 use crate::modules::account::{
-    material::Account,
-    tools::{Create, Forgot},
+    material::{Account, PasswordReset},
+    tools::{Create, Forgot, Login, Token},
 };
 use crate::tests::TestContainer;
 
@@ -14,6 +14,7 @@ fn send_forget_password_user_does_not_exist() {
 
     let account = Account::default();
     assert!(account.send_forgot_password(&mut conn, "test@mail.de").is_ok());
+    assert!(account.password_resets.read().unwrap().is_empty());
 }
 
 #[test]
@@ -26,7 +27,7 @@ fn send_forget_password_invalid_mail() {
 }
 
 #[test]
-fn send_forgot_password_user_exists_and_receive() {
+fn send_forgot_password_replaces_previous_token() {
     let container = TestContainer::new(false);
     let (mut conn, _dns, _node) = container.run();
 
@@ -35,22 +36,70 @@ fn send_forgot_password_user_exists_and_receive() {
 
     let val_pair = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
     assert!(account.send_forgot_password(&mut conn, "abc@abc.de").is_ok());
+    let first_token = account.password_resets.read().unwrap().keys().next().cloned().unwrap();
+    assert!(account.send_forgot_password(&mut conn, "ABC@abc.de").is_ok());
 
-    let salt;
-    {
-        let member = account.member.read().unwrap();
-        salt = member.get(&val_pair.member_id).unwrap().salt.clone();
-    }
-    let forgot_id = sha3::hash(&[&val_pair.member_id.to_string(), "forgot", &salt]);
-    let receive_forgot = account.recv_forgot_password(&mut conn, &forgot_id);
-    assert!(receive_forgot.is_ok());
+    let password_resets = account.password_resets.read().unwrap();
+    assert_eq!(password_resets.len(), 1);
+    assert!(!password_resets.contains_key(&first_token));
+    let password_reset = password_resets.values().next().unwrap();
+    assert_eq!(password_reset.member_id, val_pair.member_id);
+    assert!(password_reset.exp_date > time_util::now());
 }
 
 #[test]
-fn recv_forgot_password_invalid_id() {
+fn reset_password() {
     let container = TestContainer::new(false);
     let (mut conn, _dns, _node) = container.run();
 
     let account = Account::default();
-    assert!(account.recv_forgot_password(&mut conn, "bla").is_err());
+    let post_obj = get_create_member("abc", "abc@abc.de", "Password123456Password123456Password123456");
+
+    let val_pair = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+    account.password_resets.write().unwrap().insert(
+        sha3::hash(&["someResetToken"]),
+        PasswordReset {
+            member_id: val_pair.member_id,
+            exp_date: time_util::now() + 60,
+        },
+    );
+
+    let reset = account.reset_password(&mut conn, "someResetToken", "NewPassword123456!NewPassword123456!");
+    assert!(reset.is_ok());
+    assert!(account.validate_token(&mut conn, val_pair.token.as_ref().unwrap()).is_none());
+    assert!(account.login(&mut conn, "abc@abc.de", &post_obj.credentials.password).is_err());
+    assert!(account.login(&mut conn, "abc@abc.de", "NewPassword123456!NewPassword123456!").is_ok());
+
+    // Tokens can only be used once
+    assert!(account.reset_password(&mut conn, "someResetToken", "OtherPassword123456!OtherPassword123456!").is_err());
+}
+
+#[test]
+fn reset_password_expired_token() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let post_obj = get_create_member("abc", "abc@abc.de", "Password123456Password123456Password123456");
+
+    let val_pair = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+    account.password_resets.write().unwrap().insert(
+        sha3::hash(&["someResetToken"]),
+        PasswordReset {
+            member_id: val_pair.member_id,
+            exp_date: time_util::now() - 60,
+        },
+    );
+
+    assert!(account.reset_password(&mut conn, "someResetToken", "NewPassword123456!NewPassword123456!").is_err());
+    assert!(account.login(&mut conn, "abc@abc.de", &post_obj.credentials.password).is_ok());
+}
+
+#[test]
+fn reset_password_invalid_token() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    assert!(account.reset_password(&mut conn, "bla", "NewPassword123456!NewPassword123456!").is_err());
 }
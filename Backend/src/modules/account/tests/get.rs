@@ -23,7 +23,6 @@ fn get_exists() {
                 password: "afsd".to_string(),
                 salt: "asdas".to_string(),
                 mail_confirmed: false,
-                forgot_password: false,
                 delete_account: false,
                 new_mail: "".to_string(),
                 access_rights: 0,
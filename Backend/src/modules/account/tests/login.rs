@@ -1,6 +1,10 @@
+use str_util::sha3;
+
 use crate::modules::account::tests::helper::get_create_member;
 use crate::modules::account::{material::Account, tools::Create, tools::Login};
+use crate::params;
 use crate::tests::TestContainer;
+use crate::util::database::*;
 
 // User exists login is tested when creating an account
 #[test]
@@ -39,3 +43,40 @@ fn login_two_users() {
     let login_x = account.login(&mut conn, "xyz@xyz.de", "password123password123password123");
     assert!(login_x.is_ok());
 }
+
+#[test]
+fn login_migrates_legacy_password_hash() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let post_obj = get_create_member("abc", "abc@abc.de", "password123!password123!password123!");
+    let api_token = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+
+    let legacy_hash;
+    {
+        let mut member = account.member.write().unwrap();
+        let member_entry = member.get_mut(&api_token.member_id).unwrap();
+        legacy_hash = sha3::hash(&[&post_obj.credentials.password, &member_entry.salt]);
+        member_entry.password = legacy_hash.clone();
+    }
+    assert!(conn.execute_wparams(
+        "UPDATE account_member SET password=:password WHERE id=:id",
+        params!(
+          "password" => legacy_hash.clone(),
+          "id" => api_token.member_id
+        ),
+    ));
+
+    let login = account.login(&mut conn, "abc@abc.de", "password123!password123!password123!");
+    assert!(login.is_ok());
+
+    let new_hash = account.member.read().unwrap().get(&api_token.member_id).unwrap().password.clone();
+    assert_ne!(new_hash, legacy_hash);
+    assert!(new_hash.starts_with("$argon2id$"));
+    let stored_hash: String = conn
+        .select_wparams_value("SELECT password FROM account_member WHERE id=:id", |mut row| row.take(0).unwrap(), params!("id" => api_token.member_id))
+        .unwrap();
+    assert_eq!(stored_hash, new_hash);
+    assert!(account.login(&mut conn, "abc@abc.de", "password123!password123!password123!").is_ok());
+}
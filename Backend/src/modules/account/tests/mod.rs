@@ -4,6 +4,7 @@ mod delete;
 mod forgot;
 mod get;
 mod login;
+mod role;
 mod token;
 mod update;
 
@@ -0,0 +1,138 @@
+use crate::modules::account::tests::helper::get_create_member;
+use crate::modules::account::{
+    domain_value::{AuditAction, Role},
+    dto::Failure,
+    material::{Account, Member},
+    tools::{AuditLog, Create, Roles},
+};
+use crate::tests::TestContainer;
+use crate::util::database::MockExecute;
+
+fn insert_member(account: &mut Account, member_id: u32, roles: &[Role]) {
+    let member = account.member.get_mut().unwrap();
+    member.insert(
+        member_id,
+        Member {
+            id: member_id,
+            nickname: format!("member{}", member_id),
+            mail: format!("member{}@abc.de", member_id),
+            password: "afsd".to_string(),
+            salt: "asdas".to_string(),
+            mail_confirmed: true,
+            delete_account: false,
+            new_mail: "".to_string(),
+            access_rights: roles.iter().fold(0, |flags, role| flags | role.to_flag()),
+        },
+    );
+}
+
+#[test]
+fn role_flags() {
+    assert_eq!(Role::from_flags(Role::Moderator.to_flag() | Role::Uploader.to_flag()), vec![Role::Moderator, Role::Uploader]);
+    assert_eq!(Role::from_flags(15), Role::all());
+    assert_eq!(Role::from_u8(2), Some(Role::ServerOwner));
+    assert_eq!(Role::from_u8(4), None);
+}
+
+#[test]
+fn audit_action_rows() {
+    let actions = vec![
+        AuditAction::GrantRole { member_id: 3, role: Role::Moderator },
+        AuditAction::RevokeRole { member_id: 4, role: Role::Uploader },
+        AuditAction::DeleteInstance { instance_meta_id: 42 },
+        AuditAction::DeleteCharacter { character_id: 5 },
+        AuditAction::DeleteCharacterHistory { character_history_id: 6 },
+        AuditAction::DeleteGuild { guild_id: 7 },
+        AuditAction::ReloadServers,
+    ];
+    for action in actions {
+        let (action_id, target_id, role) = action.to_row();
+        assert_eq!(AuditAction::from_row(action_id, target_id, role), Some(action));
+    }
+    assert_eq!(AuditAction::from_row(0, 3, None), None);
+    assert_eq!(AuditAction::from_row(99, 3, None), None);
+}
+
+#[test]
+fn admin_has_every_role() {
+    let mut account = Account::default();
+    insert_member(&mut account, 1, &[Role::Admin]);
+    insert_member(&mut account, 2, &[Role::Moderator]);
+    insert_member(&mut account, 3, &[]);
+
+    assert!(Role::all().into_iter().all(|role| account.has_role(1, role)));
+    assert!(account.has_role(2, Role::Moderator));
+    assert!(!account.has_role(2, Role::Admin));
+    assert!(!account.has_role(2, Role::Uploader));
+    assert!(Role::all().into_iter().all(|role| !account.has_role(3, role)));
+    assert!(!account.has_role(42, Role::Uploader));
+}
+
+#[test]
+fn grant_and_revoke_role() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let post_obj = get_create_member("abc", "abc@abc.de", "Password123456Password123456Password123456");
+    let api_token = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+    let member_id = api_token.member_id;
+
+    // New members are uploaders
+    assert!(account.has_role(member_id, Role::Uploader));
+    assert!(!account.has_role(member_id, Role::Moderator));
+
+    let account_information = account.grant_role(&mut conn, member_id, member_id, Role::Moderator).unwrap();
+    assert_eq!(account_information.access_rights, Role::Moderator.to_flag() | Role::Uploader.to_flag());
+    assert!(account.has_role(member_id, Role::Moderator));
+
+    let account_information = account.revoke_role(&mut conn, member_id, member_id, Role::Uploader).unwrap();
+    assert_eq!(account_information.access_rights, Role::Moderator.to_flag());
+    assert!(!account.has_role(member_id, Role::Uploader));
+
+    // The roles are persisted
+    let reloaded_account = Account::default().init(&mut conn);
+    assert!(reloaded_account.has_role(member_id, Role::Moderator));
+    assert!(!reloaded_account.has_role(member_id, Role::Uploader));
+
+    assert!(matches!(account.grant_role(&mut conn, member_id, member_id + 1, Role::Admin), Err(Failure::UnknownMember)));
+
+    // Each change is audited along with it
+    let audit_log = account.get_audit_log(&mut conn);
+    assert_eq!(audit_log.len(), 2);
+    assert_eq!(audit_log[0].action, AuditAction::RevokeRole { member_id, role: Role::Uploader });
+    assert_eq!(audit_log[1].action, AuditAction::GrantRole { member_id, role: Role::Moderator });
+}
+
+#[test]
+fn last_admin_keeps_role() {
+    let mut account = Account::default();
+    insert_member(&mut account, 1, &[Role::Admin]);
+    insert_member(&mut account, 2, &[Role::Moderator]);
+
+    // Rejected before anything is written
+    let mut db_main = MockExecute::new();
+    assert!(matches!(account.revoke_role(&mut db_main, 1, 1, Role::Admin), Err(Failure::LastAdmin)));
+    assert!(account.has_role(1, Role::Admin));
+}
+
+#[test]
+fn audit_log() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let post_obj = get_create_member("abc", "abc@abc.de", "Password123456Password123456Password123456");
+    let api_token = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+
+    assert!(account.get_audit_log(&mut conn).is_empty());
+    assert!(account.log_action(&mut conn, api_token.member_id, AuditAction::GrantRole { member_id: 2, role: Role::Moderator }).is_ok());
+    assert!(account.log_action(&mut conn, api_token.member_id, AuditAction::DeleteInstance { instance_meta_id: 42 }).is_ok());
+
+    // Latest first
+    let audit_log = account.get_audit_log(&mut conn);
+    assert_eq!(audit_log.len(), 2);
+    assert_eq!(audit_log[0].member_id, Some(api_token.member_id));
+    assert_eq!(audit_log[0].action, AuditAction::DeleteInstance { instance_meta_id: 42 });
+    assert_eq!(audit_log[1].action, AuditAction::GrantRole { member_id: 2, role: Role::Moderator });
+}
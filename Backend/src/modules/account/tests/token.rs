@@ -1,5 +1,7 @@
 use crate::modules::account::tests::helper::get_create_member;
 use crate::modules::account::{
+    domain_value::TokenScope,
+    dto::Failure,
     material::Account,
     tools::{Create, Login, Token, Update},
 };
@@ -134,3 +136,46 @@ fn prolong_token_by_str() {
     let in_thirty_days = time_util::get_ts_from_now_in_secs(30);
     assert!(in_thirty_days - new_token.unwrap().exp_date <= 5);
 }
+
+#[test]
+fn token_scope_flags() {
+    assert_eq!(TokenScope::to_flags(&TokenScope::all()), 63);
+    assert_eq!(TokenScope::from_flags(63), TokenScope::all());
+    assert_eq!(TokenScope::from_flags(TokenScope::to_flags(&[TokenScope::UploadLog, TokenScope::ReadOnly])), vec![TokenScope::ReadOnly, TokenScope::UploadLog]);
+    assert!(TokenScope::from_flags(0).is_empty());
+}
+
+#[test]
+fn validate_scoped_token() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let post_obj = get_create_member("abc", "abc@abc.de", "Password123456Password123456Password123456");
+
+    let api_token = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+    assert_eq!(api_token.scopes, TokenScope::all());
+    assert!(account.validate_token_scope(&mut conn, api_token.token.as_ref().unwrap(), TokenScope::Account).is_some());
+
+    let upload_token = account.create_scoped_token(&mut conn, "Uploader", api_token.member_id, time_util::get_ts_from_now_in_secs(7), &[TokenScope::UploadLog]).unwrap();
+    assert_eq!(upload_token.scopes, vec![TokenScope::UploadLog]);
+    assert!(account.validate_token_scope(&mut conn, upload_token.token.as_ref().unwrap(), TokenScope::UploadLog).is_some());
+    assert!(account.validate_token_scope(&mut conn, upload_token.token.as_ref().unwrap(), TokenScope::Account).is_none());
+    assert!(account.validate_token_scope(&mut conn, upload_token.token.as_ref().unwrap(), TokenScope::DeleteInstance).is_none());
+
+    let tokens = account.get_all_token(api_token.member_id);
+    assert!(tokens.iter().any(|token| token.id == upload_token.id && token.scopes == vec![TokenScope::UploadLog]));
+}
+
+#[test]
+fn create_token_without_scopes() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let post_obj = get_create_member("abc", "abc@abc.de", "Password123456Password123456Password123456");
+
+    let api_token = account.create(&mut conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap();
+    let token_res = account.create_scoped_token(&mut conn, "Nothing", api_token.member_id, time_util::get_ts_from_now_in_secs(7), &[]);
+    assert!(matches!(token_res, Err(Failure::InvalidTokenScopes)));
+}
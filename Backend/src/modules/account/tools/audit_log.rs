@@ -0,0 +1,49 @@
+use crate::params;
+use crate::util::database::*;
+
+use crate::modules::account::{
+    domain_value::{AuditAction, AuditLogEntry},
+    dto::Failure,
+    material::Account,
+};
+
+pub trait AuditLog {
+    fn log_action(&self, db_main: &mut impl Execute, member_id: u32, action: AuditAction) -> Result<(), Failure>;
+    fn get_audit_log(&self, db_main: &mut impl Select) -> Vec<AuditLogEntry>;
+}
+
+impl AuditLog for Account {
+    fn log_action(&self, db_main: &mut impl Execute, member_id: u32, action: AuditAction) -> Result<(), Failure> {
+        let (action, target_id, role) = action.to_row();
+        if db_main.execute_wparams(
+            "INSERT INTO account_audit_log (member_id, action, target_id, role, timestamp) VALUES (:member_id, :action, :target_id, :role, UNIX_TIMESTAMP())",
+            params!(
+              "member_id" => member_id,
+              "action" => action,
+              "target_id" => target_id,
+              "role" => role
+            ),
+        ) {
+            return Ok(());
+        }
+        Err(Failure::Unknown)
+    }
+
+    fn get_audit_log(&self, db_main: &mut impl Select) -> Vec<AuditLogEntry> {
+        db_main
+            .select("SELECT id, member_id, action, target_id, role, timestamp FROM account_audit_log ORDER BY id DESC", |mut row| {
+                let action: u8 = row.take(2).unwrap();
+                let target_id: u64 = row.take(3).unwrap();
+                let role: Option<u8> = row.take_opt(4).unwrap().ok();
+                AuditAction::from_row(action, target_id, role).map(|action| AuditLogEntry {
+                    id: row.take(0).unwrap(),
+                    member_id: row.take_opt(1).unwrap().ok(),
+                    action,
+                    timestamp: row.take(5).unwrap(),
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
@@ -1,13 +1,14 @@
 use crate::params;
 use crate::util::database::*;
 use language::{domain_value::Language, tools::Get};
-use str_util::{random, sha3, strformat};
+use str_util::{password, random, sha3, strformat};
 use validator::{
     domain_value::PasswordFailure,
     tools::{valid_mail, valid_nickname, valid_password},
 };
 
 use crate::modules::account::{
+    domain_value::Role,
     dto::Failure,
     material::{APIToken, Account, Member},
     tools::Token,
@@ -30,12 +31,15 @@ impl Create for Account {
         }
 
         match valid_password(password) {
-            Err(PasswordFailure::InvalidCharacters) => return Err(Failure::InvalidPasswordCharacters),
             Err(PasswordFailure::TooFewCharacters) => return Err(Failure::PasswordTooShort),
             Err(PasswordFailure::Pwned(num_pwned)) => return Err(Failure::PwnedPassword(num_pwned)),
             Ok(_) => (),
         };
 
+        // Hashing is slow, hence it happens before the members are locked
+        let salt: String = random::alphanumeric(16);
+        let pass: String = password::hash(password);
+
         // The following part needs to be transactional
         let member_id: u32;
         {
@@ -50,16 +54,17 @@ impl Create for Account {
                 }
             }
 
-            let salt: String = random::alphanumeric(16);
-            let pass: String = sha3::hash(&[password, &salt]);
+            // New members may upload logs right away
+            let access_rights = Role::Uploader.to_flag();
 
             if db_main.execute_wparams(
-                "INSERT IGNORE INTO account_member (`mail`, `password`, `nickname`, `salt`, `joined`) VALUES (:mail, :pass, :nickname, :salt, UNIX_TIMESTAMP())",
+                "INSERT IGNORE INTO account_member (`mail`, `password`, `nickname`, `salt`, `joined`, `access_rights`) VALUES (:mail, :pass, :nickname, :salt, UNIX_TIMESTAMP(), :access_rights)",
                 params!(
                 "nickname" => (*nickname).to_string(),
                 "mail" => lower_mail.clone(),
                 "pass" => pass.clone(),
-                "salt" => salt.clone()
+                "salt" => salt.clone(),
+                "access_rights" => access_rights
                 ),
             ) {
                 member_id = db_main
@@ -80,10 +85,9 @@ impl Create for Account {
                         password: pass,
                         salt,
                         mail_confirmed: false,
-                        forgot_password: false,
                         delete_account: false,
                         new_mail: String::new(),
-                        access_rights: 0,
+                        access_rights,
                     },
                 );
             } else {
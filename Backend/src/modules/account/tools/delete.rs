@@ -38,6 +38,7 @@ impl Delete for Account {
     }
 
     fn confirm_delete(&self, db_main: &mut impl Execute, delete_id: &str) -> Result<(), Failure> {
+        let mut password_resets = self.password_resets.write().unwrap();
         let mut requires_mail_confirmation = self.requires_mail_confirmation.write().unwrap();
         let mut api_token_to_member_id = self.api_token_to_member_id.write().unwrap();
         let mut api_token = self.api_tokens.write().unwrap();
@@ -64,9 +65,6 @@ impl Delete for Account {
                 if !member_entry.mail_confirmed {
                     requires_mail_confirmation.remove(&sha3::hash(&[&member_entry.id.to_string(), "mail", &member_entry.salt]));
                 }
-                if member_entry.forgot_password {
-                    requires_mail_confirmation.remove(&sha3::hash(&[&member_entry.id.to_string(), "forgot", &member_entry.salt]));
-                }
                 if member_entry.delete_account {
                     requires_mail_confirmation.remove(&sha3::hash(&[&member_entry.id.to_string(), "delete", &member_entry.salt]));
                 }
@@ -74,6 +72,7 @@ impl Delete for Account {
                     requires_mail_confirmation.remove(&sha3::hash(&[&member_entry.id.to_string(), "new_mail", &member_entry.salt]));
                 }
                 requires_mail_confirmation.remove(delete_id);
+                password_resets.retain(|_, password_reset| password_reset.member_id != member_id);
 
                 // Taking care of api_tokens
                 for api_token in api_token.get(&member_id).unwrap() {
@@ -2,17 +2,23 @@ use crate::params;
 use crate::util::database::*;
 use language::{domain_value::Language, tools::Get};
 use str_util::{random, sha3, strformat};
-use validator::tools::valid_mail;
+use validator::{
+    domain_value::PasswordFailure,
+    tools::{valid_mail, valid_password},
+};
 
 use crate::modules::account::{
     dto::Failure,
-    material::{APIToken, Account},
+    material::{APIToken, Account, PasswordReset},
     tools::{Token, Update},
 };
 
+// Reset tokens expire after one hour
+static RESET_TOKEN_LIFETIME: u64 = 60 * 60;
+
 pub trait Forgot {
     fn send_forgot_password(&self, db_main: &mut impl Execute, mail: &str) -> Result<(), Failure>;
-    fn recv_forgot_password(&self, db_main: &mut (impl Execute + Select), forgot_id: &str) -> Result<APIToken, Failure>;
+    fn reset_password(&self, db_main: &mut (impl Execute + Select), reset_token: &str, new_password: &str) -> Result<APIToken, Failure>;
 }
 
 impl Forgot for Account {
@@ -21,94 +27,83 @@ impl Forgot for Account {
             return Err(Failure::InvalidMail);
         }
 
-        let mut requires_mail_confirmation = self.requires_mail_confirmation.write().unwrap();
-        let mut member = self.member.write().unwrap();
-
-        let mut member_id = None;
-        {
-            let lower_mail = mail.to_lowercase();
-            for member_entry in member.values() {
-                if member_entry.mail == lower_mail {
-                    member_id = Some(member_entry.id);
-                    break;
-                }
-            }
-        }
-
-        if member_id.is_none() {
-            return Ok(()); // Don't leak information about existence
-        }
+        let mut password_resets = self.password_resets.write().unwrap();
+        let member = self.member.read().unwrap();
 
-        let unwrapped_member_id = member_id.unwrap();
-        if db_main.execute_wparams("UPDATE account_member SET forgot_password=1 WHERE id=:id", params!("id" => unwrapped_member_id)) {
-            let entry = member.get_mut(&unwrapped_member_id).unwrap();
-            let forgot_id = sha3::hash(&[&unwrapped_member_id.to_string(), "forgot", &entry.salt]);
+        let lower_mail = mail.to_lowercase();
+        let entry = match member.values().find(|member_entry| member_entry.mail == lower_mail) {
+            Some(entry) => entry,
+            None => return Ok(()), // Don't leak information about existence
+        };
 
-            entry.forgot_password = true;
-            requires_mail_confirmation.insert(forgot_id.clone(), unwrapped_member_id);
+        // Only the hash of the token is stored, the token itself is only known to the mail recipient
+        let reset_token = random::alphanumeric(64);
+        let hashed_token = sha3::hash(&[&reset_token]);
+        let exp_date = time_util::now() + RESET_TOKEN_LIFETIME;
 
-            // Only send a mail if we really set up the internal structures properly
-            if !mail::send(
-                &entry.mail,
-                &entry.nickname,
-                self.dictionary.get("forgot.confirmation.subject", Language::English),
-                strformat::fmt(self.dictionary.get("forgot.confirmation.text", Language::English), &[&forgot_id]),
-                cfg!(test),
-            ) {
-                return Err(Failure::MailSend);
-            }
+        // A new request replaces the previous token of the member
+        if !db_main.execute_wparams(
+            "REPLACE INTO account_password_reset (`member_id`, `token`, `exp_date`) VALUES (:member_id, :token, :exp_date)",
+            params!(
+              "member_id" => entry.id,
+              "token" => hashed_token.clone(),
+              "exp_date" => exp_date
+            ),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        password_resets.retain(|_, password_reset| password_reset.member_id != entry.id);
+        password_resets.insert(hashed_token, PasswordReset { member_id: entry.id, exp_date });
 
-            return Ok(());
+        // Only send a mail if we really set up the internal structures properly
+        if !mail::send(
+            &entry.mail,
+            &entry.nickname,
+            self.dictionary.get("forgot.confirmation.subject", Language::English),
+            strformat::fmt(self.dictionary.get("forgot.confirmation.text", Language::English), &[&reset_token]),
+            cfg!(test),
+        ) {
+            return Err(Failure::MailSend);
         }
-        Err(Failure::Unknown)
+        Ok(())
     }
 
-    fn recv_forgot_password(&self, db_main: &mut (impl Execute + Select), forgot_id: &str) -> Result<APIToken, Failure> {
-        let user_id;
+    fn reset_password(&self, db_main: &mut (impl Execute + Select), reset_token: &str, new_password: &str) -> Result<APIToken, Failure> {
+        match valid_password(new_password) {
+            Err(PasswordFailure::TooFewCharacters) => return Err(Failure::PasswordTooShort),
+            Err(PasswordFailure::Pwned(num_pwned)) => return Err(Failure::PwnedPassword(num_pwned)),
+            Ok(_) => (),
+        };
+
+        let member_id;
         {
-            let requires_mail_confirmation = self.requires_mail_confirmation.read().unwrap();
-            match requires_mail_confirmation.get(forgot_id) {
-                Some(member_id) => {
-                    user_id = *member_id;
-                    let mut member = self.member.write().unwrap();
-                    if db_main.execute_wparams(
-                        "UPDATE account_member SET forgot_password=0 WHERE id=:id",
-                        params!(
-                          "id" => *member_id
-                        ),
-                    ) {
-                        let entry = member.get_mut(member_id).unwrap();
-                        entry.forgot_password = false;
-                    } else {
-                        return Err(Failure::Unknown);
-                    }
-                },
-                None => return Err(Failure::ForgotNotIssued),
+            let mut password_resets = self.password_resets.write().unwrap();
+            // The token is consumed, regardless of whether it is still valid
+            let password_reset = password_resets.remove(&sha3::hash(&[reset_token])).ok_or(Failure::ForgotNotIssued)?;
+            if !db_main.execute_wparams("DELETE FROM account_password_reset WHERE member_id=:member_id", params!("member_id" => password_reset.member_id)) {
+                return Err(Failure::Unknown);
+            }
+            if password_reset.exp_date < time_util::now() {
+                return Err(Failure::ForgotNotIssued);
             }
+            member_id = password_reset.member_id;
         }
 
-        let user_pass = random::alphanumeric(32);
-        self.update_password(db_main, &user_pass, user_id).and_then(|()| {
-            // Scoping because of the create_token function
+        // Updating the password invalidates all API tokens of the member
+        self.update_password(db_main, new_password, member_id).and_then(|()| {
+            // The password is changed at this point, hence a failing notification is not an error
             {
                 let member = self.member.read().unwrap();
-                let entry = member.get(&user_id).unwrap();
-                if !mail::send(
+                let entry = member.get(&member_id).unwrap();
+                mail::send(
                     &entry.mail,
                     &entry.nickname,
                     self.dictionary.get("forgot.information.subject", Language::English),
-                    strformat::fmt(self.dictionary.get("forgot.information.text", Language::English), &[&user_pass]),
+                    self.dictionary.get("forgot.information.text", Language::English),
                     cfg!(test),
-                ) {
-                    return Err(Failure::MailSend);
-                }
-            }
-
-            {
-                let mut requires_mail_confirmation = self.requires_mail_confirmation.write().unwrap();
-                requires_mail_confirmation.remove(forgot_id);
+                );
             }
-            self.create_token(db_main, &self.dictionary.get("general.login", Language::English), user_id, time_util::get_ts_from_now_in_secs(7))
+            self.create_token(db_main, &self.dictionary.get("general.login", Language::English), member_id, time_util::get_ts_from_now_in_secs(7))
         })
     }
 }
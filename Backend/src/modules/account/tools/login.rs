@@ -1,11 +1,12 @@
 use language::{domain_value::Language, tools::Get};
-use str_util::sha3;
+use str_util::password;
 
 use crate::modules::account::{
     dto::Failure,
     material::{APIToken, Account},
     tools::Token,
 };
+use crate::params;
 use crate::util::database::{Execute, Select};
 
 pub trait Login {
@@ -15,8 +16,10 @@ pub trait Login {
 
 impl Login for Account {
     fn login(&self, db_main: &mut (impl Execute + Select), mail: &str, password: &str) -> Result<APIToken, Failure> {
-        self.validate_credentials(mail, password)
-            .and_then(|member_id| self.create_token(db_main, &self.dictionary.get("general.login", Language::English), member_id, time_util::get_ts_from_now_in_secs(7)))
+        self.validate_credentials(mail, password).and_then(|member_id| {
+            rehash_password(self, db_main, password, member_id);
+            self.create_token(db_main, &self.dictionary.get("general.login", Language::English), member_id, time_util::get_ts_from_now_in_secs(7))
+        })
     }
 
     fn validate_credentials(&self, mail: &str, password: &str) -> Result<u32, Failure> {
@@ -25,7 +28,7 @@ impl Login for Account {
             if entry.mail != lower_mail {
                 continue;
             }
-            if entry.password != sha3::hash(&[&password, &entry.salt]) {
+            if !password::verify(password, &entry.password, &entry.salt) {
                 break;
             } // Password is wrong
             return Ok(entry.id);
@@ -33,3 +36,25 @@ impl Login for Account {
         Err(Failure::InvalidCredentials)
     }
 }
+
+// Hashes of older formats are replaced once the plain password is known
+// If this fails, the old hash remains valid and is migrated on the next login
+fn rehash_password(account: &Account, db_main: &mut impl Execute, password: &str, member_id: u32) {
+    if !account.member.read().unwrap().get(&member_id).map(|entry| password::needs_rehash(&entry.password)).unwrap_or(false) {
+        return;
+    }
+
+    let hash = password::hash(password);
+    let mut member = account.member.write().unwrap();
+    if db_main.execute_wparams(
+        "UPDATE account_member SET password=:password WHERE id=:id",
+        params!(
+          "password" => hash.clone(),
+          "id" => member_id
+        ),
+    ) {
+        if let Some(entry) = member.get_mut(&member_id) {
+            entry.password = hash;
+        }
+    }
+}
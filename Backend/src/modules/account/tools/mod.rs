@@ -1,9 +1,11 @@
-pub use self::{create::Create, delete::Delete, forgot::Forgot, get::GetAccountInformation, login::Login, token::Token, update::Update};
+pub use self::{audit_log::AuditLog, create::Create, delete::Delete, forgot::Forgot, get::GetAccountInformation, login::Login, role::Roles, token::Token, update::Update};
 
+mod audit_log;
 mod create;
 mod delete;
 mod forgot;
 mod get;
 mod login;
+mod role;
 mod token;
 mod update;
@@ -0,0 +1,63 @@
+use crate::params;
+use crate::util::database::*;
+
+use crate::modules::account::{
+    domain_value::{AccountInformation, AuditAction, Role},
+    dto::Failure,
+    material::Account,
+    tools::{AuditLog, GetAccountInformation},
+};
+
+pub trait Roles {
+    fn has_role(&self, member_id: u32, role: Role) -> bool;
+    fn grant_role(&self, db_main: &mut impl Execute, admin_id: u32, member_id: u32, role: Role) -> Result<AccountInformation, Failure>;
+    fn revoke_role(&self, db_main: &mut impl Execute, admin_id: u32, member_id: u32, role: Role) -> Result<AccountInformation, Failure>;
+}
+
+impl Roles for Account {
+    fn has_role(&self, member_id: u32, role: Role) -> bool {
+        let member = self.member.read().unwrap();
+        member.get(&member_id).map(|entry| entry.access_rights & (role.to_flag() | Role::Admin.to_flag()) != 0).unwrap_or(false)
+    }
+
+    fn grant_role(&self, db_main: &mut impl Execute, admin_id: u32, member_id: u32, role: Role) -> Result<AccountInformation, Failure> {
+        set_access_rights(self, db_main, admin_id, member_id, AuditAction::GrantRole { member_id, role }, |access_rights| access_rights | role.to_flag())
+    }
+
+    fn revoke_role(&self, db_main: &mut impl Execute, admin_id: u32, member_id: u32, role: Role) -> Result<AccountInformation, Failure> {
+        set_access_rights(self, db_main, admin_id, member_id, AuditAction::RevokeRole { member_id, role }, |access_rights| access_rights & !role.to_flag())
+    }
+}
+
+/// The access rights and the audit log entry of the change are written in one transaction
+fn set_access_rights(account: &Account, db_main: &mut impl Execute, admin_id: u32, member_id: u32, action: AuditAction, update: impl Fn(u32) -> u32) -> Result<AccountInformation, Failure> {
+    {
+        let mut member = account.member.write().unwrap();
+        let previous_access_rights = member.get(&member_id).ok_or(Failure::UnknownMember)?.access_rights;
+        let access_rights = update(previous_access_rights);
+        let is_admin = |access_rights: u32| access_rights & Role::Admin.to_flag() != 0;
+        // E.g. the last admin revoking its own role
+        if is_admin(previous_access_rights) && !is_admin(access_rights) && !member.values().any(|entry| entry.id != member_id && is_admin(entry.access_rights)) {
+            return Err(Failure::LastAdmin);
+        }
+
+        if !db_main.execute_one("START TRANSACTION") {
+            return Err(Failure::Unknown);
+        }
+        let is_committed = db_main.execute_wparams(
+            "UPDATE account_member SET access_rights=:access_rights WHERE id=:id",
+            params!(
+              "access_rights" => access_rights,
+              "id" => member_id
+            ),
+        ) && account.log_action(db_main, admin_id, action).is_ok()
+            && db_main.execute_one("COMMIT");
+        if !is_committed {
+            db_main.execute_one("ROLLBACK");
+            return Err(Failure::Unknown);
+        }
+        member.get_mut(&member_id).unwrap().access_rights = access_rights;
+    }
+
+    account.get(member_id)
+}
@@ -3,6 +3,7 @@ use crate::util::database::*;
 use str_util::{random, sha3};
 
 use crate::modules::account::{
+    domain_value::TokenScope,
     dto::Failure,
     material::{APIToken, Account},
 };
@@ -10,8 +11,10 @@ use crate::modules::account::{
 pub trait Token {
     fn get_all_token(&self, member_id: u32) -> Vec<APIToken>;
     fn validate_token(&self, db_main: &mut impl Execute, api_token: &str) -> Option<u32>;
+    fn validate_token_scope(&self, db_main: &mut impl Execute, api_token: &str, scope: TokenScope) -> Option<u32>;
     fn clear_tokens(&self, db_main: &mut impl Execute, member_id: u32) -> Result<(), Failure>;
     fn create_token(&self, db_main: &mut (impl Execute + Select), purpose: &str, member_id: u32, exp_date: u64) -> Result<APIToken, Failure>;
+    fn create_scoped_token(&self, db_main: &mut (impl Execute + Select), purpose: &str, member_id: u32, exp_date: u64, scopes: &[TokenScope]) -> Result<APIToken, Failure>;
     fn delete_token(&self, db_main: &mut impl Execute, token_id: u32, member_id: u32) -> Result<(), Failure>;
     fn prolong_token(&self, db_main: &mut impl Execute, token_id: u32, member_id: u32, days: u32) -> Result<APIToken, Failure>;
     fn prolong_token_by_str(&self, db_main: &mut impl Execute, real_token: String, member_id: u32, days: u32) -> Result<APIToken, Failure>;
@@ -62,6 +65,13 @@ impl Token for Account {
         None
     }
 
+    fn validate_token_scope(&self, db_main: &mut impl Execute, api_token: &str, scope: TokenScope) -> Option<u32> {
+        let member_id = self.validate_token(db_main, api_token)?;
+        let db_token = sha3::hash(&[api_token, &"token".to_owned()]);
+        let api_tokens = self.api_tokens.read().unwrap();
+        api_tokens.get(&member_id)?.iter().find(|entry| entry.token.contains(&db_token) && entry.scopes.contains(&scope)).map(|_| member_id)
+    }
+
     fn clear_tokens(&self, db_main: &mut impl Execute, member_id: u32) -> Result<(), Failure> {
         let mut api_token_to_member_id = self.api_token_to_member_id.write().unwrap();
         let mut api_token = self.api_tokens.write().unwrap();
@@ -84,6 +94,14 @@ impl Token for Account {
     }
 
     fn create_token(&self, db_main: &mut (impl Execute + Select), purpose: &str, member_id: u32, exp_date: u64) -> Result<APIToken, Failure> {
+        self.create_scoped_token(db_main, purpose, member_id, exp_date, &TokenScope::all())
+    }
+
+    fn create_scoped_token(&self, db_main: &mut (impl Execute + Select), purpose: &str, member_id: u32, exp_date: u64, scopes: &[TokenScope]) -> Result<APIToken, Failure> {
+        if scopes.is_empty() {
+            return Err(Failure::InvalidTokenScopes);
+        }
+
         // Tokens may be valid for a maximum time of a year
         let now = time_util::now();
         if exp_date < now {
@@ -115,11 +133,12 @@ impl Token for Account {
         let mut api_tokens = self.api_tokens.write().unwrap();
 
         if !db_main.execute_wparams(
-            "INSERT INTO account_api_token (member_id, token, purpose, exp_date) VALUES (:member_id, :token, :purpose, :exp_date)",
+            "INSERT INTO account_api_token (member_id, token, purpose, scopes, exp_date) VALUES (:member_id, :token, :purpose, :scopes, :exp_date)",
             params!(
               "member_id" => member_id,
               "token" => db_token.clone(),
               "purpose" => purpose,
+              "scopes" => TokenScope::to_flags(scopes),
               "exp_date" => exp_date
             ),
         ) {
@@ -127,12 +146,13 @@ impl Token for Account {
         }
 
         match db_main.select_wparams_value(
-            "SELECT id, member_id, token, purpose, exp_date FROM account_api_token WHERE member_id=:member_id AND token=:token",
+            "SELECT id, member_id, token, purpose, exp_date, scopes FROM account_api_token WHERE member_id=:member_id AND token=:token",
             |mut row| APIToken {
                 id: row.take(0).unwrap(),
                 member_id: row.take(1).unwrap(),
                 token: Some(row.take(2).unwrap()),
                 purpose: row.take(3).unwrap(),
+                scopes: TokenScope::from_flags(row.take(5).unwrap()),
                 exp_date: row.take(4).unwrap(),
             },
             params!(
@@ -152,6 +172,7 @@ impl Token for Account {
                     member_id: token.member_id,
                     token: Some(real_token),
                     purpose: token.purpose,
+                    scopes: token.scopes,
                     exp_date: token.exp_date,
                 })
             },
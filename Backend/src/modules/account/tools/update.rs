@@ -1,7 +1,7 @@
 use crate::params;
 use crate::util::database::*;
 use language::{domain_value::Language, tools::Get};
-use str_util::{sha3, strformat};
+use str_util::{password, sha3, strformat};
 use validator::{
     domain_value::PasswordFailure,
     tools::{valid_mail, valid_nickname, valid_password},
@@ -57,7 +57,6 @@ impl Update for Account {
 
     fn change_password(&self, db_main: &mut (impl Execute + Select), new_password: &str, member_id: u32) -> Result<APIToken, Failure> {
         match valid_password(new_password) {
-            Err(PasswordFailure::InvalidCharacters) => return Err(Failure::InvalidPasswordCharacters),
             Err(PasswordFailure::TooFewCharacters) => return Err(Failure::PasswordTooShort),
             Err(PasswordFailure::Pwned(num_pwned)) => return Err(Failure::PwnedPassword(num_pwned)),
             Ok(_) => (),
@@ -68,14 +67,9 @@ impl Update for Account {
     }
 
     fn update_password(&self, db_main: &mut (impl Execute + Select), new_password: &str, member_id: u32) -> Result<(), Failure> {
+        let hash = password::hash(new_password);
         let mut member = self.member.write().unwrap();
 
-        let hash: String;
-        {
-            let entry = member.get(&member_id).unwrap();
-            hash = sha3::hash(&[new_password, &entry.salt]);
-        }
-
         if db_main.execute_wparams(
             "UPDATE account_member SET password=:password WHERE id=:id",
             params!(
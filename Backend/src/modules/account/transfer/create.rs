@@ -3,7 +3,7 @@ use rocket_contrib::json::Json;
 
 use crate::modules::account::{
     dto::{CreateMember, Failure},
-    guard::Authenticate,
+    guard::{scope, Authenticate},
     material::{APIToken, Account},
     tools::Create,
 };
@@ -26,7 +26,7 @@ pub fn confirm(mut db_main: MainDb, me: State<Account>, id: String) -> Result<()
 
 #[openapi]
 #[post("/create/resend")]
-pub fn resend_confirm(me: State<Account>, auth: Authenticate) -> Result<(), Failure> {
+pub fn resend_confirm(me: State<Account>, auth: Authenticate<scope::Account>) -> Result<(), Failure> {
     if me.send_confirmation(auth.0) {
         return Ok(());
     }
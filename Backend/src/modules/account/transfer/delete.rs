@@ -1,6 +1,11 @@
 use rocket::State;
 
-use crate::modules::account::{dto::Failure, guard::Authenticate, material::Account, tools::Delete};
+use crate::modules::account::{
+    dto::Failure,
+    guard::{scope, Authenticate},
+    material::Account,
+    tools::Delete,
+};
 use crate::MainDb;
 
 #[openapi]
@@ -11,6 +16,6 @@ pub fn confirm(mut db_main: MainDb, me: State<Account>, id: String) -> Result<()
 
 #[openapi]
 #[delete("/delete")]
-pub fn request(mut db_main: MainDb, me: State<Account>, auth: Authenticate) -> Result<(), Failure> {
+pub fn request(mut db_main: MainDb, me: State<Account>, auth: Authenticate<scope::Account>) -> Result<(), Failure> {
     me.issue_delete(&mut *db_main, auth.0)
 }
@@ -9,9 +9,9 @@ use crate::modules::account::{
 use crate::MainDb;
 
 #[openapi]
-#[get("/forgot/<id>")]
-pub fn receive_confirmation(mut db_main: MainDb, me: State<Account>, id: String) -> Result<Json<APIToken>, Failure> {
-    me.recv_forgot_password(&mut *db_main, &id).map(Json)
+#[post("/forgot/<id>", data = "<password>", format = "application/json")]
+pub fn reset_password(mut db_main: MainDb, me: State<Account>, id: String, password: Json<String>) -> Result<Json<APIToken>, Failure> {
+    me.reset_password(&mut *db_main, &id, &password).map(Json)
 }
 
 #[openapi]
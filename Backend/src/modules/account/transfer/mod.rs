@@ -3,5 +3,6 @@ pub mod delete;
 pub mod forgot;
 pub mod get;
 pub mod login;
+pub mod role;
 pub mod token;
 pub mod update;
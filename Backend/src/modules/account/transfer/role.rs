@@ -0,0 +1,31 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::{
+    domain_value::{AccountInformation, AuditLogEntry},
+    dto::{Failure, UpdateRole},
+    guard::{role, scope, HasRole},
+    material::Account,
+    tools::{AuditLog, Roles},
+};
+use crate::MainDb;
+
+#[openapi]
+#[post("/role/grant", format = "application/json", data = "<params>")]
+pub fn grant_role(mut db_main: MainDb, me: State<Account>, auth: HasRole<role::Admin, scope::Account>, params: Json<UpdateRole>) -> Result<Json<AccountInformation>, Failure> {
+    let UpdateRole { member_id, role } = params.into_inner();
+    me.grant_role(&mut *db_main, auth.0, member_id, role).map(Json)
+}
+
+#[openapi]
+#[post("/role/revoke", format = "application/json", data = "<params>")]
+pub fn revoke_role(mut db_main: MainDb, me: State<Account>, auth: HasRole<role::Admin, scope::Account>, params: Json<UpdateRole>) -> Result<Json<AccountInformation>, Failure> {
+    let UpdateRole { member_id, role } = params.into_inner();
+    me.revoke_role(&mut *db_main, auth.0, member_id, role).map(Json)
+}
+
+#[openapi]
+#[get("/audit_log")]
+pub fn get_audit_log(mut db_main: MainDb, me: State<Account>, _auth: HasRole<role::Moderator, scope::ReadOnly>) -> Json<Vec<AuditLogEntry>> {
+    Json(me.get_audit_log(&mut *db_main))
+}
@@ -4,7 +4,7 @@ use rocket_contrib::json::Json;
 use crate::modules::account::{
     domain_value::AccountInformation,
     dto::Failure,
-    guard::Authenticate,
+    guard::{scope, Authenticate},
     material::{APIToken, Account},
     tools::Update,
 };
@@ -12,19 +12,19 @@ use crate::MainDb;
 
 #[openapi]
 #[post("/update/password", format = "application/json", data = "<content>")]
-pub fn password(mut db_main: MainDb, me: State<Account>, auth: Authenticate, content: Json<String>) -> Result<Json<APIToken>, Failure> {
+pub fn password(mut db_main: MainDb, me: State<Account>, auth: Authenticate<scope::Account>, content: Json<String>) -> Result<Json<APIToken>, Failure> {
     me.change_password(&mut *db_main, &content, auth.0).map(Json)
 }
 
 #[openapi]
 #[post("/update/nickname", format = "application/json", data = "<content>")]
-pub fn nickname(mut db_main: MainDb, me: State<Account>, auth: Authenticate, content: Json<String>) -> Result<Json<AccountInformation>, Failure> {
+pub fn nickname(mut db_main: MainDb, me: State<Account>, auth: Authenticate<scope::Account>, content: Json<String>) -> Result<Json<AccountInformation>, Failure> {
     me.change_name(&mut *db_main, &content, auth.0).map(Json)
 }
 
 #[openapi]
 #[post("/update/mail", format = "application/json", data = "<content>")]
-pub fn request_mail(me: State<Account>, auth: Authenticate, content: Json<String>) -> Result<Json<bool>, Failure> {
+pub fn request_mail(me: State<Account>, auth: Authenticate<scope::Account>, content: Json<String>) -> Result<Json<bool>, Failure> {
     me.request_change_mail(&content, auth.0).map(Json)
 }
 
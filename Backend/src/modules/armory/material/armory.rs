@@ -1,4 +1,7 @@
-use std::{collections::HashMap, sync::RwLock};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 use crate::util::database::*;
 
@@ -8,17 +11,18 @@ use crate::modules::armory::{
     material::{Character, CharacterHistory, Guild},
 };
 
-#[derive(Debug)]
+// Clones share the underlying state, e.g. with the upload workers of the live data processor
+#[derive(Debug, Clone)]
 pub struct Armory {
-    pub characters: RwLock<HashMap<u32, Character>>,
-    pub guilds: RwLock<HashMap<u32, Guild>>,
+    pub characters: Arc<RwLock<HashMap<u32, Character>>>,
+    pub guilds: Arc<RwLock<HashMap<u32, Guild>>>,
 }
 
 impl Default for Armory {
     fn default() -> Self {
         Armory {
-            characters: RwLock::new(HashMap::new()),
-            guilds: RwLock::new(HashMap::new()),
+            characters: Arc::new(RwLock::new(HashMap::new())),
+            guilds: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 }
@@ -1,28 +1,48 @@
 use crate::util::database::*;
 
+use crate::modules::account::{Account, AuditAction, AuditLog};
 use crate::modules::armory::{dto::ArmoryFailure, tools::GetCharacter, Armory};
 use crate::params;
 
 pub trait DeleteCharacter {
     fn delete_character(&self, db_main: &mut impl Execute, id: u32) -> Result<(), ArmoryFailure>;
     fn delete_character_by_uid(&self, db_main: &mut impl Execute, server_id: u32, uid: u64) -> Result<(), ArmoryFailure>;
+    fn moderate_character(&self, db_main: &mut impl Execute, account: &Account, moderator_id: u32, id: u32) -> Result<(), ArmoryFailure>;
 }
 
 impl DeleteCharacter for Armory {
     fn delete_character(&self, db_main: &mut impl Execute, id: u32) -> Result<(), ArmoryFailure> {
-        let mut characters = self.characters.write().unwrap();
-        if db_main.execute_wparams(
-            "DELETE FROM armory_character WHERE id=:id",
-            params!(
-              "id" => id
-            ),
-        ) {
-            return characters.remove(&id).ok_or(ArmoryFailure::InvalidInput).map(|_| ());
-        }
-        Err(ArmoryFailure::Database("delete_character".to_owned()))
+        remove_character(self, db_main, id, None)
     }
 
     fn delete_character_by_uid(&self, db_main: &mut impl Execute, server_id: u32, uid: u64) -> Result<(), ArmoryFailure> {
         self.get_character_id_by_uid(server_id, uid).ok_or(ArmoryFailure::InvalidInput).and_then(|id| self.delete_character(db_main, id))
     }
+
+    fn moderate_character(&self, db_main: &mut impl Execute, account: &Account, moderator_id: u32, id: u32) -> Result<(), ArmoryFailure> {
+        remove_character(self, db_main, id, Some((account, moderator_id)))
+    }
+}
+
+// The removal by a moderator and its audit log entry are written in one transaction
+fn remove_character(armory: &Armory, db_main: &mut impl Execute, id: u32, moderator: Option<(&Account, u32)>) -> Result<(), ArmoryFailure> {
+    let mut characters = armory.characters.write().unwrap();
+    if moderator.is_some() && !db_main.execute_one("START TRANSACTION") {
+        return Err(ArmoryFailure::Database("delete_character".to_owned()));
+    }
+    let is_committed = db_main.execute_wparams(
+        "DELETE FROM armory_character WHERE id=:id",
+        params!(
+          "id" => id
+        ),
+    ) && moderator.map_or(true, |(account, moderator_id)| {
+        account.log_action(db_main, moderator_id, AuditAction::DeleteCharacter { character_id: id }).is_ok() && db_main.execute_one("COMMIT")
+    });
+    if !is_committed {
+        if moderator.is_some() {
+            db_main.execute_one("ROLLBACK");
+        }
+        return Err(ArmoryFailure::Database("delete_character".to_owned()));
+    }
+    characters.remove(&id).ok_or(ArmoryFailure::InvalidInput).map(|_| ())
 }
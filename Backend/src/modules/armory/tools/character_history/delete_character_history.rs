@@ -1,37 +1,57 @@
 use crate::params;
 use crate::util::database::*;
 
+use crate::modules::account::{Account, AuditAction, AuditLog};
 use crate::modules::armory::{dto::ArmoryFailure, tools::GetCharacterHistory, Armory};
 
 pub trait DeleteCharacterHistory {
     fn delete_character_history(&self, db_main: &mut (impl Execute + Select), character_history_id: u32) -> Result<(), ArmoryFailure>;
+    fn moderate_character_history(&self, db_main: &mut (impl Execute + Select), account: &Account, moderator_id: u32, character_history_id: u32) -> Result<(), ArmoryFailure>;
 }
 
 impl DeleteCharacterHistory for Armory {
     fn delete_character_history(&self, db_main: &mut (impl Execute + Select), character_history_id: u32) -> Result<(), ArmoryFailure> {
-        let character_history_res = self.get_character_history(db_main, character_history_id);
-        if character_history_res.is_err() {
-            return Err(ArmoryFailure::InvalidInput);
-        }
-        let character_history = character_history_res.unwrap();
+        remove_character_history(self, db_main, character_history_id, None)
+    }
+
+    fn moderate_character_history(&self, db_main: &mut (impl Execute + Select), account: &Account, moderator_id: u32, character_history_id: u32) -> Result<(), ArmoryFailure> {
+        remove_character_history(self, db_main, character_history_id, Some((account, moderator_id)))
+    }
+}
 
-        let mut characters = self.characters.write().unwrap();
-        if db_main.execute_wparams(
-            "DELETE FROM armory_character_history WHERE id=:id",
-            params!(
-              "id" => character_history_id
-            ),
-        ) {
-            let mut character = characters.get_mut(&character_history.character_id).unwrap();
-            let (hm_index, _) = character.history_moments.iter().enumerate().find(|(_index, history_moment)| history_moment.id == character_history_id).unwrap();
-            character.history_moments.remove(hm_index);
-            if character.last_update.contains(&character_history) {
-                if let Some(last_id) = character.history_moments.last() {
-                    character.last_update = self.get_character_history(db_main, last_id.id).ok();
-                }
-            }
-            return Ok(());
+// The removal by a moderator and its audit log entry are written in one transaction
+fn remove_character_history(armory: &Armory, db_main: &mut (impl Execute + Select), character_history_id: u32, moderator: Option<(&Account, u32)>) -> Result<(), ArmoryFailure> {
+    let character_history_res = armory.get_character_history(db_main, character_history_id);
+    if character_history_res.is_err() {
+        return Err(ArmoryFailure::InvalidInput);
+    }
+    let character_history = character_history_res.unwrap();
+
+    let mut characters = armory.characters.write().unwrap();
+    if moderator.is_some() && !db_main.execute_one("START TRANSACTION") {
+        return Err(ArmoryFailure::Database("delete_character_history".to_owned()));
+    }
+    let is_committed = db_main.execute_wparams(
+        "DELETE FROM armory_character_history WHERE id=:id",
+        params!(
+          "id" => character_history_id
+        ),
+    ) && moderator.map_or(true, |(account, moderator_id)| {
+        account.log_action(db_main, moderator_id, AuditAction::DeleteCharacterHistory { character_history_id }).is_ok() && db_main.execute_one("COMMIT")
+    });
+    if !is_committed {
+        if moderator.is_some() {
+            db_main.execute_one("ROLLBACK");
+        }
+        return Err(ArmoryFailure::Database("delete_character_history".to_owned()));
+    }
+    let mut character = characters.get_mut(&character_history.character_id).unwrap();
+    let (hm_index, _) = character.history_moments.iter().enumerate().find(|(_index, history_moment)| history_moment.id == character_history_id).unwrap();
+    character.history_moments.remove(hm_index);
+    if character.last_update.contains(&character_history) {
+        if let Some(last_id) = character.history_moments.last() {
+            character.last_update = armory.get_character_history(db_main, last_id.id).ok();
         }
-        Err(ArmoryFailure::Database("delete_character_history".to_owned()))
     }
+    Ok(())
 }
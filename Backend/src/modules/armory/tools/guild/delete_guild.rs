@@ -1,28 +1,48 @@
 use crate::params;
 use crate::util::database::*;
 
+use crate::modules::account::{Account, AuditAction, AuditLog};
 use crate::modules::armory::{dto::ArmoryFailure, tools::GetGuild, Armory};
 
 pub trait DeleteGuild {
     fn delete_guild(&self, db_main: &mut impl Execute, id: u32) -> Result<(), ArmoryFailure>;
     fn delete_guild_by_uid(&self, db_main: &mut impl Execute, server_id: u32, uid: u64) -> Result<(), ArmoryFailure>;
+    fn moderate_guild(&self, db_main: &mut impl Execute, account: &Account, moderator_id: u32, id: u32) -> Result<(), ArmoryFailure>;
 }
 
 impl DeleteGuild for Armory {
     fn delete_guild(&self, db_main: &mut impl Execute, id: u32) -> Result<(), ArmoryFailure> {
-        let mut guilds = self.guilds.write().unwrap();
-        if db_main.execute_wparams(
-            "DELETE FROM armory_guild WHERE id=:id",
-            params!(
-              "id" => id
-            ),
-        ) {
-            return guilds.remove(&id).ok_or_else(|| ArmoryFailure::Database("Invalid guild id o.O".to_owned())).map(|_| ());
-        }
-        Err(ArmoryFailure::Database("delete_guild".to_owned()))
+        remove_guild(self, db_main, id, None)
     }
 
     fn delete_guild_by_uid(&self, db_main: &mut impl Execute, server_id: u32, uid: u64) -> Result<(), ArmoryFailure> {
         self.get_guild_id_by_uid(server_id, uid).ok_or(ArmoryFailure::InvalidInput).and_then(|id| self.delete_guild(db_main, id))
     }
+
+    fn moderate_guild(&self, db_main: &mut impl Execute, account: &Account, moderator_id: u32, id: u32) -> Result<(), ArmoryFailure> {
+        remove_guild(self, db_main, id, Some((account, moderator_id)))
+    }
+}
+
+// The removal by a moderator and its audit log entry are written in one transaction
+fn remove_guild(armory: &Armory, db_main: &mut impl Execute, id: u32, moderator: Option<(&Account, u32)>) -> Result<(), ArmoryFailure> {
+    let mut guilds = armory.guilds.write().unwrap();
+    if moderator.is_some() && !db_main.execute_one("START TRANSACTION") {
+        return Err(ArmoryFailure::Database("delete_guild".to_owned()));
+    }
+    let is_committed = db_main.execute_wparams(
+        "DELETE FROM armory_guild WHERE id=:id",
+        params!(
+          "id" => id
+        ),
+    ) && moderator.map_or(true, |(account, moderator_id)| {
+        account.log_action(db_main, moderator_id, AuditAction::DeleteGuild { guild_id: id }).is_ok() && db_main.execute_one("COMMIT")
+    });
+    if !is_committed {
+        if moderator.is_some() {
+            db_main.execute_one("ROLLBACK");
+        }
+        return Err(ArmoryFailure::Database("delete_guild".to_owned()));
+    }
+    guilds.remove(&id).ok_or_else(|| ArmoryFailure::Database("Invalid guild id o.O".to_owned())).map(|_| ())
 }
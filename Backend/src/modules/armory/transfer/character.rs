@@ -3,7 +3,10 @@ use rocket_contrib::json::Json;
 
 use crate::modules::armory::dto::BasicCharacter;
 use crate::modules::{
-    account::guard::ServerOwner,
+    account::{
+        guard::{role, scope, HasRole, ServerOwner},
+        Account,
+    },
     armory::{
         dto::{ArmoryFailure, CharacterDto},
         material::Character,
@@ -15,7 +18,7 @@ use crate::MainDb;
 
 #[openapi]
 #[post("/character", format = "application/json", data = "<character>")]
-pub fn set_character(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner, character: Json<CharacterDto>) -> Result<(), ArmoryFailure> {
+pub fn set_character(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner<scope::ManageArmory>, character: Json<CharacterDto>) -> Result<(), ArmoryFailure> {
     me.set_character(&mut *db_main, owner.0, character.into_inner()).map(|_| ())
 }
 
@@ -45,18 +48,18 @@ pub fn get_characters_by_name(me: State<Armory>, name: String) -> Json<Vec<Chara
 
 #[openapi]
 #[get("/character/by_uid/<uid>")]
-pub fn get_character_by_uid(me: State<Armory>, owner: ServerOwner, uid: u64) -> Result<Json<Character>, ArmoryFailure> {
+pub fn get_character_by_uid(me: State<Armory>, owner: ServerOwner<scope::ManageArmory>, uid: u64) -> Result<Json<Character>, ArmoryFailure> {
     me.get_character_by_uid(owner.0, uid).map(Json).ok_or(ArmoryFailure::InvalidInput)
 }
 
 #[openapi]
 #[delete("/character/<id>")]
-pub fn delete_character(mut db_main: MainDb, me: State<Armory>, _owner: ServerOwner, id: u32) -> Result<(), ArmoryFailure> {
-    me.delete_character(&mut *db_main, id)
+pub fn delete_character(mut db_main: MainDb, me: State<Armory>, account: State<Account>, moderator: HasRole<role::Moderator, scope::ManageArmory>, id: u32) -> Result<(), ArmoryFailure> {
+    me.moderate_character(&mut *db_main, &account, moderator.0, id)
 }
 
 #[openapi]
 #[delete("/character/by_uid/<uid>")]
-pub fn delete_character_by_uid(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner, uid: u64) -> Result<(), ArmoryFailure> {
+pub fn delete_character_by_uid(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner<scope::ManageArmory>, uid: u64) -> Result<(), ArmoryFailure> {
     me.delete_character_by_uid(&mut *db_main, owner.0, uid)
 }
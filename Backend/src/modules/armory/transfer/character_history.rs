@@ -2,7 +2,10 @@ use rocket::State;
 use rocket_contrib::json::Json;
 
 use crate::modules::{
-    account::guard::ServerOwner,
+    account::{
+        guard::{role, scope, HasRole, ServerOwner},
+        Account,
+    },
     armory::{
         dto::{ArmoryFailure, CharacterHistoryDto},
         material::CharacterHistory,
@@ -14,7 +17,7 @@ use crate::MainDb;
 
 #[openapi]
 #[post("/character_history/<character_uid>", format = "application/json", data = "<character_history>")]
-pub fn set_character_history(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner, character_history: Json<CharacterHistoryDto>, character_uid: u64) -> Result<(), ArmoryFailure> {
+pub fn set_character_history(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner<scope::ManageArmory>, character_history: Json<CharacterHistoryDto>, character_uid: u64) -> Result<(), ArmoryFailure> {
     me.set_character_history(&mut *db_main, owner.0, character_history.into_inner(), character_uid).map(|_| ())
 }
 
@@ -26,6 +29,6 @@ pub fn get_character_history(mut db_main: MainDb, me: State<Armory>, id: u32) ->
 
 #[openapi]
 #[delete("/character_history/<id>")]
-pub fn delete_character_history(mut db_main: MainDb, me: State<Armory>, id: u32) -> Result<(), ArmoryFailure> {
-    me.delete_character_history(&mut *db_main, id)
+pub fn delete_character_history(mut db_main: MainDb, me: State<Armory>, account: State<Account>, moderator: HasRole<role::Moderator, scope::ManageArmory>, id: u32) -> Result<(), ArmoryFailure> {
+    me.moderate_character_history(&mut *db_main, &account, moderator.0, id)
 }
@@ -12,9 +12,10 @@ use crate::{
         data::Data,
     },
 };
+use std::sync::Arc;
 
 #[openapi]
 #[post("/character_search", format = "application/json", data = "<filter>")]
-pub fn get_character_search_result(me: State<Armory>, data: State<Data>, filter: Json<CharacterSearchFilter>) -> Json<SearchResult<CharacterSearchResult>> {
+pub fn get_character_search_result(me: State<Armory>, data: State<Arc<Data>>, filter: Json<CharacterSearchFilter>) -> Json<SearchResult<CharacterSearchResult>> {
     Json(me.get_character_search_result(&data, filter.into_inner()))
 }
@@ -11,10 +11,11 @@ use crate::modules::{
 };
 use crate::MainDb;
 use rocket::response::Redirect;
+use std::sync::Arc;
 
 #[openapi]
 #[get("/character_viewer/<server_name>/<character_name>")]
-pub fn get_character_viewer(mut db_main: MainDb, me: State<Armory>, data: State<Data>, language: Language, server_name: String, character_name: String) -> Result<Json<CharacterViewerDto>, ArmoryFailure> {
+pub fn get_character_viewer(mut db_main: MainDb, me: State<Armory>, data: State<Arc<Data>>, language: Language, server_name: String, character_name: String) -> Result<Json<CharacterViewerDto>, ArmoryFailure> {
     data.get_server_by_name(server_name).ok_or(ArmoryFailure::InvalidInput).and_then(|server| {
         me.get_character_by_name(server.id, character_name)
             .ok_or(ArmoryFailure::InvalidInput)
@@ -24,7 +25,7 @@ pub fn get_character_viewer(mut db_main: MainDb, me: State<Armory>, data: State<
 
 #[openapi]
 #[get("/character_viewer/<server_name>/<character_name>/<character_history_id>")]
-pub fn get_character_viewer_by_history(mut db_main: MainDb, me: State<Armory>, data: State<Data>, language: Language, server_name: String, character_name: String, character_history_id: u32) -> Result<Json<CharacterViewerDto>, ArmoryFailure> {
+pub fn get_character_viewer_by_history(mut db_main: MainDb, me: State<Armory>, data: State<Arc<Data>>, language: Language, server_name: String, character_name: String, character_history_id: u32) -> Result<Json<CharacterViewerDto>, ArmoryFailure> {
     data.get_server_by_name(server_name).ok_or(ArmoryFailure::InvalidInput).and_then(|server| {
         me.get_character_by_name(server.id, character_name)
             .ok_or(ArmoryFailure::InvalidInput)
@@ -35,7 +36,7 @@ pub fn get_character_viewer_by_history(mut db_main: MainDb, me: State<Armory>, d
 #[openapi]
 #[get("/character_viewer/by_date/<server_name>/<character_name>/<character_history_date>")]
 pub fn get_character_viewer_by_history_date(
-    mut db_main: MainDb, me: State<Armory>, data: State<Data>, language: Language, server_name: String, character_name: String, character_history_date: String,
+    mut db_main: MainDb, me: State<Armory>, data: State<Arc<Data>>, language: Language, server_name: String, character_name: String, character_history_date: String,
 ) -> Result<Json<CharacterViewerDto>, ArmoryFailure> {
     data.get_server_by_name(server_name).ok_or(ArmoryFailure::InvalidInput).and_then(|server| {
         me.get_character_by_name(server.id, character_name)
@@ -46,7 +47,7 @@ pub fn get_character_viewer_by_history_date(
 
 #[openapi(skip)]
 #[get("/character_viewer_model/<character_history_id>")]
-pub fn get_character_viewer_picture(mut db_main: MainDb, me: State<Armory>, data: State<Data>, character_history_id: u32) -> Result<Redirect, ArmoryFailure> {
+pub fn get_character_viewer_picture(mut db_main: MainDb, me: State<Armory>, data: State<Arc<Data>>, character_history_id: u32) -> Result<Redirect, ArmoryFailure> {
     let model_data = me.get_character_viewer_model_data(&mut *db_main, &data, character_history_id)?;
     let uri = format!(
         "{}/model_viewer/{}{}/{}/{}/{}/{}/{}/{}",
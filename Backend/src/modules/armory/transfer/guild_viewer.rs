@@ -9,10 +9,11 @@ use crate::modules::{
     },
     data::{guard::Language, tools::RetrieveServer, Data},
 };
+use std::sync::Arc;
 
 #[openapi]
 #[get("/guild_viewer/<server_name>/<guild_name>")]
-pub fn get_guild_view(me: State<Armory>, data: State<Data>, language: Language, server_name: String, guild_name: String) -> Result<Json<GuildViewerDto>, ArmoryFailure> {
+pub fn get_guild_view(me: State<Armory>, data: State<Arc<Data>>, language: Language, server_name: String, guild_name: String) -> Result<Json<GuildViewerDto>, ArmoryFailure> {
     data.get_server_by_name(server_name).ok_or(ArmoryFailure::InvalidInput).and_then(|server| {
         me.get_guild_by_name(server.id, guild_name)
             .ok_or(ArmoryFailure::InvalidInput)
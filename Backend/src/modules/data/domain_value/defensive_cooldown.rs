@@ -0,0 +1,10 @@
+/// Spell that is used to survive incoming damage, every rank is listed with the id of its first rank
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct DefensiveCooldown {
+    pub expansion_id: u8,
+    pub spell_id: u32,
+    pub base_spell_id: u32,
+    pub hero_class_id: u8,
+    // Talents are only available to the spec of their talent tree
+    pub talent_tree: Option<u8>,
+}
@@ -0,0 +1,18 @@
+/// Deviations from the default kill detection of an encounter
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub enum EncounterKillCondition {
+    // Deaths only end the attempt, if it is the death of this npc
+    FinalDeath(u32),
+    // This npc is counted like a player that is still in combat, e.g. drakes that are used as vehicles
+    Vehicle(u32),
+}
+
+impl EncounterKillCondition {
+    pub fn from_condition(condition_type: u8, npc_id: u32) -> Option<Self> {
+        match condition_type {
+            0 => Some(EncounterKillCondition::FinalDeath(npc_id)),
+            1 => Some(EncounterKillCondition::Vehicle(npc_id)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::modules::data::domain_value::EncounterSignal;
+
+/// Hard mode of an encounter, which applies if enough of its triggers were signaled during the attempt.
+/// If first_signals is set, only the first signals of the attempt are considered, e.g. if the order matters.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct EncounterRule {
+    pub id: u32,
+    pub encounter_id: u32,
+    // Applies to all difficulties if not set
+    pub difficulty_id: Option<u8>,
+    pub hard_mode_encounter_id: u32,
+    pub min_triggers: u8,
+    pub max_triggers: Option<u8>,
+    pub first_signals: Option<u8>,
+    // In milliseconds, e.g. for time limit achievements
+    pub max_duration: Option<u64>,
+    pub triggers: Vec<EncounterRuleTrigger>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct EncounterRuleTrigger {
+    pub signal: EncounterSignal,
+    // The rule does not apply if an excluded trigger was signaled
+    pub is_excluded: bool,
+}
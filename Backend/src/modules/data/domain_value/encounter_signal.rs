@@ -0,0 +1,19 @@
+/// Something that happens during an attempt and is relevant for an encounter rule.
+/// NpcDeath carries the npc id, AuraApplication and SpellCast carry the spell id.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+pub enum EncounterSignal {
+    NpcDeath(u32),
+    AuraApplication(u32),
+    SpellCast(u32),
+}
+
+impl EncounterSignal {
+    pub fn from_trigger(trigger_type: u8, trigger_id: u32) -> Option<Self> {
+        match trigger_type {
+            0 => Some(EncounterSignal::NpcDeath(trigger_id)),
+            1 => Some(EncounterSignal::AuraApplication(trigger_id)),
+            2 => Some(EncounterSignal::SpellCast(trigger_id)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/// A patch or season, rankings can be restricted to the attempts within it
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct RankingBracket {
+    pub id: u32,
+    pub expansion_id: u8,
+    pub name: String,
+    pub start_ts: u64,
+    // Open ended if the bracket is still ongoing
+    pub end_ts: Option<u64>,
+}
+
+impl RankingBracket {
+    pub fn contains(&self, timestamp: u64) -> bool {
+        timestamp >= self.start_ts && self.end_ts.map(|end_ts| timestamp < end_ts).unwrap_or(true)
+    }
+}
@@ -7,4 +7,8 @@ pub struct SpellEffect {
     pub points_upper: i32,
     pub chain_targets: u16,
     pub radius: u32,
+    // Milliseconds between ticks, 0 if unknown
+    pub aura_period: u32,
+    // Starts at 1, as referenced by descriptions, e.g. $s2
+    pub effect_index: u8,
 }
@@ -9,6 +9,7 @@ use rocket::{
 use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder};
 
 use crate::modules::data::{tools::RetrieveExpansion, Data};
+use std::sync::Arc;
 
 pub struct Expansion(pub u8);
 
@@ -27,7 +28,7 @@ impl<'a, 'r> FromRequest<'a, 'r> for Expansion {
         }
         let expansion = expansion_res.unwrap();
 
-        let data_res = req.guard::<State<'_, Data>>();
+        let data_res = req.guard::<State<'_, Arc<Data>>>();
         if data_res.is_failure() {
             return Failure((Status::NotFound, ()));
         }
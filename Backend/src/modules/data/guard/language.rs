@@ -9,6 +9,7 @@ use rocket::{
 use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder};
 
 use crate::modules::data::{tools::RetrieveLanguage, Data};
+use std::sync::Arc;
 
 pub struct Language(pub u8);
 
@@ -22,7 +23,7 @@ impl<'a, 'r> FromRequest<'a, 'r> for Language {
         }
 
         let lang_short_code = lang_header.unwrap().to_lowercase();
-        let data_res = req.guard::<State<'_, Data>>();
+        let data_res = req.guard::<State<'_, Arc<Data>>>();
         if data_res.is_failure() {
             return Success(Language(1));
         }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::modules::data::domain_value::{Difficulty, Encounter, EncounterNpc, Map};
+use crate::modules::data::domain_value::{DefensiveCooldown, Difficulty, Encounter, EncounterKillCondition, EncounterNpc, EncounterRule, EncounterRuleTrigger, EncounterSignal, Map, RankingBracket};
 use crate::modules::data::{
     domain_value::{
         DispelType, Enchant, Expansion, Gem, HeroClass, HeroClassTalent, Icon, Item, ItemBonding, ItemClass, ItemDamage, ItemDamageType, ItemEffect, ItemInventoryType, ItemQuality, ItemRandomProperty, ItemRandomPropertyPoints, ItemSheath,
@@ -51,6 +51,13 @@ pub struct Data {
     pub difficulties: HashMap<u8, Difficulty>,
     pub encounters: HashMap<u32, Encounter>,
     pub encounter_npcs: HashMap<u32, EncounterNpc>,
+    // encounter_id => rules, ordered by id
+    pub encounter_rules: HashMap<u32, Vec<EncounterRule>>,
+    // encounter_id => conditions
+    pub encounter_kill_conditions: HashMap<u32, Vec<EncounterKillCondition>>,
+    pub ranking_brackets: HashMap<u32, RankingBracket>,
+    // (expansion_id, hero_class_id) => defensive cooldowns
+    pub defensive_cooldowns: HashMap<(u8, u8), Vec<DefensiveCooldown>>,
 }
 
 impl Default for Data {
@@ -95,6 +102,10 @@ impl Default for Data {
             difficulties: HashMap::new(),
             encounters: HashMap::new(),
             encounter_npcs: HashMap::new(),
+            encounter_rules: HashMap::new(),
+            encounter_kill_conditions: HashMap::new(),
+            ranking_brackets: HashMap::new(),
+            defensive_cooldowns: HashMap::new(),
         }
     }
 }
@@ -140,6 +151,10 @@ impl Data {
         self.difficulties.init(db_main);
         self.encounters.init(db_main);
         self.encounter_npcs.init(db_main);
+        self.encounter_rules.init(db_main);
+        self.encounter_kill_conditions.init(db_main);
+        self.ranking_brackets.init(db_main);
+        self.defensive_cooldowns.init(db_main);
         self
     }
 }
@@ -368,6 +383,8 @@ impl Init for Vec<HashMap<u32, Vec<SpellEffect>>> {
                 points_upper: row.take(4).unwrap(),
                 chain_targets: row.take(5).unwrap(),
                 radius: row.take(6).unwrap(),
+                aura_period: row.take(7).unwrap(),
+                effect_index: row.take(8).unwrap(),
             })
             .into_iter()
             .for_each(|result| {
@@ -926,3 +943,94 @@ impl Init for HashMap<u32, EncounterNpc> {
             });
     }
 }
+
+impl Init for HashMap<u32, Vec<EncounterRule>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        let mut triggers: HashMap<u32, Vec<EncounterRuleTrigger>> = HashMap::new();
+        db_main
+            .select("SELECT rule_id, trigger_type, trigger_id, is_excluded FROM data_encounter_rule_trigger", |mut row| {
+                (
+                    row.take::<u32, usize>(0).unwrap(),
+                    row.take::<u8, usize>(1).unwrap(),
+                    row.take::<u32, usize>(2).unwrap(),
+                    row.take::<bool, usize>(3).unwrap(),
+                )
+            })
+            .into_iter()
+            .for_each(|(rule_id, trigger_type, trigger_id, is_excluded)| {
+                if let Some(signal) = EncounterSignal::from_trigger(trigger_type, trigger_id) {
+                    triggers.entry(rule_id).or_insert_with(Vec::new).push(EncounterRuleTrigger { signal, is_excluded });
+                }
+            });
+
+        db_main
+            .select(
+                "SELECT id, encounter_id, difficulty_id, hard_mode_encounter_id, min_triggers, max_triggers, first_signals, max_duration FROM data_encounter_rule ORDER BY id",
+                |mut row| EncounterRule {
+                    id: row.take(0).unwrap(),
+                    encounter_id: row.take(1).unwrap(),
+                    difficulty_id: row.take_opt(2).unwrap().ok(),
+                    hard_mode_encounter_id: row.take(3).unwrap(),
+                    min_triggers: row.take(4).unwrap(),
+                    max_triggers: row.take_opt(5).unwrap().ok(),
+                    first_signals: row.take_opt(6).unwrap().ok(),
+                    max_duration: row.take_opt(7).unwrap().ok(),
+                    triggers: Vec::new(),
+                },
+            )
+            .into_iter()
+            .for_each(|mut result| {
+                result.triggers = triggers.remove(&result.id).unwrap_or_else(Vec::new);
+                self.entry(result.encounter_id).or_insert_with(Vec::new).push(result);
+            });
+    }
+}
+
+impl Init for HashMap<u32, Vec<EncounterKillCondition>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT encounter_id, condition_type, npc_id FROM data_encounter_kill_condition", |mut row| {
+                (row.take::<u32, usize>(0).unwrap(), row.take::<u8, usize>(1).unwrap(), row.take::<u32, usize>(2).unwrap())
+            })
+            .into_iter()
+            .for_each(|(encounter_id, condition_type, npc_id)| {
+                if let Some(kill_condition) = EncounterKillCondition::from_condition(condition_type, npc_id) {
+                    self.entry(encounter_id).or_insert_with(Vec::new).push(kill_condition);
+                }
+            });
+    }
+}
+
+impl Init for HashMap<u32, RankingBracket> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT id, expansion_id, name, start_ts, end_ts FROM data_ranking_bracket", |mut row| RankingBracket {
+                id: row.take(0).unwrap(),
+                expansion_id: row.take(1).unwrap(),
+                name: row.take(2).unwrap(),
+                start_ts: row.take(3).unwrap(),
+                end_ts: row.take_opt(4).unwrap().ok(),
+            })
+            .into_iter()
+            .for_each(|result| {
+                self.insert(result.id, result);
+            });
+    }
+}
+
+impl Init for HashMap<(u8, u8), Vec<DefensiveCooldown>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT expansion_id, spell_id, base_spell_id, hero_class_id, talent_tree FROM data_defensive_cooldown", |mut row| DefensiveCooldown {
+                expansion_id: row.take(0).unwrap(),
+                spell_id: row.take(1).unwrap(),
+                base_spell_id: row.take(2).unwrap(),
+                hero_class_id: row.take(3).unwrap(),
+                talent_tree: row.take_opt(4).unwrap().ok(),
+            })
+            .into_iter()
+            .for_each(|result| {
+                self.entry((result.expansion_id, result.hero_class_id)).or_insert_with(Vec::new).push(result);
+            });
+    }
+}
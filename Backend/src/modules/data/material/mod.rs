@@ -1,4 +1,6 @@
 pub use self::data::Data;
 pub use self::data::Init;
+pub use self::spell_template::{render_spell_template, SpellTemplateContext};
 
 mod data;
+mod spell_template;
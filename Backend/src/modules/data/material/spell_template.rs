@@ -0,0 +1,584 @@
+use crate::modules::data::domain_value::{Spell, SpellEffect};
+
+// Descriptions that are included with $@spelldesc may include further descriptions
+static MAX_INCLUDE_DEPTH: u8 = 3;
+
+/// Provides the spells that a description template refers to
+pub trait SpellTemplateContext {
+    fn get_spell(&self, spell_id: u32) -> Option<Spell>;
+    fn get_spell_effects(&self, spell_id: u32) -> Vec<SpellEffect>;
+    fn get_description_template(&self, spell_id: u32) -> Option<String>;
+    fn get_aura_template(&self, spell_id: u32) -> Option<String>;
+    fn format_duration(&self, duration: u32) -> String;
+
+    /// Conditions of the template, e.g. whether the reader learned a talent
+    fn knows_spell(&self, _spell_id: u32) -> bool {
+        false
+    }
+    fn has_aura(&self, _spell_id: u32) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TemplateNode {
+    Text(String),
+    Variable(Variable),
+    // ${$m1*3}.1
+    Expression { expression: Expression, decimals: Option<usize>, raw: String },
+    // $?s12345[...]?a23456[...][...]
+    Conditional { branches: Vec<(Condition, Vec<TemplateNode>)>, otherwise: Vec<TemplateNode> },
+    // $lsingular:plural;
+    Plural { singular: String, plural: String },
+    // $@spelldesc12345 and $@spellaura12345
+    Include { spell_id: u32, is_aura: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VariableKind {
+    Value,
+    MinValue,
+    MaxValue,
+    TotalValue,
+    Duration,
+    Period,
+    ChainTargets,
+    Radius,
+    Range,
+}
+
+/// E.g. $s1, $12345d or $/1000;S1
+#[derive(Debug, Clone, PartialEq)]
+struct Variable {
+    spell_id: Option<u32>,
+    kind: VariableKind,
+    effect_index: usize,
+    factor: Option<f64>,
+    raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    Number(f64),
+    Variable(Variable),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    KnowsSpell(u32),
+    HasAura(u32),
+    // E.g. conditions on the class of the reader
+    Unknown,
+    Not(Box<Condition>),
+    Any(Vec<Condition>),
+    All(Vec<Condition>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExpressionToken {
+    Number(f64),
+    Variable(Variable),
+    Operator(Operator),
+    OpenParenthesis,
+    CloseParenthesis,
+}
+
+/// Renders a description template of the spell.
+/// Tokens that cannot be resolved remain in the text as they are.
+pub fn render_spell_template(template: &str, spell_id: u32, context: &impl SpellTemplateContext) -> String {
+    let mut renderer = Renderer { context, last_number: None };
+    renderer.render_template(template, spell_id, 0)
+}
+
+struct Parser<'a> {
+    chars: &'a [char],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn consume(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn raw_text(&self, start: usize) -> String {
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn parse_number(&mut self) -> Option<u32> {
+        let start = self.position;
+        while self.peek().map(|character| character.is_ascii_digit()).unwrap_or(false) {
+            self.position += 1;
+        }
+        self.raw_text(start).parse::<u32>().ok()
+    }
+
+    fn parse_decimal(&mut self) -> Option<f64> {
+        let start = self.position;
+        while self.peek().map(|character| character.is_ascii_digit() || character == '.').unwrap_or(false) {
+            self.position += 1;
+        }
+        self.raw_text(start).parse::<f64>().ok()
+    }
+
+    fn read_until(&mut self, terminator: char) -> Option<String> {
+        let start = self.position;
+        while let Some(character) = self.peek() {
+            if character == terminator {
+                let content = self.raw_text(start);
+                self.position += 1;
+                return Some(content);
+            }
+            self.position += 1;
+        }
+        None
+    }
+
+    /// Parses until the end of the template or the end of the current conditional branch
+    fn parse_nodes(&mut self, is_branch: bool) -> Vec<TemplateNode> {
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+        while let Some(character) = self.peek() {
+            if is_branch && character == ']' {
+                break;
+            }
+            if character != '$' {
+                text.push(character);
+                self.position += 1;
+                continue;
+            }
+
+            let start = self.position;
+            self.position += 1;
+            match self.parse_token() {
+                Some(node) => {
+                    if !text.is_empty() {
+                        nodes.push(TemplateNode::Text(std::mem::take(&mut text)));
+                    }
+                    nodes.push(node);
+                },
+                None => {
+                    // Only the $ is consumed, such that nested tokens are still parsed
+                    self.position = start + 1;
+                    text.push('$');
+                },
+            }
+        }
+        if !text.is_empty() {
+            nodes.push(TemplateNode::Text(text));
+        }
+        nodes
+    }
+
+    fn parse_token(&mut self) -> Option<TemplateNode> {
+        match self.peek()? {
+            '{' => {
+                let start = self.position - 1;
+                self.position += 1;
+                let content = self.read_until('}')?;
+                let expression = match parse_expression(&content) {
+                    Some(expression) => expression,
+                    // E.g. functions like $max(...), which must not be read as variables
+                    None => return Some(TemplateNode::Text(self.raw_text(start))),
+                };
+                let mut decimals = None;
+                if self.peek() == Some('.') && self.chars.get(self.position + 1).map(|character| character.is_ascii_digit()).unwrap_or(false) {
+                    self.position += 1;
+                    decimals = self.parse_number().map(|decimals| decimals as usize);
+                }
+                Some(TemplateNode::Expression {
+                    expression,
+                    decimals,
+                    raw: self.raw_text(start),
+                })
+            },
+            '?' => {
+                self.position += 1;
+                self.parse_conditional()
+            },
+            '@' => {
+                self.position += 1;
+                let start = self.position;
+                while self.peek().map(|character| character.is_ascii_alphabetic()).unwrap_or(false) {
+                    self.position += 1;
+                }
+                let is_aura = match self.raw_text(start).as_str() {
+                    "spelldesc" => false,
+                    "spellaura" => true,
+                    _ => return None,
+                };
+                Some(TemplateNode::Include { spell_id: self.parse_number()?, is_aura })
+            },
+            'l' | 'L' => {
+                self.position += 1;
+                let singular = self.read_until(':')?;
+                let plural = self.read_until(';')?;
+                Some(TemplateNode::Plural { singular, plural })
+            },
+            // There is no reader, hence the male form is used
+            'g' | 'G' => {
+                self.position += 1;
+                let male = self.read_until(':')?;
+                self.read_until(';')?;
+                Some(TemplateNode::Text(male))
+            },
+            _ => self.parse_variable().map(TemplateNode::Variable),
+        }
+    }
+
+    /// After the $, e.g. s1, 12345d or /1000;S1
+    fn parse_variable(&mut self) -> Option<Variable> {
+        let start = self.position - 1;
+        let mut factor = None;
+        if let Some(operator) = self.peek().filter(|character| *character == '/' || *character == '*') {
+            self.position += 1;
+            let operand = self.parse_decimal()?;
+            if !self.consume(';') {
+                return None;
+            }
+            factor = Some(if operator == '/' { 1.0 / operand } else { operand });
+        }
+
+        let spell_id = if self.peek()?.is_ascii_digit() { Some(self.parse_number()?) } else { None };
+        let kind = match self.peek()? {
+            's' | 'S' => VariableKind::Value,
+            'm' => VariableKind::MinValue,
+            'M' => VariableKind::MaxValue,
+            'o' | 'O' => VariableKind::TotalValue,
+            'd' | 'D' => VariableKind::Duration,
+            't' | 'T' => VariableKind::Period,
+            'x' | 'X' => VariableKind::ChainTargets,
+            'a' | 'A' => VariableKind::Radius,
+            'r' | 'R' => VariableKind::Range,
+            _ => return None,
+        };
+        self.position += 1;
+        let effect_index = match self.peek() {
+            Some(character @ '1'..='3') => {
+                self.position += 1;
+                character.to_digit(10).unwrap() as usize
+            },
+            _ => 1,
+        };
+        Some(Variable {
+            spell_id,
+            kind,
+            effect_index,
+            factor,
+            raw: self.raw_text(start),
+        })
+    }
+
+    /// After the $?
+    fn parse_conditional(&mut self) -> Option<TemplateNode> {
+        let mut branches = Vec::new();
+        loop {
+            let condition = self.parse_condition()?;
+            branches.push((condition, self.parse_branch()?));
+            if !self.consume('?') {
+                break;
+            }
+        }
+        let otherwise = if self.peek() == Some('[') { self.parse_branch()? } else { Vec::new() };
+        Some(TemplateNode::Conditional { branches, otherwise })
+    }
+
+    fn parse_branch(&mut self) -> Option<Vec<TemplateNode>> {
+        if !self.consume('[') {
+            return None;
+        }
+        let nodes = self.parse_nodes(true);
+        if !self.consume(']') {
+            return None;
+        }
+        Some(nodes)
+    }
+
+    fn parse_condition(&mut self) -> Option<Condition> {
+        let mut any = vec![self.parse_all_conditions()?];
+        while self.consume('|') {
+            any.push(self.parse_all_conditions()?);
+        }
+        Some(if any.len() == 1 { any.pop().unwrap() } else { Condition::Any(any) })
+    }
+
+    fn parse_all_conditions(&mut self) -> Option<Condition> {
+        let mut all = vec![self.parse_single_condition()?];
+        while self.consume('&') {
+            all.push(self.parse_single_condition()?);
+        }
+        Some(if all.len() == 1 { all.pop().unwrap() } else { Condition::All(all) })
+    }
+
+    fn parse_single_condition(&mut self) -> Option<Condition> {
+        if self.consume('!') {
+            return self.parse_single_condition().map(|condition| Condition::Not(Box::new(condition)));
+        }
+        if self.consume('(') {
+            let condition = self.parse_condition()?;
+            return if self.consume(')') { Some(condition) } else { None };
+        }
+        let kind = self.peek().filter(|character| character.is_ascii_alphabetic())?;
+        self.position += 1;
+        let id = self.parse_number()?;
+        Some(match kind {
+            's' => Condition::KnowsSpell(id),
+            'a' => Condition::HasAura(id),
+            _ => Condition::Unknown,
+        })
+    }
+}
+
+fn parse_template(template: &str) -> Vec<TemplateNode> {
+    let chars = template.chars().collect::<Vec<char>>();
+    Parser { chars: &chars, position: 0 }.parse_nodes(false)
+}
+
+fn tokenize_expression(content: &str) -> Option<Vec<ExpressionToken>> {
+    let chars = content.chars().collect::<Vec<char>>();
+    let mut parser = Parser { chars: &chars, position: 0 };
+    let mut tokens = Vec::new();
+    while let Some(character) = parser.peek() {
+        let token = match character {
+            ' ' => {
+                parser.position += 1;
+                continue;
+            },
+            '+' => ExpressionToken::Operator(Operator::Add),
+            '-' => ExpressionToken::Operator(Operator::Subtract),
+            '*' => ExpressionToken::Operator(Operator::Multiply),
+            '/' => ExpressionToken::Operator(Operator::Divide),
+            '(' => ExpressionToken::OpenParenthesis,
+            ')' => ExpressionToken::CloseParenthesis,
+            '$' => {
+                parser.position += 1;
+                tokens.push(ExpressionToken::Variable(parser.parse_variable()?));
+                continue;
+            },
+            '0'..='9' | '.' => {
+                tokens.push(ExpressionToken::Number(parser.parse_decimal()?));
+                continue;
+            },
+            _ => return None,
+        };
+        parser.position += 1;
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+fn parse_expression(content: &str) -> Option<Expression> {
+    let tokens = tokenize_expression(content)?;
+    let mut position = 0;
+    let expression = parse_sum(&tokens, &mut position)?;
+    if position == tokens.len() {
+        Some(expression)
+    } else {
+        None
+    }
+}
+
+fn parse_sum(tokens: &[ExpressionToken], position: &mut usize) -> Option<Expression> {
+    let mut expression = parse_product(tokens, position)?;
+    while let Some(ExpressionToken::Operator(operator @ Operator::Add)) | Some(ExpressionToken::Operator(operator @ Operator::Subtract)) = tokens.get(*position) {
+        *position += 1;
+        expression = Expression::Binary(*operator, Box::new(expression), Box::new(parse_product(tokens, position)?));
+    }
+    Some(expression)
+}
+
+fn parse_product(tokens: &[ExpressionToken], position: &mut usize) -> Option<Expression> {
+    let mut expression = parse_factor(tokens, position)?;
+    while let Some(ExpressionToken::Operator(operator @ Operator::Multiply)) | Some(ExpressionToken::Operator(operator @ Operator::Divide)) = tokens.get(*position) {
+        *position += 1;
+        expression = Expression::Binary(*operator, Box::new(expression), Box::new(parse_factor(tokens, position)?));
+    }
+    Some(expression)
+}
+
+fn parse_factor(tokens: &[ExpressionToken], position: &mut usize) -> Option<Expression> {
+    let token = tokens.get(*position)?;
+    *position += 1;
+    match token {
+        ExpressionToken::Number(number) => Some(Expression::Number(*number)),
+        ExpressionToken::Variable(variable) => Some(Expression::Variable(variable.clone())),
+        ExpressionToken::Operator(Operator::Subtract) => parse_factor(tokens, position).map(|expression| Expression::Negate(Box::new(expression))),
+        ExpressionToken::OpenParenthesis => {
+            let expression = parse_sum(tokens, position)?;
+            if tokens.get(*position) != Some(&ExpressionToken::CloseParenthesis) {
+                return None;
+            }
+            *position += 1;
+            Some(expression)
+        },
+        _ => None,
+    }
+}
+
+struct Renderer<'a, T: SpellTemplateContext> {
+    context: &'a T,
+    // Plural forms refer to the last number before them
+    last_number: Option<f64>,
+}
+
+impl<'a, T: SpellTemplateContext> Renderer<'a, T> {
+    fn render_template(&mut self, template: &str, spell_id: u32, depth: u8) -> String {
+        let nodes = parse_template(template);
+        let mut result = String::new();
+        self.render_nodes(&nodes, spell_id, depth, &mut result);
+        result
+    }
+
+    fn render_nodes(&mut self, nodes: &[TemplateNode], spell_id: u32, depth: u8, result: &mut String) {
+        for node in nodes.iter() {
+            match node {
+                TemplateNode::Text(text) => result.push_str(text),
+                TemplateNode::Variable(variable) => match self.render_variable(variable, spell_id) {
+                    Some(rendered) => result.push_str(&rendered),
+                    None => result.push_str(&variable.raw),
+                },
+                TemplateNode::Expression { expression, decimals, raw } => match self.evaluate(expression, spell_id) {
+                    Some(value) => {
+                        self.last_number = Some(value.abs());
+                        result.push_str(&format_number(value, *decimals));
+                    },
+                    None => result.push_str(raw),
+                },
+                TemplateNode::Conditional { branches, otherwise } => {
+                    let nodes = branches.iter().find(|(condition, _)| self.is_fulfilled(condition)).map(|(_, nodes)| nodes).unwrap_or(otherwise);
+                    self.render_nodes(nodes, spell_id, depth, result);
+                },
+                TemplateNode::Plural { singular, plural } => {
+                    if self.last_number == Some(1.0) {
+                        result.push_str(singular);
+                    } else {
+                        result.push_str(plural);
+                    }
+                },
+                TemplateNode::Include { spell_id: included_spell_id, is_aura } => {
+                    let template = if *is_aura {
+                        self.context.get_aura_template(*included_spell_id)
+                    } else {
+                        self.context.get_description_template(*included_spell_id)
+                    };
+                    if let Some(template) = template.filter(|_| depth < MAX_INCLUDE_DEPTH) {
+                        let included = self.render_template(&template, *included_spell_id, depth + 1);
+                        result.push_str(&included);
+                    }
+                },
+            }
+        }
+    }
+
+    fn is_fulfilled(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::KnowsSpell(spell_id) => self.context.knows_spell(*spell_id),
+            Condition::HasAura(spell_id) => self.context.has_aura(*spell_id),
+            Condition::Unknown => false,
+            Condition::Not(condition) => !self.is_fulfilled(condition),
+            Condition::Any(conditions) => conditions.iter().any(|condition| self.is_fulfilled(condition)),
+            Condition::All(conditions) => conditions.iter().all(|condition| self.is_fulfilled(condition)),
+        }
+    }
+
+    fn render_variable(&mut self, variable: &Variable, spell_id: u32) -> Option<String> {
+        let spell_id = variable.spell_id.unwrap_or(spell_id);
+        if variable.kind == VariableKind::Duration && variable.factor.is_none() {
+            let spell = self.context.get_spell(spell_id)?;
+            return Some(self.context.format_duration(spell.duration.abs() as u32));
+        }
+
+        // Ranges are only shown if the value is not modified
+        if variable.kind == VariableKind::Value && variable.factor.is_none() {
+            let spell_effect = get_spell_effect(self.context, spell_id, variable.effect_index)?;
+            let min_value = spell_effect.points_lower.abs();
+            let max_value = spell_effect.points_upper.abs();
+            self.last_number = Some(max_value as f64);
+            // Effects without a range may not store the lower bound
+            if min_value != 0 && min_value != max_value {
+                return Some(format!("{} to {}", min_value.min(max_value), min_value.max(max_value)));
+            }
+            return Some(max_value.to_string());
+        }
+
+        let value = self.get_value(variable, spell_id)?;
+        self.last_number = Some(value.abs());
+        Some(format_number(value, None))
+    }
+
+    fn evaluate(&self, expression: &Expression, spell_id: u32) -> Option<f64> {
+        match expression {
+            Expression::Number(number) => Some(*number),
+            Expression::Variable(variable) => self.get_value(variable, variable.spell_id.unwrap_or(spell_id)),
+            Expression::Negate(expression) => self.evaluate(expression, spell_id).map(|value| -value),
+            Expression::Binary(operator, left, right) => {
+                let left = self.evaluate(left, spell_id)?;
+                let right = self.evaluate(right, spell_id)?;
+                match operator {
+                    Operator::Add => Some(left + right),
+                    Operator::Subtract => Some(left - right),
+                    Operator::Multiply => Some(left * right),
+                    Operator::Divide if right != 0.0 => Some(left / right),
+                    Operator::Divide => None,
+                }
+            },
+        }
+    }
+
+    /// Effect values are signed here, e.g. for ${$m1/-1000}
+    fn get_value(&self, variable: &Variable, spell_id: u32) -> Option<f64> {
+        let value = match variable.kind {
+            VariableKind::Duration => self.context.get_spell(spell_id)?.duration.abs() as f64 / 1000.0,
+            VariableKind::Range => self.context.get_spell(spell_id)?.range_max as f64,
+            kind => {
+                let spell_effect = get_spell_effect(self.context, spell_id, variable.effect_index)?;
+                match kind {
+                    VariableKind::MinValue if spell_effect.points_lower != 0 => spell_effect.points_lower as f64,
+                    VariableKind::TotalValue => {
+                        let duration = self.context.get_spell(spell_id)?.duration.abs() as u32;
+                        // Without the period, only the value per tick is known
+                        let ticks = duration.checked_div(spell_effect.aura_period).unwrap_or(1);
+                        spell_effect.points_upper as f64 * ticks as f64
+                    },
+                    VariableKind::Period if spell_effect.aura_period == 0 => return None,
+                    VariableKind::Period => spell_effect.aura_period as f64 / 1000.0,
+                    VariableKind::ChainTargets => spell_effect.chain_targets as f64,
+                    VariableKind::Radius => spell_effect.radius as f64,
+                    _ => spell_effect.points_upper as f64,
+                }
+            },
+        };
+        Some(value * variable.factor.unwrap_or(1.0))
+    }
+}
+
+/// Effects without points are not stored, hence variables of such effects are not resolved
+fn get_spell_effect(context: &impl SpellTemplateContext, spell_id: u32, effect_index: usize) -> Option<SpellEffect> {
+    context.get_spell_effects(spell_id).into_iter().find(|spell_effect| spell_effect.effect_index as usize == effect_index)
+}
+
+fn format_number(value: f64, decimals: Option<usize>) -> String {
+    let value = value.abs();
+    match decimals {
+        Some(decimals) => format!("{:.*}", decimals, value),
+        None if (value - value.round()).abs() < 0.000_001 => format!("{}", value.round() as i64),
+        None => format!("{:.1}", value),
+    }
+}
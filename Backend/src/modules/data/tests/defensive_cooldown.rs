@@ -0,0 +1,24 @@
+use crate::modules::data::domain_value::DefensiveCooldown;
+use crate::modules::data::{tools::RetrieveDefensiveCooldown, Data};
+
+fn defensive_cooldown(spell_id: u32, base_spell_id: u32, talent_tree: Option<u8>) -> DefensiveCooldown {
+    DefensiveCooldown {
+        expansion_id: 3,
+        spell_id,
+        base_spell_id,
+        hero_class_id: 1,
+        talent_tree,
+    }
+}
+
+#[test]
+fn get_defensive_cooldowns() {
+    let mut data = Data::default();
+    data.defensive_cooldowns.insert((3, 1), vec![defensive_cooldown(871, 871, None), defensive_cooldown(12975, 12975, Some(2))]);
+
+    let spell_ids = |talent_tree: Option<u8>| data.get_defensive_cooldowns(3, 1, talent_tree).into_iter().map(|defensive_cooldown| defensive_cooldown.spell_id).collect::<Vec<u32>>();
+    assert_eq!(spell_ids(Some(2)), vec![871, 12975]);
+    assert_eq!(spell_ids(Some(0)), vec![871]);
+    assert_eq!(spell_ids(None), vec![871]);
+    assert!(data.get_defensive_cooldowns(2, 1, Some(2)).is_empty());
+}
@@ -0,0 +1,105 @@
+use crate::modules::data::domain_value::{EncounterKillCondition, EncounterRule, EncounterRuleTrigger, EncounterSignal};
+use crate::modules::data::{tools::RetrieveEncounterRule, Data};
+
+fn encounter_rule(id: u32, hard_mode_encounter_id: u32, min_triggers: u8, max_triggers: Option<u8>, triggers: Vec<(EncounterSignal, bool)>) -> EncounterRule {
+    EncounterRule {
+        id,
+        encounter_id: 42,
+        difficulty_id: None,
+        hard_mode_encounter_id,
+        min_triggers,
+        max_triggers,
+        first_signals: None,
+        max_duration: None,
+        triggers: triggers.into_iter().map(|(signal, is_excluded)| EncounterRuleTrigger { signal, is_excluded }).collect(),
+    }
+}
+
+#[test]
+fn get_encounter_rules() {
+    let mut data = Data::default();
+    let rule = encounter_rule(1, 43, 1, None, vec![(EncounterSignal::NpcDeath(1), false)]);
+    data.encounter_rules.insert(42, vec![rule.clone()]);
+
+    assert_eq!(data.get_encounter_rules(42), vec![rule.clone()]);
+    assert!(data.get_encounter_rules(0).is_empty());
+    assert_eq!(data.get_all_encounter_rules(), vec![rule]);
+    assert!(data.is_encounter_signal(42, &EncounterSignal::NpcDeath(1)));
+    assert!(!data.is_encounter_signal(42, &EncounterSignal::SpellCast(1)));
+}
+
+#[test]
+fn hard_mode_by_number_of_triggers() {
+    let mut data = Data::default();
+    let triggers = vec![(EncounterSignal::AuraApplication(1), false), (EncounterSignal::AuraApplication(2), false)];
+    data.encounter_rules.insert(42, vec![encounter_rule(1, 43, 1, Some(1), triggers.clone()), encounter_rule(2, 44, 2, Some(2), triggers)]);
+
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &[], 0), None);
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &[EncounterSignal::AuraApplication(2)], 0), Some(43));
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &[EncounterSignal::AuraApplication(2), EncounterSignal::AuraApplication(1)], 0), Some(44));
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &[EncounterSignal::SpellCast(1)], 0), None);
+}
+
+#[test]
+fn hard_mode_by_order_of_signals() {
+    let mut data = Data::default();
+    let mut rule = encounter_rule(1, 43, 2, None, vec![(EncounterSignal::NpcDeath(1), false), (EncounterSignal::NpcDeath(2), false), (EncounterSignal::NpcDeath(3), true)]);
+    rule.first_signals = Some(2);
+    data.encounter_rules.insert(42, vec![rule]);
+
+    let last_excluded = [EncounterSignal::NpcDeath(1), EncounterSignal::NpcDeath(2), EncounterSignal::NpcDeath(3)];
+    let first_excluded = [EncounterSignal::NpcDeath(3), EncounterSignal::NpcDeath(1), EncounterSignal::NpcDeath(2)];
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &last_excluded, 0), Some(43));
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &first_excluded, 0), None);
+}
+
+#[test]
+fn hard_mode_by_duration_and_difficulty() {
+    let mut data = Data::default();
+    let mut rule = encounter_rule(1, 43, 0, None, Vec::new());
+    rule.max_duration = Some(120000);
+    rule.difficulty_id = Some(4);
+    data.encounter_rules.insert(42, vec![rule]);
+
+    assert_eq!(data.get_hard_mode_encounter_id(42, Some(4), &[], 120000), Some(43));
+    assert_eq!(data.get_hard_mode_encounter_id(42, Some(4), &[], 120001), None);
+    assert_eq!(data.get_hard_mode_encounter_id(42, Some(3), &[], 60000), None);
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &[], 60000), None);
+}
+
+#[test]
+fn kill_conditions() {
+    let mut data = Data::default();
+    data.encounter_kill_conditions.insert(42, vec![EncounterKillCondition::FinalDeath(1), EncounterKillCondition::Vehicle(2)]);
+
+    assert!(data.can_death_end_encounter(42, 1));
+    assert!(!data.can_death_end_encounter(42, 2));
+    assert!(data.can_death_end_encounter(43, 2));
+    assert!(data.is_encounter_vehicle(42, 2));
+    assert!(!data.is_encounter_vehicle(42, 1));
+    assert!(!data.is_encounter_vehicle(43, 2));
+}
+
+#[test]
+fn hard_mode_by_number_of_keepers() {
+    // Yogg-Saron, see data_encounter_rule
+    let mut data = Data::default();
+    let blessings = vec![62670, 62650, 62671, 62702];
+    let triggers = blessings.iter().map(|spell_id| (EncounterSignal::AuraApplication(*spell_id), false)).collect::<Vec<(EncounterSignal, bool)>>();
+    data.encounter_rules.insert(
+        42,
+        vec![
+            encounter_rule(13, 158, 3, Some(3), triggers.clone()),
+            encounter_rule(14, 159, 2, Some(2), triggers.clone()),
+            encounter_rule(15, 160, 1, Some(1), triggers.clone()),
+            encounter_rule(16, 161, 0, Some(0), triggers),
+        ],
+    );
+
+    let keepers = |num_keepers: usize| blessings.iter().take(num_keepers).map(|spell_id| EncounterSignal::AuraApplication(*spell_id)).collect::<Vec<EncounterSignal>>();
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &keepers(4), 0), None);
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &keepers(3), 0), Some(158));
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &keepers(2), 0), Some(159));
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &keepers(1), 0), Some(160));
+    assert_eq!(data.get_hard_mode_encounter_id(42, None, &keepers(0), 0), Some(161));
+}
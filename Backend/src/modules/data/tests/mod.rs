@@ -1,8 +1,10 @@
+mod defensive_cooldown;
 mod difficulty;
 mod dispel_type;
 mod enchant;
 mod encounter;
 mod encounter_npc;
+mod encounter_rule;
 mod expansion;
 mod gem;
 mod hero_class;
@@ -29,9 +31,11 @@ mod npc;
 mod power_type;
 mod profession;
 mod race;
+mod ranking_bracket;
 mod server;
 mod spell;
 mod spell_description;
 mod spell_effect;
+mod spell_template;
 mod stat_type;
 mod title;
@@ -0,0 +1,35 @@
+use crate::modules::data::domain_value::RankingBracket;
+use crate::modules::data::{tools::RetrieveRankingBracket, Data};
+
+fn ranking_bracket(id: u32, start_ts: u64, end_ts: Option<u64>) -> RankingBracket {
+    RankingBracket {
+        id,
+        expansion_id: 2,
+        name: format!("Phase {}", id),
+        start_ts,
+        end_ts,
+    }
+}
+
+#[test]
+fn get_ranking_bracket() {
+    let mut data = Data::default();
+    data.ranking_brackets.insert(1, ranking_bracket(1, 1000, Some(2000)));
+
+    assert_eq!(data.get_ranking_bracket(1), Some(ranking_bracket(1, 1000, Some(2000))));
+    assert!(data.get_ranking_bracket(2).is_none());
+    assert_eq!(data.get_all_ranking_brackets().len(), 1);
+}
+
+#[test]
+fn get_ranking_bracket_by_timestamp() {
+    let mut data = Data::default();
+    data.ranking_brackets.insert(1, ranking_bracket(1, 1000, Some(2000)));
+    data.ranking_brackets.insert(2, ranking_bracket(2, 2000, None));
+
+    assert!(data.get_ranking_bracket_by_timestamp(2, 999).is_none());
+    assert_eq!(data.get_ranking_bracket_by_timestamp(2, 1999).map(|ranking_bracket| ranking_bracket.id), Some(1));
+    assert_eq!(data.get_ranking_bracket_by_timestamp(2, 2000).map(|ranking_bracket| ranking_bracket.id), Some(2));
+    assert_eq!(data.get_ranking_bracket_by_timestamp(2, u64::MAX).map(|ranking_bracket| ranking_bracket.id), Some(2));
+    assert!(data.get_ranking_bracket_by_timestamp(1, 1500).is_none());
+}
@@ -15,6 +15,8 @@ fn get_spell_effects() {
         points_upper: 0,
         chain_targets: 0,
         radius: 0,
+        aura_period: 0,
+        effect_index: 1,
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(spell_id, vec![spell_effect.clone()]);
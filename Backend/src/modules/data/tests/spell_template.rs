@@ -0,0 +1,154 @@
+use crate::modules::data::domain_value::{Spell, SpellEffect};
+use crate::modules::data::material::{render_spell_template, SpellTemplateContext};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Default)]
+struct TemplateContext {
+    spells: HashMap<u32, Spell>,
+    spell_effects: HashMap<u32, Vec<SpellEffect>>,
+    descriptions: HashMap<u32, String>,
+    known_spells: HashSet<u32>,
+}
+
+impl TemplateContext {
+    fn add_spell(&mut self, spell_id: u32, duration: i32, description: &str, spell_effects: Vec<(u8, i32, i32, u32)>) {
+        self.spells.insert(
+            spell_id,
+            Spell {
+                id: spell_id,
+                expansion_id: 1,
+                localization_id: 0,
+                subtext_localization_id: 0,
+                cost: 0,
+                cost_in_percent: false,
+                power_type: 0,
+                cast_time: 0,
+                school_mask: 0,
+                dispel_type: 0,
+                range_max: 30,
+                cooldown: 0,
+                duration,
+                icon: 0,
+                description_localization_id: 0,
+                aura_localization_id: 0,
+            },
+        );
+        self.spell_effects.insert(
+            spell_id,
+            spell_effects
+                .into_iter()
+                .map(|(effect_index, points_lower, points_upper, aura_period)| SpellEffect {
+                    id: 0,
+                    expansion_id: 1,
+                    spell_id,
+                    points_lower,
+                    points_upper,
+                    chain_targets: 3,
+                    radius: 10,
+                    aura_period,
+                    effect_index,
+                })
+                .collect(),
+        );
+        self.descriptions.insert(spell_id, description.to_string());
+    }
+
+    fn render(&self, spell_id: u32) -> String {
+        render_spell_template(self.descriptions.get(&spell_id).unwrap(), spell_id, self)
+    }
+}
+
+impl SpellTemplateContext for TemplateContext {
+    fn get_spell(&self, spell_id: u32) -> Option<Spell> {
+        self.spells.get(&spell_id).cloned()
+    }
+
+    fn get_spell_effects(&self, spell_id: u32) -> Vec<SpellEffect> {
+        self.spell_effects.get(&spell_id).cloned().unwrap_or_default()
+    }
+
+    fn get_description_template(&self, spell_id: u32) -> Option<String> {
+        self.descriptions.get(&spell_id).cloned()
+    }
+
+    fn get_aura_template(&self, _spell_id: u32) -> Option<String> {
+        None
+    }
+
+    fn format_duration(&self, duration: u32) -> String {
+        format!("{} sec", duration / 1000)
+    }
+
+    fn knows_spell(&self, spell_id: u32) -> bool {
+        self.known_spells.contains(&spell_id)
+    }
+}
+
+#[test]
+fn render_vanilla_tooltips() {
+    let mut context = TemplateContext::default();
+    context.add_spell(133, 4000, "Hurls a fiery ball that causes $s1 Fire damage and an additional $o2 Fire damage over $d.", vec![(1, 14, 22, 0), (2, 1, 1, 2000)]);
+    context.add_spell(139, 15000, "Heals the target of $o1 damage over $d.", vec![(1, 9, 9, 3000)]);
+    context.add_spell(1459, 1800000, "Increases the target's Intellect by $s1 for $d.", vec![(1, 2, 2, 0)]);
+
+    assert_eq!(context.render(133), "Hurls a fiery ball that causes 14 to 22 Fire damage and an additional 2 Fire damage over 4 sec.");
+    assert_eq!(context.render(139), "Heals the target of 45 damage over 15 sec.");
+    assert_eq!(context.render(1459), "Increases the target's Intellect by 2 for 1800 sec.");
+}
+
+#[test]
+fn render_tbc_tooltips() {
+    let mut context = TemplateContext::default();
+    context.add_spell(11069, 0, "Reduces the casting time of your Fireball spell by ${$m1/-1000}.1 sec.", vec![(1, -100, -100, 0)]);
+    context.add_spell(
+        26573,
+        8000,
+        "Consecrates the land beneath the Paladin, doing $/8;s1 Holy damage every $t1 sec over $d to enemies who enter the area.",
+        vec![(1, 64, 64, 1000)],
+    );
+    context.add_spell(2818, 12000, "Deals ${$m1*$d/$t1} Nature damage over $d.", vec![(1, 9, 9, 3000)]);
+
+    assert_eq!(context.render(11069), "Reduces the casting time of your Fireball spell by 0.1 sec.");
+    assert_eq!(context.render(26573), "Consecrates the land beneath the Paladin, doing 8 Holy damage every 1 sec over 8 sec to enemies who enter the area.");
+    assert_eq!(context.render(2818), "Deals 36 Nature damage over 12 sec.");
+}
+
+#[test]
+fn render_wotlk_tooltips() {
+    let mut context = TemplateContext::default();
+    context.add_spell(12654, 4000, "Deals Fire damage every $t1 sec.", vec![(1, 0, 0, 2000)]);
+    context.add_spell(
+        11119,
+        0,
+        "Your critical strikes from non-periodic Fire damage spells cause the target to burn for an additional $s1% of your spell's damage over $12654d.",
+        vec![(1, 8, 8, 0)],
+    );
+    context.add_spell(44448, 0, "$?s56368[Your Hot Streak also triggers][Your critical strikes build] up to $s1 $lstack:stacks;.", vec![(1, 1, 1, 0)]);
+    context.add_spell(54646, 0, "$@spelldesc11119", vec![(1, 0, 0, 0)]);
+
+    assert_eq!(
+        context.render(11119),
+        "Your critical strikes from non-periodic Fire damage spells cause the target to burn for an additional 8% of your spell's damage over 4 sec."
+    );
+    assert_eq!(context.render(44448), "Your critical strikes build up to 1 stack.");
+    context.known_spells.insert(56368);
+    assert_eq!(context.render(44448), "Your Hot Streak also triggers up to 1 stack.");
+    assert_eq!(context.render(54646), context.render(11119));
+}
+
+#[test]
+fn render_unknown_tokens_raw() {
+    let mut context = TemplateContext::default();
+    context.add_spell(1, 10000, "Costs $n charges and $<damage>, ticks every $t1 sec, for $99999s1 and ${$max(1,2)} over $d.", vec![(1, 5, 5, 0)]);
+
+    assert_eq!(context.render(1), "Costs $n charges and $<damage>, ticks every $t1 sec, for $99999s1 and ${$max(1,2)} over 10 sec.");
+}
+
+#[test]
+fn render_effects_by_their_index() {
+    let mut context = TemplateContext::default();
+    // The first effect has no points and is not stored
+    context.add_spell(9346, 6000, "Deals $s2 damage every $t3 sec, $s1 and $o1 over $d.", vec![(2, 18, 18, 0), (3, 5, 5, 2000)]);
+
+    assert_eq!(context.render(9346), "Deals 18 damage every 2 sec, $s1 and $o1 over 6 sec.");
+}
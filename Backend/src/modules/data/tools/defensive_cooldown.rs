@@ -0,0 +1,22 @@
+use crate::modules::data::domain_value::DefensiveCooldown;
+use crate::modules::data::Data;
+
+pub trait RetrieveDefensiveCooldown {
+    /// The defensive cooldowns of the class, those of talents only if the talent tree is known
+    fn get_defensive_cooldowns(&self, expansion_id: u8, hero_class_id: u8, talent_tree: Option<u8>) -> Vec<DefensiveCooldown>;
+}
+
+impl RetrieveDefensiveCooldown for Data {
+    fn get_defensive_cooldowns(&self, expansion_id: u8, hero_class_id: u8, talent_tree: Option<u8>) -> Vec<DefensiveCooldown> {
+        self.defensive_cooldowns
+            .get(&(expansion_id, hero_class_id))
+            .map(|defensive_cooldowns| {
+                defensive_cooldowns
+                    .iter()
+                    .filter(|defensive_cooldown| defensive_cooldown.talent_tree.is_none() || defensive_cooldown.talent_tree == talent_tree)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::modules::data::domain_value::{EncounterKillCondition, EncounterRule, EncounterSignal};
+use crate::modules::data::Data;
+
+pub trait RetrieveEncounterRule {
+    fn get_encounter_rules(&self, encounter_id: u32) -> Vec<EncounterRule>;
+    fn get_all_encounter_rules(&self) -> Vec<EncounterRule>;
+    fn is_encounter_signal(&self, encounter_id: u32, signal: &EncounterSignal) -> bool;
+    /// The first rule of the encounter that applies to the signals of the attempt
+    fn get_hard_mode_encounter_id(&self, encounter_id: u32, difficulty_id: Option<u8>, signals: &[EncounterSignal], duration: u64) -> Option<u32>;
+    fn can_death_end_encounter(&self, encounter_id: u32, npc_id: u32) -> bool;
+    fn is_encounter_vehicle(&self, encounter_id: u32, npc_id: u32) -> bool;
+}
+
+impl RetrieveEncounterRule for Data {
+    fn get_encounter_rules(&self, encounter_id: u32) -> Vec<EncounterRule> {
+        self.encounter_rules.get(&encounter_id).cloned().unwrap_or_else(Vec::new)
+    }
+
+    fn get_all_encounter_rules(&self) -> Vec<EncounterRule> {
+        self.encounter_rules.iter().flat_map(|(_, encounter_rules)| encounter_rules.iter().cloned()).collect()
+    }
+
+    fn is_encounter_signal(&self, encounter_id: u32, signal: &EncounterSignal) -> bool {
+        self.encounter_rules
+            .get(&encounter_id)
+            .map(|encounter_rules| encounter_rules.iter().any(|encounter_rule| encounter_rule.triggers.iter().any(|trigger| trigger.signal == *signal)))
+            .unwrap_or(false)
+    }
+
+    fn get_hard_mode_encounter_id(&self, encounter_id: u32, difficulty_id: Option<u8>, signals: &[EncounterSignal], duration: u64) -> Option<u32> {
+        self.encounter_rules
+            .get(&encounter_id)?
+            .iter()
+            .find(|encounter_rule| rule_applies(encounter_rule, difficulty_id, signals, duration))
+            .map(|encounter_rule| encounter_rule.hard_mode_encounter_id)
+    }
+
+    fn can_death_end_encounter(&self, encounter_id: u32, npc_id: u32) -> bool {
+        let mut final_deaths = self
+            .encounter_kill_conditions
+            .get(&encounter_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|kill_condition| match kill_condition {
+                EncounterKillCondition::FinalDeath(final_npc_id) => Some(*final_npc_id),
+                _ => None,
+            })
+            .peekable();
+        final_deaths.peek().is_none() || final_deaths.any(|final_npc_id| final_npc_id == npc_id)
+    }
+
+    fn is_encounter_vehicle(&self, encounter_id: u32, npc_id: u32) -> bool {
+        self.encounter_kill_conditions
+            .get(&encounter_id)
+            .map(|kill_conditions| kill_conditions.contains(&EncounterKillCondition::Vehicle(npc_id)))
+            .unwrap_or(false)
+    }
+}
+
+fn rule_applies(encounter_rule: &EncounterRule, difficulty_id: Option<u8>, signals: &[EncounterSignal], duration: u64) -> bool {
+    if encounter_rule.difficulty_id.is_some() && encounter_rule.difficulty_id != difficulty_id {
+        return false;
+    }
+    if encounter_rule.max_duration.map(|max_duration| duration > max_duration).unwrap_or(false) {
+        return false;
+    }
+
+    let considered_signals = match encounter_rule.first_signals {
+        Some(first_signals) => &signals[..signals.len().min(first_signals as usize)],
+        None => signals,
+    };
+    if encounter_rule.triggers.iter().any(|trigger| trigger.is_excluded && considered_signals.contains(&trigger.signal)) {
+        return false;
+    }
+    let num_triggered = encounter_rule.triggers.iter().filter(|trigger| !trigger.is_excluded && considered_signals.contains(&trigger.signal)).count();
+    num_triggered >= encounter_rule.min_triggers as usize && encounter_rule.max_triggers.map(|max_triggers| num_triggered <= max_triggers as usize).unwrap_or(true)
+}
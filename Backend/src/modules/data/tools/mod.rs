@@ -1,17 +1,19 @@
 pub use self::{
-    difficulty::RetrieveDifficulty, dispel_type::RetrieveDispelType, enchant::RetrieveEnchant, encounter::RetrieveEncounter, encounter_npc::RetrieveEncounterNpc, expansion::RetrieveExpansion, gem::RetrieveGem, hero_class::RetrieveHeroClass,
+    defensive_cooldown::RetrieveDefensiveCooldown, difficulty::RetrieveDifficulty, dispel_type::RetrieveDispelType, enchant::RetrieveEnchant, encounter::RetrieveEncounter, encounter_npc::RetrieveEncounterNpc, encounter_rule::RetrieveEncounterRule, expansion::RetrieveExpansion, gem::RetrieveGem, hero_class::RetrieveHeroClass,
     icon::RetrieveIcon, item::RetrieveItem, item_bonding::RetrieveItemBonding, item_class::RetrieveItemClass, item_damage::RetrieveItemDamage, item_damage_type::RetrieveItemDamageType, item_effect::RetrieveItemEffect,
     item_inventory_type::RetrieveItemInventoryType, item_quality::RetrieveItemQuality, item_random_property::RetrieveItemRandomProperty, item_random_property_points::RetrieveItemRandomPropertyPoints, item_sheath::RetrieveItemSheath,
     item_socket::RetrieveItemSocket, item_stat::RetrieveItemStat, itemset_effect::RetrieveItemsetEffect, itemset_name::RetrieveItemsetName, language::RetrieveLanguage, localization::RetrieveLocalization, map::RetrieveMap, npc::RetrieveNPC,
-    power_type::RetrievePowerType, profession::RetrieveProfession, race::RetrieveRace, server::RetrieveServer, spell::RetrieveSpell, spell_description::SpellDescription, spell_effect::RetrieveSpellEffect, stat_type::RetrieveStatType,
+    power_type::RetrievePowerType, profession::RetrieveProfession, race::RetrieveRace, ranking_bracket::RetrieveRankingBracket, server::RetrieveServer, spell::RetrieveSpell, spell_description::SpellDescription, spell_effect::RetrieveSpellEffect, stat_type::RetrieveStatType,
     title::RetrieveTitle,
 };
 
+mod defensive_cooldown;
 mod difficulty;
 mod dispel_type;
 mod enchant;
 mod encounter;
 mod encounter_npc;
+mod encounter_rule;
 mod expansion;
 mod gem;
 mod hero_class;
@@ -38,6 +40,7 @@ mod npc;
 mod power_type;
 mod profession;
 mod race;
+mod ranking_bracket;
 mod server;
 mod spell;
 mod spell_description;
@@ -0,0 +1,26 @@
+use crate::modules::data::domain_value::RankingBracket;
+use crate::modules::data::Data;
+
+pub trait RetrieveRankingBracket {
+    fn get_ranking_bracket(&self, id: u32) -> Option<RankingBracket>;
+    fn get_all_ranking_brackets(&self) -> Vec<RankingBracket>;
+    fn get_ranking_bracket_by_timestamp(&self, expansion_id: u8, timestamp: u64) -> Option<RankingBracket>;
+}
+
+impl RetrieveRankingBracket for Data {
+    fn get_ranking_bracket(&self, id: u32) -> Option<RankingBracket> {
+        self.ranking_brackets.get(&id).cloned()
+    }
+
+    fn get_all_ranking_brackets(&self) -> Vec<RankingBracket> {
+        self.ranking_brackets.values().cloned().collect()
+    }
+
+    fn get_ranking_bracket_by_timestamp(&self, expansion_id: u8, timestamp: u64) -> Option<RankingBracket> {
+        self.ranking_brackets
+            .values()
+            .filter(|ranking_bracket| ranking_bracket.expansion_id == expansion_id && ranking_bracket.contains(timestamp))
+            .max_by_key(|ranking_bracket| ranking_bracket.start_ts)
+            .cloned()
+    }
+}
@@ -2,6 +2,8 @@ use language::{domain_value::Language, material::Dictionary, tools::Get};
 use regex::Regex;
 
 use crate::modules::data::{
+    domain_value::{Spell, SpellEffect},
+    material::{render_spell_template, SpellTemplateContext},
     tools::{RetrieveLocalization, RetrieveSpell, RetrieveSpellEffect},
     Data, Stat,
 };
@@ -14,104 +16,12 @@ pub trait SpellDescription {
 
 impl SpellDescription for Data {
     fn get_localized_spell_description(&self, expansion_id: u8, language_id: u8, spell_id: u32) -> Option<String> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"\$(\d+)(s\d|x\d|a\d|o\d|m\d|M\d|d|o)").unwrap();
-        }
-
         let spell = self.get_spell(expansion_id, spell_id)?;
-        let mut template = self.get_localization(language_id, spell.description_localization_id).map(|localization| localization.content).unwrap();
-
-        let spell_effects = self.get_spell_effects(expansion_id, spell_id)?;
-        template = template.replace("$d1", &format_duration(&self.dictionary, language_id, spell.duration.abs() as u32));
-        template = template.replace("$d", &format_duration(&self.dictionary, language_id, spell.duration.abs() as u32));
-
-        let spell_effects_len = spell_effects.len();
-        for (i, se_item) in spell_effects.iter().enumerate() {
-            if i + 1 == spell_effects_len {
-                template = template.replace(&format!("$s{}", i + 2), &se_item.points_upper.abs().to_string());
-            }
-
-            template = template.replace(&format!("$s{}", i + 1), &se_item.points_upper.abs().to_string());
-            template = template.replace(&format!("${{$m{}/-1000}}.1", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 1000.0).abs()));
-            template = template.replace(&format!("${{$m{}/-1000}}.2", i + 1), &format!("{:.2}", (se_item.points_upper as f64 / 1000.0).abs()));
-            template = template.replace(&format!("${{$m{}/1000}}", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 1000.0).abs()));
-            template = template.replace(&format!("${{$m{}/-1000}}", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 1000.0).abs()));
-            template = template.replace(&format!("${{$m{}/-10}}", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 10.0).abs()));
-            template = template.replace(&format!("$/1000;s{}", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 1000.0).abs()));
-            template = template.replace(&format!("$/1000;S{}", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 1000.0).abs()));
-            template = template.replace(&format!("$/10;s{}", i + 1), &format!("{:.1}", (se_item.points_upper as f64 / 10.0).abs()));
-            template = template.replace(&format!("$M{}", i + 1), &se_item.points_upper.to_string());
-            template = template.replace(&format!("$m{}", i + 1), &se_item.points_upper.to_string());
-            template = template.replace(&format!("$o{}", i + 1), &se_item.points_upper.to_string());
-            template = template.replace(&format!("$x{}", i + 1), &se_item.chain_targets.to_string());
-            template = template.replace(&format!("$a{}", i + 1), &se_item.radius.to_string());
-            template = template.replace("${50/10}", "5");
-
-            template = template.replace("$o", &se_item.points_upper.to_string()); // ?!
-            if RE.is_match(&template) {
-                let mut temp_res = template.clone();
-                for capture in RE.captures_iter(&template) {
-                    if capture.len() < 2 {
-                        continue;
-                    }
-
-                    let inner_spell_id_res = capture[1].parse::<u32>();
-                    if inner_spell_id_res.is_err() {
-                        continue;
-                    }
-                    let inner_spell_id = inner_spell_id_res.unwrap();
-
-                    let inner_spell_res = self.get_spell(expansion_id, inner_spell_id);
-                    if inner_spell_res.is_none() {
-                        continue;
-                    }
-                    let inner_spell = inner_spell_res.unwrap();
-                    temp_res = temp_res.replace(&format!("${}d1", capture[1].to_string()), &format_duration(&self.dictionary, language_id, inner_spell.duration.abs() as u32));
-                    temp_res = temp_res.replace(&format!("${}d", capture[1].to_string()), &format_duration(&self.dictionary, language_id, inner_spell.duration.abs() as u32));
-                    let inner_spell_effects = self.get_spell_effects(expansion_id, inner_spell_id).unwrap();
-                    for (i, ise_item) in inner_spell_effects.iter().enumerate() {
-                        temp_res = temp_res.replace(&format!("${}s{}", capture[1].to_string(), i + 1), &ise_item.points_upper.abs().to_string());
-                        temp_res = temp_res.replace(&format!("${{${}m{}/-1000}}.1", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 1000.0).abs()));
-                        temp_res = temp_res.replace(&format!("${{${}m{}/-1000}}.2", capture[1].to_string(), i + 1), &format!("{:.2}", (ise_item.points_upper as f64 / 1000.0).abs()));
-                        temp_res = temp_res.replace(&format!("${{${}m{}*15/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 * 0.15).abs()));
-                        temp_res = temp_res.replace(&format!("${{${}m{}*45/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 * 0.45).abs()));
-                        temp_res = temp_res.replace(&format!("${}{{$m{}/1000}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 1000.0).abs()));
-                        temp_res = temp_res.replace(&format!("${}{{$m{}/-1000}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 1000.0).abs()));
-                        temp_res = temp_res.replace(&format!("${}{{$m{}/-10}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 10.0).abs()));
-                        temp_res = temp_res.replace(&format!("${}{{$m{}*15/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 * 0.15).abs()));
-                        temp_res = temp_res.replace(&format!("${}{{$m{}*45/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 * 0.45).abs()));
-                        temp_res = temp_res.replace(&format!("${}/1000;s{}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 1000.0).abs()));
-                        temp_res = temp_res.replace(&format!("${}/1000;S{}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 1000.0).abs()));
-                        temp_res = temp_res.replace(&format!("${}/10;s{}", capture[1].to_string(), i + 1), &format!("{:.1}", (ise_item.points_upper as f64 / 10.0).abs()));
-                        temp_res = temp_res.replace(&format!("${}o{}", capture[1].to_string(), i + 1), &ise_item.points_upper.abs().to_string());
-                        temp_res = temp_res.replace(&format!("${}x{}", capture[1].to_string(), i + 1), &ise_item.chain_targets.to_string());
-                        temp_res = temp_res.replace(&format!("${}a{}", capture[1].to_string(), i + 1), &ise_item.radius.to_string());
-                        temp_res = temp_res.replace(&format!("${}o", capture[1].to_string()), &ise_item.points_upper.abs().to_string()); // ?!
-                    }
-
-                    if inner_spell_effects.len() == 1 {
-                        for i in 0..10 {
-                            temp_res = temp_res.replace(&format!("${}s{}", capture[1].to_string(), i + 1), &inner_spell_effects[0].points_upper.abs().to_string());
-                            temp_res = temp_res.replace(&format!("${{${}m{}/-1000}}.1", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 1000.0).abs()));
-                            temp_res = temp_res.replace(&format!("${{${}m{}/-1000}}.2", capture[1].to_string(), i + 1), &format!("{:.2}", (inner_spell_effects[0].points_upper as f64 / 1000.0).abs()));
-                            temp_res = temp_res.replace(&format!("${{${}m{}*15/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 * 0.15).abs()));
-                            temp_res = temp_res.replace(&format!("${{${}m{}*45/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 * 0.45).abs()));
-                            temp_res = temp_res.replace(&format!("${}{{$m{}/1000}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 1000.0).abs()));
-                            temp_res = temp_res.replace(&format!("${}{{$m{}/-1000}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 1000.0).abs()));
-                            temp_res = temp_res.replace(&format!("${}{{$m{}/-10}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 10.0).abs()));
-                            temp_res = temp_res.replace(&format!("${}{{$m{}*15/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 * 0.15).abs()));
-                            temp_res = temp_res.replace(&format!("${}{{$m{}*45/100}}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 * 0.45).abs()));
-                            temp_res = temp_res.replace(&format!("${}/1000;s{}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 1000.0).abs()));
-                            temp_res = temp_res.replace(&format!("${}/1000;S{}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 1000.0).abs()));
-                            temp_res = temp_res.replace(&format!("${}/10;s{}", capture[1].to_string(), i + 1), &format!("{:.1}", (inner_spell_effects[0].points_upper as f64 / 10.0).abs()));
-                        }
-                    }
-                }
-                template = temp_res.to_owned();
-            }
-        }
+        let template = self.get_localization(language_id, spell.description_localization_id).map(|localization| localization.content).unwrap();
+        self.get_spell_effects(expansion_id, spell_id)?;
 
-        Some(template)
+        let context = LocalizedSpellTemplate { data: self, expansion_id, language_id };
+        Some(render_spell_template(&template, spell_id, &context))
     }
 
     fn parse_stats(&self, expansion_id: u8, spell_id: u32) -> Vec<Stat> {
@@ -275,6 +185,36 @@ impl SpellDescription for Data {
     }
 }
 
+struct LocalizedSpellTemplate<'a> {
+    data: &'a Data,
+    expansion_id: u8,
+    language_id: u8,
+}
+
+impl<'a> SpellTemplateContext for LocalizedSpellTemplate<'a> {
+    fn get_spell(&self, spell_id: u32) -> Option<Spell> {
+        self.data.get_spell(self.expansion_id, spell_id)
+    }
+
+    fn get_spell_effects(&self, spell_id: u32) -> Vec<SpellEffect> {
+        self.data.get_spell_effects(self.expansion_id, spell_id).unwrap_or_default()
+    }
+
+    fn get_description_template(&self, spell_id: u32) -> Option<String> {
+        let spell = self.get_spell(spell_id)?;
+        self.data.get_localization(self.language_id, spell.description_localization_id).map(|localization| localization.content)
+    }
+
+    fn get_aura_template(&self, spell_id: u32) -> Option<String> {
+        let spell = self.get_spell(spell_id)?;
+        self.data.get_localization(self.language_id, spell.aura_localization_id).map(|localization| localization.content)
+    }
+
+    fn format_duration(&self, duration: u32) -> String {
+        format_duration(&self.data.dictionary, self.language_id, duration)
+    }
+}
+
 fn format_duration(dictionary: &Dictionary, language_id: u8, duration: u32) -> String {
     let language = Language::from_u8(language_id - 1);
 
@@ -7,22 +7,23 @@ use crate::modules::data::{
     tools::{RetrieveDifficulty, RetrieveLocalization},
     Data,
 };
+use std::sync::Arc;
 
 #[openapi]
 #[get("/difficulty/<id>")]
-pub fn get_difficulty(me: State<Data>, id: u8) -> Option<Json<Difficulty>> {
+pub fn get_difficulty(me: State<Arc<Data>>, id: u8) -> Option<Json<Difficulty>> {
     me.get_difficulty(id).map(Json)
 }
 
 #[openapi]
 #[get("/difficulty")]
-pub fn get_all_difficulties(me: State<Data>) -> Json<Vec<Difficulty>> {
+pub fn get_all_difficulties(me: State<Arc<Data>>) -> Json<Vec<Difficulty>> {
     Json(me.get_all_difficulties())
 }
 
 #[openapi]
 #[get("/difficulty/localized/<id>")]
-pub fn get_difficulty_localized(me: State<Data>, language: Language, id: u8) -> Option<Json<Localized<Difficulty>>> {
+pub fn get_difficulty_localized(me: State<Arc<Data>>, language: Language, id: u8) -> Option<Json<Localized<Difficulty>>> {
     me.get_difficulty(id).map(|difficulty| {
         Json(Localized {
             localization: me.get_localization(language.0, difficulty.localization_id).unwrap().content,
@@ -33,7 +34,7 @@ pub fn get_difficulty_localized(me: State<Data>, language: Language, id: u8) ->
 
 #[openapi]
 #[get("/difficulty/localized")]
-pub fn get_all_difficulties_localized(me: State<Data>, language: Language) -> Json<Vec<Localized<Difficulty>>> {
+pub fn get_all_difficulties_localized(me: State<Arc<Data>>, language: Language) -> Json<Vec<Localized<Difficulty>>> {
     Json(
         me.get_all_difficulties()
             .iter()
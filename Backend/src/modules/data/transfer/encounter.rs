@@ -5,22 +5,23 @@ use crate::modules::data::domain_value::Localized;
 use crate::modules::data::guard::Language;
 use crate::modules::data::tools::RetrieveLocalization;
 use crate::modules::data::{domain_value::Encounter, tools::RetrieveEncounter, Data};
+use std::sync::Arc;
 
 #[openapi]
 #[get("/encounter/<id>")]
-pub fn get_encounter(me: State<Data>, id: u32) -> Option<Json<Encounter>> {
+pub fn get_encounter(me: State<Arc<Data>>, id: u32) -> Option<Json<Encounter>> {
     me.get_encounter(id).map(Json)
 }
 
 #[openapi]
 #[get("/encounter")]
-pub fn get_all_encounters(me: State<Data>) -> Json<Vec<Encounter>> {
+pub fn get_all_encounters(me: State<Arc<Data>>) -> Json<Vec<Encounter>> {
     Json(me.get_all_encounters())
 }
 
 #[openapi]
 #[get("/encounter/localized")]
-pub fn get_all_encounters_localized(me: State<Data>, language: Language) -> Json<Vec<Localized<Encounter>>> {
+pub fn get_all_encounters_localized(me: State<Arc<Data>>, language: Language) -> Json<Vec<Localized<Encounter>>> {
     Json(
         me.get_all_encounters()
             .into_iter()
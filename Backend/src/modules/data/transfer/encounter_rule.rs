@@ -0,0 +1,17 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::data::{domain_value::EncounterRule, tools::RetrieveEncounterRule, Data};
+use std::sync::Arc;
+
+#[openapi]
+#[get("/encounter_rule/<encounter_id>")]
+pub fn get_encounter_rules(me: State<Arc<Data>>, encounter_id: u32) -> Json<Vec<EncounterRule>> {
+    Json(me.get_encounter_rules(encounter_id))
+}
+
+#[openapi]
+#[get("/encounter_rule")]
+pub fn get_all_encounter_rules(me: State<Arc<Data>>) -> Json<Vec<EncounterRule>> {
+    Json(me.get_all_encounter_rules())
+}
@@ -2,15 +2,16 @@ use rocket::State;
 use rocket_contrib::json::Json;
 
 use crate::modules::data::{domain_value::Expansion, tools::RetrieveExpansion, Data};
+use std::sync::Arc;
 
 #[openapi]
 #[get("/expansion/<id>")]
-pub fn get_expansion(me: State<Data>, id: u8) -> Option<Json<Expansion>> {
+pub fn get_expansion(me: State<Arc<Data>>, id: u8) -> Option<Json<Expansion>> {
     me.get_expansion(id).map(Json)
 }
 
 #[openapi]
 #[get("/expansion")]
-pub fn get_all_expansions(me: State<Data>) -> Json<Vec<Expansion>> {
+pub fn get_all_expansions(me: State<Arc<Data>>) -> Json<Vec<Expansion>> {
     Json(me.get_all_expansions())
 }
@@ -7,22 +7,23 @@ use crate::modules::data::{
     tools::{RetrieveHeroClass, RetrieveLocalization},
     Data,
 };
+use std::sync::Arc;
 
 #[openapi]
 #[get("/hero_class/<id>")]
-pub fn get_hero_class(me: State<Data>, id: u8) -> Option<Json<HeroClass>> {
+pub fn get_hero_class(me: State<Arc<Data>>, id: u8) -> Option<Json<HeroClass>> {
     me.get_hero_class(id).map(Json)
 }
 
 #[openapi]
 #[get("/hero_class")]
-pub fn get_all_hero_classes(me: State<Data>) -> Json<Vec<HeroClass>> {
+pub fn get_all_hero_classes(me: State<Arc<Data>>) -> Json<Vec<HeroClass>> {
     Json(me.get_all_hero_classes())
 }
 
 #[openapi]
 #[get("/hero_class/localized/<id>")]
-pub fn get_hero_class_localized(me: State<Data>, language: Language, id: u8) -> Option<Json<Localized<HeroClass>>> {
+pub fn get_hero_class_localized(me: State<Arc<Data>>, language: Language, id: u8) -> Option<Json<Localized<HeroClass>>> {
     me.get_hero_class(id).map(|hero_class| {
         Json(Localized {
             localization: me.get_localization(language.0, hero_class.localization_id).unwrap().content,
@@ -33,7 +34,7 @@ pub fn get_hero_class_localized(me: State<Data>, language: Language, id: u8) ->
 
 #[openapi]
 #[get("/hero_class/localized")]
-pub fn get_all_hero_classes_localized(me: State<Data>, language: Language) -> Json<Vec<Localized<HeroClass>>> {
+pub fn get_all_hero_classes_localized(me: State<Arc<Data>>, language: Language) -> Json<Vec<Localized<HeroClass>>> {
     Json(
         me.get_all_hero_classes()
             .iter()
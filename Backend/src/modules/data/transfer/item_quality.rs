@@ -2,15 +2,16 @@ use rocket::State;
 use rocket_contrib::json::Json;
 
 use crate::modules::data::{domain_value::ItemQuality, tools::RetrieveItemQuality, Data};
+use std::sync::Arc;
 
 #[openapi]
 #[get("/item_quality/<id>")]
-pub fn get_item_quality(me: State<Data>, id: u8) -> Option<Json<ItemQuality>> {
+pub fn get_item_quality(me: State<Arc<Data>>, id: u8) -> Option<Json<ItemQuality>> {
     me.get_item_quality(id).map(Json)
 }
 
 #[openapi]
 #[get("/item_quality")]
-pub fn get_all_item_qualities(me: State<Data>) -> Json<Vec<ItemQuality>> {
+pub fn get_all_item_qualities(me: State<Arc<Data>>) -> Json<Vec<ItemQuality>> {
     Json(me.get_all_item_qualities())
 }
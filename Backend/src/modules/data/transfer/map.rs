@@ -7,28 +7,29 @@ use crate::modules::data::{
     tools::{RetrieveLocalization, RetrieveMap},
     Data,
 };
+use std::sync::Arc;
 
 #[openapi]
 #[get("/map/<id>")]
-pub fn get_map(me: State<Data>, id: u16) -> Option<Json<Map>> {
+pub fn get_map(me: State<Arc<Data>>, id: u16) -> Option<Json<Map>> {
     me.get_map(id).map(Json)
 }
 
 #[openapi]
 #[get("/map")]
-pub fn get_all_maps(me: State<Data>) -> Json<Vec<Map>> {
+pub fn get_all_maps(me: State<Arc<Data>>) -> Json<Vec<Map>> {
     Json(me.get_all_maps())
 }
 
 #[openapi]
 #[get("/map/by_type/<map_type>")]
-pub fn get_all_maps_by_type(me: State<Data>, map_type: u8) -> Json<Vec<Map>> {
+pub fn get_all_maps_by_type(me: State<Arc<Data>>, map_type: u8) -> Json<Vec<Map>> {
     Json(me.get_all_maps().into_iter().filter(|map| map.map_type == map_type).collect())
 }
 
 #[openapi]
 #[get("/map/localized/<id>")]
-pub fn get_map_localized(me: State<Data>, language: Language, id: u16) -> Option<Json<Localized<Map>>> {
+pub fn get_map_localized(me: State<Arc<Data>>, language: Language, id: u16) -> Option<Json<Localized<Map>>> {
     me.get_map(id).map(|map| {
         Json(Localized {
             localization: me.get_localization(language.0, map.localization_id).unwrap().content,
@@ -39,7 +40,7 @@ pub fn get_map_localized(me: State<Data>, language: Language, id: u16) -> Option
 
 #[openapi]
 #[get("/map/localized")]
-pub fn get_all_maps_localized(me: State<Data>, language: Language) -> Json<Vec<Localized<Map>>> {
+pub fn get_all_maps_localized(me: State<Arc<Data>>, language: Language) -> Json<Vec<Localized<Map>>> {
     Json(
         me.get_all_maps()
             .into_iter()
@@ -53,7 +54,7 @@ pub fn get_all_maps_localized(me: State<Data>, language: Language) -> Json<Vec<L
 
 #[openapi]
 #[get("/map/localized/by_type/<map_type>")]
-pub fn get_all_maps_localized_by_type(me: State<Data>, language: Language, map_type: u8) -> Json<Vec<Localized<Map>>> {
+pub fn get_all_maps_localized_by_type(me: State<Arc<Data>>, language: Language, map_type: u8) -> Json<Vec<Localized<Map>>> {
     Json(
         me.get_all_maps()
             .into_iter()
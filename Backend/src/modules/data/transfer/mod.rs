@@ -3,6 +3,7 @@ pub mod dispel_type;
 pub mod enchant;
 pub mod encounter;
 pub mod encounter_npc;
+pub mod encounter_rule;
 pub mod expansion;
 pub mod gem;
 pub mod hero_class;
@@ -29,6 +30,7 @@ pub mod npc;
 pub mod power_type;
 pub mod profession;
 pub mod race;
+pub mod ranking_bracket;
 pub mod server;
 pub mod spell;
 pub mod spell_effect;
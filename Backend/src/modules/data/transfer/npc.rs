@@ -5,16 +5,17 @@ use crate::modules::data::domain_value::Localized;
 use crate::modules::data::guard::Language;
 use crate::modules::data::tools::RetrieveLocalization;
 use crate::modules::data::{domain_value::NPC, tools::RetrieveNPC, Data};
+use std::sync::Arc;
 
 #[openapi]
 #[get("/npc/<expansion_id>/<npc_id>")]
-pub fn get_npc(me: State<Data>, expansion_id: u8, npc_id: u32) -> Option<Json<NPC>> {
+pub fn get_npc(me: State<Arc<Data>>, expansion_id: u8, npc_id: u32) -> Option<Json<NPC>> {
     me.get_npc(expansion_id, npc_id).map(Json)
 }
 
 #[openapi]
 #[get("/npc/localized/<expansion_id>/<npc_id>")]
-pub fn get_npc_localized(me: State<Data>, language: Language, expansion_id: u8, npc_id: u32) -> Option<Json<Localized<NPC>>> {
+pub fn get_npc_localized(me: State<Arc<Data>>, language: Language, expansion_id: u8, npc_id: u32) -> Option<Json<Localized<NPC>>> {
     me.get_npc(expansion_id, npc_id).map(|npc| {
         Json(Localized {
             localization: me.get_localization(language.0, npc.localization_id).unwrap().content,
@@ -31,7 +32,7 @@ pub struct GetNpcs {
 
 #[openapi(skip)]
 #[post("/npcs/localized", format = "application/json", data = "<data>")]
-pub fn get_npcs_localized(me: State<Data>, language: Language, data: Json<GetNpcs>) -> Json<Vec<Localized<NPC>>> {
+pub fn get_npcs_localized(me: State<Arc<Data>>, language: Language, data: Json<GetNpcs>) -> Json<Vec<Localized<NPC>>> {
     Json(
         data.npc_ids
             .iter()
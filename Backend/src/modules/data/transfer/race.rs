@@ -7,22 +7,23 @@ use crate::modules::data::{
     tools::{RetrieveLocalization, RetrieveRace},
     Data,
 };
+use std::sync::Arc;
 
 #[openapi]
 #[get("/race/<id>")]
-pub fn get_race(me: State<Data>, id: u8) -> Option<Json<Race>> {
+pub fn get_race(me: State<Arc<Data>>, id: u8) -> Option<Json<Race>> {
     me.get_race(id).map(Json)
 }
 
 #[openapi]
 #[get("/race")]
-pub fn get_all_races(me: State<Data>) -> Json<Vec<Race>> {
+pub fn get_all_races(me: State<Arc<Data>>) -> Json<Vec<Race>> {
     Json(me.get_all_races())
 }
 
 #[openapi]
 #[get("/race/localized/<id>")]
-pub fn get_race_localized(me: State<Data>, language: Language, id: u8) -> Option<Json<Localized<Race>>> {
+pub fn get_race_localized(me: State<Arc<Data>>, language: Language, id: u8) -> Option<Json<Localized<Race>>> {
     me.get_race(id).map(|race| {
         Json(Localized {
             localization: me.get_localization(language.0, race.localization_id).unwrap().content,
@@ -33,7 +34,7 @@ pub fn get_race_localized(me: State<Data>, language: Language, id: u8) -> Option
 
 #[openapi]
 #[get("/race/localized")]
-pub fn get_all_races_localized(me: State<Data>, language: Language) -> Json<Vec<Localized<Race>>> {
+pub fn get_all_races_localized(me: State<Arc<Data>>, language: Language) -> Json<Vec<Localized<Race>>> {
     Json(
         me.get_all_races()
             .iter()
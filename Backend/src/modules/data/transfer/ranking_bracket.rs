@@ -0,0 +1,17 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::data::{domain_value::RankingBracket, tools::RetrieveRankingBracket, Data};
+use std::sync::Arc;
+
+#[openapi]
+#[get("/ranking_bracket/<id>")]
+pub fn get_ranking_bracket(me: State<Arc<Data>>, id: u32) -> Option<Json<RankingBracket>> {
+    me.get_ranking_bracket(id).map(Json)
+}
+
+#[openapi]
+#[get("/ranking_bracket")]
+pub fn get_all_ranking_brackets(me: State<Arc<Data>>) -> Json<Vec<RankingBracket>> {
+    Json(me.get_all_ranking_brackets())
+}
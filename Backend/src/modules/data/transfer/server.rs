@@ -2,23 +2,27 @@
 use rocket::State;
 use rocket_contrib::json::Json;
 
+use crate::modules::account::guard::{role, scope, HasRole};
+use crate::modules::account::{Account, AuditAction, AuditLog, Failure};
 use crate::modules::data::{dto::AvailableServer, tools::RetrieveServer, Data};
 use crate::MainDb;
+use std::sync::Arc;
 
 #[openapi]
 #[get("/server/<id>")]
-pub fn get_server(me: State<Data>, id: u32) -> Option<Json<AvailableServer>> {
+pub fn get_server(me: State<Arc<Data>>, id: u32) -> Option<Json<AvailableServer>> {
     me.get_server(id).map(Json)
 }
 
 #[openapi]
 #[get("/server")]
-pub fn get_all_servers(me: State<Data>) -> Json<Vec<AvailableServer>> {
+pub fn get_all_servers(me: State<Arc<Data>>) -> Json<Vec<AvailableServer>> {
     Json(me.get_all_servers())
 }
 
 #[openapi(skip)]
 #[get("/server/reload")]
-pub fn reload_server(mut db_main: MainDb, me: State<Data>) {
+pub fn reload_server(mut db_main: MainDb, me: State<Arc<Data>>, account: State<Account>, admin: HasRole<role::Admin, scope::Account>) -> Result<(), Failure> {
     me.reload_server(&mut *db_main);
+    account.log_action(&mut *db_main, admin.0, AuditAction::ReloadServers)
 }
@@ -8,16 +8,17 @@ use crate::modules::data::dto::BasicSpell;
 use crate::modules::data::guard::Language;
 use crate::modules::data::tools::{RetrieveIcon, RetrieveLocalization};
 use crate::modules::data::{domain_value::Spell, tools::RetrieveSpell, Data};
+use std::sync::Arc;
 
 #[openapi]
 #[get("/spell/<expansion_id>/<spell_id>")]
-pub fn get_spell(me: State<Data>, expansion_id: u8, spell_id: u32) -> Option<Json<Spell>> {
+pub fn get_spell(me: State<Arc<Data>>, expansion_id: u8, spell_id: u32) -> Option<Json<Spell>> {
     me.get_spell(expansion_id, spell_id).map(Json)
 }
 
 #[openapi]
 #[get("/spell/localized/basic_spell/<expansion_id>/<spell_id>")]
-pub fn get_localized_basic_spell(me: State<Data>, language: Language, expansion_id: u8, spell_id: u32) -> Option<Json<Localized<BasicSpell>>> {
+pub fn get_localized_basic_spell(me: State<Arc<Data>>, language: Language, expansion_id: u8, spell_id: u32) -> Option<Json<Localized<BasicSpell>>> {
     me.get_spell(expansion_id, spell_id)
         .map(|spell| Localized {
             base: BasicSpell {
@@ -38,7 +39,7 @@ pub struct GetSpells {
 
 #[openapi(skip)]
 #[post("/spells/localized/basic_spell", format = "application/json", data = "<data>")]
-pub fn get_localized_basic_spells(me: State<Data>, language: Language, data: Json<GetSpells>) -> Json<Vec<Localized<BasicSpell>>> {
+pub fn get_localized_basic_spells(me: State<Arc<Data>>, language: Language, data: Json<GetSpells>) -> Json<Vec<Localized<BasicSpell>>> {
     Json(
         data.spell_ids
             .iter()
@@ -0,0 +1,76 @@
+use crate::modules::instance::dto::ConsumableType;
+
+/// Auras of flasks, elixirs and food buffs
+pub static CONSUMABLES: &[(u32, ConsumableType)] = &[
+    (17626, ConsumableType::Flask),          // Flask of the Titans
+    (17627, ConsumableType::Flask),          // Flask of Distilled Wisdom
+    (17628, ConsumableType::Flask),          // Flask of Supreme Power
+    (17629, ConsumableType::Flask),          // Flask of Chromatic Resistance
+    (28518, ConsumableType::Flask),          // Flask of Fortification
+    (28519, ConsumableType::Flask),          // Flask of Mighty Restoration
+    (28520, ConsumableType::Flask),          // Flask of Relentless Assault
+    (28521, ConsumableType::Flask),          // Flask of Blinding Light
+    (28540, ConsumableType::Flask),          // Flask of Pure Death
+    (53755, ConsumableType::Flask),          // Flask of the Frost Wyrm
+    (53758, ConsumableType::Flask),          // Flask of Stoneblood
+    (53760, ConsumableType::Flask),          // Flask of Endless Rage
+    (54212, ConsumableType::Flask),          // Flask of Pure Mojo
+    (11405, ConsumableType::BattleElixir),   // Elixir of Giants
+    (11474, ConsumableType::BattleElixir),   // Elixir of Shadow Power
+    (17538, ConsumableType::BattleElixir),   // Elixir of the Mongoose
+    (17539, ConsumableType::BattleElixir),   // Greater Arcane Elixir
+    (21920, ConsumableType::BattleElixir),   // Elixir of Frost Power
+    (26276, ConsumableType::BattleElixir),   // Elixir of Greater Firepower
+    (28490, ConsumableType::BattleElixir),   // Elixir of Major Strength
+    (28491, ConsumableType::BattleElixir),   // Elixir of Healing Power
+    (28497, ConsumableType::BattleElixir),   // Elixir of Major Agility
+    (28501, ConsumableType::BattleElixir),   // Elixir of Major Firepower
+    (28503, ConsumableType::BattleElixir),   // Elixir of Major Shadow Power
+    (33720, ConsumableType::BattleElixir),   // Onslaught Elixir
+    (33721, ConsumableType::BattleElixir),   // Adept's Elixir
+    (53746, ConsumableType::BattleElixir),   // Wrath Elixir
+    (53748, ConsumableType::BattleElixir),   // Elixir of Mighty Strength
+    (60340, ConsumableType::BattleElixir),   // Elixir of Accuracy
+    (60341, ConsumableType::BattleElixir),   // Elixir of Deadly Strikes
+    (60344, ConsumableType::BattleElixir),   // Elixir of Expertise
+    (60345, ConsumableType::BattleElixir),   // Elixir of Armor Piercing
+    (60346, ConsumableType::BattleElixir),   // Elixir of Lightning Speed
+    (3593, ConsumableType::GuardianElixir),  // Elixir of Fortitude
+    (11348, ConsumableType::GuardianElixir), // Elixir of Superior Defense
+    (24361, ConsumableType::GuardianElixir), // Major Troll's Blood Potion
+    (24363, ConsumableType::GuardianElixir), // Mageblood Potion
+    (28502, ConsumableType::GuardianElixir), // Elixir of Major Defense
+    (28509, ConsumableType::GuardianElixir), // Elixir of Major Mageblood
+    (39625, ConsumableType::GuardianElixir), // Elixir of Major Fortitude
+    (39627, ConsumableType::GuardianElixir), // Elixir of Draenic Wisdom
+    (53747, ConsumableType::GuardianElixir), // Elixir of Spirit
+    (53751, ConsumableType::GuardianElixir), // Elixir of Mighty Fortitude
+    (53764, ConsumableType::GuardianElixir), // Elixir of Mighty Mageblood
+    (60343, ConsumableType::GuardianElixir), // Elixir of Mighty Defense
+    (60347, ConsumableType::GuardianElixir), // Elixir of Mighty Thoughts
+    (18192, ConsumableType::Food),           // Grilled Squid
+    (18194, ConsumableType::Food),           // Nightfin Soup
+    (19705, ConsumableType::Food),           // Well Fed
+    (19706, ConsumableType::Food),           // Well Fed
+    (19708, ConsumableType::Food),           // Well Fed
+    (19709, ConsumableType::Food),           // Well Fed
+    (19710, ConsumableType::Food),           // Well Fed
+    (19711, ConsumableType::Food),           // Well Fed
+    (22730, ConsumableType::Food),           // Runn Tum Tuber Surprise
+    (24799, ConsumableType::Food),           // Smoked Desert Dumplings
+    (25661, ConsumableType::Food),           // Dirge's Kickin' Chimaerok Chops
+    (33254, ConsumableType::Food),           // Well Fed
+    (33256, ConsumableType::Food),           // Well Fed
+    (33257, ConsumableType::Food),           // Well Fed
+    (33259, ConsumableType::Food),           // Well Fed
+    (33261, ConsumableType::Food),           // Well Fed
+    (33263, ConsumableType::Food),           // Well Fed
+    (33265, ConsumableType::Food),           // Well Fed
+    (33268, ConsumableType::Food),           // Well Fed
+    (35272, ConsumableType::Food),           // Well Fed
+    (57399, ConsumableType::Food),           // Fish Feast
+];
+
+pub fn get_consumable_type(spell_id: u32) -> Option<ConsumableType> {
+    CONSUMABLES.iter().find(|(consumable_spell_id, _)| *consumable_spell_id == spell_id).map(|(_, consumable_type)| *consumable_type)
+}
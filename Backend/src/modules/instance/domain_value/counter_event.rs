@@ -0,0 +1,42 @@
+use crate::modules::instance::dto::MeterUnit;
+use serde_json::Value;
+
+/// An interrupt, dispel or spell steal of the instance storage
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterEvent {
+    pub event_id: u32,
+    pub timestamp: u64,
+    pub source: MeterUnit,
+    pub target: MeterUnit,
+    pub spell_id: u32,
+    // The interrupted spell or the removed aura
+    pub affected_spell_id: u32,
+}
+
+impl CounterEvent {
+    /// [id,timestamp,cause_event_id,source,target,spell_id,interrupted_spell_id]
+    pub fn from_interrupt(line: &str) -> Option<Self> {
+        let (event_id, timestamp, _, source, target, spell_id, affected_spell_id): (u32, u64, u32, Value, Value, u32, u32) = serde_json::from_str(line).ok()?;
+        Some(CounterEvent {
+            event_id,
+            timestamp,
+            source: MeterUnit::from_value(&source)?,
+            target: MeterUnit::from_value(&target)?,
+            spell_id,
+            affected_spell_id,
+        })
+    }
+
+    /// Dispel and SpellSteal: [id,timestamp,cause_event_id,target_event_id,source,target,spell_id,aura_spell_id]
+    pub fn from_un_aura(line: &str) -> Option<Self> {
+        let (event_id, timestamp, _, _, source, target, spell_id, affected_spell_id): (u32, u64, u32, u32, Value, Value, u32, u32) = serde_json::from_str(line).ok()?;
+        Some(CounterEvent {
+            event_id,
+            timestamp,
+            source: MeterUnit::from_value(&source)?,
+            target: MeterUnit::from_value(&target)?,
+            spell_id,
+            affected_spell_id,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+/// Positions of the units and spells in a line of the text format of an event type
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventLayout {
+    pub source: Option<usize>,
+    pub target: Option<usize>,
+    pub spells: &'static [usize],
+}
+
+impl EventLayout {
+    pub fn from_event_type(event_type: u8) -> Self {
+        let (source, target, spells): (Option<usize>, Option<usize>, &'static [usize]) = match event_type {
+            // SpellCast
+            0 => (Some(2), Some(3), &[4]),
+            // Death: The murder is the source
+            1 => (Some(3), Some(2), &[]),
+            // AuraApplication: The caster is the source
+            6 => (Some(3), Some(2), &[4]),
+            // Interrupt
+            7 => (Some(3), Some(4), &[5, 6]),
+            // SpellSteal, Dispel
+            8 | 9 => (Some(4), Some(5), &[6, 7]),
+            // Summon
+            11 => (Some(2), Some(3), &[]),
+            // MeleeDamage
+            12 => (Some(2), Some(3), &[]),
+            // SpellDamage, Heal, Threat
+            13..=15 => (Some(3), Some(4), &[5]),
+            // CombatState, Loot, Position, Power, ThreatWipe
+            _ => (Some(2), None, &[]),
+        };
+        EventLayout { source, target, spells }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::modules::instance::dto::{MeterAmount, MeterUnit};
+use serde_json::Value;
+
+/// A damage, heal or threat event of the instance storage, reduced to what the meters aggregate
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeterEvent {
+    pub timestamp: u64,
+    pub source: MeterUnit,
+    pub target: MeterUnit,
+    pub spell_id: u32,
+    pub amount: MeterAmount,
+    // The amount split by the school mask of its components
+    pub schools: Vec<(u8, MeterAmount)>,
+}
+
+// amount, school_mask, absorb, resist, block
+type DamageComponent = (u32, u8, u32, u32, u32);
+// id, timestamp, cause_event_id, source, target, spell_id, hit_mask, school_mask, total, effective, absorb, resist, block
+type HealLine = (u32, u64, u32, Value, Value, u32, u32, u8, u32, u32, u32, u32, u32);
+
+impl MeterEvent {
+    pub fn from_melee_damage(line: &str) -> Option<Self> {
+        let (_, timestamp, source, target, _, components): (u32, u64, Value, Value, u32, Vec<DamageComponent>) = serde_json::from_str(line).ok()?;
+        from_damage(timestamp, &source, &target, 0, components)
+    }
+
+    pub fn from_spell_damage(line: &str) -> Option<Self> {
+        let (_, timestamp, _, source, target, spell_id, _, components): (u32, u64, u32, Value, Value, u32, u32, Vec<DamageComponent>) = serde_json::from_str(line).ok()?;
+        from_damage(timestamp, &source, &target, spell_id, components)
+    }
+
+    pub fn from_heal(line: &str) -> Option<Self> {
+        let (_, timestamp, _, source, target, spell_id, _, school_mask, total, effective, absorb, _, _): HealLine = serde_json::from_str(line).ok()?;
+        let amount = MeterAmount {
+            amount: effective as i64,
+            overheal: total.saturating_sub(effective) as u64,
+            absorb: absorb as u64,
+            count: 1,
+        };
+        Some(MeterEvent {
+            timestamp,
+            source: MeterUnit::from_value(&source)?,
+            target: MeterUnit::from_value(&target)?,
+            spell_id,
+            amount,
+            schools: vec![(school_mask, amount)],
+        })
+    }
+
+    pub fn from_threat(line: &str) -> Option<Self> {
+        let (_, timestamp, _, source, target, spell_id, _, school_mask, threat): (u32, u64, u32, Value, Value, u32, u32, u8, i32) = serde_json::from_str(line).ok()?;
+        let amount = MeterAmount {
+            amount: threat as i64,
+            count: 1,
+            ..Default::default()
+        };
+        Some(MeterEvent {
+            timestamp,
+            source: MeterUnit::from_value(&source)?,
+            target: MeterUnit::from_value(&target)?,
+            spell_id,
+            amount,
+            schools: vec![(school_mask, amount)],
+        })
+    }
+}
+
+fn from_damage(timestamp: u64, source: &Value, target: &Value, spell_id: u32, components: Vec<DamageComponent>) -> Option<MeterEvent> {
+    let schools = components
+        .into_iter()
+        .map(|(amount, school_mask, absorb, _, _)| {
+            (
+                school_mask,
+                MeterAmount {
+                    amount: amount as i64,
+                    absorb: absorb as u64,
+                    count: 1,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect::<Vec<(u8, MeterAmount)>>();
+    let amount = schools.iter().fold(MeterAmount { count: 1, ..Default::default() }, |mut total, (_, component)| {
+        total.amount += component.amount;
+        total.absorb += component.absorb;
+        total
+    });
+    Some(MeterEvent {
+        timestamp,
+        source: MeterUnit::from_value(source)?,
+        target: MeterUnit::from_value(target)?,
+        spell_id,
+        amount,
+        schools,
+    })
+}
@@ -1,5 +1,13 @@
+pub use self::consumable::{get_consumable_type, CONSUMABLES};
+pub use self::counter_event::CounterEvent;
+pub use self::event_layout::EventLayout;
 pub use self::instance_meta::InstanceMeta;
 pub use self::meta_type::MetaType;
+pub use self::meter_event::MeterEvent;
 
+mod consumable;
+mod counter_event;
+mod event_layout;
 mod instance_meta;
 mod meta_type;
+mod meter_event;
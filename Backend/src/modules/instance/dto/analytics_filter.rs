@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnalyticsFilter {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+}
@@ -0,0 +1,21 @@
+use crate::modules::instance::dto::MeterUnit;
+
+/// Presence of an aura on a unit, regardless of who applied it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AuraUptime {
+    pub target: MeterUnit,
+    pub spell_id: u32,
+    // Milliseconds in which at least one application was active
+    pub uptime: u64,
+    pub uptime_percent: f64,
+    // Ordered by start_ts. Applications of different casters may overlap.
+    pub timeline: Vec<AuraInterval>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AuraInterval {
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub caster: MeterUnit,
+    pub stack_amount: u32,
+}
@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AuraUptimeFilter {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    // All auras if none are given
+    pub spell_ids: Option<Vec<u32>>,
+}
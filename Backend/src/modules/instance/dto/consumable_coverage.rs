@@ -0,0 +1,19 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum ConsumableType {
+    Flask,
+    BattleElixir,
+    GuardianElixir,
+    Food,
+}
+
+/// Consumables that were active on a player at the pull
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ConsumableCoverage {
+    pub character_id: u32,
+    pub flask: Option<u32>,
+    pub battle_elixir: Option<u32>,
+    pub guardian_elixir: Option<u32>,
+    pub food: Option<u32>,
+    // Food and either a flask or both kinds of elixirs
+    pub is_covered: bool,
+}
@@ -0,0 +1,58 @@
+use crate::modules::instance::dto::{MeterAmount, MeterUnit};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeathRecap {
+    pub event_id: u32,
+    pub timestamp: u64,
+    pub character_id: u32,
+    pub murder: Option<MeterUnit>,
+    pub killing_blow: Option<DeathRecapEvent>,
+    // Incoming damage and healing, ordered by timestamp
+    pub events: Vec<DeathRecapEvent>,
+    pub auras: Vec<DeathRecapAura>,
+    pub powers: Vec<DeathRecapPower>,
+    pub defensive_cooldowns: Vec<DeathRecapCooldown>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum DeathRecapEventType {
+    Damage,
+    Heal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeathRecapEvent {
+    pub event_type: DeathRecapEventType,
+    pub timestamp: u64,
+    pub source: MeterUnit,
+    // 0 is melee
+    pub spell_id: u32,
+    pub amount: MeterAmount,
+}
+
+/// Aura that was active on the victim when it died
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeathRecapAura {
+    pub spell_id: u32,
+    pub caster: MeterUnit,
+    pub applied_ts: u64,
+    pub stack_amount: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeathRecapPower {
+    pub timestamp: u64,
+    pub power_type: u8,
+    pub current_power: u32,
+    pub max_power: u32,
+}
+
+/// Defensive cooldown of the class and spec of the victim
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeathRecapCooldown {
+    pub spell_id: u32,
+    // None if the victim did not use it before it died
+    pub last_used_ts: Option<u64>,
+    pub cooldown: u32,
+    pub is_available: bool,
+}
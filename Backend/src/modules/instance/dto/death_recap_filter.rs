@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeathRecapFilter {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    // How far the recap reaches back before the death, defaults to 10 seconds
+    pub recap_seconds: Option<u32>,
+}
@@ -0,0 +1,14 @@
+use crate::modules::instance::dto::UnitFilter;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EventExportFilter {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub sources: Option<Vec<UnitFilter>>,
+    pub targets: Option<Vec<UnitFilter>>,
+    pub spell_ids: Option<Vec<u32>>,
+    // Id of the last event of the previous page
+    pub cursor: Option<u32>,
+    pub limit: Option<u32>,
+}
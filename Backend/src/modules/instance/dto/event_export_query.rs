@@ -0,0 +1,37 @@
+use crate::modules::instance::dto::{EventExportFilter, InstanceFailure, UnitFilter};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+// Lists are comma separated, e.g. sources=player:5,creature:15990:42&spell_ids=133,48441
+#[derive(Debug, FromForm)]
+pub struct EventExportQuery {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub sources: Option<String>,
+    pub targets: Option<String>,
+    pub spell_ids: Option<String>,
+    pub cursor: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+impl TryFrom<EventExportQuery> for EventExportFilter {
+    type Error = InstanceFailure;
+
+    fn try_from(query: EventExportQuery) -> Result<Self, Self::Error> {
+        Ok(EventExportFilter {
+            attempt_id: query.attempt_id,
+            start_ts: query.start_ts,
+            end_ts: query.end_ts,
+            sources: parse_list::<UnitFilter>(query.sources)?,
+            targets: parse_list::<UnitFilter>(query.targets)?,
+            spell_ids: parse_list::<u32>(query.spell_ids)?,
+            cursor: query.cursor,
+            limit: query.limit,
+        })
+    }
+}
+
+fn parse_list<T: FromStr>(list: Option<String>) -> Result<Option<Vec<T>>, InstanceFailure> {
+    list.map(|list| list.split(',').map(|item| item.trim().parse::<T>().map_err(|_| InstanceFailure::InvalidInput)).collect()).transpose()
+}
@@ -0,0 +1,23 @@
+use crate::modules::armory::dto::SearchGuildDto;
+
+/// Progress of a guild in a raid of a difficulty across all of its uploaded instances
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GuildProgression {
+    pub guild: SearchGuildDto,
+    pub server_id: u32,
+    pub map_id: u16,
+    pub difficulty_id: u8,
+    pub killed_encounters: u32,
+    pub total_encounters: u32,
+    pub encounters: Vec<EncounterProgression>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EncounterProgression {
+    pub encounter_id: u32,
+    pub first_kill_ts: Option<u64>,
+    pub first_kill_instance_meta_id: Option<u32>,
+    // All wipes, if the encounter was not killed yet
+    pub wipes_before_kill: u32,
+    pub hard_mode_first_kill_ts: Option<u64>,
+}
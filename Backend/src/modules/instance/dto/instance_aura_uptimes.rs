@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::{AuraUptime, ConsumableCoverage};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InstanceAuraUptimes {
+    pub instance_meta_id: u32,
+    pub attempt_id: Option<u32>,
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub auras: Vec<AuraUptime>,
+    // Checked at start_ts
+    pub consumables: Vec<ConsumableCoverage>,
+}
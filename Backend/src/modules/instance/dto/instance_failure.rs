@@ -7,6 +7,7 @@ use std::io::Cursor;
 #[derive(Debug, JsonSchema)]
 pub enum InstanceFailure {
     InvalidInput,
+    Database(String),
 }
 
 impl Responder<'static> for InstanceFailure {
@@ -17,6 +18,10 @@ impl Responder<'static> for InstanceFailure {
                 body = "Invalid input!".to_owned();
                 Status::new(534, "InvalidInput")
             },
+            Self::Database(hint) => {
+                body = hint;
+                Status::new(535, "Database")
+            },
         };
         Response::build().status(status).sized_body(Cursor::new(body)).ok()
     }
@@ -26,7 +31,8 @@ impl OpenApiResponder<'static> for InstanceFailure {
     fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
         let mut responses = Responses::default();
         let schema = gen.json_schema::<String>();
-        add_schema_response(&mut responses, 534, "text/plain", schema)?;
+        add_schema_response(&mut responses, 534, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 535, "text/plain", schema)?;
         Ok(responses)
     }
 }
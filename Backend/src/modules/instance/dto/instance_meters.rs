@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::Meter;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InstanceMeters {
+    pub instance_meta_id: u32,
+    pub attempt_id: Option<u32>,
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub damage: Meter,
+    pub heal: Meter,
+    pub threat: Meter,
+}
@@ -0,0 +1,50 @@
+use crate::modules::instance::dto::MeterUnit;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InterruptReport {
+    pub instance_meta_id: u32,
+    pub attempt_id: Option<u32>,
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub interrupts: Vec<EffectivenessRanking>,
+    pub dispels: Vec<EffectivenessRanking>,
+    pub spell_steals: Vec<EffectivenessRanking>,
+    pub missed_interrupts: Vec<MissedInterrupt>,
+}
+
+/// Sorted by count, descending
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EffectivenessRanking {
+    pub source: MeterUnit,
+    pub count: u32,
+    // Milliseconds from the application of the aura to its removal
+    pub average_reaction_time: Option<u64>,
+    pub affected_spells: Vec<EffectivenessSpell>,
+    pub events: Vec<EffectivenessEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EffectivenessSpell {
+    pub spell_id: u32,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EffectivenessEvent {
+    pub event_id: u32,
+    pub timestamp: u64,
+    pub target: MeterUnit,
+    pub spell_id: u32,
+    // The interrupted spell or the removed aura
+    pub affected_spell_id: u32,
+    pub reaction_time: Option<u64>,
+}
+
+/// Cast of an enemy that completed without being interrupted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MissedInterrupt {
+    pub event_id: u32,
+    pub timestamp: u64,
+    pub caster: MeterUnit,
+    pub spell_id: u32,
+}
@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InterruptReportFilter {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    // Enemy casts of these spells that completed are reported as missed interrupts
+    pub interruptible_spell_ids: Option<Vec<u32>>,
+}
@@ -0,0 +1,29 @@
+use crate::modules::instance::dto::{MeterAmount, MeterUnit};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Meter {
+    pub total: MeterAmount,
+    pub by_source: Vec<MeterUnitEntry>,
+    pub by_spell: Vec<MeterSpellEntry>,
+    pub by_target: Vec<MeterUnitEntry>,
+    pub by_school: Vec<MeterSchoolEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeterUnitEntry {
+    pub unit: MeterUnit,
+    pub amount: MeterAmount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeterSpellEntry {
+    // 0 is melee
+    pub spell_id: u32,
+    pub amount: MeterAmount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeterSchoolEntry {
+    pub school_mask: u8,
+    pub amount: MeterAmount,
+}
@@ -0,0 +1,19 @@
+use std::ops::AddAssign;
+
+/// Amount is the effective amount, i.e. without overheal and absorbs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MeterAmount {
+    pub amount: i64,
+    pub overheal: u64,
+    pub absorb: u64,
+    pub count: u32,
+}
+
+impl AddAssign for MeterAmount {
+    fn add_assign(&mut self, other: Self) {
+        self.amount += other.amount;
+        self.overheal += other.overheal;
+        self.absorb += other.absorb;
+        self.count += other.count;
+    }
+}
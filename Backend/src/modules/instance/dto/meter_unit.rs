@@ -0,0 +1,22 @@
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+pub enum MeterUnit {
+    Player { character_id: u32 },
+    Creature { creature_id: u64, entry: u32 },
+}
+
+impl MeterUnit {
+    /// Unit of the text format. Player: [1,character_id], Creature: [0,creature_id,entry(,owner)]
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let unit = value.as_array()?;
+        match unit.first()?.as_u64()? {
+            0 => Some(MeterUnit::Creature {
+                creature_id: unit.get(1)?.as_u64()?,
+                entry: unit.get(2)?.as_u64()? as u32,
+            }),
+            1 => Some(MeterUnit::Player { character_id: unit.get(1)?.as_u64()? as u32 }),
+            _ => None,
+        }
+    }
+}
@@ -1,35 +1,79 @@
+pub use self::analytics_filter::AnalyticsFilter;
+pub use self::aura_uptime::{AuraInterval, AuraUptime};
+pub use self::aura_uptime_filter::AuraUptimeFilter;
 pub use self::battleground_search_filter::BattlegroundSearchFilter;
+pub use self::consumable_coverage::{ConsumableCoverage, ConsumableType};
+pub use self::death_recap::{DeathRecap, DeathRecapAura, DeathRecapCooldown, DeathRecapEvent, DeathRecapEventType, DeathRecapPower};
+pub use self::death_recap_filter::DeathRecapFilter;
+pub use self::event_export_filter::EventExportFilter;
+pub use self::event_export_query::EventExportQuery;
+pub use self::guild_progression::{EncounterProgression, GuildProgression};
 pub use self::instance_failure::InstanceFailure;
+pub use self::instance_aura_uptimes::InstanceAuraUptimes;
+pub use self::instance_meters::InstanceMeters;
 pub use self::instance_viewer_attempt::InstanceViewerAttempt;
 pub use self::instance_viewer_guild::InstanceViewerGuild;
 pub use self::instance_viewer_meta::InstanceViewerMeta;
 pub use self::instance_viewer_participant::InstanceViewerParticipant;
+pub use self::interrupt_report::{EffectivenessEvent, EffectivenessRanking, EffectivenessSpell, InterruptReport, MissedInterrupt};
+pub use self::interrupt_report_filter::InterruptReportFilter;
 pub use self::meta_battleground_search::MetaBattlegroundSearch;
 pub use self::meta_raid_search::MetaRaidSearch;
 pub use self::meta_rated_arena_search::MetaRatedArenaSearch;
 pub use self::meta_skirmish_search::MetaSkirmishSearch;
+pub use self::meter::{Meter, MeterSchoolEntry, MeterSpellEntry, MeterUnitEntry};
+pub use self::meter_amount::MeterAmount;
+pub use self::meter_unit::MeterUnit;
+pub use self::progression_filter::ProgressionFilter;
 pub use self::raid_search_filter::RaidSearchFilter;
 pub use self::ranking_character_meta::RankingCharacterMeta;
+pub use self::ranking_filter::{RankingFilter, RankingMetric};
+pub use self::ranking_history_entry::RankingHistoryEntry;
+pub use self::ranking_page::{RankingEntry, RankingPage};
 pub use self::ranking_result::RankingResult;
 pub use self::rated_arena_search_filter::RatedArenaSearchFilter;
 pub use self::responder_raw_json::*;
 pub use self::search_arena_team::SearchArenaTeam;
 pub use self::skirmish_search_filter::SkirmishSearchFilter;
+pub use self::speed_run::SpeedRun;
+pub use self::unit_filter::UnitFilter;
 
+mod analytics_filter;
+mod aura_uptime;
+mod aura_uptime_filter;
 mod battleground_search_filter;
+mod consumable_coverage;
+mod death_recap;
+mod death_recap_filter;
+mod event_export_filter;
+mod event_export_query;
+mod guild_progression;
 mod instance_failure;
+mod instance_aura_uptimes;
+mod instance_meters;
 mod instance_viewer_attempt;
 mod instance_viewer_guild;
 mod instance_viewer_meta;
 mod instance_viewer_participant;
+mod interrupt_report;
+mod interrupt_report_filter;
 mod meta_battleground_search;
 mod meta_raid_search;
 mod meta_rated_arena_search;
 mod meta_skirmish_search;
+mod meter;
+mod meter_amount;
+mod meter_unit;
+mod progression_filter;
 mod raid_search_filter;
 mod ranking_character_meta;
+mod ranking_filter;
+mod ranking_history_entry;
+mod ranking_page;
 mod ranking_result;
 mod rated_arena_search_filter;
 mod responder_raw_json;
 mod search_arena_team;
 mod skirmish_search_filter;
+mod speed_run;
+mod unit_filter;
@@ -0,0 +1,9 @@
+// Passed as query parameters, e.g. ?server_id=1&map_id=533
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, FromForm)]
+pub struct ProgressionFilter {
+    pub server_id: Option<u32>,
+    pub expansion_id: Option<u8>,
+    pub map_id: Option<u16>,
+    pub difficulty_id: Option<u8>,
+    pub guild_id: Option<u32>,
+}
@@ -0,0 +1,28 @@
+use crate::modules::instance::material::Role;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema, FromFormValue)]
+pub enum RankingMetric {
+    Dps,
+    Hps,
+    Tps,
+}
+
+// Passed as query parameters, e.g. ?metric=Dps&encounter_id=1&role=Healer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, FromForm)]
+pub struct RankingFilter {
+    pub metric: RankingMetric,
+    pub encounter_id: u32,
+    pub difficulty_id: Option<u8>,
+    pub hero_class_id: Option<u8>,
+    // Index of the talent tree with the most points
+    pub spec_id: Option<u8>,
+    pub role: Option<Role>,
+    pub server_id: Option<u32>,
+    // Patch or season, see data_ranking_bracket
+    pub bracket_id: Option<u32>,
+    // Ranks by the amount per second of the time that the character was active instead of the whole attempt
+    pub rank_by_active_time: Option<bool>,
+    // Starts at 0
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
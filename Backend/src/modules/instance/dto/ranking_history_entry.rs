@@ -0,0 +1,12 @@
+/// A result of a character, compared to the current leaderboard
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RankingHistoryEntry {
+    pub attempt_id: u32,
+    pub start_ts: u64,
+    pub amount: u32,
+    pub duration: u64,
+    pub active_time: u64,
+    pub per_second: f64,
+    pub active_per_second: f64,
+    pub percentile: Option<f64>,
+}
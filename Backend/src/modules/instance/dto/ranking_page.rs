@@ -0,0 +1,27 @@
+use crate::modules::instance::dto::{RankingCharacterMeta, RankingResult};
+use crate::modules::instance::material::Role;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RankingPage {
+    // Number of ranked characters that satisfy the filter
+    pub total: u32,
+    pub page: u32,
+    pub page_size: u32,
+    pub entries: Vec<RankingEntry>,
+}
+
+/// The best result of a character
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RankingEntry {
+    // Starts at 1
+    pub rank: u32,
+    // Share of the ranked characters that did not perform better, the best one is at 100
+    pub percentile: f64,
+    pub character_id: u32,
+    pub character_meta: RankingCharacterMeta,
+    pub spec_id: Option<u8>,
+    pub role: Option<Role>,
+    pub result: RankingResult,
+    pub per_second: f64,
+    pub active_per_second: f64,
+}
@@ -1,6 +1,27 @@
+use crate::modules::instance::material::Role;
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RankingResult {
     pub attempt_id: u32,
     pub amount: u32,
+    // Of the whole attempt
     pub duration: u64,
+    // Of the character, i.e. without the time that it was dead or had not joined yet
+    pub active_time: u64,
+    pub server_id: u32,
+    pub difficulty_id: u8,
+    pub start_ts: u64,
+    // Of the character when the attempt was committed
+    pub spec_id: Option<u8>,
+    pub role: Option<Role>,
+}
+
+impl RankingResult {
+    pub fn per_second(&self) -> f64 {
+        self.amount as f64 * 1000.0 / self.duration.max(1) as f64
+    }
+
+    pub fn active_per_second(&self) -> f64 {
+        self.amount as f64 * 1000.0 / self.active_time.max(1) as f64
+    }
 }
@@ -0,0 +1,20 @@
+use crate::modules::armory::dto::SearchGuildDto;
+
+/// Full clear of a raid within one instance
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SpeedRun {
+    // Starts at 1 for each map and difficulty
+    pub rank: u32,
+    pub instance_meta_id: u32,
+    pub guild: Option<SearchGuildDto>,
+    pub server_id: u32,
+    pub map_id: u16,
+    pub difficulty_id: u8,
+    // From the first pull until the last kill
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub clear_time: u64,
+    // Time spent within attempts
+    pub encounter_time: u64,
+    pub wipes: u32,
+}
@@ -0,0 +1,31 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum UnitFilter {
+    Player { character_id: u32 },
+    // All creatures of the entry, unless a specific creature is given
+    Creature { entry: u32, creature_id: Option<u64> },
+}
+
+// player:<character_id>, creature:<entry> or creature:<entry>:<creature_id>
+impl FromStr for UnitFilter {
+    type Err = ();
+
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        let fields = unit.split(':').collect::<Vec<&str>>();
+        match fields.as_slice() {
+            ["player", character_id] => Ok(UnitFilter::Player {
+                character_id: character_id.parse().map_err(|_| ())?,
+            }),
+            ["creature", entry] => Ok(UnitFilter::Creature {
+                entry: entry.parse().map_err(|_| ())?,
+                creature_id: None,
+            }),
+            ["creature", entry, creature_id] => Ok(UnitFilter::Creature {
+                entry: entry.parse().map_err(|_| ())?,
+                creature_id: Some(creature_id.parse().map_err(|_| ())?),
+            }),
+            _ => Err(()),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::modules::instance::domain_value::get_consumable_type;
+use crate::modules::instance::dto::{AuraInterval, AuraUptime, ConsumableCoverage, ConsumableType, MeterUnit};
+use rust_lapper::{Interval, Lapper};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+enum AuraChangeKind {
+    Aura { caster: MeterUnit, spell_id: u32, stack_amount: u32 },
+    Death,
+}
+
+struct AuraChange {
+    event_id: u32,
+    timestamp: u64,
+    target: MeterUnit,
+    kind: AuraChangeKind,
+}
+
+// (caster, stack_amount)
+type TimelineInterval = Interval<u64, (MeterUnit, u32)>;
+type AuraLapper = Lapper<u64, (MeterUnit, u32)>;
+
+/// Collects the aura changes of an instance, in order to reconstruct the intervals in which each aura was active
+#[derive(Default)]
+pub struct AuraTimelineBuilder {
+    changes: Vec<AuraChange>,
+}
+
+/// The intervals of each aura of each unit, clipped to the time window
+pub struct AuraTimeline {
+    pub start_ts: u64,
+    pub end_ts: u64,
+    auras: BTreeMap<(MeterUnit, u32), AuraLapper>,
+}
+
+impl AuraTimelineBuilder {
+    /// Lines of the text format of the Death and AuraApplication event types
+    pub fn add_line(&mut self, event_type: u8, line: &str) {
+        if let Ok(Value::Array(event)) = serde_json::from_str::<Value>(line) {
+            self.add_event_values(event_type, &event);
+        }
+    }
+
+    fn add_event_values(&mut self, event_type: u8, event: &[Value]) -> Option<()> {
+        let event_id = event.first()?.as_u64()? as u32;
+        let timestamp = event.get(1)?.as_u64()?;
+        let target = MeterUnit::from_value(event.get(2)?)?;
+        let kind = match event_type {
+            // [id,timestamp,subject(,murder)]
+            1 => AuraChangeKind::Death,
+            // [id,timestamp,subject,caster,spell_id,stack_amount,school_mask]
+            6 => AuraChangeKind::Aura {
+                caster: MeterUnit::from_value(event.get(3)?)?,
+                spell_id: event.get(4)?.as_u64()? as u32,
+                stack_amount: event.get(5)?.as_u64()? as u32,
+            },
+            _ => return None,
+        };
+        self.changes.push(AuraChange { event_id, timestamp, target, kind });
+        Some(())
+    }
+
+    /// The timeline only spans the part of the window that is covered by events.
+    /// Auras that are removed without being applied before were applied before the log started.
+    pub fn build(mut self, (start_ts, end_ts): (u64, u64)) -> AuraTimeline {
+        self.changes.sort_by_key(|change| (change.timestamp, change.event_id));
+        let timeline_start_ts = self.changes.first().map(|change| change.timestamp.max(start_ts)).unwrap_or(start_ts);
+        let timeline_end_ts = self.changes.last().map(|change| change.timestamp.min(end_ts)).unwrap_or(start_ts).max(timeline_start_ts);
+        let first_ts = self.changes.first().map(|change| change.timestamp).unwrap_or(0);
+
+        let mut intervals: BTreeMap<(MeterUnit, u32), Vec<TimelineInterval>> = BTreeMap::new();
+        let mut add_interval = |target: MeterUnit, spell_id: u32, caster: MeterUnit, stack_amount: u32, start: u64, stop: u64| {
+            let (start, stop) = (start.max(timeline_start_ts), stop.min(timeline_end_ts));
+            if start < stop {
+                intervals.entry((target, spell_id)).or_default().push(Interval { start, stop, val: (caster, stack_amount) });
+            }
+        };
+
+        // (target, spell_id, caster) => (applied_ts, stack_amount)
+        let mut open_auras: HashMap<(MeterUnit, u32, MeterUnit), (u64, u32)> = HashMap::new();
+        let mut seen_auras: HashSet<(MeterUnit, u32, MeterUnit)> = HashSet::new();
+        for change in self.changes.iter() {
+            match change.kind {
+                AuraChangeKind::Aura { caster, spell_id, stack_amount } => {
+                    let key = (change.target, spell_id, caster);
+                    if let Some((applied_ts, open_stack_amount)) = open_auras.remove(&key) {
+                        add_interval(change.target, spell_id, caster, open_stack_amount, applied_ts, change.timestamp);
+                    } else if stack_amount == 0 && !seen_auras.contains(&key) {
+                        add_interval(change.target, spell_id, caster, 1, first_ts, change.timestamp);
+                    }
+                    if stack_amount > 0 {
+                        open_auras.insert(key, (change.timestamp, stack_amount));
+                    }
+                    seen_auras.insert(key);
+                },
+                // Auras do not survive a death
+                AuraChangeKind::Death => {
+                    let died_auras: Vec<(MeterUnit, u32, MeterUnit)> = open_auras.keys().filter(|(target, _, _)| *target == change.target).cloned().collect();
+                    for key in died_auras {
+                        let (applied_ts, stack_amount) = open_auras.remove(&key).unwrap();
+                        add_interval(key.0, key.1, key.2, stack_amount, applied_ts, change.timestamp);
+                    }
+                },
+            }
+        }
+        for ((target, spell_id, caster), (applied_ts, stack_amount)) in open_auras {
+            add_interval(target, spell_id, caster, stack_amount, applied_ts, timeline_end_ts);
+        }
+
+        AuraTimeline {
+            start_ts: timeline_start_ts,
+            end_ts: timeline_end_ts,
+            auras: intervals.into_iter().map(|(key, intervals)| (key, Lapper::new(intervals))).collect(),
+        }
+    }
+}
+
+impl AuraTimeline {
+    /// Uptimes of all auras, unless only some spells are requested
+    pub fn get_uptimes(&self, spell_ids: Option<&[u32]>) -> Vec<AuraUptime> {
+        let duration = self.end_ts - self.start_ts;
+        self.auras
+            .iter()
+            .filter(|((_, spell_id), _)| spell_ids.map(|spell_ids| spell_ids.contains(spell_id)).unwrap_or(true))
+            .map(|((target, spell_id), lapper)| {
+                let uptime = lapper.cov();
+                AuraUptime {
+                    target: *target,
+                    spell_id: *spell_id,
+                    uptime,
+                    uptime_percent: if duration == 0 { 0.0 } else { uptime as f64 * 100.0 / duration as f64 },
+                    timeline: lapper
+                        .iter()
+                        .map(|interval| AuraInterval {
+                            start_ts: interval.start,
+                            end_ts: interval.stop,
+                            caster: interval.val.0,
+                            stack_amount: interval.val.1,
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Start of the continuous presence of the aura at the timestamp, regardless of who applied it
+    pub fn get_applied_ts(&self, target: MeterUnit, spell_id: u32, timestamp: u64) -> Option<u64> {
+        let lapper = self.auras.get(&(target, spell_id))?;
+        let mut presence: Option<(u64, u64)> = None;
+        for interval in lapper.iter().take_while(|interval| interval.start <= timestamp) {
+            presence = match presence {
+                Some((start, stop)) if interval.start <= stop => Some((start, stop.max(interval.stop))),
+                _ => Some((interval.start, interval.stop)),
+            };
+        }
+        presence.filter(|(_, stop)| *stop >= timestamp).map(|(start, _)| start)
+    }
+
+    /// Consumables of every player that had any aura within the timeline
+    pub fn get_consumable_coverage(&self, pull_ts: u64) -> Vec<ConsumableCoverage> {
+        let mut coverage: BTreeMap<u32, ConsumableCoverage> = BTreeMap::new();
+        for ((target, spell_id), lapper) in self.auras.iter() {
+            let character_id = match target {
+                MeterUnit::Player { character_id } => *character_id,
+                _ => continue,
+            };
+            let consumables = coverage.entry(character_id).or_insert(ConsumableCoverage {
+                character_id,
+                flask: None,
+                battle_elixir: None,
+                guardian_elixir: None,
+                food: None,
+                is_covered: false,
+            });
+            if lapper.find(pull_ts, pull_ts + 1).next().is_none() {
+                continue;
+            }
+            match get_consumable_type(*spell_id) {
+                Some(ConsumableType::Flask) => consumables.flask = Some(*spell_id),
+                Some(ConsumableType::BattleElixir) => consumables.battle_elixir = Some(*spell_id),
+                Some(ConsumableType::GuardianElixir) => consumables.guardian_elixir = Some(*spell_id),
+                Some(ConsumableType::Food) => consumables.food = Some(*spell_id),
+                None => {},
+            };
+        }
+        for consumables in coverage.values_mut() {
+            consumables.is_covered = consumables.food.is_some() && (consumables.flask.is_some() || (consumables.battle_elixir.is_some() && consumables.guardian_elixir.is_some()));
+        }
+        coverage.values().cloned().collect()
+    }
+}
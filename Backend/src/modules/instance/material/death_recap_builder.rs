@@ -0,0 +1,198 @@
+use crate::modules::data::domain_value::DefensiveCooldown;
+use crate::modules::instance::domain_value::MeterEvent;
+use crate::modules::instance::dto::{DeathRecap, DeathRecapAura, DeathRecapCooldown, DeathRecapEvent, DeathRecapEventType, DeathRecapPower, MeterUnit};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+struct Death {
+    event_id: u32,
+    timestamp: u64,
+    character_id: u32,
+    murder: Option<MeterUnit>,
+}
+
+struct AuraChange {
+    timestamp: u64,
+    caster: MeterUnit,
+    spell_id: u32,
+    stack_amount: u32,
+}
+
+/// Collects the stored events of an instance and reconstructs the deaths of players from them
+#[derive(Default)]
+pub struct DeathRecapBuilder {
+    deaths: Vec<Death>,
+    // Events are grouped by the character that they happened to
+    events: HashMap<u32, Vec<DeathRecapEvent>>,
+    aura_changes: HashMap<u32, Vec<AuraChange>>,
+    powers: HashMap<u32, Vec<DeathRecapPower>>,
+    // (timestamp, spell_id)
+    casts: HashMap<u32, Vec<(u64, u32)>>,
+}
+
+impl DeathRecapBuilder {
+    /// Lines of the text format of the Death, SpellCast, Power, AuraApplication, damage and heal event types
+    pub fn add_line(&mut self, event_type: u8, line: &str) {
+        match event_type {
+            12 => self.add_event(DeathRecapEventType::Damage, MeterEvent::from_melee_damage(line)),
+            13 => self.add_event(DeathRecapEventType::Damage, MeterEvent::from_spell_damage(line)),
+            14 => self.add_event(DeathRecapEventType::Heal, MeterEvent::from_heal(line)),
+            _ => {
+                if let Ok(Value::Array(event)) = serde_json::from_str::<Value>(line) {
+                    self.add_event_values(event_type, &event);
+                }
+            },
+        }
+    }
+
+    fn add_event(&mut self, event_type: DeathRecapEventType, meter_event: Option<MeterEvent>) {
+        if let Some(MeterEvent {
+            timestamp,
+            source,
+            target: MeterUnit::Player { character_id },
+            spell_id,
+            amount,
+            ..
+        }) = meter_event
+        {
+            self.events.entry(character_id).or_default().push(DeathRecapEvent {
+                event_type,
+                timestamp,
+                source,
+                spell_id,
+                amount,
+            });
+        }
+    }
+
+    fn add_event_values(&mut self, event_type: u8, event: &[Value]) -> Option<()> {
+        let event_id = event.first()?.as_u64()? as u32;
+        let timestamp = event.get(1)?.as_u64()?;
+        let character_id = match MeterUnit::from_value(event.get(2)?)? {
+            MeterUnit::Player { character_id } => character_id,
+            _ => return None,
+        };
+        let number = |index: usize| event.get(index).and_then(Value::as_u64);
+        match event_type {
+            // [id,timestamp,subject,victim,spell_id,hit_mask,school_mask]
+            0 => self.casts.entry(character_id).or_default().push((timestamp, number(4)? as u32)),
+            // [id,timestamp,subject(,murder)]
+            1 => self.deaths.push(Death {
+                event_id,
+                timestamp,
+                character_id,
+                murder: event.get(3).and_then(MeterUnit::from_value),
+            }),
+            // [id,timestamp,subject,current_power,max_power,power_type]
+            5 => self.powers.entry(character_id).or_default().push(DeathRecapPower {
+                timestamp,
+                power_type: number(5)? as u8,
+                current_power: number(3)? as u32,
+                max_power: number(4)? as u32,
+            }),
+            // [id,timestamp,subject,caster,spell_id,stack_amount,school_mask]
+            6 => self.aura_changes.entry(character_id).or_default().push(AuraChange {
+                timestamp,
+                caster: MeterUnit::from_value(event.get(3)?)?,
+                spell_id: number(4)? as u32,
+                stack_amount: number(5)? as u32,
+            }),
+            _ => {},
+        };
+        Some(())
+    }
+
+    /// Deaths within the time window. The defensive cooldowns are looked up by the character, the cooldown of a spell by its id.
+    pub fn build(mut self, (start_ts, end_ts): (u64, u64), recap_duration: u64, get_defensive_cooldowns: impl Fn(u32) -> Vec<DefensiveCooldown>, get_cooldown: impl Fn(u32) -> Option<u32>) -> Vec<DeathRecap> {
+        self.deaths.sort_by_key(|death| (death.timestamp, death.event_id));
+        self.events.values_mut().for_each(|events| events.sort_by_key(|event| event.timestamp));
+        self.aura_changes.values_mut().for_each(|aura_changes| aura_changes.sort_by_key(|aura_change| aura_change.timestamp));
+        self.powers.values_mut().for_each(|powers| powers.sort_by_key(|power| power.timestamp));
+
+        let mut previous_deaths: HashMap<u32, u64> = HashMap::new();
+        let mut death_recaps = Vec::new();
+        for death in self.deaths.iter() {
+            let previous_death_ts = previous_deaths.insert(death.character_id, death.timestamp).unwrap_or(0);
+            if death.timestamp < start_ts || death.timestamp > end_ts {
+                continue;
+            }
+
+            let recap_start_ts = death.timestamp.saturating_sub(recap_duration);
+            let events = self
+                .events
+                .get(&death.character_id)
+                .map(|events| events.iter().filter(|event| event.timestamp >= recap_start_ts && event.timestamp <= death.timestamp).cloned().collect::<Vec<DeathRecapEvent>>())
+                .unwrap_or_default();
+            let killing_blow = events.iter().rev().find(|event| event.event_type == DeathRecapEventType::Damage).cloned();
+            let powers = self
+                .powers
+                .get(&death.character_id)
+                .map(|powers| powers.iter().filter(|power| power.timestamp >= recap_start_ts && power.timestamp <= death.timestamp).cloned().collect())
+                .unwrap_or_default();
+
+            // Auras do not survive a death
+            let mut auras: BTreeMap<(u32, MeterUnit), DeathRecapAura> = BTreeMap::new();
+            for aura_change in self.aura_changes.get(&death.character_id).into_iter().flatten() {
+                if aura_change.timestamp <= previous_death_ts || aura_change.timestamp > death.timestamp {
+                    continue;
+                }
+                if aura_change.stack_amount == 0 {
+                    auras.remove(&(aura_change.spell_id, aura_change.caster));
+                } else {
+                    let aura = auras.entry((aura_change.spell_id, aura_change.caster)).or_insert(DeathRecapAura {
+                        spell_id: aura_change.spell_id,
+                        caster: aura_change.caster,
+                        applied_ts: aura_change.timestamp,
+                        stack_amount: 0,
+                    });
+                    aura.stack_amount = aura_change.stack_amount;
+                }
+            }
+
+            // Ranks are grouped by their first rank. Unused ones are reported with their highest rank.
+            // base_spell_id => (spell_id, last_used_ts)
+            let candidates = get_defensive_cooldowns(death.character_id);
+            let mut defensives: BTreeMap<u32, (u32, Option<u64>)> = BTreeMap::new();
+            for defensive_cooldown in candidates.iter() {
+                let defensive = defensives.entry(defensive_cooldown.base_spell_id).or_insert((defensive_cooldown.spell_id, None));
+                defensive.0 = defensive.0.max(defensive_cooldown.spell_id);
+            }
+            for (timestamp, spell_id) in self.casts.get(&death.character_id).into_iter().flatten() {
+                if *timestamp > death.timestamp {
+                    continue;
+                }
+                if let Some(defensive_cooldown) = candidates.iter().find(|defensive_cooldown| defensive_cooldown.spell_id == *spell_id) {
+                    let defensive = defensives.get_mut(&defensive_cooldown.base_spell_id).unwrap();
+                    if defensive.1.map(|last_used_ts| last_used_ts <= *timestamp).unwrap_or(true) {
+                        *defensive = (*spell_id, Some(*timestamp));
+                    }
+                }
+            }
+            let defensive_cooldowns = defensives
+                .into_iter()
+                .map(|(_, (spell_id, last_used_ts))| {
+                    let cooldown = get_cooldown(spell_id).unwrap_or(0);
+                    DeathRecapCooldown {
+                        spell_id,
+                        last_used_ts,
+                        cooldown,
+                        is_available: last_used_ts.map(|last_used_ts| last_used_ts + cooldown as u64 <= death.timestamp).unwrap_or(true),
+                    }
+                })
+                .collect();
+
+            death_recaps.push(DeathRecap {
+                event_id: death.event_id,
+                timestamp: death.timestamp,
+                character_id: death.character_id,
+                murder: death.murder,
+                killing_blow,
+                events,
+                auras: auras.values().cloned().collect(),
+                powers,
+                defensive_cooldowns,
+            });
+        }
+        death_recaps
+    }
+}
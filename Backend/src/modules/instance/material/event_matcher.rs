@@ -0,0 +1,74 @@
+use crate::modules::instance::domain_value::EventLayout;
+use crate::modules::instance::dto::{EventExportFilter, UnitFilter};
+use serde_json::Value;
+
+/// Evaluates an export filter against the stored events of an event type
+pub struct EventMatcher {
+    layout: EventLayout,
+    start_ts: u64,
+    end_ts: u64,
+    sources: Option<Vec<UnitFilter>>,
+    targets: Option<Vec<UnitFilter>>,
+    spell_ids: Option<Vec<u32>>,
+    cursor: Option<u32>,
+}
+
+impl EventMatcher {
+    pub fn new(event_type: u8, (start_ts, end_ts): (u64, u64), filter: EventExportFilter) -> Self {
+        EventMatcher {
+            layout: EventLayout::from_event_type(event_type),
+            start_ts,
+            end_ts,
+            sources: filter.sources,
+            targets: filter.targets,
+            spell_ids: filter.spell_ids,
+            cursor: filter.cursor,
+        }
+    }
+
+    pub fn is_after_cursor(&self, event_id: u32) -> bool {
+        self.cursor.map(|cursor| event_id > cursor).unwrap_or(true)
+    }
+
+    pub fn overlaps(&self, start_ts: u64, end_ts: u64) -> bool {
+        start_ts <= self.end_ts && end_ts >= self.start_ts
+    }
+
+    pub fn matches(&self, event_id: u32, line: &str) -> bool {
+        if !self.is_after_cursor(event_id) {
+            return false;
+        }
+        let event = match serde_json::from_str::<Value>(line) {
+            Ok(Value::Array(event)) => event,
+            _ => return false,
+        };
+        let timestamp = event.get(1).and_then(Value::as_u64).unwrap_or(0);
+        if timestamp < self.start_ts || timestamp > self.end_ts {
+            return false;
+        }
+
+        let matches_unit = |position: Option<usize>, units: &Option<Vec<UnitFilter>>| match units {
+            Some(units) => position.and_then(|position| event.get(position)).map(|unit| units.iter().any(|unit_filter| is_unit(unit, unit_filter))).unwrap_or(false),
+            None => true,
+        };
+        let matches_spell = match &self.spell_ids {
+            Some(spell_ids) => self.layout.spells.iter().filter_map(|position| event.get(*position).and_then(Value::as_u64)).any(|spell_id| spell_ids.contains(&(spell_id as u32))),
+            None => true,
+        };
+        matches_unit(self.layout.source, &self.sources) && matches_unit(self.layout.target, &self.targets) && matches_spell
+    }
+}
+
+// Player: [1,character_id], Creature: [0,creature_id,entry(,owner)]
+fn is_unit(unit: &Value, unit_filter: &UnitFilter) -> bool {
+    let unit = match unit.as_array() {
+        Some(unit) => unit,
+        None => return false,
+    };
+    let field = |index: usize| unit.get(index).and_then(Value::as_u64);
+    match (field(0), unit_filter) {
+        (Some(1), UnitFilter::Player { character_id }) => field(1) == Some(*character_id as u64),
+        (Some(0), UnitFilter::Creature { entry, creature_id }) => field(2) == Some(*entry as u64) && creature_id.map(|creature_id| field(1) == Some(creature_id)).unwrap_or(true),
+        _ => false,
+    }
+}
@@ -3,6 +3,7 @@ use crate::modules::armory::tools::GetArenaTeam;
 use crate::modules::armory::Armory;
 use crate::modules::instance::domain_value::{InstanceMeta, MetaType};
 use crate::modules::instance::dto::{InstanceViewerAttempt, RankingResult};
+use crate::modules::instance::material::Role;
 use crate::params;
 use crate::util::database::Select;
 use std::collections::HashMap;
@@ -68,7 +69,7 @@ fn update_instance_rankings_dps(instance_rankings_dps: Arc<RwLock<(u32, HashMap<
     let mut rankings_dps = instance_rankings_dps.write().unwrap();
     db_main
         .select_wparams(
-            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.damage, (B.end_ts - B.start_ts) as duration FROM instance_ranking_damage A JOIN instance_attempt B ON A.attempt_id = B.id WHERE A.id > :last_queried_id ORDER BY A.id",
+            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.damage, (B.end_ts - B.start_ts) as duration, C.server_id, D.map_difficulty, B.start_ts, LEAST(IFNULL(E.active_time, B.end_ts - B.start_ts), B.end_ts - B.start_ts) as active_time, A.spec_id, A.role FROM instance_ranking_damage A JOIN instance_attempt B ON A.attempt_id = B.id JOIN instance_meta C ON B.instance_meta_id = C.id JOIN instance_raid D ON C.id = D.instance_meta_id LEFT JOIN instance_ranking_active_time E ON A.attempt_id = E.attempt_id AND A.character_id = E.character_id WHERE A.id > :last_queried_id ORDER BY A.id",
             |mut row| {
                 let id: u32 = row.take(0).unwrap();
                 let character_id: u32 = row.take(1).unwrap();
@@ -81,6 +82,12 @@ fn update_instance_rankings_dps(instance_rankings_dps: Arc<RwLock<(u32, HashMap<
                         attempt_id: row.take(3).unwrap(),
                         amount: row.take(4).unwrap(),
                         duration: row.take(5).unwrap(),
+                        server_id: row.take(6).unwrap(),
+                        difficulty_id: row.take(7).unwrap(),
+                        start_ts: row.take(8).unwrap(),
+                        active_time: row.take(9).unwrap(),
+                        spec_id: row.take_opt(10).unwrap().ok(),
+                        role: row.take_opt(11).unwrap().ok().map(Role::from_u8),
                     },
                 )
             },
@@ -99,7 +106,7 @@ fn update_instance_rankings_hps(instance_rankings_hps: Arc<RwLock<(u32, HashMap<
     let mut rankings_hps = instance_rankings_hps.write().unwrap();
     db_main
         .select_wparams(
-            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.heal, (B.end_ts - B.start_ts) as duration FROM instance_ranking_heal A JOIN instance_attempt B ON A.attempt_id = B.id WHERE A.id > :last_queried_id ORDER BY A.id",
+            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.heal, (B.end_ts - B.start_ts) as duration, C.server_id, D.map_difficulty, B.start_ts, LEAST(IFNULL(E.active_time, B.end_ts - B.start_ts), B.end_ts - B.start_ts) as active_time, A.spec_id, A.role FROM instance_ranking_heal A JOIN instance_attempt B ON A.attempt_id = B.id JOIN instance_meta C ON B.instance_meta_id = C.id JOIN instance_raid D ON C.id = D.instance_meta_id LEFT JOIN instance_ranking_active_time E ON A.attempt_id = E.attempt_id AND A.character_id = E.character_id WHERE A.id > :last_queried_id ORDER BY A.id",
             |mut row| {
                 let id: u32 = row.take(0).unwrap();
                 let character_id: u32 = row.take(1).unwrap();
@@ -112,6 +119,12 @@ fn update_instance_rankings_hps(instance_rankings_hps: Arc<RwLock<(u32, HashMap<
                         attempt_id: row.take(3).unwrap(),
                         amount: row.take(4).unwrap(),
                         duration: row.take(5).unwrap(),
+                        server_id: row.take(6).unwrap(),
+                        difficulty_id: row.take(7).unwrap(),
+                        start_ts: row.take(8).unwrap(),
+                        active_time: row.take(9).unwrap(),
+                        spec_id: row.take_opt(10).unwrap().ok(),
+                        role: row.take_opt(11).unwrap().ok().map(Role::from_u8),
                     },
                 )
             },
@@ -130,7 +143,7 @@ fn update_instance_rankings_tps(instance_rankings_tps: Arc<RwLock<(u32, HashMap<
     let mut rankings_tps = instance_rankings_tps.write().unwrap();
     db_main
         .select_wparams(
-            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.threat, (B.end_ts - B.start_ts) as duration FROM instance_ranking_threat A JOIN instance_attempt B ON A.attempt_id = B.id WHERE A.id > :last_queried_id ORDER BY A.id",
+            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.threat, (B.end_ts - B.start_ts) as duration, C.server_id, D.map_difficulty, B.start_ts, LEAST(IFNULL(E.active_time, B.end_ts - B.start_ts), B.end_ts - B.start_ts) as active_time, A.spec_id, A.role FROM instance_ranking_threat A JOIN instance_attempt B ON A.attempt_id = B.id JOIN instance_meta C ON B.instance_meta_id = C.id JOIN instance_raid D ON C.id = D.instance_meta_id LEFT JOIN instance_ranking_active_time E ON A.attempt_id = E.attempt_id AND A.character_id = E.character_id WHERE A.id > :last_queried_id ORDER BY A.id",
             |mut row| {
                 let id: u32 = row.take(0).unwrap();
                 let character_id: u32 = row.take(1).unwrap();
@@ -143,6 +156,12 @@ fn update_instance_rankings_tps(instance_rankings_tps: Arc<RwLock<(u32, HashMap<
                         attempt_id: row.take(3).unwrap(),
                         amount: row.take(4).unwrap(),
                         duration: row.take(5).unwrap(),
+                        server_id: row.take(6).unwrap(),
+                        difficulty_id: row.take(7).unwrap(),
+                        start_ts: row.take(8).unwrap(),
+                        active_time: row.take(9).unwrap(),
+                        spec_id: row.take_opt(10).unwrap().ok(),
+                        role: row.take_opt(11).unwrap().ok().map(Role::from_u8),
                     },
                 )
             },
@@ -0,0 +1,113 @@
+use crate::modules::instance::domain_value::CounterEvent;
+use crate::modules::instance::dto::{EffectivenessEvent, EffectivenessRanking, EffectivenessSpell, MeterUnit, MissedInterrupt};
+use crate::modules::instance::material::AuraTimeline;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Collects interrupts, dispels, spell steals and the casts of enemies of an instance
+#[derive(Default)]
+pub struct InterruptAudit {
+    interrupts: Vec<CounterEvent>,
+    dispels: Vec<CounterEvent>,
+    spell_steals: Vec<CounterEvent>,
+    enemy_casts: Vec<MissedInterrupt>,
+}
+
+impl InterruptAudit {
+    /// Lines of the text format of the SpellCast, Interrupt, SpellSteal and Dispel event types
+    pub fn add_line(&mut self, event_type: u8, line: &str) {
+        match event_type {
+            0 => {
+                if let Some(enemy_cast) = parse_enemy_cast(line) {
+                    self.enemy_casts.push(enemy_cast);
+                }
+            },
+            7 => self.interrupts.extend(CounterEvent::from_interrupt(line)),
+            8 => self.spell_steals.extend(CounterEvent::from_un_aura(line)),
+            9 => self.dispels.extend(CounterEvent::from_un_aura(line)),
+            _ => {},
+        };
+    }
+
+    pub fn get_interrupts(&self, window: (u64, u64)) -> Vec<EffectivenessRanking> {
+        rank(&self.interrupts, window, |_| None)
+    }
+
+    /// The reaction time is measured from the application of the removed aura
+    pub fn get_dispels(&self, window: (u64, u64), aura_timeline: &AuraTimeline) -> Vec<EffectivenessRanking> {
+        rank(&self.dispels, window, |event| reaction_time(event, aura_timeline))
+    }
+
+    pub fn get_spell_steals(&self, window: (u64, u64), aura_timeline: &AuraTimeline) -> Vec<EffectivenessRanking> {
+        rank(&self.spell_steals, window, |event| reaction_time(event, aura_timeline))
+    }
+
+    /// A spell cast is only logged once it completed, hence every cast of these spells was not interrupted.
+    /// Casts that hit multiple victims are counted once.
+    pub fn get_missed_interrupts(&self, (start_ts, end_ts): (u64, u64), interruptible_spell_ids: &[u32]) -> Vec<MissedInterrupt> {
+        let mut seen_casts = BTreeSet::new();
+        let mut missed_interrupts = self
+            .enemy_casts
+            .iter()
+            .filter(|cast| cast.timestamp >= start_ts && cast.timestamp <= end_ts && interruptible_spell_ids.contains(&cast.spell_id))
+            .filter(|cast| seen_casts.insert((cast.timestamp, cast.caster, cast.spell_id)))
+            .cloned()
+            .collect::<Vec<MissedInterrupt>>();
+        missed_interrupts.sort_by_key(|cast| (cast.timestamp, cast.event_id));
+        missed_interrupts
+    }
+}
+
+// Creatures that are not owned by a player: [id,timestamp,[0,creature_id,entry],victim,spell_id,hit_mask,school_mask]
+fn parse_enemy_cast(line: &str) -> Option<MissedInterrupt> {
+    let (event_id, timestamp, caster, _, spell_id, _, _): (u32, u64, Value, Value, u32, u32, u8) = serde_json::from_str(line).ok()?;
+    if caster.as_array()?.len() != 3 {
+        return None;
+    }
+    match MeterUnit::from_value(&caster)? {
+        caster @ MeterUnit::Creature { .. } => Some(MissedInterrupt { event_id, timestamp, caster, spell_id }),
+        MeterUnit::Player { .. } => None,
+    }
+}
+
+fn reaction_time(event: &CounterEvent, aura_timeline: &AuraTimeline) -> Option<u64> {
+    aura_timeline.get_applied_ts(event.target, event.affected_spell_id, event.timestamp).map(|applied_ts| event.timestamp - applied_ts)
+}
+
+fn rank(events: &[CounterEvent], (start_ts, end_ts): (u64, u64), get_reaction_time: impl Fn(&CounterEvent) -> Option<u64>) -> Vec<EffectivenessRanking> {
+    let mut events_by_source: BTreeMap<MeterUnit, Vec<EffectivenessEvent>> = BTreeMap::new();
+    for event in events.iter().filter(|event| event.timestamp >= start_ts && event.timestamp <= end_ts) {
+        events_by_source.entry(event.source).or_default().push(EffectivenessEvent {
+            event_id: event.event_id,
+            timestamp: event.timestamp,
+            target: event.target,
+            spell_id: event.spell_id,
+            affected_spell_id: event.affected_spell_id,
+            reaction_time: get_reaction_time(event),
+        });
+    }
+
+    let mut ranking = events_by_source
+        .into_iter()
+        .map(|(source, mut events)| {
+            events.sort_by_key(|event| (event.timestamp, event.event_id));
+            let mut affected_spells: BTreeMap<u32, u32> = BTreeMap::new();
+            for event in events.iter() {
+                *affected_spells.entry(event.affected_spell_id).or_insert(0) += 1;
+            }
+            let mut affected_spells = affected_spells.into_iter().map(|(spell_id, count)| EffectivenessSpell { spell_id, count }).collect::<Vec<EffectivenessSpell>>();
+            affected_spells.sort_by(|left, right| right.count.cmp(&left.count).then_with(|| left.spell_id.cmp(&right.spell_id)));
+
+            let reaction_times = events.iter().filter_map(|event| event.reaction_time).collect::<Vec<u64>>();
+            EffectivenessRanking {
+                source,
+                count: events.len() as u32,
+                average_reaction_time: if reaction_times.is_empty() { None } else { Some(reaction_times.iter().sum::<u64>() / reaction_times.len() as u64) },
+                affected_spells,
+                events,
+            }
+        })
+        .collect::<Vec<EffectivenessRanking>>();
+    ranking.sort_by(|left, right| right.count.cmp(&left.count).then_with(|| left.source.cmp(&right.source)));
+    ranking
+}
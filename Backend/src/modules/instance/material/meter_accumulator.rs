@@ -0,0 +1,42 @@
+use crate::modules::instance::domain_value::MeterEvent;
+use crate::modules::instance::dto::{Meter, MeterAmount, MeterSchoolEntry, MeterSpellEntry, MeterUnit, MeterUnitEntry};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Default)]
+pub struct MeterAccumulator {
+    total: MeterAmount,
+    by_source: HashMap<MeterUnit, MeterAmount>,
+    by_spell: HashMap<u32, MeterAmount>,
+    by_target: HashMap<MeterUnit, MeterAmount>,
+    by_school: HashMap<u8, MeterAmount>,
+}
+
+impl MeterAccumulator {
+    pub fn add(&mut self, event: MeterEvent) {
+        self.total += event.amount;
+        *self.by_source.entry(event.source).or_default() += event.amount;
+        *self.by_spell.entry(event.spell_id).or_default() += event.amount;
+        *self.by_target.entry(event.target).or_default() += event.amount;
+        for (school_mask, amount) in event.schools {
+            *self.by_school.entry(school_mask).or_default() += amount;
+        }
+    }
+
+    /// Entries are sorted by amount, descending
+    pub fn into_meter(self) -> Meter {
+        Meter {
+            total: self.total,
+            by_source: into_sorted(self.by_source).into_iter().map(|(unit, amount)| MeterUnitEntry { unit, amount }).collect(),
+            by_spell: into_sorted(self.by_spell).into_iter().map(|(spell_id, amount)| MeterSpellEntry { spell_id, amount }).collect(),
+            by_target: into_sorted(self.by_target).into_iter().map(|(unit, amount)| MeterUnitEntry { unit, amount }).collect(),
+            by_school: into_sorted(self.by_school).into_iter().map(|(school_mask, amount)| MeterSchoolEntry { school_mask, amount }).collect(),
+        }
+    }
+}
+
+fn into_sorted<T: Hash + Ord>(entries: HashMap<T, MeterAmount>) -> Vec<(T, MeterAmount)> {
+    let mut entries = entries.into_iter().collect::<Vec<(T, MeterAmount)>>();
+    entries.sort_by(|(left_key, left), (right_key, right)| right.amount.cmp(&left.amount).then_with(|| left_key.cmp(right_key)));
+    entries
+}
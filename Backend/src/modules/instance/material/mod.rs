@@ -1,5 +1,19 @@
+pub use self::aura_timeline::{AuraTimeline, AuraTimelineBuilder};
+pub use self::death_recap_builder::DeathRecapBuilder;
+pub use self::event_matcher::EventMatcher;
 pub use self::instance::Instance;
+pub use self::interrupt_audit::InterruptAudit;
+pub use self::meter_accumulator::MeterAccumulator;
+pub use self::raid_progress::{RaidAttempts, RaidProgress};
+pub use self::ranking_leaderboard::{RankingCandidate, RankingLeaderboard};
 pub use self::role::Role;
 
+mod aura_timeline;
+mod death_recap_builder;
+mod event_matcher;
 mod instance;
+mod interrupt_audit;
+mod meter_accumulator;
+mod raid_progress;
+mod ranking_leaderboard;
 mod role;
@@ -0,0 +1,168 @@
+use crate::modules::armory::dto::SearchGuildDto;
+use crate::modules::instance::dto::{EncounterProgression, GuildProgression, InstanceViewerAttempt, SpeedRun};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// A raid instance with all of its attempts
+pub struct RaidAttempts {
+    pub instance_meta_id: u32,
+    pub server_id: u32,
+    pub map_id: u16,
+    pub difficulty_id: u8,
+    pub guild: Option<SearchGuildDto>,
+    pub attempts: Vec<InstanceViewerAttempt>,
+}
+
+/// Derives the progression of guilds and the full clears of raids from their attempts.
+/// Attempts of a hard mode count as attempts of the encounter that it belongs to.
+pub struct RaidProgress {
+    raids: Vec<RaidAttempts>,
+    // Encounters of each map that have to be killed for a full clear
+    encounters: HashMap<u16, BTreeSet<u32>>,
+    // hard_mode_encounter_id => encounter_id
+    hard_modes: HashMap<u32, u32>,
+}
+
+impl RaidProgress {
+    pub fn new(mut raids: Vec<RaidAttempts>, encounters: HashMap<u16, BTreeSet<u32>>, hard_modes: HashMap<u32, u32>) -> Self {
+        raids.iter_mut().for_each(|raid| raid.attempts.sort_by_key(|attempt| (attempt.start_ts, attempt.id)));
+        RaidProgress { raids, encounters, hard_modes }
+    }
+
+    fn get_encounter_id(&self, encounter_id: u32) -> u32 {
+        self.hard_modes.get(&encounter_id).cloned().unwrap_or(encounter_id)
+    }
+
+    /// Ordered by map and difficulty, then by the number of killed encounters and how early that progress was reached
+    pub fn get_guild_progressions(&self) -> Vec<GuildProgression> {
+        let mut guild_raids: BTreeMap<(u32, u16, u8), Vec<&RaidAttempts>> = BTreeMap::new();
+        for raid in self.raids.iter() {
+            if let Some(guild) = raid.guild.as_ref() {
+                guild_raids.entry((guild.guild_id, raid.map_id, raid.difficulty_id)).or_default().push(raid);
+            }
+        }
+
+        let mut progressions = guild_raids
+            .into_iter()
+            .map(|((_, map_id, difficulty_id), raids)| {
+                let mut attempts = raids
+                    .iter()
+                    .flat_map(|raid| raid.attempts.iter().map(move |attempt| (raid.instance_meta_id, attempt)))
+                    .collect::<Vec<(u32, &InstanceViewerAttempt)>>();
+                attempts.sort_by_key(|(_, attempt)| (attempt.start_ts, attempt.id));
+
+                let required_encounters = self.encounters.get(&map_id).cloned().unwrap_or_default();
+                let mut encounters: BTreeMap<u32, EncounterProgression> = required_encounters.iter().map(|encounter_id| (*encounter_id, new_encounter_progression(*encounter_id))).collect();
+                for (instance_meta_id, attempt) in attempts {
+                    let encounter_id = self.get_encounter_id(attempt.encounter_id);
+                    let progression = encounters.entry(encounter_id).or_insert_with(|| new_encounter_progression(encounter_id));
+                    if progression.first_kill_ts.is_none() {
+                        if attempt.is_kill {
+                            progression.first_kill_ts = Some(attempt.end_ts);
+                            progression.first_kill_instance_meta_id = Some(instance_meta_id);
+                        } else {
+                            progression.wipes_before_kill += 1;
+                        }
+                    }
+                    if attempt.is_kill && encounter_id != attempt.encounter_id && progression.hard_mode_first_kill_ts.is_none() {
+                        progression.hard_mode_first_kill_ts = Some(attempt.end_ts);
+                    }
+                }
+
+                let killed_encounters = required_encounters.iter().filter(|encounter_id| encounters.get(encounter_id).and_then(|progression| progression.first_kill_ts).is_some()).count();
+                GuildProgression {
+                    guild: raids[0].guild.clone().unwrap(),
+                    server_id: raids[0].server_id,
+                    map_id,
+                    difficulty_id,
+                    killed_encounters: killed_encounters as u32,
+                    total_encounters: required_encounters.len() as u32,
+                    encounters: encounters.values().cloned().collect(),
+                }
+            })
+            .collect::<Vec<GuildProgression>>();
+
+        let progress_ts = |progression: &GuildProgression| progression.encounters.iter().filter_map(|encounter| encounter.first_kill_ts).max().unwrap_or(u64::MAX);
+        progressions.sort_by(|left, right| {
+            (left.map_id, left.difficulty_id)
+                .cmp(&(right.map_id, right.difficulty_id))
+                .then_with(|| right.killed_encounters.cmp(&left.killed_encounters))
+                .then_with(|| progress_ts(left).cmp(&progress_ts(right)))
+                .then_with(|| left.guild.guild_id.cmp(&right.guild.guild_id))
+        });
+        progressions
+    }
+
+    /// The fastest full clear of each guild, ranked per map and difficulty.
+    /// Trash before the first pull and after the last kill does not count.
+    /// Instances that cannot be attributed to a guild are ranked individually.
+    pub fn get_speed_runs(&self) -> Vec<SpeedRun> {
+        // (guild_id, instance_meta_id if there is no guild, map_id, difficulty_id)
+        let mut best_speed_runs: HashMap<(Option<u32>, Option<u32>, u16, u8), SpeedRun> = HashMap::new();
+        for speed_run in self.raids.iter().filter_map(|raid| self.get_speed_run(raid)) {
+            let guild_id = speed_run.guild.as_ref().map(|guild| guild.guild_id);
+            let instance_meta_id = Some(speed_run.instance_meta_id).filter(|_| guild_id.is_none());
+            let best_speed_run = best_speed_runs.entry((guild_id, instance_meta_id, speed_run.map_id, speed_run.difficulty_id)).or_insert_with(|| speed_run.clone());
+            if (speed_run.clear_time, speed_run.start_ts) < (best_speed_run.clear_time, best_speed_run.start_ts) {
+                *best_speed_run = speed_run;
+            }
+        }
+
+        let mut speed_runs = best_speed_runs.values().cloned().collect::<Vec<SpeedRun>>();
+        speed_runs.sort_by_key(|speed_run| (speed_run.map_id, speed_run.difficulty_id, speed_run.clear_time, speed_run.start_ts, speed_run.instance_meta_id));
+
+        // Ties share the rank
+        let mut previous: Option<(u16, u8, u64, u32)> = None;
+        let mut position = 0;
+        for speed_run in speed_runs.iter_mut() {
+            match previous {
+                Some((map_id, difficulty_id, clear_time, rank)) if map_id == speed_run.map_id && difficulty_id == speed_run.difficulty_id => {
+                    position += 1;
+                    speed_run.rank = if clear_time == speed_run.clear_time { rank } else { position };
+                },
+                _ => {
+                    position = 1;
+                    speed_run.rank = 1;
+                },
+            }
+            previous = Some((speed_run.map_id, speed_run.difficulty_id, speed_run.clear_time, speed_run.rank));
+        }
+        speed_runs
+    }
+
+    fn get_speed_run(&self, raid: &RaidAttempts) -> Option<SpeedRun> {
+        let mut remaining_encounters = self.encounters.get(&raid.map_id).filter(|encounters| !encounters.is_empty())?.clone();
+        let last_kill_index = raid.attempts.iter().position(|attempt| {
+            if attempt.is_kill {
+                remaining_encounters.remove(&self.get_encounter_id(attempt.encounter_id));
+            }
+            remaining_encounters.is_empty()
+        })?;
+
+        let attempts = &raid.attempts[..=last_kill_index];
+        let start_ts = attempts.first()?.start_ts;
+        let end_ts = attempts.iter().map(|attempt| attempt.end_ts).max()?;
+        Some(SpeedRun {
+            rank: 0,
+            instance_meta_id: raid.instance_meta_id,
+            guild: raid.guild.clone(),
+            server_id: raid.server_id,
+            map_id: raid.map_id,
+            difficulty_id: raid.difficulty_id,
+            start_ts,
+            end_ts,
+            clear_time: end_ts - start_ts,
+            encounter_time: attempts.iter().map(|attempt| attempt.end_ts.saturating_sub(attempt.start_ts)).sum(),
+            wipes: attempts.iter().filter(|attempt| !attempt.is_kill).count() as u32,
+        })
+    }
+}
+
+fn new_encounter_progression(encounter_id: u32) -> EncounterProgression {
+    EncounterProgression {
+        encounter_id,
+        first_kill_ts: None,
+        first_kill_instance_meta_id: None,
+        wipes_before_kill: 0,
+        hard_mode_first_kill_ts: None,
+    }
+}
@@ -0,0 +1,123 @@
+use crate::modules::data::domain_value::RankingBracket;
+use crate::modules::instance::dto::{RankingCharacterMeta, RankingEntry, RankingFilter, RankingHistoryEntry, RankingResult};
+
+/// A character with all of its results of an encounter
+pub struct RankingCandidate {
+    pub character_id: u32,
+    pub character_meta: RankingCharacterMeta,
+    pub results: Vec<RankingResult>,
+}
+
+/// The best result of each character that satisfies the filter, ordered by amount per second, descending.
+/// The amount per second is either based on the duration of the attempt or on the active time of the character.
+pub struct RankingLeaderboard {
+    entries: Vec<RankingEntry>,
+    by_active_time: bool,
+}
+
+impl RankingLeaderboard {
+    pub fn new(candidates: Vec<RankingCandidate>, filter: &RankingFilter, bracket: Option<&RankingBracket>) -> Self {
+        let by_active_time = filter.rank_by_active_time.unwrap_or(false);
+        let mut entries = candidates
+            .into_iter()
+            .filter(|candidate| filter.hero_class_id.map(|hero_class_id| candidate.character_meta.hero_class_id == hero_class_id).unwrap_or(true))
+            .filter_map(|candidate| {
+                let result = candidate
+                    .results
+                    .iter()
+                    .filter(|result| is_result_ranked(result, filter, bracket))
+                    .max_by(|left, right| ranked_per_second(left, by_active_time).partial_cmp(&ranked_per_second(right, by_active_time)).unwrap())?
+                    .clone();
+                Some(RankingEntry {
+                    rank: 0,
+                    percentile: 0.0,
+                    character_id: candidate.character_id,
+                    character_meta: candidate.character_meta,
+                    spec_id: result.spec_id,
+                    role: result.role.clone(),
+                    per_second: result.per_second(),
+                    active_per_second: result.active_per_second(),
+                    result,
+                })
+            })
+            .collect::<Vec<RankingEntry>>();
+        entries.sort_by(|left, right| {
+            ranked_per_second(&right.result, by_active_time)
+                .partial_cmp(&ranked_per_second(&left.result, by_active_time))
+                .unwrap()
+                .then_with(|| left.character_id.cmp(&right.character_id))
+        });
+
+        // Ties share the rank and the percentile of the best of them
+        let total = entries.len();
+        let mut tie: Option<(usize, f64)> = None;
+        for (index, entry) in entries.iter_mut().enumerate() {
+            let first_tied_index = match tie {
+                Some((first_tied_index, per_second)) if per_second <= ranked_per_second(&entry.result, by_active_time) => first_tied_index,
+                _ => index,
+            };
+            tie = Some((first_tied_index, ranked_per_second(&entry.result, by_active_time)));
+            entry.rank = first_tied_index as u32 + 1;
+            entry.percentile = 100.0 * (total - first_tied_index) as f64 / total as f64;
+        }
+        RankingLeaderboard { entries, by_active_time }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn page(&self, page: u32, page_size: u32) -> Vec<RankingEntry> {
+        self.entries.iter().skip(page as usize * page_size as usize).take(page_size as usize).cloned().collect()
+    }
+
+    /// Share of the ranked characters whose best result is not better, in the amount per second that is ranked by
+    pub fn get_percentile(&self, per_second: f64) -> Option<f64> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let not_better = self.entries.iter().filter(|entry| ranked_per_second(&entry.result, self.by_active_time) <= per_second).count();
+        Some(100.0 * not_better as f64 / self.entries.len() as f64)
+    }
+
+    /// Every result of the character that satisfies the filter, ordered by time
+    pub fn get_history(&self, results: &[RankingResult], filter: &RankingFilter, bracket: Option<&RankingBracket>) -> Vec<RankingHistoryEntry> {
+        let mut history = results
+            .iter()
+            .filter(|result| is_result_ranked(result, filter, bracket))
+            .map(|result| RankingHistoryEntry {
+                attempt_id: result.attempt_id,
+                start_ts: result.start_ts,
+                amount: result.amount,
+                duration: result.duration,
+                active_time: result.active_time,
+                per_second: result.per_second(),
+                active_per_second: result.active_per_second(),
+                percentile: self.get_percentile(ranked_per_second(result, self.by_active_time)),
+            })
+            .collect::<Vec<RankingHistoryEntry>>();
+        history.sort_by_key(|entry| (entry.start_ts, entry.attempt_id));
+        history
+    }
+}
+
+// The spec and role are those of the character in the attempt of the result
+fn is_result_ranked(result: &RankingResult, filter: &RankingFilter, bracket: Option<&RankingBracket>) -> bool {
+    filter.spec_id.map(|spec_id| result.spec_id == Some(spec_id)).unwrap_or(true)
+        && filter.role.as_ref().map(|role| result.role.as_ref() == Some(role)).unwrap_or(true)
+        && filter.difficulty_id.map(|difficulty_id| result.difficulty_id == difficulty_id).unwrap_or(true)
+        && filter.server_id.map(|server_id| result.server_id == server_id).unwrap_or(true)
+        && bracket.map(|bracket| bracket.contains(result.start_ts)).unwrap_or(true)
+}
+
+fn ranked_per_second(result: &RankingResult, by_active_time: bool) -> f64 {
+    if by_active_time {
+        result.active_per_second()
+    } else {
+        result.per_second()
+    }
+}
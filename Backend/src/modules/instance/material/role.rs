@@ -1,6 +1,6 @@
 use crate::modules::armory::util::talent_tree::get_talent_tree;
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, FromFormValue)]
 pub enum Role {
     Tank,
     Healer,
@@ -8,6 +8,22 @@ pub enum Role {
 }
 
 impl Role {
+    pub fn from_u8(role: u8) -> Self {
+        match role {
+            0 => Self::Tank,
+            1 => Self::Healer,
+            _ => Self::Dps,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Tank => 0,
+            Self::Healer => 1,
+            Self::Dps => 2,
+        }
+    }
+
     pub fn from_class_talent_string(hero_class_id: u8, talent_str: &str) -> Self {
         let tree = get_talent_tree(talent_str);
 
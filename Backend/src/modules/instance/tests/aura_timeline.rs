@@ -0,0 +1,94 @@
+use crate::modules::instance::dto::{AuraInterval, MeterUnit};
+use crate::modules::instance::material::{AuraTimeline, AuraTimelineBuilder};
+
+static SUNDER_ARMOR: u32 = 7386;
+static BOSS: MeterUnit = MeterUnit::Creature { creature_id: 42, entry: 15990 };
+
+fn build(window: (u64, u64)) -> AuraTimeline {
+    let lines = [
+        (6, "[1,1000,[0,42,15990],[1,7],7386,1,1]"),
+        (6, "[2,2000,[0,42,15990],[1,7],7386,2,1]"),
+        (6, "[3,5000,[0,42,15990],[1,7],7386,0,1]"),
+        (6, "[4,6000,[0,42,15990],[1,8],7386,1,1]"),
+        (6, "[5,1500,[1,5],[1,5],17628,1,1]"),
+        (6, "[6,1200,[1,5],[1,5],19709,1,1]"),
+        (6, "[7,3000,[1,6],[1,6],17538,0,1]"),
+        (1, "[8,7000,[1,5]]"),
+        (6, "[9,7000,[1,5],[1,5],17628,0,1]"),
+        (6, "[10,10000,[1,6],[1,6],11348,1,1]"),
+    ];
+    let mut aura_timeline_builder = AuraTimelineBuilder::default();
+    for (event_type, line) in lines.iter() {
+        aura_timeline_builder.add_line(*event_type, line);
+    }
+    aura_timeline_builder.build(window)
+}
+
+#[test]
+fn aura_uptimes() {
+    let aura_timeline = build((0, u64::MAX));
+    assert_eq!((aura_timeline.start_ts, aura_timeline.end_ts), (1000, 10000));
+
+    let uptimes = aura_timeline.get_uptimes(None);
+    assert_eq!(uptimes.len(), 4);
+    let sunder_armor = aura_timeline.get_uptimes(Some(&[SUNDER_ARMOR]));
+    assert_eq!(sunder_armor.len(), 1);
+    assert_eq!((sunder_armor[0].target, sunder_armor[0].uptime), (BOSS, 8000));
+    assert!((sunder_armor[0].uptime_percent - 800.0 / 9.0).abs() < 0.001);
+    assert_eq!(
+        sunder_armor[0].timeline,
+        vec![
+            AuraInterval {
+                start_ts: 1000,
+                end_ts: 2000,
+                caster: MeterUnit::Player { character_id: 7 },
+                stack_amount: 1
+            },
+            AuraInterval {
+                start_ts: 2000,
+                end_ts: 5000,
+                caster: MeterUnit::Player { character_id: 7 },
+                stack_amount: 2
+            },
+            AuraInterval {
+                start_ts: 6000,
+                end_ts: 10000,
+                caster: MeterUnit::Player { character_id: 8 },
+                stack_amount: 1
+            },
+        ]
+    );
+
+    // The flask is removed by the death
+    let flask = uptimes.iter().find(|uptime| uptime.spell_id == 17628).unwrap();
+    assert_eq!((flask.uptime, flask.timeline.len()), (5500, 1));
+    // Applied before the log started
+    let elixir = uptimes.iter().find(|uptime| uptime.spell_id == 17538).unwrap();
+    assert_eq!((elixir.timeline[0].start_ts, elixir.timeline[0].end_ts), (1000, 3000));
+}
+
+#[test]
+fn aura_uptimes_within_window() {
+    let aura_timeline = build((2000, 4000));
+    assert_eq!((aura_timeline.start_ts, aura_timeline.end_ts), (2000, 4000));
+
+    let sunder_armor = aura_timeline.get_uptimes(Some(&[SUNDER_ARMOR]));
+    assert_eq!(sunder_armor[0].uptime, 2000);
+    assert!((sunder_armor[0].uptime_percent - 100.0).abs() < 0.001);
+    assert_eq!(sunder_armor[0].timeline.len(), 1);
+    assert_eq!(sunder_armor[0].timeline[0].stack_amount, 2);
+}
+
+#[test]
+fn consumable_coverage() {
+    let consumables = build((0, u64::MAX)).get_consumable_coverage(2000);
+    assert_eq!(consumables.len(), 2);
+
+    assert_eq!(consumables[0].character_id, 5);
+    assert_eq!((consumables[0].flask, consumables[0].food), (Some(17628), Some(19709)));
+    assert!(consumables[0].is_covered);
+
+    assert_eq!(consumables[1].character_id, 6);
+    assert_eq!((consumables[1].battle_elixir, consumables[1].guardian_elixir, consumables[1].food), (Some(17538), None, None));
+    assert!(!consumables[1].is_covered);
+}
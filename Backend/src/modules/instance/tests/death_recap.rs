@@ -0,0 +1,117 @@
+use crate::modules::data::domain_value::DefensiveCooldown;
+use crate::modules::instance::dto::{DeathRecapCooldown, DeathRecapEventType, MeterUnit};
+use crate::modules::instance::material::DeathRecapBuilder;
+
+fn defensive_cooldown(spell_id: u32, base_spell_id: u32) -> DefensiveCooldown {
+    DefensiveCooldown {
+        expansion_id: 3,
+        spell_id,
+        base_spell_id,
+        hero_class_id: 1,
+        talent_tree: None,
+    }
+}
+
+fn build(lines: &[(u8, &str)], window: (u64, u64)) -> Vec<crate::modules::instance::dto::DeathRecap> {
+    let mut death_recap_builder = DeathRecapBuilder::default();
+    for (event_type, line) in lines.iter() {
+        death_recap_builder.add_line(*event_type, line);
+    }
+    death_recap_builder.build(
+        window,
+        10000,
+        |character_id| {
+            if character_id == 5 {
+                vec![defensive_cooldown(871, 871), defensive_cooldown(633, 633), defensive_cooldown(48788, 633)]
+            } else {
+                vec![]
+            }
+        },
+        |spell_id| if spell_id == 871 { Some(300000) } else { None },
+    )
+}
+
+#[test]
+fn reconstruct_death() {
+    let lines = [
+        (0, "[1,1000,[1,5],null,871,1,1]"),
+        (6, "[2,2000,[1,5],[1,6],48441,1,8]"),
+        (6, "[3,3000,[1,5],[1,6],48068,1,8]"),
+        (6, "[4,4000,[1,5],[1,6],48068,0,8]"),
+        (12, "[5,5000,[0,42,15990],[1,5],1,[[1000,1,0,0,0]]]"),
+        (14, "[6,15000,7,[1,6],[1,5],48441,1,8,2000,1500,0,0,0]"),
+        (13, "[7,19000,8,[0,42,15990],[1,5],28479,2,[[9000,16,500,0,0]]]"),
+        (13, "[8,19500,9,[0,42,15990],[1,6],28479,2,[[9000,16,0,0,0]]]"),
+        (5, "[9,18000,[1,5],3000,20000,5]"),
+        (1, "[10,20000,[1,5],[0,42,15990]]"),
+    ];
+    let death_recaps = build(&lines, (0, u64::MAX));
+    assert_eq!(death_recaps.len(), 1);
+
+    let death_recap = &death_recaps[0];
+    assert_eq!((death_recap.event_id, death_recap.timestamp, death_recap.character_id), (10, 20000, 5));
+    assert_eq!(death_recap.murder, Some(MeterUnit::Creature { creature_id: 42, entry: 15990 }));
+    assert_eq!(death_recap.events.len(), 2);
+    assert_eq!(death_recap.events[0].event_type, DeathRecapEventType::Heal);
+    assert_eq!(death_recap.events[0].amount.overheal, 500);
+    let killing_blow = death_recap.killing_blow.as_ref().unwrap();
+    assert_eq!((killing_blow.spell_id, killing_blow.amount.amount, killing_blow.amount.absorb), (28479, 9000, 500));
+    assert_eq!(death_recap.auras.iter().map(|aura| aura.spell_id).collect::<Vec<u32>>(), vec![48441]);
+    assert_eq!(death_recap.powers.len(), 1);
+    assert_eq!(death_recap.powers[0].current_power, 3000);
+    assert_eq!(
+        death_recap.defensive_cooldowns,
+        vec![
+            // Never used, hence available with its highest rank
+            DeathRecapCooldown {
+                spell_id: 48788,
+                last_used_ts: None,
+                cooldown: 0,
+                is_available: true,
+            },
+            DeathRecapCooldown {
+                spell_id: 871,
+                last_used_ts: Some(1000),
+                cooldown: 300000,
+                is_available: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn defensive_cooldowns_by_rank() {
+    let lines = [
+        (0, "[1,1000,[1,5],null,48788,1,1]"),
+        (0, "[2,3000,[1,5],null,633,1,1]"),
+        (0, "[3,6000,[1,5],null,871,1,1]"),
+        (1, "[4,5000,[1,5]]"),
+        (1, "[5,5000,[1,6]]"),
+    ];
+    let death_recaps = build(&lines, (0, u64::MAX));
+    assert_eq!(death_recaps.len(), 2);
+
+    // The lower rank that was used last and the cast after the death is ignored
+    let defensive_cooldowns = &death_recaps[0].defensive_cooldowns;
+    assert_eq!(defensive_cooldowns.len(), 2);
+    assert_eq!((defensive_cooldowns[0].spell_id, defensive_cooldowns[0].last_used_ts), (633, Some(3000)));
+    assert_eq!((defensive_cooldowns[1].spell_id, defensive_cooldowns[1].last_used_ts, defensive_cooldowns[1].is_available), (871, None, true));
+    // Other classes and unknown characters have none
+    assert!(death_recaps[1].defensive_cooldowns.is_empty());
+}
+
+#[test]
+fn auras_do_not_survive_death() {
+    let lines = [
+        (6, "[1,1000,[1,5],[1,6],48441,1,8]"),
+        (1, "[2,2000,[1,5]]"),
+        (6, "[3,3000,[1,5],[1,6],48068,1,8]"),
+        (1, "[4,4000,[1,5]]"),
+        (1, "[5,5000,[0,42,15990]]"),
+    ];
+    let death_recaps = build(&lines, (3000, u64::MAX));
+    assert_eq!(death_recaps.len(), 1);
+    assert_eq!(death_recaps[0].murder, None);
+    assert!(death_recaps[0].killing_blow.is_none());
+    assert_eq!(death_recaps[0].auras.iter().map(|aura| aura.spell_id).collect::<Vec<u32>>(), vec![48068]);
+}
@@ -0,0 +1,89 @@
+use crate::modules::instance::dto::{EventExportFilter, EventExportQuery, UnitFilter};
+use crate::modules::instance::material::EventMatcher;
+use std::convert::TryFrom;
+
+fn filter() -> EventExportFilter {
+    EventExportFilter {
+        attempt_id: None,
+        start_ts: None,
+        end_ts: None,
+        sources: None,
+        targets: None,
+        spell_ids: None,
+        cursor: None,
+        limit: None,
+    }
+}
+
+static SPELL_DAMAGE: &str = "[10,1500,7,[0,9,1863,[1,5]],[0,42,15990],133,2,[[300,4,0,0,0]]]";
+static AURA_APPLICATION: &str = "[11,2000,[1,6],[1,5],48441,1,8]";
+
+#[test]
+fn match_time_window_and_cursor() {
+    let event_matcher = EventMatcher::new(13, (1000, 2000), EventExportFilter { cursor: Some(9), ..filter() });
+    assert!(event_matcher.matches(10, SPELL_DAMAGE));
+    assert!(!EventMatcher::new(13, (1501, 2000), filter()).matches(10, SPELL_DAMAGE));
+    assert!(!EventMatcher::new(13, (0, 1000), EventExportFilter { cursor: Some(10), ..filter() }).matches(10, SPELL_DAMAGE));
+    assert!(event_matcher.overlaps(500, 1000));
+    assert!(!event_matcher.overlaps(2001, 3000));
+    assert!(!event_matcher.matches(12, "[12,1500,?!?]"));
+}
+
+#[test]
+fn match_units() {
+    let by_pet = EventExportFilter {
+        sources: Some(vec![UnitFilter::Creature { entry: 1863, creature_id: None }]),
+        ..filter()
+    };
+    let on_boss = EventExportFilter {
+        targets: Some(vec![UnitFilter::Player { character_id: 5 }, UnitFilter::Creature { entry: 15990, creature_id: Some(42) }]),
+        ..filter()
+    };
+    let by_owner = EventExportFilter {
+        sources: Some(vec![UnitFilter::Player { character_id: 5 }]),
+        ..filter()
+    };
+    assert!(EventMatcher::new(13, (0, u64::MAX), by_pet).matches(10, SPELL_DAMAGE));
+    assert!(EventMatcher::new(13, (0, u64::MAX), on_boss).matches(10, SPELL_DAMAGE));
+    assert!(!EventMatcher::new(13, (0, u64::MAX), by_owner.clone()).matches(10, SPELL_DAMAGE));
+    assert!(EventMatcher::new(6, (0, u64::MAX), by_owner).matches(11, AURA_APPLICATION));
+}
+
+#[test]
+fn match_spells() {
+    let spells = EventExportFilter { spell_ids: Some(vec![133, 48441]), ..filter() };
+    assert!(EventMatcher::new(13, (0, u64::MAX), spells.clone()).matches(10, SPELL_DAMAGE));
+    assert!(EventMatcher::new(6, (0, u64::MAX), spells.clone()).matches(11, AURA_APPLICATION));
+    assert!(!EventMatcher::new(12, (0, u64::MAX), spells).matches(12, "[12,1500,[1,5],[0,42,15990],1,[[100,1,0,0,0]]]"));
+}
+
+#[test]
+fn parse_export_query() {
+    let query = EventExportQuery {
+        attempt_id: Some(3),
+        start_ts: None,
+        end_ts: None,
+        sources: Some(String::from("player:5,creature:1863")),
+        targets: Some(String::from("creature:15990:42")),
+        spell_ids: Some(String::from("133,48441")),
+        cursor: Some(9),
+        limit: None,
+    };
+    let filter = EventExportFilter::try_from(query).unwrap();
+    assert_eq!(filter.sources, Some(vec![UnitFilter::Player { character_id: 5 }, UnitFilter::Creature { entry: 1863, creature_id: None }]));
+    assert_eq!(filter.targets, Some(vec![UnitFilter::Creature { entry: 15990, creature_id: Some(42) }]));
+    assert_eq!(filter.spell_ids, Some(vec![133, 48441]));
+    assert_eq!(filter.cursor, Some(9));
+
+    let invalid = EventExportQuery {
+        attempt_id: None,
+        start_ts: None,
+        end_ts: None,
+        sources: Some(String::from("pet:5")),
+        targets: None,
+        spell_ids: None,
+        cursor: None,
+        limit: None,
+    };
+    assert!(EventExportFilter::try_from(invalid).is_err());
+}
@@ -0,0 +1,59 @@
+use crate::modules::instance::dto::{EffectivenessSpell, MeterUnit};
+use crate::modules::instance::material::{AuraTimelineBuilder, InterruptAudit};
+
+fn build() -> InterruptAudit {
+    let lines = [
+        (0, "[1,1000,[0,42,15990],[1,5],28478,2,16]"),
+        (0, "[2,1000,[0,42,15990],[1,6],28478,2,16]"),
+        (0, "[3,2000,[0,43,15990,[1,5]],null,28478,2,16]"),
+        (0, "[4,3000,[1,5],null,28478,2,16]"),
+        (0, "[5,3500,[0,42,15990],null,1,2,1]"),
+        (7, "[6,4000,1,[1,5],[0,42,15990],1766,28478]"),
+        (7, "[7,5000,1,[1,5],[0,42,15990],1766,28479]"),
+        (7, "[8,6000,1,[1,6],[0,42,15990],2139,28478]"),
+        (9, "[9,8000,1,11,[1,7],[1,5],988,28410]"),
+    ];
+    let mut interrupt_audit = InterruptAudit::default();
+    for (event_type, line) in lines.iter() {
+        interrupt_audit.add_line(*event_type, line);
+    }
+    interrupt_audit
+}
+
+#[test]
+fn rank_interrupts() {
+    let interrupts = build().get_interrupts((0, u64::MAX));
+    assert_eq!(interrupts.len(), 2);
+    assert_eq!((interrupts[0].source, interrupts[0].count), (MeterUnit::Player { character_id: 5 }, 2));
+    assert_eq!(interrupts[0].affected_spells, vec![EffectivenessSpell { spell_id: 28478, count: 1 }, EffectivenessSpell { spell_id: 28479, count: 1 }]);
+    assert_eq!(interrupts[0].average_reaction_time, None);
+    assert_eq!((interrupts[1].source, interrupts[1].count), (MeterUnit::Player { character_id: 6 }, 1));
+
+    let interrupts = build().get_interrupts((0, 4500));
+    assert_eq!(interrupts.len(), 1);
+    assert_eq!(interrupts[0].events[0].affected_spell_id, 28478);
+}
+
+#[test]
+fn dispel_reaction_time() {
+    let mut aura_timeline_builder = AuraTimelineBuilder::default();
+    aura_timeline_builder.add_line(6, "[10,7000,[1,5],[0,42,15990],28410,1,32]");
+    aura_timeline_builder.add_line(6, "[11,8000,[1,5],[0,42,15990],28410,0,32]");
+    let aura_timeline = aura_timeline_builder.build((0, u64::MAX));
+
+    let dispels = build().get_dispels((0, u64::MAX), &aura_timeline);
+    assert_eq!(dispels.len(), 1);
+    assert_eq!(dispels[0].source, MeterUnit::Player { character_id: 7 });
+    assert_eq!(dispels[0].events[0].target, MeterUnit::Player { character_id: 5 });
+    assert_eq!(dispels[0].events[0].reaction_time, Some(1000));
+    assert_eq!(dispels[0].average_reaction_time, Some(1000));
+}
+
+#[test]
+fn find_missed_interrupts() {
+    let missed_interrupts = build().get_missed_interrupts((0, u64::MAX), &[28478]);
+    assert_eq!(missed_interrupts.len(), 1);
+    assert_eq!(missed_interrupts[0].event_id, 1);
+    assert_eq!(missed_interrupts[0].caster, MeterUnit::Creature { creature_id: 42, entry: 15990 });
+    assert!(build().get_missed_interrupts((0, u64::MAX), &[]).is_empty());
+}
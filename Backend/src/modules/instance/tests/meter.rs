@@ -0,0 +1,69 @@
+use crate::modules::instance::domain_value::MeterEvent;
+use crate::modules::instance::dto::{MeterAmount, MeterUnit};
+use crate::modules::instance::material::MeterAccumulator;
+
+fn amount(amount: i64, overheal: u64, absorb: u64) -> MeterAmount {
+    MeterAmount { amount, overheal, absorb, count: 1 }
+}
+
+#[test]
+fn parse_melee_damage() {
+    let event = MeterEvent::from_melee_damage("[1,1000,[1,5],[0,42,15990],1,[[100,1,20,0,0],[50,4,0,0,0]]]").unwrap();
+    assert_eq!(event.timestamp, 1000);
+    assert_eq!(event.source, MeterUnit::Player { character_id: 5 });
+    assert_eq!(event.target, MeterUnit::Creature { creature_id: 42, entry: 15990 });
+    assert_eq!(event.spell_id, 0);
+    assert_eq!(event.amount, amount(150, 0, 20));
+    assert_eq!(event.schools, vec![(1, amount(100, 0, 20)), (4, amount(50, 0, 0))]);
+}
+
+#[test]
+fn parse_spell_damage_of_pet() {
+    let event = MeterEvent::from_spell_damage("[2,1500,7,[0,9,1863,[1,5]],[0,42,15990],133,2,[[300,4,0,0,0]]]").unwrap();
+    assert_eq!(event.source, MeterUnit::Creature { creature_id: 9, entry: 1863 });
+    assert_eq!(event.spell_id, 133);
+    assert_eq!(event.amount, amount(300, 0, 0));
+}
+
+#[test]
+fn parse_heal() {
+    let event = MeterEvent::from_heal("[3,2000,8,[1,6],[1,5],2061,2,2,1000,700,50,0,0]").unwrap();
+    assert_eq!(event.source, MeterUnit::Player { character_id: 6 });
+    assert_eq!(event.target, MeterUnit::Player { character_id: 5 });
+    assert_eq!(event.amount, amount(700, 300, 50));
+    assert_eq!(event.schools, vec![(2, amount(700, 300, 50))]);
+}
+
+#[test]
+fn parse_threat() {
+    let event = MeterEvent::from_threat("[4,2500,1,[1,5],[0,42,15990],0,1,1,-130]").unwrap();
+    assert_eq!(event.amount, amount(-130, 0, 0));
+}
+
+#[test]
+fn parse_invalid_line() {
+    assert!(MeterEvent::from_heal("[1,1000,[1,5],[0,42,15990],1,[[100,1,20,0,0]]]").is_none());
+    assert!(MeterEvent::from_melee_damage("[1,1000,[2,5],[0,42,15990],1,[]]").is_none());
+    assert!(MeterEvent::from_threat("?!?").is_none());
+}
+
+#[test]
+fn accumulate_meter() {
+    let mut accumulator = MeterAccumulator::default();
+    accumulator.add(MeterEvent::from_melee_damage("[1,1000,[1,5],[0,42,15990],1,[[100,1,0,0,0]]]").unwrap());
+    accumulator.add(MeterEvent::from_spell_damage("[2,1500,7,[1,6],[0,42,15990],133,2,[[300,4,0,0,0]]]").unwrap());
+    accumulator.add(MeterEvent::from_spell_damage("[3,1600,7,[1,6],[0,43,15990],133,2,[[200,4,0,0,0]]]").unwrap());
+    let meter = accumulator.into_meter();
+
+    assert_eq!(meter.total, MeterAmount { amount: 600, overheal: 0, absorb: 0, count: 3 });
+    assert_eq!(meter.by_source.len(), 2);
+    assert_eq!(meter.by_source[0].unit, MeterUnit::Player { character_id: 6 });
+    assert_eq!(meter.by_source[0].amount.amount, 500);
+    assert_eq!(meter.by_source[0].amount.count, 2);
+    assert_eq!(meter.by_spell[0].spell_id, 133);
+    assert_eq!(meter.by_spell[1].spell_id, 0);
+    assert_eq!(meter.by_target[0].unit, MeterUnit::Creature { creature_id: 42, entry: 15990 });
+    assert_eq!(meter.by_target[0].amount.amount, 400);
+    assert_eq!(meter.by_school[0].school_mask, 4);
+    assert_eq!(meter.by_school[1].school_mask, 1);
+}
@@ -1 +1,7 @@
-
+mod aura_timeline;
+mod death_recap;
+mod event_matcher;
+mod interrupt_audit;
+mod meter;
+mod raid_progress;
+mod ranking_leaderboard;
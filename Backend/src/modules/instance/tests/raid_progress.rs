@@ -0,0 +1,66 @@
+use crate::modules::armory::dto::SearchGuildDto;
+use crate::modules::instance::dto::InstanceViewerAttempt;
+use crate::modules::instance::material::{RaidAttempts, RaidProgress};
+use std::collections::{BTreeSet, HashMap};
+
+fn attempt(id: u32, encounter_id: u32, start_ts: u64, end_ts: u64, is_kill: bool) -> InstanceViewerAttempt {
+    InstanceViewerAttempt { id, is_kill, encounter_id, start_ts, end_ts }
+}
+
+fn raid(instance_meta_id: u32, guild_id: Option<u32>, attempts: Vec<InstanceViewerAttempt>) -> RaidAttempts {
+    RaidAttempts {
+        instance_meta_id,
+        server_id: 1,
+        map_id: 603,
+        difficulty_id: 3,
+        guild: guild_id.map(|guild_id| SearchGuildDto { guild_id, name: format!("Guild {}", guild_id) }),
+        attempts,
+    }
+}
+
+fn build(raids: Vec<RaidAttempts>) -> RaidProgress {
+    let mut encounters = HashMap::new();
+    encounters.insert(603, vec![1, 2].into_iter().collect::<BTreeSet<u32>>());
+    let mut hard_modes = HashMap::new();
+    hard_modes.insert(101, 1);
+    RaidProgress::new(raids, encounters, hard_modes)
+}
+
+#[test]
+fn guild_progression() {
+    let raid_progress = build(vec![
+        raid(1, Some(7), vec![attempt(1, 1, 1000, 2000, false), attempt(2, 1, 3000, 4000, false), attempt(3, 2, 5000, 6000, false)]),
+        raid(2, Some(7), vec![attempt(4, 101, 10000, 11000, true), attempt(5, 2, 12000, 13000, false)]),
+        raid(3, Some(8), vec![attempt(6, 1, 500, 900, true), attempt(7, 2, 1000, 1500, true)]),
+        raid(4, None, vec![attempt(8, 1, 500, 900, true)]),
+    ]);
+    let progressions = raid_progress.get_guild_progressions();
+    assert_eq!(progressions.len(), 2);
+    assert_eq!((progressions[0].guild.guild_id, progressions[0].killed_encounters, progressions[0].total_encounters), (8, 2, 2));
+
+    let progression = &progressions[1];
+    assert_eq!((progression.guild.guild_id, progression.killed_encounters), (7, 1));
+    assert_eq!(progression.encounters[0].first_kill_ts, Some(11000));
+    assert_eq!(progression.encounters[0].first_kill_instance_meta_id, Some(2));
+    assert_eq!(progression.encounters[0].wipes_before_kill, 2);
+    assert_eq!(progression.encounters[0].hard_mode_first_kill_ts, Some(11000));
+    assert_eq!(progression.encounters[1].first_kill_ts, None);
+    assert_eq!(progression.encounters[1].wipes_before_kill, 2);
+}
+
+#[test]
+fn speed_runs() {
+    let raid_progress = build(vec![
+        // Trash after the last kill is not part of the clear
+        raid(1, Some(7), vec![attempt(1, 1, 1000, 2000, false), attempt(2, 1, 3000, 4000, true), attempt(3, 2, 5000, 6000, true), attempt(4, 1, 9000, 9500, false)]),
+        raid(2, Some(7), vec![attempt(5, 1, 1000, 2000, true), attempt(6, 2, 3000, 4000, true)]),
+        raid(3, Some(8), vec![attempt(7, 1, 1000, 2000, true)]),
+        raid(4, None, vec![attempt(8, 101, 0, 1000, true), attempt(9, 2, 2000, 3000, true)]),
+    ]);
+    let speed_runs = raid_progress.get_speed_runs();
+    assert_eq!(speed_runs.len(), 2);
+    // Ties are ordered by the earlier start
+    assert_eq!((speed_runs[0].rank, speed_runs[0].instance_meta_id, speed_runs[0].clear_time), (1, 4, 3000));
+    assert_eq!((speed_runs[0].encounter_time, speed_runs[0].wipes), (2000, 0));
+    assert_eq!((speed_runs[1].rank, speed_runs[1].instance_meta_id, speed_runs[1].clear_time), (1, 2, 3000));
+}
@@ -0,0 +1,161 @@
+use crate::modules::data::domain_value::RankingBracket;
+use crate::modules::instance::dto::{RankingCharacterMeta, RankingFilter, RankingMetric, RankingResult};
+use crate::modules::instance::material::{RankingCandidate, RankingLeaderboard, Role};
+
+fn result(attempt_id: u32, amount: u32, difficulty_id: u8, start_ts: u64) -> RankingResult {
+    RankingResult {
+        attempt_id,
+        amount,
+        duration: 10000,
+        active_time: 10000,
+        server_id: 1,
+        difficulty_id,
+        start_ts,
+        spec_id: None,
+        role: None,
+    }
+}
+
+fn candidate(character_id: u32, hero_class_id: u8, spec_id: u8, role: Role, results: Vec<RankingResult>) -> RankingCandidate {
+    RankingCandidate {
+        character_id,
+        character_meta: RankingCharacterMeta {
+            server_id: 1,
+            hero_class_id,
+            name: format!("Character{}", character_id),
+        },
+        results: results
+            .into_iter()
+            .map(|result| RankingResult {
+                spec_id: Some(spec_id),
+                role: Some(role.clone()),
+                ..result
+            })
+            .collect(),
+    }
+}
+
+fn candidates() -> Vec<RankingCandidate> {
+    vec![
+        candidate(1, 8, 1, Role::Dps, vec![result(1, 10000, 3, 1000), result(2, 30000, 3, 5000)]),
+        candidate(2, 8, 1, Role::Dps, vec![result(1, 20000, 3, 1000), result(3, 50000, 4, 5000)]),
+        candidate(3, 8, 2, Role::Dps, vec![result(1, 20000, 3, 1000)]),
+        candidate(4, 5, 1, Role::Healer, vec![result(1, 5000, 3, 1000)]),
+    ]
+}
+
+fn filter() -> RankingFilter {
+    RankingFilter {
+        metric: RankingMetric::Dps,
+        encounter_id: 1,
+        difficulty_id: Some(3),
+        hero_class_id: None,
+        spec_id: None,
+        role: None,
+        server_id: None,
+        bracket_id: None,
+        rank_by_active_time: None,
+        page: None,
+        page_size: None,
+    }
+}
+
+#[test]
+fn rank_best_results() {
+    let leaderboard = RankingLeaderboard::new(candidates(), &filter(), None);
+    assert_eq!(leaderboard.len(), 4);
+
+    let entries = leaderboard.page(0, 10);
+    assert_eq!(entries.iter().map(|entry| entry.character_id).collect::<Vec<u32>>(), vec![1, 2, 3, 4]);
+    assert_eq!(entries[0].result.attempt_id, 2);
+    assert_eq!(entries[0].per_second, 3000.0);
+    assert_eq!((entries[0].rank, entries[0].percentile), (1, 100.0));
+    // Ties share the rank
+    assert_eq!((entries[1].rank, entries[1].percentile), (2, 75.0));
+    assert_eq!((entries[2].rank, entries[2].percentile), (2, 75.0));
+    assert_eq!((entries[3].rank, entries[3].percentile), (4, 25.0));
+
+    let second_page = leaderboard.page(1, 3);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].character_id, 4);
+}
+
+#[test]
+fn rank_same_spec() {
+    let mut filter = filter();
+    filter.hero_class_id = Some(8);
+    filter.spec_id = Some(1);
+    let entries = RankingLeaderboard::new(candidates(), &filter, None).page(0, 10);
+    assert_eq!(entries.iter().map(|entry| entry.character_id).collect::<Vec<u32>>(), vec![1, 2]);
+
+    let mut filter = self::filter();
+    filter.role = Some(Role::Healer);
+    let entries = RankingLeaderboard::new(candidates(), &filter, None).page(0, 10);
+    assert_eq!(entries.iter().map(|entry| entry.character_id).collect::<Vec<u32>>(), vec![4]);
+}
+
+#[test]
+fn rank_spec_of_attempt() {
+    // The character changed its spec between the attempts
+    let mut candidates = candidates();
+    candidates[1].results[0].spec_id = Some(2);
+    candidates[1].results[0].role = Some(Role::Tank);
+
+    let mut filter = filter();
+    filter.spec_id = Some(1);
+    let entries = RankingLeaderboard::new(candidates, &filter, None).page(0, 10);
+    assert_eq!(entries.iter().map(|entry| entry.character_id).collect::<Vec<u32>>(), vec![1, 4]);
+
+    let mut candidates = self::candidates();
+    candidates[1].results[0].role = Some(Role::Tank);
+    let mut filter = self::filter();
+    filter.role = Some(Role::Tank);
+    let entries = RankingLeaderboard::new(candidates, &filter, None).page(0, 10);
+    assert_eq!(entries.iter().map(|entry| (entry.character_id, entry.role.clone())).collect::<Vec<(u32, Option<Role>)>>(), vec![(2, Some(Role::Tank))]);
+}
+
+#[test]
+fn rank_within_bracket() {
+    let bracket = RankingBracket {
+        id: 1,
+        expansion_id: 1,
+        name: String::from("Phase 1"),
+        start_ts: 0,
+        end_ts: Some(2000),
+    };
+    let entries = RankingLeaderboard::new(candidates(), &filter(), Some(&bracket)).page(0, 10);
+    assert_eq!(entries[0].result.attempt_id, 1);
+    assert_eq!(entries[0].per_second, 2000.0);
+}
+
+#[test]
+fn character_history() {
+    let leaderboard = RankingLeaderboard::new(candidates(), &filter(), None);
+    let history = leaderboard.get_history(&candidates()[0].results, &filter(), None);
+    assert_eq!(history.len(), 2);
+    assert_eq!((history[0].attempt_id, history[0].percentile), (1, Some(25.0)));
+    assert_eq!((history[1].attempt_id, history[1].percentile), (2, Some(100.0)));
+
+    let empty_leaderboard = RankingLeaderboard::new(Vec::new(), &filter(), None);
+    assert!(empty_leaderboard.is_empty());
+    assert_eq!(empty_leaderboard.get_history(&candidates()[0].results, &filter(), None)[0].percentile, None);
+}
+
+#[test]
+fn rank_by_active_time() {
+    let mut dead_result = result(4, 15000, 3, 1000);
+    dead_result.active_time = 5000;
+    let candidates = vec![candidate(1, 8, 1, Role::Dps, vec![result(1, 20000, 3, 1000)]), candidate(2, 8, 1, Role::Dps, vec![dead_result])];
+
+    let entries = RankingLeaderboard::new(candidates, &filter(), None).page(0, 10);
+    assert_eq!(entries.iter().map(|entry| entry.character_id).collect::<Vec<u32>>(), vec![1, 2]);
+    assert_eq!((entries[1].per_second, entries[1].active_per_second), (1500.0, 3000.0));
+
+    let mut filter = filter();
+    filter.rank_by_active_time = Some(true);
+    let candidates = vec![candidate(1, 8, 1, Role::Dps, vec![result(1, 20000, 3, 1000)]), candidate(2, 8, 1, Role::Dps, vec![entries[1].result.clone()])];
+    let leaderboard = RankingLeaderboard::new(candidates, &filter, None);
+    let entries = leaderboard.page(0, 10);
+    assert_eq!(entries.iter().map(|entry| entry.character_id).collect::<Vec<u32>>(), vec![2, 1]);
+    assert_eq!(leaderboard.get_percentile(2000.0), Some(50.0));
+}
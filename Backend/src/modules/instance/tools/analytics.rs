@@ -0,0 +1,59 @@
+use crate::modules::instance::domain_value::MeterEvent;
+use crate::modules::instance::dto::{AnalyticsFilter, InstanceFailure, InstanceMeters, Meter};
+use crate::modules::instance::material::MeterAccumulator;
+use crate::modules::instance::tools::ExportInstance;
+use crate::modules::instance::Instance;
+use crate::util::database::Select;
+
+type MeterEventParser = fn(&str) -> Option<MeterEvent>;
+
+pub trait InstanceAnalytics {
+    /// The time window of the filter, which is narrowed down to the attempt if one is given
+    fn get_analytics_window(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: &AnalyticsFilter) -> Result<(u64, u64), InstanceFailure>;
+    fn get_instance_meters(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: AnalyticsFilter) -> Result<InstanceMeters, InstanceFailure>;
+}
+
+impl InstanceAnalytics for Instance {
+    fn get_analytics_window(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: &AnalyticsFilter) -> Result<(u64, u64), InstanceFailure> {
+        let (mut start_ts, mut end_ts) = (filter.start_ts.unwrap_or(0), filter.end_ts.unwrap_or(u64::MAX));
+        if let Some(attempt_id) = filter.attempt_id {
+            let attempt = self.get_instance_attempts(db_main, instance_meta_id)?.into_iter().find(|attempt| attempt.id == attempt_id).ok_or(InstanceFailure::InvalidInput)?;
+            start_ts = start_ts.max(attempt.start_ts);
+            end_ts = end_ts.min(attempt.end_ts);
+        }
+        if start_ts > end_ts {
+            return Err(InstanceFailure::InvalidInput);
+        }
+        Ok((start_ts, end_ts))
+    }
+
+    fn get_instance_meters(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: AnalyticsFilter) -> Result<InstanceMeters, InstanceFailure> {
+        let (start_ts, end_ts) = self.get_analytics_window(db_main, instance_meta_id, &filter)?;
+        Ok(InstanceMeters {
+            instance_meta_id,
+            attempt_id: filter.attempt_id,
+            start_ts,
+            end_ts,
+            damage: accumulate_meter(
+                self,
+                instance_meta_id,
+                (start_ts, end_ts),
+                &[(12, MeterEvent::from_melee_damage as MeterEventParser), (13, MeterEvent::from_spell_damage as MeterEventParser)],
+            )?,
+            heal: accumulate_meter(self, instance_meta_id, (start_ts, end_ts), &[(14, MeterEvent::from_heal as MeterEventParser)])?,
+            threat: accumulate_meter(self, instance_meta_id, (start_ts, end_ts), &[(15, MeterEvent::from_threat as MeterEventParser)])?,
+        })
+    }
+}
+
+fn accumulate_meter(instance: &Instance, instance_meta_id: u32, (start_ts, end_ts): (u64, u64), event_types: &[(u8, MeterEventParser)]) -> Result<Meter, InstanceFailure> {
+    let mut accumulator = MeterAccumulator::default();
+    for (event_type, parse) in event_types {
+        for (_, line) in instance.export_instance_event_type(instance_meta_id, *event_type)? {
+            if let Some(event) = parse(&line).filter(|event| event.timestamp >= start_ts && event.timestamp <= end_ts) {
+                accumulator.add(event);
+            }
+        }
+    }
+    Ok(accumulator.into_meter())
+}
@@ -0,0 +1,41 @@
+use crate::modules::instance::dto::{AnalyticsFilter, AuraUptimeFilter, InstanceAuraUptimes, InstanceFailure};
+use crate::modules::instance::material::AuraTimelineBuilder;
+use crate::modules::instance::tools::{ExportInstance, InstanceAnalytics};
+use crate::modules::instance::Instance;
+use crate::util::database::Select;
+
+pub trait InstanceAuraUptime {
+    fn get_aura_uptimes(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: AuraUptimeFilter) -> Result<InstanceAuraUptimes, InstanceFailure>;
+}
+
+impl InstanceAuraUptime for Instance {
+    fn get_aura_uptimes(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: AuraUptimeFilter) -> Result<InstanceAuraUptimes, InstanceFailure> {
+        let window = self.get_analytics_window(
+            db_main,
+            instance_meta_id,
+            &AnalyticsFilter {
+                attempt_id: filter.attempt_id,
+                start_ts: filter.start_ts,
+                end_ts: filter.end_ts,
+            },
+        )?;
+
+        let mut aura_timeline_builder = AuraTimelineBuilder::default();
+        // Death, AuraApplication. Auras may be applied before the window starts.
+        for event_type in [1, 6].iter() {
+            for (_, line) in self.export_instance_event_type_between(instance_meta_id, *event_type, (0, window.1))? {
+                aura_timeline_builder.add_line(*event_type, &line);
+            }
+        }
+        let aura_timeline = aura_timeline_builder.build(window);
+
+        Ok(InstanceAuraUptimes {
+            instance_meta_id,
+            attempt_id: filter.attempt_id,
+            start_ts: aura_timeline.start_ts,
+            end_ts: aura_timeline.end_ts,
+            auras: aura_timeline.get_uptimes(filter.spell_ids.as_deref()),
+            consumables: aura_timeline.get_consumable_coverage(aura_timeline.start_ts),
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use crate::modules::armory::util::talent_tree::get_talent_tree;
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::{RetrieveDefensiveCooldown, RetrieveServer, RetrieveSpell};
+use crate::modules::data::Data;
+use crate::modules::instance::dto::{AnalyticsFilter, DeathRecap, DeathRecapFilter, InstanceFailure};
+use crate::modules::instance::material::DeathRecapBuilder;
+use crate::modules::instance::tools::{ExportInstance, InstanceAnalytics};
+use crate::modules::instance::Instance;
+use crate::util::database::Select;
+
+static DEFAULT_RECAP_SECONDS: u32 = 10;
+static MAX_RECAP_SECONDS: u32 = 60;
+
+pub trait ExportDeathRecap {
+    fn get_death_recaps(&self, db_main: &mut impl Select, armory: &Armory, data: &Data, instance_meta_id: u32, filter: DeathRecapFilter) -> Result<Vec<DeathRecap>, InstanceFailure>;
+}
+
+impl ExportDeathRecap for Instance {
+    fn get_death_recaps(&self, db_main: &mut impl Select, armory: &Armory, data: &Data, instance_meta_id: u32, filter: DeathRecapFilter) -> Result<Vec<DeathRecap>, InstanceFailure> {
+        let server_id = {
+            let instance_metas = self.instance_metas.read().unwrap();
+            instance_metas.get(&instance_meta_id).ok_or(InstanceFailure::InvalidInput)?.server_id
+        };
+        let expansion_id = data.get_server(server_id).map(|server| server.expansion_id).unwrap_or(0);
+        let window = self.get_analytics_window(
+            db_main,
+            instance_meta_id,
+            &AnalyticsFilter {
+                attempt_id: filter.attempt_id,
+                start_ts: filter.start_ts,
+                end_ts: filter.end_ts,
+            },
+        )?;
+        let recap_seconds = filter.recap_seconds.unwrap_or(DEFAULT_RECAP_SECONDS).min(MAX_RECAP_SECONDS);
+
+        let mut death_recap_builder = DeathRecapBuilder::default();
+        // SpellCast, Death, Power, AuraApplication, MeleeDamage, SpellDamage, Heal
+        for event_type in [0, 1, 5, 6, 12, 13, 14].iter() {
+            for (_, line) in self.export_instance_event_type(instance_meta_id, *event_type)? {
+                death_recap_builder.add_line(*event_type, &line);
+            }
+        }
+        let get_defensive_cooldowns = |character_id: u32| {
+            // TODO: Use a character at a time of the raid
+            armory
+                .get_character(character_id)
+                .and_then(|character| character.last_update)
+                .map(|last_update| {
+                    let talent_tree = last_update.character_info.talent_specialization.as_ref().map(|talents| get_talent_tree(talents));
+                    data.get_defensive_cooldowns(expansion_id, last_update.character_info.hero_class_id, talent_tree)
+                })
+                .unwrap_or_else(Vec::new)
+        };
+        Ok(death_recap_builder.build(window, recap_seconds as u64 * 1000, get_defensive_cooldowns, |spell_id| data.get_spell(expansion_id, spell_id).map(|spell| spell.cooldown)))
+    }
+}
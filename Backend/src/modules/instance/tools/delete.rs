@@ -3,28 +3,62 @@ use crate::util::database::{Execute, Select};
 use crate::modules::instance::dto::InstanceFailure;
 use crate::params;
 use crate::modules::armory::Armory;
+use crate::modules::account::{Account, AuditAction, AuditLog};
 use std::fs;
 
 pub trait DeleteInstance {
     fn delete_instance(&self, db_main: &mut (impl Execute + Select), armory: &Armory, instance_meta_id: u32, member_id: u32) -> Result<(), InstanceFailure>;
+    fn moderate_instance(&self, db_main: &mut (impl Execute + Select), armory: &Armory, account: &Account, moderator_id: u32, instance_meta_id: u32) -> Result<(), InstanceFailure>;
+}
+
+enum Remover<'a> {
+    Uploader(u32),
+    Moderator(&'a Account, u32),
 }
 
 impl DeleteInstance for Instance {
     fn delete_instance(&self, db_main: &mut (impl Execute + Select), armory: &Armory, instance_meta_id: u32, member_id: u32) -> Result<(), InstanceFailure> {
-        let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
-        let server_id: Option<u32> = db_main.select_wparams_value("SELECT server_id FROM instance_meta WHERE id=:instance_meta_id",
-                                                                  |mut row| row.take::<u32, usize>(0).unwrap(),
-                                                                  params!("instance_meta_id" => instance_meta_id));
-        if let Some(server_id) = server_id {
-            if db_main.execute_wparams("DELETE FROM instance_meta WHERE id=:instance_meta_id AND uploaded_user=:member_id", params!(
-            "instance_meta_id" => instance_meta_id,
-            "member_id" => member_id
-        )) {
-                self.update_instance_meta(db_main, armory);
-                let _ = fs::remove_dir_all(&format!("{}/{}/{}", storage_path, server_id, instance_meta_id));
-                return Ok(());
-            }
+        remove_instance(self, db_main, armory, instance_meta_id, Remover::Uploader(member_id))
+    }
+
+    fn moderate_instance(&self, db_main: &mut (impl Execute + Select), armory: &Armory, account: &Account, moderator_id: u32, instance_meta_id: u32) -> Result<(), InstanceFailure> {
+        remove_instance(self, db_main, armory, instance_meta_id, Remover::Moderator(account, moderator_id))
+    }
+}
+
+// Only the uploader may delete an instance, unless it is removed by a moderator.
+// The removal by a moderator and its audit log entry are written in one transaction.
+fn remove_instance(instance: &Instance, db_main: &mut (impl Execute + Select), armory: &Armory, instance_meta_id: u32, remover: Remover) -> Result<(), InstanceFailure> {
+    let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+    let server_id: Option<u32> = db_main.select_wparams_value("SELECT server_id FROM instance_meta WHERE id=:instance_meta_id",
+                                                              |mut row| row.take::<u32, usize>(0).unwrap(),
+                                                              params!("instance_meta_id" => instance_meta_id));
+    if let Some(server_id) = server_id {
+        let deleted = match remover {
+            Remover::Uploader(member_id) => db_main.execute_wparams("DELETE FROM instance_meta WHERE id=:instance_meta_id AND uploaded_user=:member_id", params!(
+                "instance_meta_id" => instance_meta_id,
+                "member_id" => member_id
+            )),
+            Remover::Moderator(account, moderator_id) => {
+                if !db_main.execute_one("START TRANSACTION") {
+                    return Err(InstanceFailure::Database("moderate_instance".to_owned()));
+                }
+                let is_committed = db_main.execute_wparams("DELETE FROM instance_meta WHERE id=:instance_meta_id", params!(
+                    "instance_meta_id" => instance_meta_id
+                )) && account.log_action(db_main, moderator_id, AuditAction::DeleteInstance { instance_meta_id }).is_ok()
+                    && db_main.execute_one("COMMIT");
+                if !is_committed {
+                    db_main.execute_one("ROLLBACK");
+                    return Err(InstanceFailure::Database("moderate_instance".to_owned()));
+                }
+                true
+            },
+        };
+        if deleted {
+            instance.update_instance_meta(db_main, armory);
+            let _ = fs::remove_dir_all(&format!("{}/{}/{}", storage_path, server_id, instance_meta_id));
+            return Ok(());
         }
-        Err(InstanceFailure::InvalidInput)
     }
-}
\ No newline at end of file
+    Err(InstanceFailure::InvalidInput)
+}
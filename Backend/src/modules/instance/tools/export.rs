@@ -2,16 +2,22 @@ use crate::material::Cachable;
 use crate::modules::armory::tools::GetCharacter;
 use crate::modules::armory::Armory;
 use crate::modules::instance::domain_value::MetaType;
-use crate::modules::instance::dto::{InstanceFailure, InstanceViewerAttempt, InstanceViewerGuild, InstanceViewerMeta, InstanceViewerParticipant};
-use crate::modules::instance::material::Role;
-use crate::modules::instance::tools::FindInstanceGuild;
+use crate::modules::instance::dto::{AnalyticsFilter, EventExportFilter, InstanceFailure, InstanceViewerAttempt, InstanceViewerGuild, InstanceViewerMeta, InstanceViewerParticipant};
+use crate::modules::instance::material::{EventMatcher, Role};
+use crate::modules::instance::tools::{FindInstanceGuild, InstanceAnalytics};
 use crate::modules::instance::Instance;
 use crate::params;
 use crate::util::database::Select;
-use std::str::FromStr;
+
+// Same as the limit of the unfiltered export
+static EVENT_EXPORT_PAGE_SIZE: u32 = 50000;
 
 pub trait ExportInstance {
     fn export_instance_event_type(&self, instance_meta_id: u32, event_type: u8) -> Result<Vec<(u32, String)>, InstanceFailure>;
+    /// Only the blocks of events that overlap the time window are read, events close to it may be included as well
+    fn export_instance_event_type_between(&self, instance_meta_id: u32, event_type: u8, window: (u64, u64)) -> Result<Vec<(u32, String)>, InstanceFailure>;
+    /// A page of the events that match the filter, ordered by event id, and the cursor of the next page
+    fn export_instance_events(&self, db_main: &mut impl Select, instance_meta_id: u32, event_type: u8, filter: EventExportFilter) -> Result<(Vec<(u32, String)>, Option<u32>), InstanceFailure>;
     fn get_instance_meta(&self, armory: &Armory, instance_meta_id: u32) -> Result<InstanceViewerMeta, InstanceFailure>;
     fn get_instance_participants(&self, armory: &Armory, instance_meta_id: u32) -> Result<Vec<InstanceViewerParticipant>, InstanceFailure>;
     fn get_instance_attempts(&self, db_main: &mut impl Select, instance_meta_id: u32) -> Result<Vec<InstanceViewerAttempt>, InstanceFailure>;
@@ -37,23 +43,57 @@ impl ExportInstance for Instance {
         }
 
         let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
-        let event_path = format!("{}/{}/{}/{}", storage_path, server_id, instance_meta_id, event_type);
-        if let Ok(file_content) = std::fs::read_to_string(event_path) {
-            let lines = file_content.lines().collect::<Vec<&str>>();
-            let mut events = Vec::with_capacity(lines.len());
-            for segment in lines {
-                let id = u32::from_str(&segment[1..segment.find(',').expect("Must exist if data is not broken")]).expect("First element is the id");
-                events.push((id, segment.to_owned()));
-            }
-
+        let instance_path = format!("{}/{}/{}", storage_path, server_id, instance_meta_id);
+        if let Ok(events) = event_storage::read_events(&instance_path, event_type) {
             let mut instance_exports = self.instance_exports.write().unwrap();
             instance_exports.insert((instance_meta_id, event_type), Cachable::new(events.clone()));
-
             return Ok(events);
         }
         Ok(vec![])
     }
 
+    fn export_instance_event_type_between(&self, instance_meta_id: u32, event_type: u8, (start_ts, end_ts): (u64, u64)) -> Result<Vec<(u32, String)>, InstanceFailure> {
+        let server_id = {
+            let instance_metas = self.instance_metas.read().unwrap();
+            instance_metas.get(&instance_meta_id).ok_or(InstanceFailure::InvalidInput)?.server_id
+        };
+
+        let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+        let instance_path = format!("{}/{}/{}", storage_path, server_id, instance_meta_id);
+        Ok(event_storage::read_events_where(&instance_path, event_type, |block| block.overlaps(start_ts, end_ts)).unwrap_or_default())
+    }
+
+    fn export_instance_events(&self, db_main: &mut impl Select, instance_meta_id: u32, event_type: u8, filter: EventExportFilter) -> Result<(Vec<(u32, String)>, Option<u32>), InstanceFailure> {
+        let server_id = {
+            let instance_metas = self.instance_metas.read().unwrap();
+            instance_metas.get(&instance_meta_id).ok_or(InstanceFailure::InvalidInput)?.server_id
+        };
+        let window = self.get_analytics_window(
+            db_main,
+            instance_meta_id,
+            &AnalyticsFilter {
+                attempt_id: filter.attempt_id,
+                start_ts: filter.start_ts,
+                end_ts: filter.end_ts,
+            },
+        )?;
+        let limit = filter.limit.unwrap_or(EVENT_EXPORT_PAGE_SIZE).min(EVENT_EXPORT_PAGE_SIZE).max(1) as usize;
+        let event_matcher = EventMatcher::new(event_type, window, filter);
+
+        let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+        let instance_path = format!("{}/{}/{}", storage_path, server_id, instance_meta_id);
+        let mut events = event_storage::read_events_where(&instance_path, event_type, |block| event_matcher.overlaps(block.min_timestamp, block.max_timestamp) && event_matcher.is_after_cursor(block.max_id))
+            .map_err(|_| InstanceFailure::InvalidInput)?
+            .into_iter()
+            .filter(|(id, line)| event_matcher.matches(*id, line))
+            .collect::<Vec<(u32, String)>>();
+        events.sort_by_key(|(id, _)| *id);
+
+        let next_cursor = if events.len() > limit { Some(events[limit - 1].0) } else { None };
+        events.truncate(limit);
+        Ok((events, next_cursor))
+    }
+
     fn get_instance_meta(&self, armory: &Armory, instance_meta_id: u32) -> Result<InstanceViewerMeta, InstanceFailure> {
         let instance_metas = self.instance_metas.read().unwrap();
         if let Some(instance_meta) = instance_metas.get(&instance_meta_id) {
@@ -0,0 +1,50 @@
+use crate::modules::instance::dto::{AnalyticsFilter, InstanceFailure, InterruptReport, InterruptReportFilter};
+use crate::modules::instance::material::{AuraTimelineBuilder, InterruptAudit};
+use crate::modules::instance::tools::{ExportInstance, InstanceAnalytics};
+use crate::modules::instance::Instance;
+use crate::util::database::Select;
+
+pub trait InstanceInterruptReport {
+    fn get_interrupt_report(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: InterruptReportFilter) -> Result<InterruptReport, InstanceFailure>;
+}
+
+impl InstanceInterruptReport for Instance {
+    fn get_interrupt_report(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: InterruptReportFilter) -> Result<InterruptReport, InstanceFailure> {
+        let window = self.get_analytics_window(
+            db_main,
+            instance_meta_id,
+            &AnalyticsFilter {
+                attempt_id: filter.attempt_id,
+                start_ts: filter.start_ts,
+                end_ts: filter.end_ts,
+            },
+        )?;
+
+        let mut interrupt_audit = InterruptAudit::default();
+        // SpellCast, Interrupt, SpellSteal, Dispel
+        for event_type in [0, 7, 8, 9].iter() {
+            for (_, line) in self.export_instance_event_type_between(instance_meta_id, *event_type, window)? {
+                interrupt_audit.add_line(*event_type, &line);
+            }
+        }
+        // Auras may be applied before the window starts
+        let mut aura_timeline_builder = AuraTimelineBuilder::default();
+        for event_type in [1, 6].iter() {
+            for (_, line) in self.export_instance_event_type_between(instance_meta_id, *event_type, (0, window.1))? {
+                aura_timeline_builder.add_line(*event_type, &line);
+            }
+        }
+        let aura_timeline = aura_timeline_builder.build((0, u64::MAX));
+
+        Ok(InterruptReport {
+            instance_meta_id,
+            attempt_id: filter.attempt_id,
+            start_ts: window.0,
+            end_ts: window.1,
+            interrupts: interrupt_audit.get_interrupts(window),
+            dispels: interrupt_audit.get_dispels(window, &aura_timeline),
+            spell_steals: interrupt_audit.get_spell_steals(window, &aura_timeline),
+            missed_interrupts: interrupt_audit.get_missed_interrupts(window, filter.interruptible_spell_ids.as_deref().unwrap_or(&[])),
+        })
+    }
+}
@@ -1,13 +1,23 @@
+pub use self::analytics::InstanceAnalytics;
+pub use self::aura_uptime::InstanceAuraUptime;
+pub use self::death_recap::ExportDeathRecap;
 pub use self::export::ExportInstance;
 pub use self::instance_guild::FindInstanceGuild;
+pub use self::interrupt_report::InstanceInterruptReport;
 pub use self::meta::ExportMeta;
 pub use self::meta_search::MetaSearch;
+pub use self::progression::InstanceProgression;
 pub use self::ranking::*;
 pub use self::delete::DeleteInstance;
 
+mod analytics;
+mod aura_uptime;
+mod death_recap;
 mod export;
 mod instance_guild;
+mod interrupt_report;
 mod meta;
 mod meta_search;
+mod progression;
 mod ranking;
 mod delete;
\ No newline at end of file
@@ -0,0 +1,93 @@
+use crate::modules::armory::dto::SearchGuildDto;
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::{RetrieveEncounter, RetrieveEncounterRule, RetrieveServer};
+use crate::modules::data::Data;
+use crate::modules::instance::domain_value::MetaType;
+use crate::modules::instance::dto::{GuildProgression, InstanceFailure, InstanceViewerAttempt, ProgressionFilter, SpeedRun};
+use crate::modules::instance::material::{RaidAttempts, RaidProgress};
+use crate::modules::instance::tools::{ExportMeta, FindInstanceGuild};
+use crate::modules::instance::Instance;
+use crate::util::database::Select;
+use std::collections::{BTreeSet, HashMap};
+
+pub trait InstanceProgression {
+    fn get_guild_progressions(&self, db_main: &mut impl Select, armory: &Armory, data: &Data, filter: ProgressionFilter) -> Result<Vec<GuildProgression>, InstanceFailure>;
+    fn get_speed_runs(&self, db_main: &mut impl Select, armory: &Armory, data: &Data, filter: ProgressionFilter) -> Result<Vec<SpeedRun>, InstanceFailure>;
+}
+
+impl InstanceProgression for Instance {
+    fn get_guild_progressions(&self, db_main: &mut impl Select, armory: &Armory, data: &Data, filter: ProgressionFilter) -> Result<Vec<GuildProgression>, InstanceFailure> {
+        get_raid_progress(self, db_main, armory, data, &filter).map(|raid_progress| raid_progress.get_guild_progressions())
+    }
+
+    fn get_speed_runs(&self, db_main: &mut impl Select, armory: &Armory, data: &Data, filter: ProgressionFilter) -> Result<Vec<SpeedRun>, InstanceFailure> {
+        get_raid_progress(self, db_main, armory, data, &filter).map(|raid_progress| raid_progress.get_speed_runs())
+    }
+}
+
+fn get_raid_progress(instance: &Instance, db_main: &mut impl Select, armory: &Armory, data: &Data, filter: &ProgressionFilter) -> Result<RaidProgress, InstanceFailure> {
+    let mut attempts = get_raid_attempts(db_main, filter);
+    let mut raids = Vec::new();
+    for raid in instance.export_meta(0) {
+        let difficulty_id = match raid.instance_specific {
+            MetaType::Raid { map_difficulty } => map_difficulty,
+            _ => continue,
+        };
+        let expansion_id = data.get_server(raid.server_id).map(|server| server.expansion_id);
+        if filter.server_id.map(|server_id| raid.server_id != server_id).unwrap_or(false)
+            || filter.expansion_id.map(|filter_expansion_id| expansion_id != Some(filter_expansion_id)).unwrap_or(false)
+            || filter.map_id.map(|map_id| raid.map_id != map_id).unwrap_or(false)
+            || filter.difficulty_id.map(|filter_difficulty_id| difficulty_id != filter_difficulty_id).unwrap_or(false)
+        {
+            continue;
+        }
+
+        let guild = raid.participants.find_instance_guild(armory).map(|guild| SearchGuildDto { guild_id: guild.id, name: guild.name });
+        if filter.guild_id.map(|guild_id| guild.as_ref().map(|guild| guild.guild_id) != Some(guild_id)).unwrap_or(false) {
+            continue;
+        }
+
+        raids.push(RaidAttempts {
+            instance_meta_id: raid.instance_meta_id,
+            server_id: raid.server_id,
+            map_id: raid.map_id,
+            difficulty_id,
+            guild,
+            attempts: attempts.remove(&raid.instance_meta_id).unwrap_or_default(),
+        });
+    }
+
+    let hard_modes: HashMap<u32, u32> = data.get_all_encounter_rules().into_iter().map(|encounter_rule| (encounter_rule.hard_mode_encounter_id, encounter_rule.encounter_id)).collect();
+    let mut encounters: HashMap<u16, BTreeSet<u32>> = HashMap::new();
+    for encounter in data.get_all_encounters() {
+        if !hard_modes.contains_key(&encounter.id) {
+            encounters.entry(encounter.map_id).or_default().insert(encounter.id);
+        }
+    }
+
+    Ok(RaidProgress::new(raids, encounters, hard_modes))
+}
+
+fn get_raid_attempts(db_main: &mut impl Select, filter: &ProgressionFilter) -> HashMap<u32, Vec<InstanceViewerAttempt>> {
+    let mut attempts: HashMap<u32, Vec<InstanceViewerAttempt>> = HashMap::new();
+    for (instance_meta_id, attempt) in db_main.select_wparams(
+        "SELECT A.instance_meta_id, A.id, A.encounter_id, A.start_ts, A.end_ts, A.is_kill FROM instance_attempt A JOIN instance_meta B ON A.instance_meta_id = B.id JOIN instance_raid C ON B.id = C.instance_meta_id \
+         WHERE (:server_id IS NULL OR B.server_id = :server_id) AND (:map_id IS NULL OR B.map_id = :map_id) AND (:difficulty_id IS NULL OR C.map_difficulty = :difficulty_id)",
+        |mut row| {
+            (
+                row.take::<u32, usize>(0).unwrap(),
+                InstanceViewerAttempt {
+                    id: row.take(1).unwrap(),
+                    encounter_id: row.take(2).unwrap(),
+                    start_ts: row.take(3).unwrap(),
+                    end_ts: row.take(4).unwrap(),
+                    is_kill: row.take(5).unwrap(),
+                },
+            )
+        },
+        params!("server_id" => filter.server_id, "map_id" => filter.map_id, "difficulty_id" => filter.difficulty_id),
+    ) {
+        attempts.entry(instance_meta_id).or_default().push(attempt);
+    }
+    attempts
+}
@@ -1,13 +1,21 @@
 use crate::modules::armory::tools::GetCharacter;
+use crate::modules::armory::util::talent_tree::get_talent_tree;
 use crate::modules::armory::Armory;
-use crate::modules::data::tools::{RetrieveEncounter, RetrieveLocalization};
+use crate::modules::data::tools::{RetrieveEncounter, RetrieveLocalization, RetrieveRankingBracket};
 use crate::modules::data::Data;
-use crate::modules::instance::dto::{InstanceFailure, RankingCharacterMeta, RankingResult};
+use crate::modules::instance::dto::{InstanceFailure, RankingCharacterMeta, RankingFilter, RankingHistoryEntry, RankingMetric, RankingPage, RankingResult};
+use crate::modules::instance::material::{RankingCandidate, RankingLeaderboard, Role};
 use crate::modules::instance::Instance;
 use std::collections::HashMap;
 
+static DEFAULT_PAGE_SIZE: u32 = 50;
+static MAX_PAGE_SIZE: u32 = 500;
+
 pub trait ExportRanking {
     fn get_character_ranking(&self, data: &Data, language_id: u8, character_id: u32) -> Result<Vec<(String, Option<RankingResult>, Option<RankingResult>, Option<RankingResult>)>, InstanceFailure>;
+    fn search_rankings(&self, armory: &Armory, data: &Data, filter: RankingFilter) -> Result<RankingPage, InstanceFailure>;
+    /// Percentile of each result of the character within the current leaderboard of the filter
+    fn get_character_ranking_history(&self, armory: &Armory, data: &Data, character_id: u32, filter: RankingFilter) -> Result<Vec<RankingHistoryEntry>, InstanceFailure>;
 }
 
 impl ExportRanking for Instance {
@@ -46,6 +54,70 @@ impl ExportRanking for Instance {
             })
             .collect())
     }
+
+    fn search_rankings(&self, armory: &Armory, data: &Data, filter: RankingFilter) -> Result<RankingPage, InstanceFailure> {
+        let bracket = filter.bracket_id.map(|bracket_id| data.get_ranking_bracket(bracket_id).ok_or(InstanceFailure::InvalidInput)).transpose()?;
+        let leaderboard = RankingLeaderboard::new(get_ranking_candidates(self, armory, &filter), &filter, bracket.as_ref());
+        let page = filter.page.unwrap_or(0);
+        let page_size = filter.page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1).min(MAX_PAGE_SIZE);
+        Ok(RankingPage {
+            total: leaderboard.len() as u32,
+            page,
+            page_size,
+            entries: leaderboard.page(page, page_size),
+        })
+    }
+
+    fn get_character_ranking_history(&self, armory: &Armory, data: &Data, character_id: u32, filter: RankingFilter) -> Result<Vec<RankingHistoryEntry>, InstanceFailure> {
+        let bracket = filter.bracket_id.map(|bracket_id| data.get_ranking_bracket(bracket_id).ok_or(InstanceFailure::InvalidInput)).transpose()?;
+        let candidates = get_ranking_candidates(self, armory, &filter);
+        let results = candidates.iter().find(|candidate| candidate.character_id == character_id).map(|candidate| candidate.results.clone()).unwrap_or_else(Vec::new);
+        Ok(RankingLeaderboard::new(candidates, &filter, bracket.as_ref()).get_history(&results, &filter, bracket.as_ref()))
+    }
+}
+
+fn get_ranking_candidates(instance: &Instance, armory: &Armory, filter: &RankingFilter) -> Vec<RankingCandidate> {
+    let rankings = match filter.metric {
+        RankingMetric::Dps => instance.instance_rankings_dps.read().unwrap(),
+        RankingMetric::Hps => instance.instance_rankings_hps.read().unwrap(),
+        RankingMetric::Tps => instance.instance_rankings_tps.read().unwrap(),
+    };
+    rankings
+        .1
+        .get(&filter.encounter_id)
+        .map(|char_rankings| {
+            char_rankings
+                .iter()
+                .filter_map(|(character_id, results)| {
+                    let character = armory.get_character(*character_id)?;
+                    let last_update = character.last_update?;
+                    let hero_class_id = last_update.character_info.hero_class_id;
+                    // Results that were committed before the spec was stored fall back to the current talents
+                    let talent_specialization = last_update.character_info.talent_specialization.clone();
+                    let results = results
+                        .iter()
+                        .map(|result| match result.role {
+                            Some(_) => result.clone(),
+                            None => RankingResult {
+                                spec_id: talent_specialization.as_ref().map(|talents| get_talent_tree(talents)),
+                                role: Some(Role::from_class_talent_string(hero_class_id, talent_specialization.as_deref().unwrap_or(""))),
+                                ..result.clone()
+                            },
+                        })
+                        .collect();
+                    Some(RankingCandidate {
+                        character_id: *character_id,
+                        character_meta: RankingCharacterMeta {
+                            server_id: character.server_id,
+                            hero_class_id,
+                            name: last_update.character_name.clone(),
+                        },
+                        results,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_else(Vec::new)
 }
 
 pub fn create_ranking_export(rankings: &HashMap<u32, HashMap<u32, Vec<RankingResult>>>, armory: &Armory) -> Vec<(u32, Vec<(u32, RankingCharacterMeta, Vec<RankingResult>)>)> {
@@ -77,10 +149,23 @@ pub fn create_ranking_export(rankings: &HashMap<u32, HashMap<u32, Vec<RankingRes
 }
 
 fn helper_get_best_ranking(ranking: &Vec<RankingResult>) -> RankingResult {
-    ranking.iter().fold(RankingResult { attempt_id: 0, amount: 0, duration: 1 }, |best, ranking_result| {
-        if (best.amount as f64 / best.duration as f64) < (ranking_result.amount as f64 / ranking_result.duration as f64) {
-            return ranking_result.clone();
-        }
-        best
-    })
+    ranking.iter().fold(
+        RankingResult {
+            attempt_id: 0,
+            amount: 0,
+            duration: 1,
+            active_time: 1,
+            server_id: 0,
+            difficulty_id: 0,
+            start_ts: 0,
+            spec_id: None,
+            role: None,
+        },
+        |best, ranking_result| {
+            if (best.amount as f64 / best.duration as f64) < (ranking_result.amount as f64 / ranking_result.duration as f64) {
+                return ranking_result.clone();
+            }
+            best
+        },
+    )
 }
@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::{AnalyticsFilter, InstanceFailure, InstanceMeters};
+use crate::modules::instance::tools::InstanceAnalytics;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+use rocket::State;
+use rocket_contrib::json::Json;
+
+#[openapi]
+#[post("/analytics/meters/<instance_meta_id>", format = "application/json", data = "<filter>")]
+pub fn get_instance_meters(me: State<Instance>, mut db_main: MainDb, instance_meta_id: u32, filter: Json<AnalyticsFilter>) -> Result<Json<InstanceMeters>, InstanceFailure> {
+    me.get_instance_meters(&mut (*db_main), instance_meta_id, filter.into_inner()).map(Json)
+}
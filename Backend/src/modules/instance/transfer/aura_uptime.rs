@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::{AuraUptimeFilter, InstanceAuraUptimes, InstanceFailure};
+use crate::modules::instance::tools::InstanceAuraUptime;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+use rocket::State;
+use rocket_contrib::json::Json;
+
+#[openapi]
+#[post("/analytics/aura_uptime/<instance_meta_id>", format = "application/json", data = "<filter>")]
+pub fn get_aura_uptimes(me: State<Instance>, mut db_main: MainDb, instance_meta_id: u32, filter: Json<AuraUptimeFilter>) -> Result<Json<InstanceAuraUptimes>, InstanceFailure> {
+    me.get_aura_uptimes(&mut (*db_main), instance_meta_id, filter.into_inner()).map(Json)
+}
@@ -0,0 +1,15 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::instance::dto::{DeathRecap, DeathRecapFilter, InstanceFailure};
+use crate::modules::instance::tools::ExportDeathRecap;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+use rocket::State;
+use rocket_contrib::json::Json;
+use std::sync::Arc;
+
+#[openapi]
+#[post("/analytics/death_recap/<instance_meta_id>", format = "application/json", data = "<filter>")]
+pub fn get_death_recaps(me: State<Instance>, mut db_main: MainDb, armory: State<Armory>, data: State<Arc<Data>>, instance_meta_id: u32, filter: Json<DeathRecapFilter>) -> Result<Json<Vec<DeathRecap>>, InstanceFailure> {
+    me.get_death_recaps(&mut (*db_main), &armory, &data, instance_meta_id, filter.into_inner()).map(Json)
+}
@@ -2,13 +2,20 @@ use crate::modules::instance::dto::InstanceFailure;
 use crate::modules::instance::Instance;
 use crate::MainDb;
 use rocket::State;
-use crate::modules::account::guard::Authenticate;
+use crate::modules::account::guard::{role, scope, Authenticate, HasRole};
+use crate::modules::account::Account;
 use crate::modules::instance::tools::DeleteInstance;
 use crate::modules::armory::Armory;
 use rocket_contrib::json::Json;
 
 #[openapi]
 #[delete("/delete", data = "<data>")]
-pub fn delete_instance(mut db_main: MainDb, me: State<Instance>, armory: State<Armory>, data: Json<u32>, auth: Authenticate) -> Result<(), InstanceFailure> {
+pub fn delete_instance(mut db_main: MainDb, me: State<Instance>, armory: State<Armory>, data: Json<u32>, auth: Authenticate<scope::DeleteInstance>) -> Result<(), InstanceFailure> {
     me.delete_instance(&mut *db_main, &armory, data.into_inner(), auth.0)
-}
\ No newline at end of file
+}
+
+#[openapi]
+#[delete("/moderate", data = "<data>")]
+pub fn moderate_instance(mut db_main: MainDb, me: State<Instance>, armory: State<Armory>, account: State<Account>, data: Json<u32>, moderator: HasRole<role::Moderator, scope::DeleteInstance>) -> Result<(), InstanceFailure> {
+    me.moderate_instance(&mut *db_main, &armory, &account, moderator.0, data.into_inner())
+}
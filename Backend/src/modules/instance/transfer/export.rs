@@ -1,10 +1,12 @@
 use crate::modules::armory::Armory;
-use crate::modules::instance::dto::{InstanceFailure, InstanceViewerAttempt, InstanceViewerMeta, InstanceViewerParticipant, RawJson};
+use crate::modules::instance::dto::{EventExportFilter, EventExportQuery, InstanceFailure, InstanceViewerAttempt, InstanceViewerMeta, InstanceViewerParticipant, RawJson};
 use crate::modules::instance::tools::ExportInstance;
 use crate::modules::instance::Instance;
 use crate::MainDb;
+use rocket::request::Form;
 use rocket::State;
 use rocket_contrib::json::Json;
+use std::convert::TryFrom;
 
 #[openapi(skip)]
 #[get("/export/<instance_meta_id>/<event_type>/<last_event_id>")]
@@ -14,6 +16,16 @@ pub fn get_instance_event_type(me: State<Instance>, instance_meta_id: u32, event
         .map(|res| RawJson("[".to_owned() + &res + "]"))
 }
 
+#[openapi(skip)]
+#[get("/export/events/<instance_meta_id>/<event_type>?<query..>")]
+pub fn get_instance_events(me: State<Instance>, mut db_main: MainDb, instance_meta_id: u32, event_type: u8, query: Form<EventExportQuery>) -> Result<RawJson, InstanceFailure> {
+    let filter = EventExportFilter::try_from(query.into_inner())?;
+    me.export_instance_events(&mut (*db_main), instance_meta_id, event_type, filter).map(|(events, next_cursor)| {
+        let next_cursor = next_cursor.map(|cursor| cursor.to_string()).unwrap_or_else(|| String::from("null"));
+        RawJson(format!("{{\"events\":[{}],\"next_cursor\":{}}}", events.into_iter().map(|(_, data)| data).collect::<Vec<String>>().join(","), next_cursor))
+    })
+}
+
 #[openapi]
 #[get("/export/<instance_meta_id>")]
 pub fn get_instance_meta(me: State<Instance>, armory: State<Armory>, instance_meta_id: u32) -> Result<Json<InstanceViewerMeta>, InstanceFailure> {
@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::{InstanceFailure, InterruptReport, InterruptReportFilter};
+use crate::modules::instance::tools::InstanceInterruptReport;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+use rocket::State;
+use rocket_contrib::json::Json;
+
+#[openapi]
+#[post("/analytics/interrupts/<instance_meta_id>", format = "application/json", data = "<filter>")]
+pub fn get_interrupt_report(me: State<Instance>, mut db_main: MainDb, instance_meta_id: u32, filter: Json<InterruptReportFilter>) -> Result<Json<InterruptReport>, InstanceFailure> {
+    me.get_interrupt_report(&mut (*db_main), instance_meta_id, filter.into_inner()).map(Json)
+}
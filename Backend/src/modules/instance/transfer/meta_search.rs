@@ -1,4 +1,5 @@
 use crate::dto::SearchResult;
+use crate::modules::account::guard::CurrentUser;
 use crate::modules::armory::Armory;
 use crate::modules::data::Data;
 use crate::modules::instance::dto::{BattlegroundSearchFilter, MetaBattlegroundSearch, MetaRaidSearch, MetaRatedArenaSearch, MetaSkirmishSearch, RaidSearchFilter, RatedArenaSearchFilter, SkirmishSearchFilter};
@@ -6,11 +7,11 @@ use crate::modules::instance::tools::MetaSearch;
 use crate::modules::instance::Instance;
 use rocket::State;
 use rocket_contrib::json::Json;
-use crate::modules::account::guard::CurrentUser;
+use std::sync::Arc;
 
 #[openapi]
 #[post("/meta_search/raids", format = "application/json", data = "<filter>")]
-pub fn export_raids(me: State<Instance>, armory: State<Armory>, data: State<Data>, current_user: CurrentUser, filter: Json<RaidSearchFilter>) -> Json<SearchResult<MetaRaidSearch>> {
+pub fn export_raids(me: State<Instance>, armory: State<Armory>, data: State<Arc<Data>>, current_user: CurrentUser, filter: Json<RaidSearchFilter>) -> Json<SearchResult<MetaRaidSearch>> {
     Json(me.search_meta_raids(&armory, &data, current_user.0, filter.into_inner()))
 }
 
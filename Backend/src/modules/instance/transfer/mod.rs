@@ -1,5 +1,10 @@
+pub mod analytics;
+pub mod aura_uptime;
+pub mod death_recap;
 pub mod export;
+pub mod interrupt_report;
 pub mod meta;
 pub mod meta_search;
+pub mod progression;
 pub mod ranking;
 pub mod delete;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::instance::dto::{GuildProgression, InstanceFailure, ProgressionFilter, SpeedRun};
+use crate::modules::instance::tools::InstanceProgression;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+use rocket::request::Form;
+use rocket::State;
+use rocket_contrib::json::Json;
+use std::sync::Arc;
+
+#[openapi(skip)]
+#[get("/progression/guilds?<filter..>")]
+pub fn get_guild_progressions(me: State<Instance>, mut db_main: MainDb, armory: State<Armory>, data: State<Arc<Data>>, filter: Form<ProgressionFilter>) -> Result<Json<Vec<GuildProgression>>, InstanceFailure> {
+    me.get_guild_progressions(&mut (*db_main), &armory, &data, filter.into_inner()).map(Json)
+}
+
+#[openapi(skip)]
+#[get("/progression/speed_runs?<filter..>")]
+pub fn get_speed_runs(me: State<Instance>, mut db_main: MainDb, armory: State<Armory>, data: State<Arc<Data>>, filter: Form<ProgressionFilter>) -> Result<Json<Vec<SpeedRun>>, InstanceFailure> {
+    me.get_speed_runs(&mut (*db_main), &armory, &data, filter.into_inner()).map(Json)
+}
@@ -1,11 +1,13 @@
 use crate::modules::armory::Armory;
 use crate::modules::data::guard::Language;
 use crate::modules::data::Data;
-use crate::modules::instance::dto::{InstanceFailure, RankingCharacterMeta, RankingResult};
+use crate::modules::instance::dto::{InstanceFailure, RankingCharacterMeta, RankingFilter, RankingHistoryEntry, RankingPage, RankingResult};
 use crate::modules::instance::tools::{create_ranking_export, ExportRanking};
 use crate::modules::instance::Instance;
+use rocket::request::Form;
 use rocket::State;
 use rocket_contrib::json::Json;
+use std::sync::Arc;
 
 #[openapi]
 #[get("/ranking/dps")]
@@ -30,6 +32,18 @@ pub fn get_instance_ranking_tps(me: State<Instance>, armory: State<Armory>) -> J
 
 #[openapi]
 #[get("/ranking/character/<character_id>")]
-pub fn get_character_ranking(me: State<Instance>, data: State<Data>, language: Language, character_id: u32) -> Result<Json<Vec<(String, Option<RankingResult>, Option<RankingResult>, Option<RankingResult>)>>, InstanceFailure> {
+pub fn get_character_ranking(me: State<Instance>, data: State<Arc<Data>>, language: Language, character_id: u32) -> Result<Json<Vec<(String, Option<RankingResult>, Option<RankingResult>, Option<RankingResult>)>>, InstanceFailure> {
     me.get_character_ranking(&data, language.0, character_id).map(Json)
 }
+
+#[openapi(skip)]
+#[get("/ranking/search?<filter..>")]
+pub fn search_rankings(me: State<Instance>, armory: State<Armory>, data: State<Arc<Data>>, filter: Form<RankingFilter>) -> Result<Json<RankingPage>, InstanceFailure> {
+    me.search_rankings(&armory, &data, filter.into_inner()).map(Json)
+}
+
+#[openapi(skip)]
+#[get("/ranking/character/<character_id>/history?<filter..>")]
+pub fn get_character_ranking_history(me: State<Instance>, armory: State<Armory>, data: State<Arc<Data>>, character_id: u32, filter: Form<RankingFilter>) -> Result<Json<Vec<RankingHistoryEntry>>, InstanceFailure> {
+    me.get_character_ranking_history(&armory, &data, character_id, filter.into_inner()).map(Json)
+}
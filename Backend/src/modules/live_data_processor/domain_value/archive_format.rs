@@ -0,0 +1,29 @@
+/// Container of an uploaded combat log, detected by its magic bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    Zip,
+    // Either a single gzipped file or a tar.gz
+    Gzip,
+    // Either a single zstd compressed file or a tar.zst
+    Zstd,
+    PlainText,
+}
+
+impl ArchiveFormat {
+    pub fn detect(magic_bytes: &[u8]) -> Self {
+        if magic_bytes.starts_with(&[0x50, 0x4B, 0x03, 0x04]) {
+            ArchiveFormat::Zip
+        } else if magic_bytes.starts_with(&[0x1F, 0x8B]) {
+            ArchiveFormat::Gzip
+        } else if magic_bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            ArchiveFormat::Zstd
+        } else {
+            ArchiveFormat::PlainText
+        }
+    }
+}
+
+/// Tar archives are recognized by the "ustar" magic of the first header
+pub fn is_tar_header(header: &[u8]) -> bool {
+    header.len() >= 262 && &header[257..262] == b"ustar"
+}
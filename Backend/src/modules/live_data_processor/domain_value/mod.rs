@@ -1,3 +1,4 @@
+pub use self::archive_format::*;
 pub use self::aura_application::AuraApplication;
 pub use self::creature::Creature;
 pub use self::damage::*;
@@ -18,9 +19,12 @@ pub use self::school_mask::*;
 pub use self::spell_cast::SpellCast;
 pub use self::spell_component::*;
 pub use self::threat::Threat;
+pub use self::time_window::TimeWindow;
 pub use self::unit::Unit;
 pub use self::unit_instance::UnitInstance;
+pub use self::upload_job_status::UploadJobStatus;
 
+mod archive_format;
 mod aura_application;
 mod creature;
 mod damage;
@@ -41,5 +45,7 @@ mod school_mask;
 mod spell_cast;
 mod spell_component;
 mod threat;
+mod time_window;
 mod unit;
 mod unit_instance;
+mod upload_job_status;
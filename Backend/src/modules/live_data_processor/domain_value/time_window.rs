@@ -0,0 +1,22 @@
+/// Parse window of a combat log in UTC, whereas the log itself is written in the local time of the uploader
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeWindow {
+    pub start: u64,
+    pub end: u64,
+    // Minutes east of UTC
+    pub utc_offset: i32,
+}
+
+impl TimeWindow {
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.start <= timestamp && timestamp <= self.end
+    }
+
+    pub fn to_utc(&self, local_timestamp: u64) -> u64 {
+        (local_timestamp as i64 - self.utc_offset as i64 * 60000).max(0) as u64
+    }
+
+    pub fn to_local(&self, timestamp: u64) -> u64 {
+        (timestamp as i64 + self.utc_offset as i64 * 60000).max(0) as u64
+    }
+}
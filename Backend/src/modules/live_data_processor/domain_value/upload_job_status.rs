@@ -0,0 +1,31 @@
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum UploadJobStatus {
+    Queued,
+    Parsing,
+    Committing,
+    Done,
+    Failed,
+}
+
+impl UploadJobStatus {
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            UploadJobStatus::Queued => 0,
+            UploadJobStatus::Parsing => 1,
+            UploadJobStatus::Committing => 2,
+            UploadJobStatus::Done => 3,
+            UploadJobStatus::Failed => 4,
+        }
+    }
+
+    pub fn from_u8(status: u8) -> Option<Self> {
+        match status {
+            0 => Some(UploadJobStatus::Queued),
+            1 => Some(UploadJobStatus::Parsing),
+            2 => Some(UploadJobStatus::Committing),
+            3 => Some(UploadJobStatus::Done),
+            4 => Some(UploadJobStatus::Failed),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateUploadSession {
+    pub server_id: i32,
+    pub start_time: String,
+    pub end_time: String,
+    // Minutes east of UTC, defaults to UTC
+    pub utc_offset: Option<i32>,
+    pub num_chunks: u32,
+    pub payload_armory: Option<String>,
+}
@@ -4,14 +4,41 @@ use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder, util::add_
 use schemars::JsonSchema;
 use std::io::Cursor;
 
-#[derive(Debug, JsonSchema)]
+/// The status code of each failure is its machine readable code, whereas the body explains what to fix.
+#[derive(Debug, JsonSchema, PartialEq)]
 pub enum LiveDataProcessorFailure {
     InvalidInput,
     DatabaseFailure(String),
+    MalformedForm,
+    MissingField(String),
+    InvalidField(String),
+    InvalidTimeFormat(String),
+    InvalidUtcOffset(i32),
+    EmptyPayload,
+    InvalidArchive,
+    NoCombatLogInArchive,
+    // Line number of the first line that is not valid UTF-8
+    InvalidEncoding(u64),
+    UnknownServer(i32),
+    UnsupportedExpansion(u8),
+    ServerNotDetected,
+    UploadNotFound,
+    InvalidChunk(u32),
+    UploadIncomplete,
+    StorageFailure(String),
+    InvalidPackage,
+    // The message or its payload is shorter or longer than its format requires
+    TruncatedMessage,
+    // Message type whose payload could not be read
+    MalformedPayload(u8),
+    UnknownMessageType(u8),
+    // Unit id that neither is a player nor carries a creature entry
+    InvalidUnit(u64),
 }
 
-impl Responder<'static> for LiveDataProcessorFailure {
-    fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
+impl LiveDataProcessorFailure {
+    /// Status code and the explanation of the failure
+    pub fn describe(self) -> (Status, String) {
         let body;
         let status = match self {
             LiveDataProcessorFailure::InvalidInput => {
@@ -22,7 +49,98 @@ impl Responder<'static> for LiveDataProcessorFailure {
                 body = reason;
                 Status::new(535, "DatabaseFailure")
             },
+            LiveDataProcessorFailure::MalformedForm => {
+                body = "The request is not a valid multipart form!".to_owned();
+                Status::new(536, "MalformedForm")
+            },
+            LiveDataProcessorFailure::MissingField(field) => {
+                body = format!("The field '{}' is missing!", field);
+                Status::new(537, "MissingField")
+            },
+            LiveDataProcessorFailure::InvalidField(field) => {
+                body = format!("The field '{}' has an invalid value!", field);
+                Status::new(538, "InvalidField")
+            },
+            LiveDataProcessorFailure::InvalidTimeFormat(raw) => {
+                body = format!("'{}' does not match the format 'DD.MM.YY HH:MM AM/PM'!", raw);
+                Status::new(539, "InvalidTimeFormat")
+            },
+            LiveDataProcessorFailure::InvalidUtcOffset(utc_offset) => {
+                body = format!("The UTC offset of {} minutes is not between -720 and 840!", utc_offset);
+                Status::new(540, "InvalidUtcOffset")
+            },
+            LiveDataProcessorFailure::EmptyPayload => {
+                body = "The uploaded file is empty!".to_owned();
+                Status::new(541, "EmptyPayload")
+            },
+            LiveDataProcessorFailure::InvalidArchive => {
+                body = "The uploaded archive is corrupt or could not be decompressed!".to_owned();
+                Status::new(542, "InvalidArchive")
+            },
+            LiveDataProcessorFailure::NoCombatLogInArchive => {
+                body = "The uploaded archive does not contain a WoWCombatLog.txt!".to_owned();
+                Status::new(543, "NoCombatLogInArchive")
+            },
+            LiveDataProcessorFailure::InvalidEncoding(line) => {
+                body = format!("Line {} of the combat log is not valid UTF-8!", line);
+                Status::new(544, "InvalidEncoding")
+            },
+            LiveDataProcessorFailure::UnknownServer(server_id) => {
+                body = format!("There is no server with the id {}!", server_id);
+                Status::new(545, "UnknownServer")
+            },
+            LiveDataProcessorFailure::UnsupportedExpansion(expansion_id) => {
+                body = format!("Combat logs of the expansion {} are not supported!", expansion_id);
+                Status::new(546, "UnsupportedExpansion")
+            },
+            LiveDataProcessorFailure::ServerNotDetected => {
+                body = "The server could not be detected from the combat log!".to_owned();
+                Status::new(547, "ServerNotDetected")
+            },
+            LiveDataProcessorFailure::UploadNotFound => {
+                body = "The upload does not exist or has expired!".to_owned();
+                Status::new(548, "UploadNotFound")
+            },
+            LiveDataProcessorFailure::InvalidChunk(chunk_index) => {
+                body = format!("The chunk {} is out of range, empty or too large!", chunk_index);
+                Status::new(549, "InvalidChunk")
+            },
+            LiveDataProcessorFailure::UploadIncomplete => {
+                body = "Not all chunks of the upload were received yet!".to_owned();
+                Status::new(550, "UploadIncomplete")
+            },
+            LiveDataProcessorFailure::StorageFailure(reason) => {
+                body = reason;
+                Status::new(551, "StorageFailure")
+            },
+            LiveDataProcessorFailure::InvalidPackage => {
+                body = "The package contains a malformed message!".to_owned();
+                Status::new(552, "InvalidPackage")
+            },
+            LiveDataProcessorFailure::TruncatedMessage => {
+                body = "A message is shorter or longer than its format requires!".to_owned();
+                Status::new(553, "TruncatedMessage")
+            },
+            LiveDataProcessorFailure::MalformedPayload(message_type) => {
+                body = format!("The payload of a message of the type {} is malformed!", message_type);
+                Status::new(554, "MalformedPayload")
+            },
+            LiveDataProcessorFailure::UnknownMessageType(message_type) => {
+                body = format!("The message type {} is unknown!", message_type);
+                Status::new(555, "UnknownMessageType")
+            },
+            LiveDataProcessorFailure::InvalidUnit(unit_id) => {
+                body = format!("The unit {} is neither a player nor a creature!", unit_id);
+                Status::new(556, "InvalidUnit")
+            },
         };
+        (status, body)
+    }
+}
+
+impl Responder<'static> for LiveDataProcessorFailure {
+    fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
+        let (status, body) = self.describe();
         Response::build().status(status).sized_body(Cursor::new(body)).ok()
     }
 }
@@ -31,8 +149,9 @@ impl OpenApiResponder<'static> for LiveDataProcessorFailure {
     fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
         let mut responses = Responses::default();
         let schema = gen.json_schema::<String>();
-        add_schema_response(&mut responses, 534, "text/plain", schema.clone())?;
-        add_schema_response(&mut responses, 535, "text/plain", schema)?;
+        for code in 534..=556 {
+            add_schema_response(&mut responses, code, "text/plain", schema.clone())?;
+        }
         Ok(responses)
     }
 }
@@ -1,5 +1,6 @@
 pub use self::aura_application::AuraApplication;
 pub use self::combat_state::CombatState;
+pub use self::create_upload_session::CreateUploadSession;
 pub use self::damage_component::DamageComponent;
 pub use self::damage_done::*;
 pub use self::death::Death;
@@ -24,9 +25,13 @@ pub use self::summon::Summon;
 pub use self::threat::Threat;
 pub use self::un_aura::UnAura;
 pub use self::unit::Unit;
+pub use self::upload_diagnostic::*;
+pub use self::upload_job::UploadJob;
+pub use self::upload_session_progress::UploadSessionProgress;
 
 mod aura_application;
 mod combat_state;
+mod create_upload_session;
 mod damage_component;
 mod damage_done;
 mod death;
@@ -51,3 +56,6 @@ mod summon;
 mod threat;
 mod un_aura;
 mod unit;
+mod upload_diagnostic;
+mod upload_job;
+mod upload_session_progress;
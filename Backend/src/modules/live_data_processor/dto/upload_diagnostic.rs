@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UploadDiagnostic {
+    // 0 if the log is of a retail server that is not known yet
+    pub server_id: u32,
+    pub expansion_id: u8,
+    pub num_lines: u64,
+    pub num_messages: u64,
+    pub participants: Vec<DiagnosticParticipant>,
+    pub active_maps: Vec<DiagnosticActiveMap>,
+    pub attempts: Vec<DiagnosticAttempt>,
+    // Event keyword => Amount
+    pub unparsable_lines: HashMap<String, u32>,
+    pub commit_outcomes: EventCommitOutcomes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiagnosticParticipant {
+    pub unit_id: u64,
+    pub name: String,
+    pub hero_class_id: Option<u8>,
+    pub active_intervals: Vec<(u64, u64)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiagnosticActiveMap {
+    pub map_id: u16,
+    pub intervals: Vec<(u64, u64)>,
+    // Difficulty => Intervals
+    pub difficulties: HashMap<u8, Vec<(u64, u64)>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiagnosticAttempt {
+    pub encounter_id: u32,
+    pub start_ts: u64,
+    pub end_ts: u64,
+    pub killed: bool,
+}
+
+// How often trying to commit the next event of a subject ended in which EventParseFailureAction.
+// As waiting subjects are tried again, wait counts the events that never got committed instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EventCommitOutcomes {
+    pub committed: u64,
+    pub discard_first: u64,
+    pub wait: u64,
+    pub prepend_next: u64,
+}
+
+impl EventCommitOutcomes {
+    pub fn merge(&mut self, other: EventCommitOutcomes) {
+        self.committed += other.committed;
+        self.discard_first += other.discard_first;
+        self.wait += other.wait;
+        self.prepend_next += other.prepend_next;
+    }
+}
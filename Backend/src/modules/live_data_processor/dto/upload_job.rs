@@ -0,0 +1,16 @@
+use crate::modules::live_data_processor::domain_value::UploadJobStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UploadJob {
+    pub job_id: String,
+    pub server_id: i32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub utc_offset: i32,
+    pub status: UploadJobStatus,
+    pub lines_parsed: u64,
+    pub events_committed: u64,
+    pub failure_reason: Option<String>,
+    pub created: u64,
+    pub updated: u64,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UploadSessionProgress {
+    pub upload_id: String,
+    pub num_chunks: u32,
+    pub received_chunks: Vec<u32>,
+}
@@ -1,11 +1,11 @@
+use crate::modules::data::domain_value::EncounterSignal;
 use std::collections::{BTreeSet, HashMap, VecDeque};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attempt {
     pub encounter_id: u32,
-    pub hard_mode_encounter_id: Option<u32>,
-    pub hard_mode_found_buffs: BTreeSet<u32>,
-    pub hard_mode_npcs_died: BTreeSet<u32>,
+    // In order of their first occurrence, see data_encounter_rule
+    pub hard_mode_signals: Vec<EncounterSignal>,
 
     pub start_ts: u64,
     pub end_ts: u64,
@@ -20,6 +20,9 @@ pub struct Attempt {
     pub ranking_damage: HashMap<u32, u32>,
     pub ranking_heal: HashMap<u32, u32>,
     pub ranking_threat: HashMap<u32, i32>,
+    // Milliseconds that each character was alive and in combat
+    pub ranking_active_time: HashMap<u32, u64>,
+    pub active_since: HashMap<u32, u64>,
 }
 
 impl Attempt {
@@ -37,11 +40,48 @@ impl Attempt {
             ranking_damage: HashMap::new(),
             ranking_heal: HashMap::new(),
             ranking_threat: HashMap::new(),
+            ranking_active_time: HashMap::new(),
+            active_since: HashMap::new(),
             encounter_has_pivot,
             pivot_is_finished: false,
-            hard_mode_encounter_id: None,
-            hard_mode_found_buffs: BTreeSet::new(),
-            hard_mode_npcs_died: BTreeSet::new(),
+            hard_mode_signals: Vec::new(),
+        }
+    }
+
+    pub fn add_hard_mode_signal(&mut self, signal: EncounterSignal) {
+        if !self.hard_mode_signals.contains(&signal) {
+            self.hard_mode_signals.push(signal);
+        }
+    }
+
+    /// Entering combat or any contribution to the ranking, e.g. of a late joiner or after a resurrection
+    pub fn set_player_active(&mut self, character_id: u32, timestamp: u64) {
+        let start_ts = self.start_ts;
+        self.active_since.entry(character_id).or_insert_with(|| timestamp.max(start_ts));
+    }
+
+    /// Leaving combat or dying
+    pub fn set_player_inactive(&mut self, character_id: u32, timestamp: u64) {
+        if let Some(active_since) = self.active_since.remove(&character_id) {
+            *self.ranking_active_time.entry(character_id).or_insert(0) += timestamp.saturating_sub(active_since);
+        }
+    }
+
+    /// Removes the rankings of everyone who was active for less than the minimum active time, e.g. a late joiner shortly before the kill
+    pub fn retain_ranked_characters(&mut self, min_active_time: u64) {
+        let ranking_active_time = &self.ranking_active_time;
+        let is_ranked = |character_id: &u32| ranking_active_time.get(character_id).map(|active_time| *active_time >= min_active_time).unwrap_or(false);
+        self.ranking_damage.retain(|character_id, _| is_ranked(character_id));
+        self.ranking_heal.retain(|character_id, _| is_ranked(character_id));
+        self.ranking_threat.retain(|character_id, _| is_ranked(character_id));
+    }
+
+    /// Counts the active time of everyone who is still active until the end of the attempt
+    pub fn finish_active_time(&mut self) {
+        let end_ts = self.end_ts;
+        let active_characters = self.active_since.keys().cloned().collect::<Vec<u32>>();
+        for character_id in active_characters {
+            self.set_player_inactive(character_id, end_ts);
         }
     }
 }
@@ -0,0 +1,136 @@
+use crate::modules::live_data_processor::domain_value::{is_tar_header, ArchiveFormat};
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// Uploaded archive that contains the combat log, or the plain combat log itself.
+/// The log is streamed out of it on every pass, hence it is never held in memory as a whole.
+#[derive(Debug, Clone)]
+pub struct CombatLogArchive {
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveEntry {
+    CombatLog,
+    // SavedVariables of the armory collector addon
+    Armory,
+}
+
+impl CombatLogArchive {
+    pub fn new(path: &Path) -> Self {
+        CombatLogArchive { path: path.to_path_buf() }
+    }
+
+    pub fn get_format(&self) -> Result<ArchiveFormat, LiveDataProcessorFailure> {
+        let mut magic_bytes = Vec::with_capacity(4);
+        File::open(&self.path)
+            .and_then(|file| file.take(4).read_to_end(&mut magic_bytes))
+            .map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("get_format")))?;
+        Ok(ArchiveFormat::detect(&magic_bytes))
+    }
+
+    /// Returns the number of lines that were read
+    pub fn for_each_line(&self, mut on_line: impl FnMut(&str) -> Result<(), LiveDataProcessorFailure>) -> Result<u64, LiveDataProcessorFailure> {
+        self.read_entry(ArchiveEntry::CombatLog, |reader| for_each_line(reader, &mut on_line))?.ok_or(LiveDataProcessorFailure::NoCombatLogInArchive)
+    }
+
+    /// The SavedVariables of the armory collector addon, if they were packed along with the combat log
+    pub fn read_armory(&self) -> Result<Option<String>, LiveDataProcessorFailure> {
+        self.read_entry(ArchiveEntry::Armory, |reader| {
+            let mut content = String::new();
+            reader.read_to_string(&mut content).map_err(|_| LiveDataProcessorFailure::InvalidArchive)?;
+            Ok(content)
+        })
+    }
+
+    fn read_entry<T>(&self, entry: ArchiveEntry, on_entry: impl FnOnce(&mut dyn Read) -> Result<T, LiveDataProcessorFailure>) -> Result<Option<T>, LiveDataProcessorFailure> {
+        let file = BufReader::new(File::open(&self.path).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("read_entry")))?);
+        match self.get_format()? {
+            ArchiveFormat::Zip => read_zip_entry(file, entry, on_entry),
+            ArchiveFormat::Gzip => read_stream_entry(flate2::read::GzDecoder::new(file), entry, on_entry),
+            ArchiveFormat::Zstd => read_stream_entry(zstd::stream::read::Decoder::with_buffer(file).map_err(|_| LiveDataProcessorFailure::InvalidArchive)?, entry, on_entry),
+            ArchiveFormat::PlainText => read_stream_entry(file, entry, on_entry),
+        }
+    }
+}
+
+impl ArchiveEntry {
+    fn matches(&self, path: &str) -> bool {
+        let file_name = path.rsplit(|c| c == '/' || c == '\\').next().unwrap_or("").to_lowercase();
+        match self {
+            // E.g. WoWCombatLog.txt or WoWCombatLog-2020-12-24.txt
+            ArchiveEntry::CombatLog => file_name.contains("combatlog") && file_name.ends_with(".txt"),
+            // E.g. RPLLWotLKArmoryCollector.lua
+            ArchiveEntry::Armory => file_name.starts_with("rpll") && file_name.ends_with("armorycollector.lua"),
+        }
+    }
+}
+
+fn read_zip_entry<T>(file: BufReader<File>, entry: ArchiveEntry, on_entry: impl FnOnce(&mut dyn Read) -> Result<T, LiveDataProcessorFailure>) -> Result<Option<T>, LiveDataProcessorFailure> {
+    let mut zip = zip::ZipArchive::new(file).map_err(|_| LiveDataProcessorFailure::InvalidArchive)?;
+    let mut index = None;
+    for i in 0..zip.len() {
+        let zip_file = zip.by_index(i).map_err(|_| LiveDataProcessorFailure::InvalidArchive)?;
+        if !zip_file.is_dir() && entry.matches(zip_file.name()) {
+            index = Some(i);
+            break;
+        }
+    }
+
+    // Archives that only hold a single file were always taken as combat log
+    if index.is_none() && entry == ArchiveEntry::CombatLog && zip.len() == 1 {
+        index = Some(0);
+    }
+
+    match index {
+        Some(index) => {
+            let mut zip_file = zip.by_index(index).map_err(|_| LiveDataProcessorFailure::InvalidArchive)?;
+            on_entry(&mut zip_file).map(Some)
+        },
+        None => Ok(None),
+    }
+}
+
+// Decompressed streams are either a tar archive or the combat log itself
+fn read_stream_entry<T>(mut reader: impl Read, entry: ArchiveEntry, on_entry: impl FnOnce(&mut dyn Read) -> Result<T, LiveDataProcessorFailure>) -> Result<Option<T>, LiveDataProcessorFailure> {
+    let mut header = Vec::with_capacity(512);
+    (&mut reader).take(512).read_to_end(&mut header).map_err(|_| LiveDataProcessorFailure::InvalidArchive)?;
+    let is_tar = is_tar_header(&header);
+    let mut reader = Cursor::new(header).chain(reader);
+
+    if !is_tar {
+        if entry == ArchiveEntry::CombatLog {
+            return on_entry(&mut reader).map(Some);
+        }
+        return Ok(None);
+    }
+
+    let mut tar = tar::Archive::new(reader);
+    for tar_entry in tar.entries().map_err(|_| LiveDataProcessorFailure::InvalidArchive)? {
+        let mut tar_entry = tar_entry.map_err(|_| LiveDataProcessorFailure::InvalidArchive)?;
+        let is_match = tar_entry.header().entry_type().is_file() && tar_entry.path().ok().and_then(|path| path.to_str().map(|path| entry.matches(path))).unwrap_or(false);
+        if is_match {
+            return on_entry(&mut tar_entry).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn for_each_line(reader: &mut dyn Read, on_line: &mut impl FnMut(&str) -> Result<(), LiveDataProcessorFailure>) -> Result<u64, LiveDataProcessorFailure> {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::with_capacity(1024);
+    let mut num_lines = 0;
+    loop {
+        line.clear();
+        // Corrupt compressed data only surfaces while it is read
+        if reader.read_until(b'\n', &mut line).map_err(|_| LiveDataProcessorFailure::InvalidArchive)? == 0 {
+            break;
+        }
+        num_lines += 1;
+        let content = std::str::from_utf8(&line).map_err(|_| LiveDataProcessorFailure::InvalidEncoding(num_lines))?;
+        on_line(content.trim_end_matches('\n'))?;
+    }
+    Ok(num_lines)
+}
@@ -1,20 +1,43 @@
-use crate::modules::live_data_processor::material::Server;
+use crate::modules::live_data_processor::material::{Server, ServerMetrics, UploadSession};
 use crate::params;
 use crate::util::database::Select;
-use std::collections::HashMap;
-use std::sync::RwLock;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, PoisonError, RwLock};
 
+// Clones share the underlying state, which is how the upload workers access it
+#[derive(Clone)]
 pub struct LiveDataProcessor {
-    pub servers: RwLock<HashMap<u32, RwLock<Server>>>,
+    pub servers: Arc<RwLock<HashMap<u32, RwLock<Server>>>>,
+    // upload_id => UploadSession
+    pub upload_sessions: Arc<RwLock<HashMap<String, UploadSession>>>,
+    // Queued job ids, workers wait on the condvar
+    pub upload_queue: Arc<(Mutex<VecDeque<String>>, Condvar)>,
+    // server_id => Held by the job that is committing into the server
+    pub upload_server_locks: Arc<Mutex<HashMap<u32, Arc<Mutex<()>>>>>,
+    pub metrics: ServerMetrics,
+    // Staged payloads, upload sessions and jobs are stored below
+    pub upload_storage_path: String,
 }
 
 impl Default for LiveDataProcessor {
     fn default() -> Self {
-        LiveDataProcessor { servers: RwLock::new(HashMap::new()) }
+        LiveDataProcessor {
+            servers: Arc::new(RwLock::new(HashMap::new())),
+            upload_sessions: Arc::new(RwLock::new(HashMap::new())),
+            upload_queue: Arc::new((Mutex::new(VecDeque::new()), Condvar::new())),
+            upload_server_locks: Arc::new(Mutex::new(HashMap::new())),
+            metrics: ServerMetrics::default(),
+            upload_storage_path: String::from("./Storage/Upload"),
+        }
     }
 }
 
 impl LiveDataProcessor {
+    pub fn with_upload_storage(mut self, upload_storage_path: String) -> Self {
+        self.upload_storage_path = upload_storage_path;
+        self
+    }
+
     pub fn init(self, db_main: &mut impl Select) -> Self {
         {
             let mut servers = self.servers.write().unwrap();
@@ -22,7 +45,7 @@ impl LiveDataProcessor {
                 .select("SELECT id, expansion_id FROM data_server", |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<u8, usize>(1).unwrap()))
                 .into_iter()
                 .for_each(|(server_id, expansion_id)| {
-                    servers.insert(server_id, RwLock::new(Server::new(server_id, expansion_id).init(db_main)));
+                    servers.insert(server_id, RwLock::new(Server::new(server_id, expansion_id).init(db_main).restore_snapshot()));
                 });
         }
         self
@@ -43,7 +66,23 @@ impl LiveDataProcessor {
                     params!("server_id" => server_id),
                 )
                 .unwrap();
-            servers.insert(server_id, RwLock::new(Server::new(server_id, expansion_id).init(db_main)));
+            servers.insert(server_id, RwLock::new(Server::new(server_id, expansion_id).init(db_main).restore_snapshot()));
+        }
+    }
+    /// A panic while the server was locked leaves its state inconsistent, hence it is rebuilt like after a restart
+    pub fn reset_server_if_poisoned(&self, db_main: &mut impl Select, server_id: u32) {
+        let expansion_id = {
+            let servers = self.servers.read().unwrap();
+            match servers.get(&server_id).filter(|server| server.is_poisoned()) {
+                Some(server) => server.read().unwrap_or_else(PoisonError::into_inner).expansion_id,
+                None => return,
+            }
+        };
+
+        let mut servers = self.servers.write().unwrap();
+        // Another thread may have rebuilt it in the meantime
+        if servers.get(&server_id).map(|server| server.is_poisoned()).unwrap_or(false) {
+            servers.insert(server_id, RwLock::new(Server::new(server_id, expansion_id).init(db_main).restore_snapshot()));
         }
     }
 }
@@ -0,0 +1,83 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// The clocks of different uploaders are never exactly in sync
+pub static MERGE_TIMESTAMP_TOLERANCE: u64 = 1000;
+// Cause events are looked up at most 90 seconds back, see Server::cleanup
+static CAUSE_EVENT_LOOKBACK: u64 = 90000;
+
+/// An upload that contributes to the instance of another uploader.
+/// Holds the events that were already saved for the instance, so identical events are not saved twice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedInstance {
+    pub instance_meta_id: u32,
+    pub canonical_member_id: u32,
+    // event_type => saved lines that were not fingerprinted yet
+    saved_lines: HashMap<u8, Vec<String>>,
+    // fingerprint => timestamp => [event_id]
+    saved_events: HashMap<u64, BTreeMap<u64, Vec<u32>>>,
+    // event_id of the contributing upload => (event_id of the saved event, timestamp)
+    mapped_event_ids: HashMap<u32, (u32, u64)>,
+}
+
+impl MergedInstance {
+    pub fn new(instance_meta_id: u32, canonical_member_id: u32) -> Self {
+        MergedInstance {
+            instance_meta_id,
+            canonical_member_id,
+            saved_lines: HashMap::new(),
+            saved_events: HashMap::new(),
+            mapped_event_ids: HashMap::new(),
+        }
+    }
+
+    pub fn insert_saved_lines(&mut self, event_type: u8, lines: Vec<String>) {
+        self.saved_lines.entry(event_type).or_insert_with(Vec::new).extend(lines);
+    }
+
+    pub fn take_saved_lines(&mut self, event_type: u8) -> Vec<String> {
+        self.saved_lines.remove(&event_type).unwrap_or_default()
+    }
+
+    pub fn insert_saved_event(&mut self, fingerprint: u64, timestamp: u64, event_id: u32) {
+        self.saved_events.entry(fingerprint).or_insert_with(BTreeMap::new).entry(timestamp).or_insert_with(Vec::new).push(event_id);
+    }
+
+    /// Returns the id of the closest saved event with the same fingerprint, which can't be matched again afterwards
+    pub fn take_saved_event(&mut self, fingerprint: u64, timestamp: u64) -> Option<u32> {
+        let saved_events = self.saved_events.get_mut(&fingerprint)?;
+        let closest_timestamp = saved_events
+            .range(timestamp.saturating_sub(MERGE_TIMESTAMP_TOLERANCE)..=timestamp + MERGE_TIMESTAMP_TOLERANCE)
+            .map(|(saved_timestamp, _)| *saved_timestamp)
+            .min_by_key(|saved_timestamp| (*saved_timestamp as i64 - timestamp as i64).abs())?;
+
+        let event_ids = saved_events.get_mut(&closest_timestamp).unwrap();
+        let event_id = event_ids.remove(0);
+        if event_ids.is_empty() {
+            saved_events.remove(&closest_timestamp);
+        }
+        if saved_events.is_empty() {
+            self.saved_events.remove(&fingerprint);
+        }
+        Some(event_id)
+    }
+
+    pub fn map_event_id(&mut self, event_id: u32, saved_event_id: u32, timestamp: u64) {
+        self.mapped_event_ids.insert(event_id, (saved_event_id, timestamp));
+    }
+
+    pub fn get_mapped_event_id(&self, event_id: u32) -> Option<u32> {
+        self.mapped_event_ids.get(&event_id).map(|(saved_event_id, _)| *saved_event_id)
+    }
+
+    /// Events of an upload are processed in order, hence nothing before the timestamp is matched anymore
+    pub fn prune(&mut self, timestamp: u64) {
+        let min_timestamp = timestamp.saturating_sub(MERGE_TIMESTAMP_TOLERANCE);
+        self.saved_events.retain(|_, saved_events| {
+            *saved_events = saved_events.split_off(&min_timestamp);
+            !saved_events.is_empty()
+        });
+        // Mapped ids of cause events are kept as long as they can be referred to
+        let min_timestamp = timestamp.saturating_sub(CAUSE_EVENT_LOOKBACK + MERGE_TIMESTAMP_TOLERANCE);
+        self.mapped_event_ids.retain(|_, (_, mapped_timestamp)| *mapped_timestamp >= min_timestamp);
+    }
+}
@@ -1,16 +1,30 @@
 pub use self::active_map::*;
 pub use self::attempt::Attempt;
+pub use self::combat_log_archive::CombatLogArchive;
 pub use self::live_data_processor::LiveDataProcessor;
+pub use self::merged_instance::*;
 pub use self::participant::Participant;
+pub use self::prepared_combat_log::PreparedCombatLog;
 pub use self::server::Server;
+pub use self::server_metrics::ServerMetrics;
+pub use self::server_snapshot::*;
+pub use self::upload_meta::UploadMeta;
+pub use self::upload_session::UploadSession;
 pub use self::wow_retail_classic_parser::WoWRetailClassicParser;
 pub use self::wow_tbc_parser::WoWTBCParser;
 pub use self::wow_vanilla_parser::WoWVanillaParser;
 pub use self::wow_wotlk_parser::WoWWOTLKParser;
 
 mod attempt;
+mod combat_log_archive;
 mod live_data_processor;
+mod merged_instance;
+mod prepared_combat_log;
 mod server;
+mod server_metrics;
+mod server_snapshot;
+mod upload_meta;
+mod upload_session;
 
 mod active_map;
 mod participant;
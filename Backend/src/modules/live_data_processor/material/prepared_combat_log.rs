@@ -0,0 +1,20 @@
+use crate::modules::live_data_processor::domain_value::TimeWindow;
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use std::collections::{BTreeSet, HashMap};
+
+/// Result of the first pass over a combat log, which is required to stream its messages in the second pass
+pub struct PreparedCombatLog {
+    // Knows all participants, active maps and pets, once the first pass is done
+    pub collector: Box<dyn CombatLogParser>,
+    // Reproduces the messages of the first pass
+    pub emitter: Box<dyn CombatLogParser>,
+    pub server_id: u32,
+    pub num_lines: u64,
+    // Event keyword => Number of lines
+    pub unparsable_lines: HashMap<String, u32>,
+    pub time_window: TimeWindow,
+    // (Map Id, Difficulty) => Instance Id
+    pub instance_ids: HashMap<(u16, Option<u8>), u32>,
+    pub remove_unit: BTreeSet<u64>,
+    pub replace_unit_id: HashMap<u64, u64>,
+}
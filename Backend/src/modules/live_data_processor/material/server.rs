@@ -1,6 +1,6 @@
 use crate::modules::live_data_processor::domain_value::{Event, NonCommittedEvent, Unit, UnitInstance};
 use crate::modules::live_data_processor::dto::InstanceResetDto;
-use crate::modules::live_data_processor::material::Attempt;
+use crate::modules::live_data_processor::material::{Attempt, MergedInstance};
 use crate::params;
 use crate::util::database::Select;
 use std::collections::{BTreeSet, HashMap, VecDeque};
@@ -11,9 +11,12 @@ pub struct Server {
 
     // Meta Data
     pub summons: HashMap<u64, Unit>,
-    // TODO: This grows uncontrollable
+    // Finalized instances are evicted, see evict_instance
     // Key: (instance_id, member_id)
     pub active_instances: HashMap<(u32, u32), UnitInstance>,
+    // Evicted once their committed events are saved, see schedule_eviction
+    // (instance_id, member_id) => (instance_meta_id, finalized timestamp)
+    pub finalized_instances: HashMap<(u32, u32), (u32, u64)>,
     pub unit_instance_id: HashMap<u64, u32>,
     pub instance_resets: HashMap<u16, InstanceResetDto>,
     // instance_meta_id => [(character_id, history_id)]
@@ -22,6 +25,12 @@ pub struct Server {
     // though most of the times only 1
     // Key: (instance_id, member_id)
     pub active_attempts: HashMap<(u32, u32), HashMap<u32, Attempt>>,
+    // Players that are in combat, whether or not an attempt is active
+    // Key: (instance_id, member_id)
+    pub infight_players: HashMap<(u32, u32), BTreeSet<u32>>,
+    // Uploads that are merged into the instance of another uploader
+    // Key: (instance_id, member_id)
+    pub merged_instances: HashMap<(u32, u32), MergedInstance>,
 
     // Used to handle unordered events
     pub subject_prepend_mode_set: BTreeSet<u64>, // Contains server_uid of subject
@@ -37,6 +46,8 @@ pub struct Server {
 
     // PERFORMANCE TEST
     pub cache_unit: HashMap<u64, Unit>,
+
+    pub num_evicted_instances: u64,
 }
 
 impl Server {
@@ -44,9 +55,9 @@ impl Server {
         Server {
             server_id,
             expansion_id,
-            // TODO: Preserve all data when the app is restarted?
             summons: HashMap::new(),
             active_instances: HashMap::new(),
+            finalized_instances: HashMap::new(),
             unit_instance_id: HashMap::new(),
             instance_resets: HashMap::new(),
             instance_participants: HashMap::new(),
@@ -55,9 +66,12 @@ impl Server {
             committed_events_count: HashMap::new(),
             subject_prepend_mode_set: BTreeSet::new(),
             active_attempts: HashMap::new(),
+            infight_players: HashMap::new(),
+            merged_instances: HashMap::new(),
             post_processing_last_precessed_event_id: HashMap::new(),
             recently_committed_spell_cast_and_aura_applications: HashMap::new(),
             cache_unit: HashMap::new(),
+            num_evicted_instances: 0,
         }
     }
 
@@ -0,0 +1,38 @@
+use rocket_prometheus::prometheus::{IntCounterVec, IntGaugeVec, Opts, Registry};
+
+/// Per server metrics of the live state, which are exposed through the Prometheus fairing
+#[derive(Clone)]
+pub struct ServerMetrics {
+    // Labels: server_id, collection
+    pub state_entries: IntGaugeVec,
+    // Labels: server_id
+    pub snapshot_bytes: IntGaugeVec,
+    // Labels: server_id
+    pub snapshot_failures: IntCounterVec,
+    // Labels: server_id
+    pub evicted_instances: IntCounterVec,
+}
+
+impl Default for ServerMetrics {
+    fn default() -> Self {
+        ServerMetrics {
+            state_entries: IntGaugeVec::new(Opts::new("live_data_processor_state_entries", "Number of entries in the live state of a server"), &["server_id", "collection"]).unwrap(),
+            snapshot_bytes: IntGaugeVec::new(Opts::new("live_data_processor_snapshot_bytes", "Size of the last snapshot of the live state of a server"), &["server_id"]).unwrap(),
+            snapshot_failures: IntCounterVec::new(Opts::new("live_data_processor_snapshot_failures_total", "Number of snapshots of the live state of a server that could not be saved"), &["server_id"]).unwrap(),
+            evicted_instances: IntCounterVec::new(
+                Opts::new("live_data_processor_evicted_instances_total", "Number of finalized instances that were evicted from the live state of a server"),
+                &["server_id"],
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl ServerMetrics {
+    pub fn register(&self, registry: &Registry) {
+        registry.register(Box::new(self.state_entries.clone())).unwrap();
+        registry.register(Box::new(self.snapshot_bytes.clone())).unwrap();
+        registry.register(Box::new(self.snapshot_failures.clone())).unwrap();
+        registry.register(Box::new(self.evicted_instances.clone())).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+use crate::modules::live_data_processor::domain_value::{Event, NonCommittedEvent, Unit, UnitInstance};
+use crate::modules::live_data_processor::material::{Attempt, MergedInstance, Server};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::io::Write;
+
+/// Incremented whenever the layout of the snapshot changes, older snapshots are discarded then
+pub static SERVER_SNAPSHOT_VERSION: u8 = 5;
+
+/// The live state of a server that is not persisted in the database.
+/// A new snapshot borrows the state of the server, whereas a decoded one owns it.
+/// Key of the per instance maps: (instance_id, member_id)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSnapshot<'a> {
+    pub version: u8,
+    pub server_id: u32,
+    pub created: u64,
+    pub summons: Cow<'a, HashMap<u64, Unit>>,
+    pub active_instances: Cow<'a, HashMap<(u32, u32), UnitInstance>>,
+    pub unit_instance_id: Cow<'a, HashMap<u64, u32>>,
+    pub active_attempts: Cow<'a, HashMap<(u32, u32), HashMap<u32, Attempt>>>,
+    pub infight_players: Cow<'a, HashMap<(u32, u32), BTreeSet<u32>>>,
+    pub merged_instances: Cow<'a, HashMap<(u32, u32), MergedInstance>>,
+    pub subject_prepend_mode_set: Cow<'a, BTreeSet<u64>>,
+    pub post_processing_last_precessed_event_id: Cow<'a, HashMap<u32, u32>>,
+    pub non_committed_events: Cow<'a, HashMap<u64, NonCommittedEvent>>,
+    pub committed_events: Cow<'a, HashMap<(u32, u32), VecDeque<Event>>>,
+    pub committed_events_count: Cow<'a, HashMap<(u32, u32), u32>>,
+    pub recently_committed_spell_cast_and_aura_applications: Cow<'a, HashMap<(u32, u32), VecDeque<Event>>>,
+    pub cache_unit: Cow<'a, HashMap<u64, Unit>>,
+}
+
+impl<'a> ServerSnapshot<'a> {
+    pub fn new(server: &'a Server, created: u64) -> Self {
+        ServerSnapshot {
+            version: SERVER_SNAPSHOT_VERSION,
+            server_id: server.server_id,
+            created,
+            summons: Cow::Borrowed(&server.summons),
+            active_instances: Cow::Borrowed(&server.active_instances),
+            unit_instance_id: Cow::Borrowed(&server.unit_instance_id),
+            active_attempts: Cow::Borrowed(&server.active_attempts),
+            infight_players: Cow::Borrowed(&server.infight_players),
+            merged_instances: Cow::Borrowed(&server.merged_instances),
+            subject_prepend_mode_set: Cow::Borrowed(&server.subject_prepend_mode_set),
+            post_processing_last_precessed_event_id: Cow::Borrowed(&server.post_processing_last_precessed_event_id),
+            non_committed_events: Cow::Borrowed(&server.non_committed_events),
+            committed_events: Cow::Borrowed(&server.committed_events),
+            committed_events_count: Cow::Borrowed(&server.committed_events_count),
+            recently_committed_spell_cast_and_aura_applications: Cow::Borrowed(&server.recently_committed_spell_cast_and_aura_applications),
+            cache_unit: Cow::Borrowed(&server.cache_unit),
+        }
+    }
+
+    /// Cheap compared to the compression, hence this is the only step that needs the server to be locked
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+
+    pub fn compress(serialized: &[u8]) -> Option<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(serialized).ok()?;
+        encoder.finish().ok()
+    }
+
+    pub fn encode(&self) -> Option<Vec<u8>> {
+        ServerSnapshot::compress(&self.serialize()?)
+    }
+
+    /// Restores the live state on top of the state that was loaded from the database.
+    /// Instances that expired since the snapshot was taken are not restored.
+    pub fn restore(self, server: &mut Server) {
+        if self.server_id != server.server_id {
+            return;
+        }
+
+        let active_instances = &server.active_instances;
+        let is_active = |instance_key: &(u32, u32), instance_meta_id: u32| active_instances.get(instance_key).map(|unit_instance| unit_instance.instance_meta_id == instance_meta_id).unwrap_or(false);
+        let restored_instances = self
+            .active_instances
+            .into_owned()
+            .into_iter()
+            .filter(|(instance_key, unit_instance)| is_active(instance_key, unit_instance.instance_meta_id))
+            .map(|(instance_key, _)| instance_key)
+            .collect::<BTreeSet<(u32, u32)>>();
+        let active_instance_meta_ids = active_instances.values().map(|unit_instance| unit_instance.instance_meta_id).collect::<BTreeSet<u32>>();
+
+        // Events may have been saved after the snapshot was taken, hence only the events after the last saved one are restored
+        for (instance_key, mut committed_events) in self.committed_events.into_owned().into_iter().filter(|(instance_key, _)| restored_instances.contains(instance_key)) {
+            let saved_events_count = server.committed_events_count.get(&instance_key).cloned().unwrap_or(0);
+            committed_events.retain(|event| event.id >= saved_events_count);
+            server.committed_events.insert(instance_key, committed_events);
+        }
+        for (instance_key, committed_events_count) in self.committed_events_count.into_owned().into_iter().filter(|(instance_key, _)| restored_instances.contains(instance_key)) {
+            let saved_events_count = server.committed_events_count.entry(instance_key).or_insert(0);
+            *saved_events_count = (*saved_events_count).max(committed_events_count);
+        }
+        server.recently_committed_spell_cast_and_aura_applications = self
+            .recently_committed_spell_cast_and_aura_applications
+            .into_owned()
+            .into_iter()
+            .filter(|(instance_key, _)| restored_instances.contains(instance_key))
+            .collect();
+        server.active_attempts = self.active_attempts.into_owned().into_iter().filter(|(instance_key, _)| restored_instances.contains(instance_key)).collect();
+        server.infight_players = self.infight_players.into_owned().into_iter().filter(|(instance_key, _)| restored_instances.contains(instance_key)).collect();
+        server.merged_instances = self
+            .merged_instances
+            .into_owned()
+            .into_iter()
+            .filter(|(_, merged_instance)| active_instance_meta_ids.contains(&merged_instance.instance_meta_id))
+            .collect();
+
+        server.post_processing_last_precessed_event_id = self
+            .post_processing_last_precessed_event_id
+            .into_owned()
+            .into_iter()
+            .filter(|(instance_meta_id, _)| active_instance_meta_ids.contains(instance_meta_id))
+            .collect();
+        server.summons = self.summons.into_owned();
+        server.unit_instance_id = self.unit_instance_id.into_owned();
+        server.subject_prepend_mode_set = self.subject_prepend_mode_set.into_owned();
+        server.non_committed_events = self.non_committed_events.into_owned();
+        server.cache_unit = self.cache_unit.into_owned();
+    }
+}
+
+impl ServerSnapshot<'static> {
+    /// Returns None if the snapshot is corrupt or was written by another version
+    pub fn decode(content: &[u8]) -> Option<Self> {
+        let snapshot: ServerSnapshot<'static> = bincode::deserialize_from(GzDecoder::new(content)).ok()?;
+        if snapshot.version != SERVER_SNAPSHOT_VERSION {
+            return None;
+        }
+        Some(snapshot)
+    }
+}
@@ -0,0 +1,22 @@
+use crate::modules::live_data_processor::domain_value::TimeWindow;
+
+#[derive(Debug, Clone)]
+pub struct UploadMeta {
+    pub server_id: i32,
+    // UTC
+    pub start_time: u64,
+    pub end_time: u64,
+    // Minutes east of UTC of the uploader's clock
+    pub utc_offset: i32,
+    pub armory_content: Option<String>,
+}
+
+impl UploadMeta {
+    pub fn time_window(&self) -> TimeWindow {
+        TimeWindow {
+            start: self.start_time,
+            end: self.end_time,
+            utc_offset: self.utc_offset,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub member_id: u32,
+    pub server_id: i32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub utc_offset: i32,
+    pub num_chunks: u32,
+    pub received_chunks: BTreeSet<u32>,
+    pub armory_content: Option<String>,
+    pub last_update: u64,
+}
+
+impl UploadSession {
+    pub fn new(member_id: u32, server_id: i32, start_time: u64, end_time: u64, utc_offset: i32, num_chunks: u32, armory_content: Option<String>) -> Self {
+        UploadSession {
+            member_id,
+            server_id,
+            start_time,
+            end_time,
+            utc_offset,
+            num_chunks,
+            received_chunks: BTreeSet::new(),
+            armory_content,
+            last_update: time_util::now(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.received_chunks.len() as u32 == self.num_chunks
+    }
+}
@@ -0,0 +1,51 @@
+use crate::modules::live_data_processor::material::Attempt;
+
+#[test]
+fn active_time_excludes_dead_time() {
+    let mut attempt = Attempt::new(7, 1000, false);
+    attempt.set_player_active(1, 500);
+    attempt.set_player_active(2, 1000);
+    attempt.set_player_inactive(2, 4000);
+    // Resurrected and back in combat
+    attempt.set_player_active(2, 8000);
+    attempt.set_player_active(2, 9000);
+    // Late joiner
+    attempt.set_player_active(3, 6000);
+    attempt.end_ts = 11000;
+    attempt.finish_active_time();
+
+    assert_eq!(attempt.ranking_active_time.get(&1), Some(&10000));
+    assert_eq!(attempt.ranking_active_time.get(&2), Some(&6000));
+    assert_eq!(attempt.ranking_active_time.get(&3), Some(&5000));
+    assert!(attempt.active_since.is_empty());
+}
+
+#[test]
+fn inactive_player_has_no_active_time() {
+    let mut attempt = Attempt::new(7, 1000, false);
+    attempt.set_player_inactive(1, 2000);
+    attempt.end_ts = 5000;
+    attempt.finish_active_time();
+    assert!(attempt.ranking_active_time.is_empty());
+}
+
+#[test]
+fn briefly_active_player_is_not_ranked() {
+    let mut attempt = Attempt::new(7, 1000, false);
+    attempt.set_player_active(1, 1000);
+    // Joined shortly before the kill
+    attempt.set_player_active(2, 9000);
+    attempt.ranking_damage.insert(1, 5000);
+    attempt.ranking_damage.insert(2, 5000);
+    attempt.ranking_heal.insert(2, 300);
+    // Without active time
+    attempt.ranking_threat.insert(3, 100);
+    attempt.end_ts = 11000;
+    attempt.finish_active_time();
+    attempt.retain_ranked_characters(5000);
+
+    assert_eq!(attempt.ranking_damage.keys().cloned().collect::<Vec<u32>>(), vec![1]);
+    assert!(attempt.ranking_heal.is_empty());
+    assert!(attempt.ranking_threat.is_empty());
+    assert_eq!(attempt.ranking_active_time.get(&2), Some(&2000));
+}
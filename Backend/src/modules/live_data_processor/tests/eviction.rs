@@ -0,0 +1,157 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::{Creature, Unit, UnitInstance};
+use crate::modules::live_data_processor::dto;
+use crate::modules::live_data_processor::dto::{InstanceMap, Message, MessageType, Position};
+use crate::modules::live_data_processor::material::{Attempt, Server};
+use crate::params;
+use crate::tests::TestContainer;
+use crate::util::database::Select;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+fn server_with_instances() -> Server {
+    let mut server = Server::new(2, 2);
+    for (instance_meta_id, instance_id, member_id) in vec![(10, 1, 3), (11, 2, 3)] {
+        server.active_instances.insert(
+            (instance_id, member_id),
+            UnitInstance {
+                instance_meta_id,
+                entered: 0,
+                map_id: 249,
+                instance_id,
+                uploaded_user: member_id,
+            },
+        );
+        server.instance_participants.insert(instance_meta_id, vec![1, 2].into_iter().collect::<BTreeSet<u32>>());
+        server.active_attempts.insert((instance_id, member_id), vec![(7, Attempt::new(7, 0, false))].into_iter().collect::<HashMap<u32, Attempt>>());
+        server.infight_players.insert((instance_id, member_id), vec![1].into_iter().collect::<BTreeSet<u32>>());
+        server.committed_events.insert((instance_id, member_id), VecDeque::new());
+        server.committed_events_count.insert((instance_id, member_id), 1);
+
+        let unit_id = 100 + instance_id as u64;
+        server.unit_instance_id.insert(unit_id, instance_id);
+        server.summons.insert(unit_id, Unit::Creature(Creature { creature_id: 1, entry: 2, owner: None }));
+        server.cache_unit.insert(unit_id, Unit::Creature(Creature { creature_id: unit_id, entry: 3, owner: None }));
+    }
+    server
+}
+
+#[test]
+fn evict_instance_removes_instance_state() {
+    let mut server = server_with_instances();
+    server.evict_instance((1, 3), 10);
+
+    assert!(!server.active_instances.contains_key(&(1, 3)));
+    assert!(!server.instance_participants.contains_key(&10));
+    assert!(!server.active_attempts.contains_key(&(1, 3)));
+    assert!(!server.infight_players.contains_key(&(1, 3)));
+    assert!(!server.committed_events.contains_key(&(1, 3)));
+    assert!(!server.committed_events_count.contains_key(&(1, 3)));
+    assert!(!server.unit_instance_id.contains_key(&101));
+    assert!(!server.summons.contains_key(&101));
+    assert!(!server.cache_unit.contains_key(&101));
+    assert_eq!(server.num_evicted_instances, 1);
+
+    // The other instance is untouched
+    assert!(server.active_instances.contains_key(&(2, 3)));
+    assert!(server.instance_participants.contains_key(&11));
+    assert!(server.unit_instance_id.contains_key(&102));
+    assert!(server.cache_unit.contains_key(&102));
+}
+
+#[test]
+fn evict_instance_keeps_units_of_other_uploader() {
+    let mut server = server_with_instances();
+    server.active_instances.insert(
+        (1, 4),
+        UnitInstance {
+            instance_meta_id: 12,
+            entered: 0,
+            map_id: 249,
+            instance_id: 1,
+            uploaded_user: 4,
+        },
+    );
+    server.evict_instance((1, 3), 10);
+
+    assert!(server.unit_instance_id.contains_key(&101));
+    assert!(server.cache_unit.contains_key(&101));
+}
+
+#[test]
+fn state_sizes_count_nested_entries() {
+    let server = server_with_instances();
+    let state_sizes = server.get_state_sizes().into_iter().collect::<HashMap<&'static str, usize>>();
+    assert_eq!(state_sizes.get("active_instances"), Some(&2));
+    assert_eq!(state_sizes.get("instance_participants"), Some(&4));
+    assert_eq!(state_sizes.get("active_attempts"), Some(&2));
+    assert_eq!(state_sizes.get("infight_players"), Some(&2));
+    assert_eq!(state_sizes.get("cache_unit"), Some(&2));
+}
+
+#[test]
+fn finalized_instance_keeps_committed_events() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+    let storage_path = std::env::temp_dir().join("rpll_instance_storage").to_string_lossy().to_string();
+    std::env::set_var("INSTANCE_STORAGE_PATH", &storage_path);
+
+    let server_id = 7;
+    let instance_id = 14;
+    let _ = std::fs::remove_dir_all(format!("{}/{}", storage_path, server_id));
+    let mut server = Server::new(server_id, 1);
+    let unit = dto::Unit {
+        is_player: false,
+        unit_id: 0xF130000000000000 + 40,
+    };
+
+    let message = |message_count: u64, timestamp: u64, message_type: MessageType| Message {
+        message_count,
+        api_version: 0,
+        message_length: 0,
+        timestamp,
+        message_type,
+    };
+    let position = |unit: &dto::Unit| {
+        MessageType::Position(Position {
+            unit: unit.clone(),
+            x: 0,
+            y: 0,
+            z: 0,
+            orientation: 0,
+        })
+    };
+    let messages = vec![
+        message(
+            0,
+            0,
+            MessageType::InstanceMap(InstanceMap {
+                map_id: 249,
+                instance_id,
+                map_difficulty: 0,
+                unit: unit.clone(),
+            }),
+        ),
+        message(1, 1000, position(&unit)),
+        message(2, 2000, position(&unit)),
+        message(3, 3000, position(&unit)),
+        // The instance is deleted before the last position was committed
+        message(4, 4000, MessageType::InstanceDelete { instance_id }),
+    ];
+    assert!(server.parse_events(&mut conn, &Armory::default(), &Data::default(), messages, 1).is_ok());
+
+    assert!(server.active_instances.is_empty());
+    assert!(server.finalized_instances.is_empty());
+    assert!(server.committed_events.is_empty());
+    assert_eq!(server.num_evicted_instances, 1);
+
+    let instance_meta_id = conn
+        .select_wparams_value(
+            "SELECT id FROM instance_meta WHERE server_id=:server_id AND instance_id=:instance_id AND expired IS NOT NULL",
+            |mut row| row.take::<u32, usize>(0).unwrap(),
+            params!("server_id" => server_id, "instance_id" => instance_id),
+        )
+        .unwrap();
+    let saved_events = event_storage::read_events(&format!("{}/{}/{}", storage_path, server_id, instance_meta_id), 4).unwrap();
+    assert_eq!(saved_events.len(), 3);
+}
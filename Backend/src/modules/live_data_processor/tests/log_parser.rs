@@ -0,0 +1,48 @@
+use crate::modules::live_data_processor::domain_value::TimeWindow;
+use crate::modules::live_data_processor::tools::log_parser::{get_event_keyword, LogTimestampParser};
+
+#[test]
+fn event_keyword_of_combat_log_event() {
+    assert_eq!(get_event_keyword("SPELL_DAMAGE,0x0000000000000001,\"Foo\",0x511"), "SPELL_DAMAGE");
+    assert_eq!(get_event_keyword("UNIT_DIED"), "UNIT_DIED");
+}
+
+#[test]
+fn event_keyword_of_plain_text() {
+    assert_eq!(get_event_keyword("Foo hits Bar for 12."), "UNKNOWN_TEXT");
+    assert_eq!(get_event_keyword(""), "UNKNOWN_TEXT");
+}
+
+#[test]
+fn log_timestamp_rolls_over_new_year() {
+    // 31.12.2020 22:00 UTC until 01.01.2021 02:00 UTC
+    let mut parser = LogTimestampParser::new(TimeWindow {
+        start: 1609452000000,
+        end: 1609466400000,
+        utc_offset: 0,
+    });
+    assert_eq!(parser.parse("12/31 23:59:59.000"), Some(1609459199000));
+    assert_eq!(parser.parse("1/1 00:00:01.000"), Some(1609459201000));
+}
+
+#[test]
+fn log_timestamp_year_of_window_start() {
+    // A log that starts after midnight belongs to the year of the window start
+    let mut parser = LogTimestampParser::new(TimeWindow {
+        start: 1609452000000,
+        end: 1609466400000,
+        utc_offset: 0,
+    });
+    assert_eq!(parser.parse("1/1 00:00:01.000"), Some(1609459201000));
+}
+
+#[test]
+fn log_timestamp_converted_to_utc() {
+    let mut parser = LogTimestampParser::new(TimeWindow {
+        start: 1609452000000,
+        end: 1609466400000,
+        utc_offset: 60,
+    });
+    assert_eq!(parser.parse("12/31 23:59:59.000"), Some(1609455599000));
+    assert!(parser.parse("12/31 23:59").is_none());
+}
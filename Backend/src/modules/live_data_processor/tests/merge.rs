@@ -0,0 +1,170 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::{Creature, Event, EventType, Unit};
+use crate::modules::live_data_processor::dto;
+use crate::modules::live_data_processor::dto::{InstanceMap, Message, MessageType, Position};
+use crate::modules::live_data_processor::material::{MergedInstance, Server};
+use crate::modules::live_data_processor::tools::server::event_fingerprint;
+use crate::params;
+use crate::tests::TestContainer;
+use crate::util::database::Select;
+use std::collections::BTreeSet;
+
+fn cause_event() -> Box<Event> {
+    Box::new(Event::new(0, 0, Unit::Creature(Creature { creature_id: 1, entry: 2, owner: None }), EventType::ThreatWipe))
+}
+
+fn interrupt() -> EventType {
+    EventType::Interrupt {
+        cause_event: cause_event(),
+        interrupted_spell_id: 42,
+    }
+}
+
+fn dispel() -> EventType {
+    EventType::Dispel {
+        cause_event: cause_event(),
+        target_event: cause_event(),
+    }
+}
+
+fn position_message(message_count: u64, timestamp: u64, unit_id: u64, x: i32) -> Message {
+    Message {
+        message_count,
+        api_version: 0,
+        message_length: 0,
+        timestamp,
+        message_type: MessageType::Position(Position {
+            unit: dto::Unit { is_player: false, unit_id },
+            x,
+            y: 0,
+            z: 0,
+            orientation: 0,
+        }),
+    }
+}
+
+fn upload_messages(unit_id: u64, instance_id: u32, start_ts: u64, positions: &[(u64, i32)]) -> Vec<Message> {
+    let mut messages = vec![Message {
+        message_count: 0,
+        api_version: 0,
+        message_length: 0,
+        timestamp: start_ts,
+        message_type: MessageType::InstanceMap(InstanceMap {
+            map_id: 249,
+            instance_id,
+            map_difficulty: 0,
+            unit: dto::Unit { is_player: false, unit_id },
+        }),
+    }];
+    for (index, (timestamp, x)) in positions.iter().enumerate() {
+        messages.push(position_message(index as u64 + 1, *timestamp, unit_id, *x));
+    }
+    // Events are committed once the subject's next event arrives
+    messages.push(position_message(positions.len() as u64 + 1, start_ts + 600000, unit_id, 0));
+    messages
+}
+
+#[test]
+fn event_fingerprint_ignores_event_ids() {
+    let (event_id, timestamp, fingerprint) = event_fingerprint(&interrupt(), "[12,1000,7,[1,2,3],[4,5,6],100]").unwrap();
+    assert_eq!(event_id, 12);
+    assert_eq!(timestamp, 1000);
+
+    let (_, _, other_fingerprint) = event_fingerprint(&interrupt(), "[40,1200,31,[1,2,3],[4,5,6],100]").unwrap();
+    assert_eq!(fingerprint, other_fingerprint);
+}
+
+#[test]
+fn event_fingerprint_differs_by_content() {
+    let (_, _, fingerprint) = event_fingerprint(&interrupt(), "[12,1000,7,[1,2,3],[4,5,6],100]").unwrap();
+    let (_, _, other_amount) = event_fingerprint(&interrupt(), "[12,1000,7,[1,2,3],[4,5,6],101]").unwrap();
+    let (_, _, other_event_type) = event_fingerprint(&EventType::ThreatWipe, "[12,1000,7,[1,2,3],[4,5,6],100]").unwrap();
+    assert_ne!(fingerprint, other_amount);
+    assert_ne!(fingerprint, other_event_type);
+}
+
+#[test]
+fn event_fingerprint_invalid_line() {
+    assert!(event_fingerprint(&EventType::ThreatWipe, "").is_none());
+    assert!(event_fingerprint(&EventType::ThreatWipe, "[a,1000,[1,2,3]]").is_none());
+    assert!(event_fingerprint(&dispel(), "[1,1000,5]").is_none());
+}
+
+#[test]
+fn merged_instance_takes_closest_saved_event() {
+    let mut merged_instance = MergedInstance::new(1, 2);
+    merged_instance.insert_saved_event(42, 1000, 1);
+    merged_instance.insert_saved_event(42, 1600, 2);
+
+    assert_eq!(merged_instance.take_saved_event(42, 1500), Some(2));
+    assert_eq!(merged_instance.take_saved_event(42, 1500), Some(1));
+    assert_eq!(merged_instance.take_saved_event(42, 1500), None);
+}
+
+#[test]
+fn merged_instance_respects_tolerance() {
+    let mut merged_instance = MergedInstance::new(1, 2);
+    merged_instance.insert_saved_event(42, 1000, 1);
+
+    assert_eq!(merged_instance.take_saved_event(42, 2001), None);
+    assert_eq!(merged_instance.take_saved_event(43, 1000), None);
+    assert_eq!(merged_instance.take_saved_event(42, 2000), Some(1));
+}
+
+#[test]
+fn merged_instance_prunes_old_events() {
+    let mut merged_instance = MergedInstance::new(1, 2);
+    merged_instance.insert_saved_event(42, 1000, 1);
+    merged_instance.insert_saved_event(42, 5000, 2);
+    merged_instance.map_event_id(3, 1, 1000);
+
+    merged_instance.prune(200000);
+    assert_eq!(merged_instance.take_saved_event(42, 1000), None);
+    assert_eq!(merged_instance.get_mapped_event_id(3), None);
+}
+
+#[test]
+fn merged_upload_saves_only_new_events() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+    let storage_path = std::env::temp_dir().join("rpll_instance_storage").to_string_lossy().to_string();
+    std::env::set_var("INSTANCE_STORAGE_PATH", &storage_path);
+
+    let server_id = 6;
+    let instance_id = 13;
+    let _ = std::fs::remove_dir_all(format!("{}/{}", storage_path, server_id));
+    let armory = Armory::default();
+    let data = Data::default();
+    let unit_id = 0xF130000000000000 + 40;
+
+    // The first uploader creates the instance
+    let mut server = Server::new(server_id, 1);
+    let messages = upload_messages(unit_id, instance_id, 1000, &[(1000, 1), (2000, 2), (3000, 3)]);
+    assert!(server.parse_events(&mut conn, &armory, &data, messages, 1).is_ok());
+
+    // The second uploader logged the same events with a clock that is slightly off, as well as one event that the first one missed
+    let mut server = Server::new(server_id, 1);
+    let messages = upload_messages(unit_id, instance_id, 1300, &[(1300, 1), (1800, 99), (2300, 2), (3300, 3)]);
+    assert!(server.parse_events(&mut conn, &armory, &data, messages, 2).is_ok());
+
+    let instance_meta_ids = conn.select_wparams(
+        "SELECT id FROM instance_meta WHERE server_id=:server_id AND instance_id=:instance_id",
+        |mut row| row.take::<u32, usize>(0).unwrap(),
+        params!("server_id" => server_id, "instance_id" => instance_id),
+    );
+    assert_eq!(instance_meta_ids.len(), 1);
+
+    let instance_path = format!("{}/{}/{}", storage_path, server_id, instance_meta_ids[0]);
+    let saved_events = event_storage::read_events(&instance_path, 4).unwrap();
+    assert_eq!(saved_events.len(), 4);
+    assert_eq!(saved_events.iter().map(|(event_id, _)| *event_id).collect::<BTreeSet<u32>>().len(), 4);
+    assert_eq!(
+        saved_events
+            .iter()
+            .filter_map(|(_, line)| event_fingerprint(&EventType::ThreatWipe, line))
+            .map(|(_, timestamp, _)| timestamp)
+            .collect::<BTreeSet<u64>>(),
+        vec![1000, 1800, 2000, 3000].into_iter().collect()
+    );
+}
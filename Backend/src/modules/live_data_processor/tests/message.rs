@@ -1,4 +1,4 @@
-use crate::modules::live_data_processor::dto::MessageType;
+use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, MessageType};
 use crate::modules::live_data_processor::tools::MessageParser;
 
 #[test]
@@ -45,7 +45,7 @@ fn parse_message_negative_invalid_length() {
     let message = message_vec.parse_message();
 
     // Assert
-    assert!(message.is_err());
+    assert_eq!(message, Err(LiveDataProcessorFailure::TruncatedMessage));
 }
 
 #[test]
@@ -1,4 +1,4 @@
-use crate::modules::live_data_processor::dto::MessageType;
+use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, MessageType};
 use crate::modules::live_data_processor::tools::payload_mapper::MapMessageType;
 
 #[test]
@@ -37,5 +37,18 @@ fn test_map_message_type_negative() {
     let message_type = message_type_number.to_message_type(&payload);
 
     // Assert
-    assert!(message_type.is_err());
+    assert_eq!(message_type, Err(LiveDataProcessorFailure::UnknownMessageType(255)));
+}
+
+#[test]
+fn test_map_message_type_malformed_payload() {
+    // Arrange
+    let payload = vec![1, 2, 3];
+    let message_type_number: u8 = 1;
+
+    // Act
+    let message_type = message_type_number.to_message_type(&payload);
+
+    // Assert
+    assert_eq!(message_type, Err(LiveDataProcessorFailure::MalformedPayload(1)));
 }
@@ -1,7 +1,13 @@
+mod attempt;
 mod byte_reader;
+mod eviction;
 mod guid;
+mod log_parser;
+mod merge;
 mod message;
 mod message_type;
 mod payload_mapper;
 mod server;
+mod server_snapshot;
 mod try_parse_interrupt;
+mod upload;
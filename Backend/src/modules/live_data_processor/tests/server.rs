@@ -1,8 +1,10 @@
+use crate::modules::armory::tools::GetCharacter;
 use crate::modules::armory::Armory;
 use crate::modules::data::Data;
-use crate::modules::live_data_processor::dto::{DamageComponent, DamageDone, InstanceMap, Message, MessageType, Position, SpellCast, Unit};
+use crate::modules::live_data_processor::dto::{DamageComponent, DamageDone, InstanceMap, Message, MessageType, Position, SpellCast, Summon, Unit};
 use crate::modules::live_data_processor::material::Server;
 use crate::tests::TestContainer;
+use crate::util::database::Select;
 
 #[test]
 #[ignore]
@@ -161,3 +163,72 @@ fn parse_spell_damage() {
     assert_eq!(server.committed_events.get(&caster_instance_id).unwrap().len(), 5);
     assert!(!server.non_committed_events.contains_key(&caster_unit_id));
 }
+
+#[test]
+fn dry_run_events_persists_nothing() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+    let count = |conn: &mut crate::mysql::Conn, table: &str| conn.select_value(&format!("SELECT COUNT(*) FROM {}", table), |mut row| row.take::<u64, usize>(0).unwrap()).unwrap();
+    let num_characters = count(&mut conn, "armory_character");
+    let num_instances = count(&mut conn, "instance_meta");
+
+    let server_id = 5;
+    let mut server = Server::new(server_id, 3);
+    let armory = Armory::default();
+    let data = Data::default();
+
+    let player = Unit { is_player: true, unit_id: 42 };
+    let mut messages = vec![Message {
+        message_count: 0,
+        api_version: 0,
+        message_length: 0,
+        timestamp: 0,
+        message_type: MessageType::InstanceMap(InstanceMap {
+            map_id: 249,
+            instance_id: 7,
+            map_difficulty: 0,
+            unit: player.clone(),
+        }),
+    }];
+    for (message_count, timestamp) in vec![(1, 0), (2, 10), (3, 5000)] {
+        messages.push(Message {
+            message_count,
+            api_version: 0,
+            message_length: 0,
+            timestamp,
+            message_type: MessageType::Position(Position {
+                unit: player.clone(),
+                x: 0,
+                y: 0,
+                z: 0,
+                orientation: 0,
+            }),
+        });
+    }
+
+    // The owner of a summon is resolved as well
+    let owner = Unit { is_player: true, unit_id: 43 };
+    messages.push(Message {
+        message_count: 4,
+        api_version: 0,
+        message_length: 0,
+        timestamp: 5000,
+        message_type: MessageType::Summon(Summon {
+            owner: owner.clone(),
+            unit: Unit {
+                is_player: false,
+                unit_id: 0xF130000000000000 + 40,
+            },
+        }),
+    });
+
+    let commit_outcomes = server.dry_run_events(&armory, &data, messages);
+    assert!(commit_outcomes.committed > 0);
+    assert!(server.committed_events.is_empty());
+
+    // The unknown player got a placeholder instead of a new character
+    assert!(armory.get_character_id_by_uid(server_id, player.unit_id).is_none());
+    assert!(armory.get_character_id_by_uid(server_id, owner.unit_id).is_none());
+    assert_eq!(count(&mut conn, "armory_character"), num_characters);
+    assert_eq!(count(&mut conn, "instance_meta"), num_instances);
+}
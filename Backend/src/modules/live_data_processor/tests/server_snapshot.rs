@@ -0,0 +1,88 @@
+use crate::modules::live_data_processor::domain_value::{Creature, Event, EventType, Unit, UnitInstance};
+use crate::modules::live_data_processor::material::{Attempt, Server, ServerSnapshot, SERVER_SNAPSHOT_VERSION};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+fn unit_instance(instance_meta_id: u32, instance_id: u32, member_id: u32) -> UnitInstance {
+    UnitInstance {
+        instance_meta_id,
+        entered: 0,
+        map_id: 249,
+        instance_id,
+        uploaded_user: member_id,
+    }
+}
+
+fn event(id: u32) -> Event {
+    let mut event = Event::new(0, id as u64 * 1000, Unit::Creature(Creature { creature_id: 1, entry: 2, owner: None }), EventType::ThreatWipe);
+    event.id = id;
+    event
+}
+
+fn live_server() -> Server {
+    let mut server = Server::new(2, 2);
+    server.active_instances.insert((1, 3), unit_instance(10, 1, 3));
+    server.active_instances.insert((2, 3), unit_instance(11, 2, 3));
+    server.committed_events_count.insert((1, 3), 6);
+    server.committed_events.insert((1, 3), (3..6).map(event).collect::<VecDeque<Event>>());
+    server.active_attempts.insert((1, 3), vec![(7, Attempt::new(7, 1000, false))].into_iter().collect::<HashMap<u32, Attempt>>());
+    server.active_attempts.insert((2, 3), vec![(8, Attempt::new(8, 1000, false))].into_iter().collect::<HashMap<u32, Attempt>>());
+    server.infight_players.insert((1, 3), vec![5].into_iter().collect::<BTreeSet<u32>>());
+    server.infight_players.insert((2, 3), vec![6].into_iter().collect::<BTreeSet<u32>>());
+    server.summons.insert(42, Unit::Creature(Creature { creature_id: 43, entry: 44, owner: None }));
+    server.unit_instance_id.insert(42, 1);
+    server.post_processing_last_precessed_event_id.insert(10, 4);
+    server.post_processing_last_precessed_event_id.insert(11, 2);
+    server
+}
+
+#[test]
+fn server_snapshot_round_trip() {
+    let live_server = live_server();
+    let snapshot = ServerSnapshot::new(&live_server, 1234);
+    let decoded = ServerSnapshot::decode(&snapshot.encode().unwrap()).unwrap();
+    assert_eq!(decoded.version, SERVER_SNAPSHOT_VERSION);
+    assert_eq!(decoded.server_id, 2);
+    assert_eq!(decoded.created, 1234);
+    assert_eq!(decoded.committed_events.get(&(1, 3)).unwrap().len(), 3);
+    assert_eq!(decoded.summons.len(), 1);
+}
+
+#[test]
+fn server_snapshot_rejects_corrupt_content() {
+    assert!(ServerSnapshot::decode(&[]).is_none());
+    assert!(ServerSnapshot::decode(&[31, 139, 8, 0]).is_none());
+}
+
+#[test]
+fn server_snapshot_restores_active_instances_only() {
+    let live_server = live_server();
+    let snapshot = ServerSnapshot::new(&live_server, 0);
+
+    // The instance (2, 3) expired in the meantime and events up to id 4 were saved already
+    let mut server = Server::new(2, 2);
+    server.active_instances.insert((1, 3), unit_instance(10, 1, 3));
+    server.committed_events_count.insert((1, 3), 5);
+    snapshot.restore(&mut server);
+
+    assert_eq!(server.committed_events_count.get(&(1, 3)), Some(&6));
+    assert_eq!(server.committed_events.get(&(1, 3)).unwrap().iter().map(|event| event.id).collect::<Vec<u32>>(), vec![5]);
+    assert!(server.active_attempts.contains_key(&(1, 3)));
+    assert!(!server.active_attempts.contains_key(&(2, 3)));
+    assert!(server.infight_players.contains_key(&(1, 3)));
+    assert!(!server.infight_players.contains_key(&(2, 3)));
+    assert_eq!(server.unit_instance_id.get(&42), Some(&1));
+    assert!(server.summons.contains_key(&42));
+    assert_eq!(server.post_processing_last_precessed_event_id.get(&10), Some(&4));
+    assert!(!server.post_processing_last_precessed_event_id.contains_key(&11));
+}
+
+#[test]
+fn server_snapshot_ignores_other_server() {
+    let live_server = live_server();
+    let snapshot = ServerSnapshot::new(&live_server, 0);
+    let mut server = Server::new(3, 2);
+    server.active_instances.insert((1, 3), unit_instance(10, 1, 3));
+    snapshot.restore(&mut server);
+    assert!(server.summons.is_empty());
+    assert!(server.committed_events.is_empty());
+}
@@ -0,0 +1,327 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::{ArchiveFormat, UploadJobStatus};
+use crate::modules::live_data_processor::dto;
+use crate::modules::live_data_processor::dto::{CreateUploadSession, InstanceMap, LiveDataProcessorFailure, Message, MessageType, Position};
+use crate::modules::live_data_processor::material::{CombatLogArchive, UploadSession};
+use crate::modules::live_data_processor::tools::{parse_upload_time, ProcessMessages, UploadJobs, UploadSessions};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::mysql::Opts;
+use crate::tests::TestContainer;
+use std::io::Write;
+use std::sync::Arc;
+
+// Each test gets its own storage, as restoring the sessions removes what it can't restore
+fn get_upload_storage_path() -> String {
+    std::env::temp_dir().join(format!("rpll_upload_storage_{}", str_util::random::alphanumeric(16))).to_string_lossy().to_string()
+}
+
+fn get_create_upload_session(num_chunks: u32) -> CreateUploadSession {
+    CreateUploadSession {
+        server_id: 5,
+        start_time: "24.12.20 08:30 PM".to_string(),
+        end_time: "24.12.20 11:30 PM".to_string(),
+        utc_offset: None,
+        num_chunks,
+        payload_armory: None,
+    }
+}
+
+#[test]
+fn parse_upload_time_valid() {
+    let result = parse_upload_time("24.12.20 08:30 PM", 0);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap(), 1608841800000);
+}
+
+#[test]
+fn parse_upload_time_utc_offset() {
+    assert_eq!(parse_upload_time("24.12.20 08:30 PM", 60).unwrap(), 1608841800000 - 60 * 60 * 1000);
+    assert_eq!(parse_upload_time("24.12.20 08:30 PM", -300).unwrap(), 1608841800000 + 300 * 60 * 1000);
+    assert_eq!(parse_upload_time("24.12.20 08:30 PM", 15 * 60), Err(LiveDataProcessorFailure::InvalidUtcOffset(15 * 60)));
+}
+
+#[test]
+fn parse_upload_time_invalid() {
+    assert_eq!(parse_upload_time("2020-12-24 20:30", 0), Err(LiveDataProcessorFailure::InvalidTimeFormat("2020-12-24 20:30".to_string())));
+}
+
+#[test]
+fn upload_session_is_complete() {
+    let mut session = UploadSession::new(1, 5, 0, 1, 0, 2, None);
+    assert!(!session.is_complete());
+    session.received_chunks.insert(1);
+    assert!(!session.is_complete());
+    session.received_chunks.insert(0);
+    assert!(session.is_complete());
+}
+
+#[test]
+fn upload_session_survives_failed_finalize_and_restart() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+    let storage_path = get_upload_storage_path();
+    let orphan_path = format!("{}/sessions/{}", storage_path, str_util::random::alphanumeric(32));
+    std::fs::create_dir_all(&orphan_path).unwrap();
+
+    let live_data_processor = LiveDataProcessor::default().with_upload_storage(storage_path.clone());
+    let upload_id = live_data_processor.open_upload_session(1, get_create_upload_session(2)).unwrap().upload_id;
+    assert!(live_data_processor.store_upload_chunk(1, &upload_id, 0, "12/31 23:59:59.000  UNIT_DIED\n".as_bytes()).is_ok());
+    assert!(live_data_processor.store_upload_chunk(1, &upload_id, 1, "1/1 00:00:01.000  UNIT_DIED\n".as_bytes()).is_ok());
+
+    // The chunk vanished, e.g. because the disk was cleaned up
+    std::fs::remove_file(format!("{}/sessions/{}/1", storage_path, upload_id)).unwrap();
+    assert!(matches!(live_data_processor.finalize_upload_session(&mut conn, 1, &upload_id), Err(LiveDataProcessorFailure::StorageFailure(_))));
+    // The session and the remaining chunks are kept, so the chunk can be uploaded again
+    assert_eq!(live_data_processor.get_upload_session_progress(1, &upload_id).unwrap().received_chunks, vec![0]);
+
+    let restarted = LiveDataProcessor::default().with_upload_storage(storage_path.clone()).restore_upload_sessions();
+    let progress = restarted.get_upload_session_progress(1, &upload_id).unwrap();
+    assert_eq!(progress.num_chunks, 2);
+    assert_eq!(progress.received_chunks, vec![0]);
+    assert!(restarted.get_upload_session_progress(2, &upload_id).is_err());
+    // Chunks without a session are removed
+    assert!(!std::path::Path::new(&orphan_path).exists());
+    let _ = std::fs::remove_dir_all(&storage_path);
+}
+
+#[test]
+fn upload_worker_keeps_processing_failed_jobs() {
+    let container = TestContainer::new(false);
+    let (mut conn, dns, _node) = container.run();
+    let storage_path = get_upload_storage_path();
+
+    let data = Arc::new(Data::default().init(&mut conn));
+    let armory = Armory::default().init(&mut conn);
+    let live_data_processor = LiveDataProcessor::default()
+        .with_upload_storage(storage_path.clone())
+        .init(&mut conn)
+        .init_upload_workers(Opts::from_url(&dns).unwrap(), data, &armory, 1);
+
+    // Neither retail log contains anything to detect the server from
+    let mut job_ids = Vec::new();
+    for _ in 0..2 {
+        let session = CreateUploadSession { server_id: -1, ..get_create_upload_session(1) };
+        let upload_id = live_data_processor.open_upload_session(1, session).unwrap().upload_id;
+        assert!(live_data_processor.store_upload_chunk(1, &upload_id, 0, "12/31 23:59:59.000  UNIT_DIED\n".as_bytes()).is_ok());
+        job_ids.push(live_data_processor.finalize_upload_session(&mut conn, 1, &upload_id).unwrap().job_id);
+    }
+
+    for job_id in job_ids.iter() {
+        let mut job = live_data_processor.get_upload_job(&mut conn, 1, job_id).unwrap();
+        for _ in 0..300 {
+            if job.status == UploadJobStatus::Done || job.status == UploadJobStatus::Failed {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            job = live_data_processor.get_upload_job(&mut conn, 1, job_id).unwrap();
+        }
+        assert_eq!(job.status, UploadJobStatus::Failed);
+        assert!(job.failure_reason.map(|reason| !reason.is_empty() && reason.chars().count() <= 255).unwrap_or(false));
+    }
+    let _ = std::fs::remove_dir_all(&storage_path);
+}
+
+#[test]
+fn server_is_rebuilt_after_a_panicking_job() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+    let storage_path = std::env::temp_dir().join("rpll_instance_storage").to_string_lossy().to_string();
+    std::env::set_var("INSTANCE_STORAGE_PATH", &storage_path);
+
+    let server_id = 1;
+    let _ = std::fs::remove_dir_all(format!("{}/{}", storage_path, server_id));
+    let live_data_processor = LiveDataProcessor::default().init(&mut conn);
+
+    // The job panics while it commits into the server
+    let me = live_data_processor.clone();
+    let job = std::thread::spawn(move || {
+        let servers = me.servers.read().unwrap();
+        let _server = servers.get(&server_id).unwrap().write().unwrap();
+        panic!("Panicked while committing");
+    });
+    assert!(job.join().is_err());
+    assert!(live_data_processor.servers.read().unwrap().get(&server_id).unwrap().is_poisoned());
+
+    // Neither the metrics nor the next upload trip over the poisoned server
+    live_data_processor.update_server_metrics();
+    let unit = dto::Unit {
+        is_player: false,
+        unit_id: 0xF130000000000000 + 40,
+    };
+    let message = |message_count: u64, timestamp: u64, message_type: MessageType| Message {
+        message_count,
+        api_version: 0,
+        message_length: 0,
+        timestamp,
+        message_type,
+    };
+    let messages = vec![
+        message(
+            0,
+            0,
+            MessageType::InstanceMap(InstanceMap {
+                map_id: 249,
+                instance_id: 15,
+                map_difficulty: 0,
+                unit: unit.clone(),
+            }),
+        ),
+        message(1, 1000, MessageType::Position(Position { unit, x: 0, y: 0, z: 0, orientation: 0 })),
+    ];
+    assert!(live_data_processor.process_messages(&mut conn, server_id, &Armory::default(), &Data::default(), messages, 1).is_ok());
+
+    let servers = live_data_processor.servers.read().unwrap();
+    let server = servers.get(&server_id).unwrap();
+    assert!(!server.is_poisoned());
+    assert!(server.read().unwrap().active_instances.contains_key(&(15, 1)));
+}
+
+#[test]
+fn upload_job_status_round_trip() {
+    for status in [UploadJobStatus::Queued, UploadJobStatus::Parsing, UploadJobStatus::Committing, UploadJobStatus::Done, UploadJobStatus::Failed].iter() {
+        assert_eq!(UploadJobStatus::from_u8(status.to_u8()), Some(*status));
+    }
+    assert!(UploadJobStatus::from_u8(5).is_none());
+}
+
+#[test]
+fn combat_log_archive_streams_lines() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.zip", str_util::random::alphanumeric(16)));
+    {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file("WoWCombatLog.txt", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"12/31 23:59:59.000  UNIT_DIED\r\n1/1 00:00:01.000  UNIT_DIED\r\n").unwrap();
+        zip.finish().unwrap();
+    }
+
+    let mut lines = Vec::new();
+    let num_lines = CombatLogArchive::new(&path).for_each_line(|line| {
+        lines.push(line.to_string());
+        Ok(())
+    });
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(num_lines.ok(), Some(2));
+    assert_eq!(lines, vec!["12/31 23:59:59.000  UNIT_DIED\r".to_string(), "1/1 00:00:01.000  UNIT_DIED\r".to_string()]);
+}
+
+#[test]
+fn combat_log_archive_accepts_plain_text() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.txt", str_util::random::alphanumeric(16)));
+    std::fs::write(&path, "12/31 23:59:59.000  UNIT_DIED\n").unwrap();
+    let archive = CombatLogArchive::new(&path);
+    let format = archive.get_format();
+    let num_lines = archive.for_each_line(|_| Ok(()));
+    let armory = archive.read_armory();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(format.ok(), Some(ArchiveFormat::PlainText));
+    assert_eq!(num_lines.ok(), Some(1));
+    assert_eq!(armory.ok(), Some(None));
+}
+
+#[test]
+fn combat_log_archive_picks_entries_by_name() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.zip", str_util::random::alphanumeric(16)));
+    {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file("readme.md", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"Not a combat log\n").unwrap();
+        zip.start_file("Logs/WoWCombatLog.txt", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"12/31 23:59:59.000  UNIT_DIED\n").unwrap();
+        zip.start_file("SavedVariables/RPLLWotLKArmoryCollector.lua", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"RPLL_PlayerInformation = {}").unwrap();
+        zip.finish().unwrap();
+    }
+
+    let archive = CombatLogArchive::new(&path);
+    let mut lines = Vec::new();
+    let num_lines = archive.for_each_line(|line| {
+        lines.push(line.to_string());
+        Ok(())
+    });
+    let armory = archive.read_armory();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(num_lines.ok(), Some(1));
+    assert_eq!(lines, vec!["12/31 23:59:59.000  UNIT_DIED".to_string()]);
+    assert_eq!(armory.ok(), Some(Some("RPLL_PlayerInformation = {}".to_string())));
+}
+
+#[test]
+fn combat_log_archive_streams_gzip() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.txt.gz", str_util::random::alphanumeric(16)));
+    {
+        let mut encoder = flate2::write::GzEncoder::new(std::fs::File::create(&path).unwrap(), flate2::Compression::default());
+        encoder.write_all(b"12/31 23:59:59.000  UNIT_DIED\n1/1 00:00:01.000  UNIT_DIED\n").unwrap();
+        encoder.finish().unwrap();
+    }
+
+    let archive = CombatLogArchive::new(&path);
+    let format = archive.get_format();
+    let num_lines = archive.for_each_line(|_| Ok(()));
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(format.ok(), Some(ArchiveFormat::Gzip));
+    assert_eq!(num_lines.ok(), Some(2));
+}
+
+#[test]
+fn combat_log_archive_streams_tar_gz() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.tar.gz", str_util::random::alphanumeric(16)));
+    {
+        let encoder = flate2::write::GzEncoder::new(std::fs::File::create(&path).unwrap(), flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+        for (name, content) in [("RPLLTBCArmoryCollector.lua", "RPLL_PlayerInformation = {}"), ("WoWCombatLog.txt", "12/31 23:59:59.000  UNIT_DIED\n")].iter() {
+            let mut header = tar::Header::new_ustar();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            tar.append_data(&mut header, name, content.as_bytes()).unwrap();
+        }
+        tar.into_inner().unwrap().finish().unwrap();
+    }
+
+    let archive = CombatLogArchive::new(&path);
+    let num_lines = archive.for_each_line(|_| Ok(()));
+    let armory = archive.read_armory();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(num_lines.ok(), Some(1));
+    assert_eq!(armory.ok(), Some(Some("RPLL_PlayerInformation = {}".to_string())));
+}
+
+#[test]
+fn combat_log_archive_without_combat_log() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.zip", str_util::random::alphanumeric(16)));
+    {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file("readme.md", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"Not a combat log\n").unwrap();
+        zip.start_file("notes.md", zip::write::FileOptions::default()).unwrap();
+        zip.write_all(b"Neither\n").unwrap();
+        zip.finish().unwrap();
+    }
+
+    let result = CombatLogArchive::new(&path).for_each_line(|_| Ok(()));
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(result, Err(LiveDataProcessorFailure::NoCombatLogInArchive));
+}
+
+#[test]
+fn combat_log_archive_reports_invalid_encoding() {
+    let path = std::env::temp_dir().join(format!("combat_log_archive_{}.txt", str_util::random::alphanumeric(16)));
+    std::fs::write(&path, b"12/31 23:59:59.000  UNIT_DIED\n12/31 23:59:59.500  \xFF\xFE\n").unwrap();
+    let result = CombatLogArchive::new(&path).for_each_line(|_| Ok(()));
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(result, Err(LiveDataProcessorFailure::InvalidEncoding(2)));
+}
+
+#[test]
+fn archive_format_detect() {
+    assert_eq!(ArchiveFormat::detect(&[0x50, 0x4B, 0x03, 0x04]), ArchiveFormat::Zip);
+    assert_eq!(ArchiveFormat::detect(&[0x1F, 0x8B, 0x08, 0x00]), ArchiveFormat::Gzip);
+    assert_eq!(ArchiveFormat::detect(&[0x28, 0xB5, 0x2F, 0xFD]), ArchiveFormat::Zstd);
+    assert_eq!(ArchiveFormat::detect(b"12/3"), ArchiveFormat::PlainText);
+    assert_eq!(ArchiveFormat::detect(&[]), ArchiveFormat::PlainText);
+}
@@ -5,6 +5,7 @@ use crate::modules::live_data_processor::material::{ActiveMapVec, Participant};
 
 pub trait CombatLogParser {
     fn parse_cbl_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Option<Vec<MessageType>>;
+    // Called for consecutive chunks of the log, once all lines have been parsed
     fn do_message_post_processing(&mut self, data: &Data, messages: &mut Vec<Message>);
     // Server that need to be created have Id=0!
     fn get_involved_server(&self) -> Option<Vec<(u32, String, String)>>;
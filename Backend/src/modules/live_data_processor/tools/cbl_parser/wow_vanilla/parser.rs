@@ -1082,22 +1082,6 @@ impl CombatLogParser for WoWVanillaParser {
             };
         }
 
-        // And create pet summon events
-        let mut summon_events: Vec<Message> = Vec::with_capacity(40);
-        for (pet_unit_id, owner_unit_id) in self.pet_owner.iter() {
-            summon_events.push(Message::new_parsed(
-                0,
-                0,
-                MessageType::Summon(Summon {
-                    owner: Unit { is_player: true, unit_id: *owner_unit_id },
-                    unit: Unit {
-                        is_player: false,
-                        unit_id: 0xF14000FFFF000000 + (*pet_unit_id | 0x0000000000FFFFFF),
-                    },
-                }),
-            ));
-        }
-
         // Find caster of aura applications
         // For a gain its usually ~100ms apart, else we assume its the target
         // There are also group buffs to consider, like Greater Blessings, GMotW, Fortitude, Shouts
@@ -1151,7 +1135,6 @@ impl CombatLogParser for WoWVanillaParser {
             }
         }
 
-        messages.append(&mut summon_events);
         messages.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
     }
 
@@ -1325,7 +1308,25 @@ impl CombatLogParser for WoWVanillaParser {
     }
 
     fn get_bonus_messages(&self) -> Option<Vec<Message>> {
-        None
+        // Pet summon events
+        Some(
+            self.pet_owner
+                .iter()
+                .map(|(pet_unit_id, owner_unit_id)| {
+                    Message::new_parsed(
+                        0,
+                        0,
+                        MessageType::Summon(Summon {
+                            owner: Unit { is_player: true, unit_id: *owner_unit_id },
+                            unit: Unit {
+                                is_player: false,
+                                unit_id: 0xF14000FFFF000000 + (*pet_unit_id | 0x0000000000FFFFFF),
+                            },
+                        }),
+                    )
+                })
+                .collect(),
+        )
     }
 }
 
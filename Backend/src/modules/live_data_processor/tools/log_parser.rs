@@ -1,79 +1,67 @@
+use crate::modules::armory::dto::CharacterDto;
 use crate::modules::armory::tools::{GetCharacter, SetCharacter};
 use crate::modules::armory::Armory;
 use crate::modules::data::tools::{RetrieveNPC, RetrieveServer};
 use crate::modules::data::Data;
-use crate::modules::live_data_processor::dto::{CombatState, InstanceMap, Message, MessageType, Unit};
-use crate::modules::live_data_processor::material::{RetrieveActiveMap, Participant};
+use crate::modules::live_data_processor::domain_value::TimeWindow;
+use crate::modules::live_data_processor::dto::{CombatState, InstanceMap, LiveDataProcessorFailure, Message, MessageType, Unit};
+use crate::modules::live_data_processor::material::{ActiveMapVec, CombatLogArchive, Participant, PreparedCombatLog, RetrieveActiveMap};
 use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
 use crate::modules::live_data_processor::tools::GUID;
 use crate::util::database::{Execute, Select};
 use chrono::{Datelike, NaiveDateTime};
 use rust_lapper::{Interval, Lapper};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-pub fn parse_cbl(parser: &mut impl CombatLogParser, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, file_content: &str, start_parse: u64, end_parse: u64) -> Option<(u32, Vec<Message>)> {
-    let mut messages = Vec::with_capacity(1000000);
+static MALFORMED_LINE: &str = "MALFORMED_LINE";
+static UNKNOWN_TEXT: &str = "UNKNOWN_TEXT";
+// Validations of logs of retail servers that were not created yet use this server id
+pub static UNKNOWN_RETAIL_SERVER_ID: u32 = 0;
 
-    // Pre processing
-    // TODO: Handle 31/12 => 01/01 raids
-    let current_year = NaiveDateTime::from_timestamp((start_parse / 1000) as i64, 0).year();
-    for line in file_content.split('\n').into_iter() {
-        let meta = line.split("  ").collect::<Vec<&str>>();
-        if meta.len() != 2 {
-            continue;
-        }
-        if let Ok(timestamp) = NaiveDateTime::parse_from_str(&format!("{}/{}", current_year, meta[0]), "%Y/%m/%d %H:%M:%S%.3f") {
-            let event_timestamp = timestamp.timestamp_millis() as u64;
-            if event_timestamp < start_parse || event_timestamp > end_parse {
-                continue;
-            }
+pub static MESSAGE_BATCH_SIZE: usize = 50000;
+// Post processing may relate messages that are close to each other, e.g. a dispel to its cast.
+// Hence messages are post processed in chunks, that are only cut at larger gaps.
+static POST_PROCESSING_GAP: u64 = 100;
+static MAX_CHUNK_SIZE: usize = 4 * MESSAGE_BATCH_SIZE;
+// Generated messages may be dated back, e.g. by the appearance offset or the combat timeout of an NPC.
+// Messages are held back this long before they are emitted in order, hence it must exceed these offsets.
+static REORDER_WINDOW: u64 = 6 * 60000;
+// How often units are checked for a combat timeout
+static COMBAT_TIMEOUT_SWEEP_INTERVAL: u64 = 1000;
 
-            if let Some(message_types) = parser.parse_cbl_line(data, event_timestamp, meta[1].trim_end_matches("\r")) {
-                let mut message_count = (messages.len() + message_types.len()) as u64;
-                for message_type in message_types {
-                    message_count -= 1;
-                    messages.push(Message {
-                        api_version: 0,
-                        message_length: 0,
-                        timestamp: event_timestamp,
-                        message_count,
-                        message_type,
-                    });
-                }
-            }
-        }
-    }
+// Messages of equal timestamps keep this order
+static ORDER_LOG: u8 = 0;
+static ORDER_BONUS: u8 = 1;
+static ORDER_ADDITIONAL: u8 = 2;
 
-    // Post processing step
-    parser.do_message_post_processing(data, &mut messages);
+/// First pass: Collects the participants, active maps and the server of the log, whereas its messages are dropped right away.
+/// The emitter must be a fresh parser of the same kind as the collector.
+pub fn prepare_cbl(
+    mut collector: Box<dyn CombatLogParser>, emitter: Box<dyn CombatLogParser>, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, archive: &CombatLogArchive, time_window: &TimeWindow,
+) -> Result<PreparedCombatLog, LiveDataProcessorFailure> {
+    let (num_lines, unparsable_lines, instance_ids) = collect_cbl(collector.as_mut(), data, archive, time_window)?;
 
-    let expansion_id = parser.get_expansion_id();
-    let mut server_id = parser.get_server_id();
-    if let Some(involved_server) = parser.get_involved_server() {
+    let expansion_id = collector.get_expansion_id();
+    let mut server_id = collector.get_server_id();
+    if let Some(involved_server) = collector.get_involved_server() {
         for (retail_server_id, server_name, patch_tag) in involved_server {
-            let mut server = data.get_internal_server_by_retail_id(retail_server_id);
-            if server.is_none() {
-                server = Some(data.set_internal_retail_server(db_main, server_name, expansion_id, patch_tag, retail_server_id));
-            }
-            let server = server.unwrap();
+            let server = data
+                .get_internal_server_by_retail_id(retail_server_id)
+                .unwrap_or_else(|| data.set_internal_retail_server(db_main, server_name, expansion_id, patch_tag, retail_server_id));
             server_id = Some(server.id); // TODO: Better way to determine server id
         }
     }
 
-    let server_id = server_id?;
+    let server_id = server_id.ok_or(LiveDataProcessorFailure::ServerNotDetected)?;
 
     let mut remove_unit = BTreeSet::new();
     let mut replace_unit_id = HashMap::new();
-    for (retail_server_id, character_dto) in parser.get_involved_character_builds() {
-        let server_id = retail_server_id.map(|id| data.get_internal_server_by_retail_id(id).unwrap().id).unwrap_or(server_id);
-        if server_id == 4 || server_id == 5 {
-            if let Some(character) = armory.get_character_by_name(server_id, character_dto.character_history.as_ref().unwrap().character_name.clone()) {
-                replace_unit_id.insert(character_dto.server_uid, character.server_uid);
-            } else {
-                remove_unit.insert(character_dto.server_uid);
-                continue;
-            }
-        } else {
+    for (retail_server_id, character_dto) in collector.get_involved_character_builds() {
+        let server_id = match retail_server_id {
+            Some(retail_server_id) => data.get_internal_server_by_retail_id(retail_server_id).ok_or(LiveDataProcessorFailure::ServerNotDetected)?.id,
+            None => server_id,
+        };
+        if !map_imported_character(armory, server_id, &character_dto, &mut remove_unit, &mut replace_unit_id) {
             // Only set char if gear is not empty or char does not exist
             if let Some(character_info) = &character_dto.character_history {
                 if character_info.character_info.gear.is_naked() {
@@ -90,69 +78,381 @@ pub fn parse_cbl(parser: &mut impl CombatLogParser, db_main: &mut (impl Select +
         }
     }
 
-    // Pre-fill instance ids
+    Ok(PreparedCombatLog {
+        collector,
+        emitter,
+        server_id,
+        num_lines,
+        unparsable_lines,
+        time_window: *time_window,
+        instance_ids,
+        remove_unit,
+        replace_unit_id,
+    })
+}
+
+/// First pass of a validation: Like prepare_cbl, but servers and characters are only looked up.
+/// Logs of retail servers that were not created yet get UNKNOWN_RETAIL_SERVER_ID.
+pub fn inspect_cbl(mut collector: Box<dyn CombatLogParser>, emitter: Box<dyn CombatLogParser>, data: &Data, armory: &Armory, archive: &CombatLogArchive, time_window: &TimeWindow) -> Result<PreparedCombatLog, LiveDataProcessorFailure> {
+    let (num_lines, unparsable_lines, instance_ids) = collect_cbl(collector.as_mut(), data, archive, time_window)?;
+
+    let mut server_id = collector.get_server_id();
+    if let Some(involved_server) = collector.get_involved_server() {
+        for (retail_server_id, _, _) in involved_server {
+            server_id = Some(data.get_internal_server_by_retail_id(retail_server_id).map(|server| server.id).unwrap_or(UNKNOWN_RETAIL_SERVER_ID));
+        }
+    }
+
+    let server_id = server_id.ok_or(LiveDataProcessorFailure::ServerNotDetected)?;
+
+    let mut remove_unit = BTreeSet::new();
+    let mut replace_unit_id = HashMap::new();
+    for (retail_server_id, character_dto) in collector.get_involved_character_builds() {
+        let server_id = match retail_server_id {
+            Some(retail_server_id) => match data.get_internal_server_by_retail_id(retail_server_id) {
+                Some(server) => server.id,
+                None => continue,
+            },
+            None => server_id,
+        };
+        map_imported_character(armory, server_id, &character_dto, &mut remove_unit, &mut replace_unit_id);
+    }
+
+    Ok(PreparedCombatLog {
+        collector,
+        emitter,
+        server_id,
+        num_lines,
+        unparsable_lines,
+        time_window: *time_window,
+        instance_ids,
+        remove_unit,
+        replace_unit_id,
+    })
+}
+
+// Returns the number of lines, the unparsable lines and the instance ids of the log
+fn collect_cbl(collector: &mut dyn CombatLogParser, data: &Data, archive: &CombatLogArchive, time_window: &TimeWindow) -> Result<(u64, HashMap<String, u32>, HashMap<(u16, Option<u8>), u32>), LiveDataProcessorFailure> {
+    let mut unparsable_lines = HashMap::new();
     let mut instance_ids = HashMap::new();
-    for Message { message_type, .. } in messages.iter() {
-        if let MessageType::InstanceMap(map) = message_type {
+    let num_lines = for_each_cbl_message(collector, data, archive, time_window, &mut unparsable_lines, |message| {
+        if let MessageType::InstanceMap(map) = &message.message_type {
             // TODO (If I ever get more of these events): This only works for vanilla!
             instance_ids.insert((map.map_id as u16, None), map.instance_id);
         }
+        Ok(())
+    })?;
+    Ok((num_lines, unparsable_lines, instance_ids))
+}
+
+// Characters of these servers are imported by name, those that are unknown are removed from the log.
+// Returns false for characters of other servers.
+fn map_imported_character(armory: &Armory, server_id: u32, character_dto: &CharacterDto, remove_unit: &mut BTreeSet<u64>, replace_unit_id: &mut HashMap<u64, u64>) -> bool {
+    if server_id != 4 && server_id != 5 {
+        return false;
+    }
+    if let Some(character) = armory.get_character_by_name(server_id, character_dto.character_history.as_ref().unwrap().character_name.clone()) {
+        replace_unit_id.insert(character_dto.server_uid, character.server_uid);
+    } else {
+        remove_unit.insert(character_dto.server_uid);
+    }
+    true
+}
+
+/// Second pass: Emits the messages of the log in order and in batches of at most MESSAGE_BATCH_SIZE.
+/// Only the last minutes of the log are held in memory, regardless of its size.
+pub fn stream_cbl_messages(prepared: PreparedCombatLog, data: &Data, archive: &CombatLogArchive, mut on_batch: impl FnMut(Vec<Message>) -> Result<(), LiveDataProcessorFailure>) -> Result<(), LiveDataProcessorFailure> {
+    let PreparedCombatLog {
+        mut collector,
+        mut emitter,
+        server_id,
+        time_window,
+        instance_ids,
+        remove_unit,
+        replace_unit_id,
+        ..
+    } = prepared;
+
+    let mut stream = MessageStream::new(collector.as_ref(), server_id, instance_ids, remove_unit, replace_unit_id);
+    // These are taken from the addon's SavedVariables, which are written in local time as well
+    if let Some(bonus_msgs) = collector.get_bonus_messages() {
+        for mut msg in bonus_msgs {
+            msg.timestamp = time_window.to_utc(msg.timestamp);
+            stream.push(ORDER_BONUS, msg);
+        }
     }
 
-    let mut temp_intervals = Vec::with_capacity(4000);
-    let mut player_temp_intervals = Vec::with_capacity(1000);
-
-    let mut parsed_participants = parser.get_participants();
-    let mut incombat_participant_helper = Participant::new(0xF13000FFFE000000, false, "CBT Helper".to_string(), 10000);
-    incombat_participant_helper.active_intervals.push((10001, u64::MAX.rotate_right(2)));
-    parsed_participants.iter().for_each(|participant| {
-        participant.active_intervals.iter().for_each(|(start, end)| {
-            temp_intervals.push(Interval {
-                start: *start - 200,
-                stop: *end + 200,
-                val: (participant.id, participant.is_player),
-            });
+    let mut chunk = Vec::with_capacity(MESSAGE_BATCH_SIZE);
+    for_each_cbl_message(emitter.as_mut(), data, archive, &time_window, &mut HashMap::new(), |message| {
+        let is_gap = chunk.last().map(|last: &Message| message.timestamp.saturating_sub(last.timestamp) > POST_PROCESSING_GAP).unwrap_or(false);
+        if (chunk.len() >= MESSAGE_BATCH_SIZE && is_gap) || chunk.len() >= MAX_CHUNK_SIZE {
+            let full_chunk = std::mem::replace(&mut chunk, Vec::with_capacity(MESSAGE_BATCH_SIZE));
+            stream.push_chunk(collector.as_mut(), data, full_chunk, &mut on_batch)?;
+        }
+        chunk.push(message);
+        Ok(())
+    })?;
+    stream.push_chunk(collector.as_mut(), data, chunk, &mut on_batch)?;
+    stream.finish(&mut on_batch)
+}
 
-            temp_intervals.push(Interval {
-                start: *start - 200,
-                stop: *end + 200,
-                val: (incombat_participant_helper.id, incombat_participant_helper.is_player),
-            });
+/// Pre processing step of a single pass, also counts the unparsable lines by event keyword
+fn for_each_cbl_message(
+    parser: &mut dyn CombatLogParser, data: &Data, archive: &CombatLogArchive, time_window: &TimeWindow, unparsable_lines: &mut HashMap<String, u32>, mut on_message: impl FnMut(Message) -> Result<(), LiveDataProcessorFailure>,
+) -> Result<u64, LiveDataProcessorFailure> {
+    let mut message_count = 0;
+    let mut timestamp_parser = LogTimestampParser::new(*time_window);
+    archive.for_each_line(|line| {
+        let meta = line.split("  ").collect::<Vec<&str>>();
+        if meta.len() != 2 {
+            if !line.trim().is_empty() {
+                *unparsable_lines.entry(MALFORMED_LINE.to_string()).or_insert(0) += 1;
+            }
+            return Ok(());
+        }
+        if let Some(event_timestamp) = timestamp_parser.parse(meta[0]) {
+            if !time_window.contains(event_timestamp) {
+                return Ok(());
+            }
 
-            if participant.is_player {
-                player_temp_intervals.push(Interval {
+            let content = meta[1].trim_end_matches('\r');
+            if let Some(message_types) = parser.parse_cbl_line(data, event_timestamp, content) {
+                message_count += message_types.len() as u64;
+                let mut current_message_count = message_count;
+                for message_type in message_types {
+                    current_message_count -= 1;
+                    on_message(Message {
+                        api_version: 0,
+                        message_length: 0,
+                        timestamp: event_timestamp,
+                        message_count: current_message_count,
+                        message_type,
+                    })?;
+                }
+            } else {
+                *unparsable_lines.entry(get_event_keyword(content).to_string()).or_insert(0) += 1;
+            }
+        } else {
+            *unparsable_lines.entry(MALFORMED_LINE.to_string()).or_insert(0) += 1;
+        }
+        Ok(())
+    })
+}
+
+/// The leading keyword of an event, e.g. SPELL_DAMAGE. Vanilla logs are plain text and have none.
+pub fn get_event_keyword(content: &str) -> &str {
+    let keyword = content.split(',').next().unwrap_or("");
+    if !keyword.is_empty() && keyword.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') {
+        return keyword;
+    }
+    UNKNOWN_TEXT
+}
+
+/// Combat log timestamps, e.g. "12/31 23:59:59.123", lack the year.
+/// It is inferred from the time window and rolled forward once the month wraps on New Year's Eve.
+pub struct LogTimestampParser {
+    time_window: TimeWindow,
+    year: Option<i32>,
+    last_month: u32,
+}
+
+impl LogTimestampParser {
+    pub fn new(time_window: TimeWindow) -> Self {
+        LogTimestampParser { time_window, year: None, last_month: 0 }
+    }
+
+    /// Returns the UTC timestamp in milliseconds
+    pub fn parse(&mut self, raw: &str) -> Option<u64> {
+        let mut year = match self.year {
+            Some(year) => year,
+            None => self.initial_year(raw)?,
+        };
+        let mut date_time = parse_log_date_time(year, raw)?;
+        if date_time.month() < self.last_month {
+            year += 1;
+            date_time = parse_log_date_time(year, raw)?;
+        }
+        self.year = Some(year);
+        self.last_month = date_time.month();
+        Some(self.time_window.to_utc(date_time.timestamp_millis() as u64))
+    }
+
+    // The year that brings the first line closest to the start of the time window
+    fn initial_year(&self, raw: &str) -> Option<i32> {
+        let start_year = NaiveDateTime::from_timestamp((self.time_window.to_local(self.time_window.start) / 1000) as i64, 0).year();
+        (start_year - 1..=start_year + 1)
+            .filter_map(|year| parse_log_date_time(year, raw).map(|date_time| (year, date_time)))
+            .min_by_key(|(_, date_time)| (self.time_window.to_utc(date_time.timestamp_millis() as u64) as i64 - self.time_window.start as i64).abs())
+            .map(|(year, _)| year)
+    }
+}
+
+fn parse_log_date_time(year: i32, raw: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(&format!("{}/{}", year, raw), "%Y/%m/%d %H:%M:%S%.3f").ok()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CombatUpdate {
+    timestamp: u64,
+    timeout: u64,
+    is_player: bool,
+    left_combat: bool,
+}
+
+/// Post processing of the messages of the pre processing step, which inserts instance map and combat state messages
+struct MessageStream {
+    server_id: u32,
+    expansion_id: u8,
+    instance_ids: HashMap<(u16, Option<u8>), u32>,
+    remove_unit: BTreeSet<u64>,
+    replace_unit_id: HashMap<u64, u64>,
+
+    parsed_participants: Vec<Participant>,
+    participants_by_interval: Lapper<u64, (u64, bool)>,
+    player_participants_by_interval: Lapper<u64, u64>,
+    active_maps: ActiveMapVec,
+
+    current_map: Option<(u16, Option<u8>)>,
+    participants: HashMap<u64, bool>,
+    last_combat_update: HashMap<u64, CombatUpdate>,
+    last_combat_timeout_sweep: u64,
+
+    // (Timestamp, Order, Sequence) => Message
+    pending_messages: BTreeMap<(u64, u8, u64), Message>,
+    sequence: u64,
+    batch: Vec<Message>,
+}
+
+impl MessageStream {
+    fn new(parser: &dyn CombatLogParser, server_id: u32, instance_ids: HashMap<(u16, Option<u8>), u32>, remove_unit: BTreeSet<u64>, replace_unit_id: HashMap<u64, u64>) -> Self {
+        let mut temp_intervals = Vec::with_capacity(4000);
+        let mut player_temp_intervals = Vec::with_capacity(1000);
+
+        let mut parsed_participants = parser.get_participants();
+        let mut incombat_participant_helper = Participant::new(0xF13000FFFE000000, false, "CBT Helper".to_string(), 10000);
+        incombat_participant_helper.active_intervals.push((10001, u64::MAX.rotate_right(2)));
+        parsed_participants.iter().for_each(|participant| {
+            participant.active_intervals.iter().for_each(|(start, end)| {
+                temp_intervals.push(Interval {
                     start: *start - 200,
                     stop: *end + 200,
-                    val: participant.id,
+                    val: (participant.id, participant.is_player),
                 });
-            }
+
+                temp_intervals.push(Interval {
+                    start: *start - 200,
+                    stop: *end + 200,
+                    val: (incombat_participant_helper.id, incombat_participant_helper.is_player),
+                });
+
+                if participant.is_player {
+                    player_temp_intervals.push(Interval {
+                        start: *start - 200,
+                        stop: *end + 200,
+                        val: participant.id,
+                    });
+                }
+            });
         });
-    });
-    parsed_participants.push(incombat_participant_helper);
-
-    let participants_by_interval = Lapper::new(temp_intervals);
-    let player_participants_by_interval = Lapper::new(player_temp_intervals);
-    let active_maps = parser.get_active_maps();
-
-    let mut current_map = None;
-    let mut participants = HashMap::new();
-    let mut last_combat_update = HashMap::new();
-    let mut additional_messages = Vec::with_capacity(20000);
-    for Message { timestamp, message_count, message_type, .. } in messages.iter() {
+        parsed_participants.push(incombat_participant_helper);
+
+        MessageStream {
+            server_id,
+            expansion_id: parser.get_expansion_id(),
+            instance_ids,
+            remove_unit,
+            replace_unit_id,
+            parsed_participants,
+            participants_by_interval: Lapper::new(temp_intervals),
+            player_participants_by_interval: Lapper::new(player_temp_intervals),
+            active_maps: parser.get_active_maps(),
+            current_map: None,
+            participants: HashMap::new(),
+            last_combat_update: HashMap::new(),
+            last_combat_timeout_sweep: 0,
+            pending_messages: BTreeMap::new(),
+            sequence: 0,
+            batch: Vec::with_capacity(MESSAGE_BATCH_SIZE),
+        }
+    }
+
+    fn push(&mut self, order: u8, message: Message) {
+        self.sequence += 1;
+        self.pending_messages.insert((message.timestamp, order, self.sequence), message);
+    }
+
+    fn push_chunk(&mut self, parser: &mut dyn CombatLogParser, data: &Data, mut chunk: Vec<Message>, on_batch: &mut impl FnMut(Vec<Message>) -> Result<(), LiveDataProcessorFailure>) -> Result<(), LiveDataProcessorFailure> {
+        parser.do_message_post_processing(data, &mut chunk);
+
+        let mut additional_messages = Vec::with_capacity(20000);
+        for message in chunk.iter() {
+            self.annotate(parser, data, message, &mut additional_messages);
+        }
+
+        let watermark = chunk.last().map(|message| message.timestamp.saturating_sub(REORDER_WINDOW)).unwrap_or(0);
+        for message in chunk {
+            self.push(ORDER_LOG, message);
+        }
+        for message in additional_messages {
+            self.push(ORDER_ADDITIONAL, message);
+        }
+
+        let later_messages = self.pending_messages.split_off(&(watermark, 0, 0));
+        let ready_messages = std::mem::replace(&mut self.pending_messages, later_messages);
+        self.emit(ready_messages.into_iter().map(|(_, message)| message), on_batch)
+    }
+
+    fn finish(mut self, on_batch: &mut impl FnMut(Vec<Message>) -> Result<(), LiveDataProcessorFailure>) -> Result<(), LiveDataProcessorFailure> {
+        let ready_messages = std::mem::replace(&mut self.pending_messages, BTreeMap::new());
+        self.emit(ready_messages.into_iter().map(|(_, message)| message), on_batch)?;
+        if !self.batch.is_empty() {
+            on_batch(std::mem::replace(&mut self.batch, Vec::new()))?;
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self, messages: impl Iterator<Item = Message>, on_batch: &mut impl FnMut(Vec<Message>) -> Result<(), LiveDataProcessorFailure>) -> Result<(), LiveDataProcessorFailure> {
+        for mut msg in messages {
+            if self.server_id == 4 || self.server_id == 5 {
+                if is_in_remove_list(&self.remove_unit, &msg.message_type) {
+                    continue;
+                }
+                replace_ids(&self.replace_unit_id, &mut msg.message_type);
+            }
+            self.batch.push(msg);
+            if self.batch.len() >= MESSAGE_BATCH_SIZE {
+                on_batch(std::mem::replace(&mut self.batch, Vec::with_capacity(MESSAGE_BATCH_SIZE)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn annotate(&mut self, parser: &dyn CombatLogParser, data: &Data, message: &Message, additional_messages: &mut Vec<Message>) {
+        let Message { timestamp, message_count, message_type, .. } = message;
+
+        if *timestamp >= self.last_combat_timeout_sweep + COMBAT_TIMEOUT_SWEEP_INTERVAL {
+            self.sweep_combat_timeouts(*timestamp, *message_count, additional_messages);
+            self.last_combat_timeout_sweep = *timestamp;
+        }
+
         // Insert Instance Map Messages
-        if let Some((map_id, difficulty)) = active_maps.get_current_active_map(&player_participants_by_interval, expansion_id, *timestamp) {
-            if !current_map.contains(&(map_id, difficulty)) {
-                current_map = Some((map_id, difficulty));
-                participants.clear();
+        if let Some((map_id, difficulty)) = self.active_maps.get_current_active_map(&self.player_participants_by_interval, self.expansion_id, *timestamp) {
+            if !self.current_map.contains(&(map_id, difficulty)) {
+                self.current_map = Some((map_id, difficulty));
+                self.participants.clear();
             };
 
             let mut new_participants: HashMap<u64, bool> = HashMap::new();
             // TODO: This lapper does not seem to find all participants within that interval, it fails for large intervals
-            for Interval { val: (unit_id, is_player), .. } in participants_by_interval.find(*timestamp - 100, *timestamp + 100).filter(|Interval { val: (unit_id, _), .. }| !participants.contains_key(unit_id)) {
+            for Interval { val: (unit_id, is_player), .. } in self
+                .participants_by_interval
+                .find(*timestamp - 100, *timestamp + 100)
+                .filter(|Interval { val: (unit_id, _), .. }| !self.participants.contains_key(unit_id))
+            {
                 new_participants.insert(*unit_id, *is_player);
             }
 
-            let instance_id = *instance_ids.entry((map_id, difficulty)).or_insert_with(rand::random::<u32>);
+            let instance_id = *self.instance_ids.entry((map_id, difficulty)).or_insert_with(rand::random::<u32>);
             for (unit_id, is_player) in new_participants {
                 let mut ts_offset: i64 = -1;
                 if !is_player {
@@ -173,10 +473,10 @@ pub fn parse_cbl(parser: &mut impl CombatLogParser, db_main: &mut (impl Select +
                         unit: Unit { is_player, unit_id },
                     }),
                 ));
-                participants.insert(unit_id, is_player);
+                self.participants.insert(unit_id, is_player);
             }
-        } else if current_map.is_some() {
-            for (unit_id, is_player) in participants.iter() {
+        } else if self.current_map.is_some() {
+            for (unit_id, is_player) in self.participants.iter() {
                 additional_messages.push(Message::new_parsed(
                     *timestamp + 1,
                     *message_count,
@@ -188,8 +488,8 @@ pub fn parse_cbl(parser: &mut impl CombatLogParser, db_main: &mut (impl Select +
                     }),
                 ));
             }
-            participants.clear();
-            current_map = None;
+            self.participants.clear();
+            self.current_map = None;
         }
 
         // Insert Combat Start/End Events
@@ -197,21 +497,22 @@ pub fn parse_cbl(parser: &mut impl CombatLogParser, db_main: &mut (impl Select +
         // We assume end if it dies or after a timeout
         match &message_type {
             MessageType::MeleeDamage(dmg) | MessageType::SpellDamage(dmg) => {
-                add_combat_event(parser, data, expansion_id, &mut additional_messages, &mut last_combat_update, *timestamp, *message_count, &dmg.attacker);
-                add_combat_event(parser, data, expansion_id, &mut additional_messages, &mut last_combat_update, *timestamp, *message_count, &dmg.victim);
+                self.add_combat_event(parser, data, additional_messages, *timestamp, *message_count, &dmg.attacker);
+                self.add_combat_event(parser, data, additional_messages, *timestamp, *message_count, &dmg.victim);
             },
             MessageType::Death(death) => {
                 if !death.victim.is_player {
                     if let Some(entry) = death.victim.unit_id.get_entry() {
                         if let Some(implied_npc_ids) = parser.get_death_implied_npc_combat_state_and_offset(entry) {
                             for (npc_id, delay_ts) in implied_npc_ids {
-                                if let Some(unit_id) = parsed_participants.iter().find_map(|participant| {
+                                let implied_unit_id = self.parsed_participants.iter().find_map(|participant| {
                                     if !participant.is_player && participant.id.get_entry().contains(&npc_id) {
                                         return Some(participant.id);
                                     }
                                     None
-                                }) {
-                                    add_combat_event(parser, data, expansion_id, &mut additional_messages, &mut last_combat_update, (*timestamp as i64 + delay_ts) as u64, *message_count - 1, &Unit { is_player: false, unit_id });
+                                });
+                                if let Some(unit_id) = implied_unit_id {
+                                    self.add_combat_event(parser, data, additional_messages, (*timestamp as i64 + delay_ts) as u64, *message_count - 1, &Unit { is_player: false, unit_id });
                                 }
                             }
                         }
@@ -225,28 +526,125 @@ pub fn parse_cbl(parser: &mut impl CombatLogParser, db_main: &mut (impl Select +
                     message_count: *message_count,
                     message_type: MessageType::CombatState(CombatState { unit: death.victim.clone(), in_combat: false }),
                 });
-                last_combat_update.remove(&death.victim.unit_id);
+                self.last_combat_update.remove(&death.victim.unit_id);
             },
             _ => {},
         };
     }
 
-    if let Some(mut bonus_msgs) = parser.get_bonus_messages() {
-        messages.append(&mut bonus_msgs);
+    // A unit leaves the combat at its last update once it timed out.
+    // Emitting this right away, rather than once it reappears, keeps the messages within the reorder window.
+    fn sweep_combat_timeouts(&mut self, current_timestamp: u64, current_message_count: u64, additional_messages: &mut Vec<Message>) {
+        for (unit_id, combat_update) in self.last_combat_update.iter_mut() {
+            if !combat_update.left_combat && current_timestamp.saturating_sub(combat_update.timestamp) >= combat_update.timeout {
+                additional_messages.push(Message {
+                    api_version: 0,
+                    message_length: 0,
+                    timestamp: combat_update.timestamp,
+                    message_count: current_message_count,
+                    message_type: MessageType::CombatState(CombatState {
+                        unit: Unit {
+                            is_player: combat_update.is_player,
+                            unit_id: *unit_id,
+                        },
+                        in_combat: false,
+                    }),
+                });
+                combat_update.left_combat = true;
+            }
+        }
     }
-    messages.append(&mut additional_messages);
-    if server_id == 4 || server_id == 5 {
-        messages = messages
-            .into_iter()
-            .filter(|msg| !is_in_remove_list(&remove_unit, &msg.message_type))
-            .map(|mut msg| {
-                replace_ids(&replace_unit_id, &mut msg.message_type);
-                msg
-            })
-            .collect();
+
+    fn add_combat_event(&mut self, parser: &dyn CombatLogParser, data: &Data, additional_messages: &mut Vec<Message>, current_timestamp: u64, current_message_count: u64, unit: &Unit) {
+        let ts_offset: i64 = -1;
+        let mut timeout = 60000;
+        let mut current_unit_is_boss = false;
+        if let Some(entry) = unit.unit_id.get_entry() {
+            /*
+            if let Some(delay) = parser.get_npc_appearance_offset(entry) {
+                ts_offset = delay;
+            }
+             */
+
+            if let Some(implied_in_combat_npc_ids) = parser.get_in_combat_implied_npc_combat(entry) {
+                for unit_id in self.last_combat_update.keys().cloned().collect::<Vec<u64>>() {
+                    for npc_id in &implied_in_combat_npc_ids {
+                        if unit_id.get_entry().contains(npc_id) {
+                            self.add_combat_event(parser, data, additional_messages, current_timestamp, current_message_count, &Unit { is_player: false, unit_id });
+                        }
+                    }
+                }
+            }
+
+            if let Some(delay) = parser.get_npc_timeout(entry) {
+                timeout = delay;
+            }
+
+            // For bosses consider timeout of same entry not unit id
+            current_unit_is_boss = data.get_npc(self.expansion_id, entry).map(|npc| npc.is_boss).contains(&true);
+            if current_unit_is_boss {
+                for (unit_id, combat_update) in self.last_combat_update.clone() {
+                    if unit_id.get_entry().contains(&entry) && current_timestamp - combat_update.timestamp >= timeout {
+                        if !combat_update.left_combat {
+                            additional_messages.push(Message {
+                                api_version: 0,
+                                message_length: 0,
+                                timestamp: combat_update.timestamp,
+                                message_count: current_message_count,
+                                message_type: MessageType::CombatState(CombatState {
+                                    unit: Unit { is_player: false, unit_id },
+                                    in_combat: false,
+                                }),
+                            });
+                        }
+                        self.last_combat_update.remove(&unit_id);
+                    }
+                }
+            }
+        } else if !unit.is_player {
+            timeout = 30000;
+        }
+
+        if let Some(combat_update) = self.last_combat_update.get_mut(&unit.unit_id) {
+            if current_timestamp - combat_update.timestamp >= timeout {
+                if !current_unit_is_boss && !combat_update.left_combat {
+                    additional_messages.push(Message {
+                        api_version: 0,
+                        message_length: 0,
+                        timestamp: combat_update.timestamp,
+                        message_count: current_message_count,
+                        message_type: MessageType::CombatState(CombatState { unit: unit.clone(), in_combat: false }),
+                    });
+                }
+                additional_messages.push(Message {
+                    api_version: 0,
+                    message_length: 0,
+                    timestamp: (current_timestamp as i64 + ts_offset) as u64,
+                    message_count: current_message_count,
+                    message_type: MessageType::CombatState(CombatState { unit: unit.clone(), in_combat: true }),
+                });
+            }
+            combat_update.timestamp = current_timestamp;
+            combat_update.left_combat = false;
+        } else {
+            additional_messages.push(Message {
+                api_version: 0,
+                message_length: 0,
+                timestamp: (current_timestamp as i64 + ts_offset) as u64,
+                message_count: current_message_count,
+                message_type: MessageType::CombatState(CombatState { unit: unit.clone(), in_combat: true }),
+            });
+            self.last_combat_update.insert(
+                unit.unit_id,
+                CombatUpdate {
+                    timestamp: current_timestamp,
+                    timeout,
+                    is_player: unit.is_player,
+                    left_combat: false,
+                },
+            );
+        }
     }
-    messages.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
-    Some((server_id, messages))
 }
 
 fn replace_ids(replace_unit_id: &HashMap<u64, u64>, message_type: &mut MessageType) {
@@ -320,83 +718,3 @@ fn is_in_remove_list(remove_unit: &BTreeSet<u64>, message_type: &MessageType) ->
         _ => false,
     }
 }
-
-fn add_combat_event(parser: &impl CombatLogParser, data: &Data, expansion_id: u8, additional_messages: &mut Vec<Message>, last_combat_update: &mut HashMap<u64, u64>, current_timestamp: u64, current_message_count: u64, unit: &Unit) {
-    let ts_offset: i64 = -1;
-    let mut timeout = 60000;
-    let mut current_unit_is_boss = false;
-    if let Some(entry) = unit.unit_id.get_entry() {
-        /*
-        if let Some(delay) = parser.get_npc_appearance_offset(entry) {
-            ts_offset = delay;
-        }
-         */
-
-        if let Some(implied_in_combat_npc_ids) = parser.get_in_combat_implied_npc_combat(entry) {
-            for (unit_id, _) in last_combat_update.clone() {
-                for npc_id in &implied_in_combat_npc_ids {
-                    if unit_id.get_entry().contains(npc_id) {
-                        add_combat_event(parser, data, expansion_id, additional_messages, last_combat_update, current_timestamp, current_message_count, &Unit { is_player: false, unit_id });
-                    }
-                }
-            }
-        }
-
-        if let Some(delay) = parser.get_npc_timeout(entry) {
-            timeout = delay;
-        }
-
-        // For bosses consider timeout of same entry not unit id
-        current_unit_is_boss = data.get_npc(expansion_id, entry).map(|npc| npc.is_boss).contains(&true);
-        if current_unit_is_boss {
-            for (unit_id, last_update) in last_combat_update.clone() {
-                if unit_id.get_entry().contains(&entry) && current_timestamp - last_update >= timeout {
-                    additional_messages.push(Message {
-                        api_version: 0,
-                        message_length: 0,
-                        timestamp: current_timestamp - (current_timestamp - last_update),
-                        message_count: current_message_count,
-                        message_type: MessageType::CombatState(CombatState {
-                            unit: Unit { is_player: false, unit_id },
-                            in_combat: false,
-                        }),
-                    });
-                    last_combat_update.remove(&unit_id);
-                }
-            }
-        }
-    } else if !unit.is_player {
-        timeout = 30000;
-    }
-
-    if let Some(last_update) = last_combat_update.get_mut(&unit.unit_id) {
-        if current_timestamp - *last_update >= timeout {
-            if !current_unit_is_boss {
-                additional_messages.push(Message {
-                    api_version: 0,
-                    message_length: 0,
-                    timestamp: current_timestamp - (current_timestamp - *last_update),
-                    message_count: current_message_count,
-                    message_type: MessageType::CombatState(CombatState { unit: unit.clone(), in_combat: false }),
-                });
-            }
-            additional_messages.push(Message {
-                api_version: 0,
-                message_length: 0,
-                timestamp: (current_timestamp as i64 + ts_offset) as u64,
-                message_count: current_message_count,
-                message_type: MessageType::CombatState(CombatState { unit: unit.clone(), in_combat: true }),
-            });
-        }
-        *last_update = current_timestamp;
-    } else {
-        additional_messages.push(Message {
-            api_version: 0,
-            message_length: 0,
-            timestamp: (current_timestamp as i64 + ts_offset) as u64,
-            message_count: current_message_count,
-            message_type: MessageType::CombatState(CombatState { unit: unit.clone(), in_combat: true }),
-        });
-        last_combat_update.insert(unit.unit_id, current_timestamp);
-    }
-}
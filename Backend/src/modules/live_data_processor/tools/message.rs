@@ -9,7 +9,7 @@ pub trait MessageParser {
 impl MessageParser for Vec<u8> {
     fn parse_message(&self) -> Result<Message, LiveDataProcessorFailure> {
         if self.len() <= 19 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
 
         let api_version = self[0];
@@ -3,6 +3,10 @@ pub use self::guid::GUID;
 pub use self::message::*;
 pub use self::process::*;
 pub use self::unit::*;
+pub use self::upload::*;
+pub use self::upload_job::UploadJobs;
+pub use self::upload_session::UploadSessions;
+pub use self::upload_validation::ValidateUpload;
 
 pub mod byte_reader;
 mod deserializer;
@@ -13,5 +17,9 @@ pub mod payload_mapper;
 mod process;
 pub mod server;
 mod unit;
+mod upload;
+mod upload_job;
+mod upload_session;
+mod upload_validation;
 
 pub mod cbl_parser;
@@ -9,7 +9,7 @@ pub trait MapAuraApplication {
 impl MapAuraApplication for [u8] {
     fn to_aura_application(&self) -> Result<AuraApplication, LiveDataProcessorFailure> {
         if self.len() != 27 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(AuraApplication {
             caster: self[0..9].to_unit()?,
@@ -8,7 +8,7 @@ pub trait MapCombatState {
 impl MapCombatState for [u8] {
     fn to_combat_state(&self) -> Result<CombatState, LiveDataProcessorFailure> {
         if self.len() != 10 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(CombatState {
             unit: self[0..9].to_unit()?,
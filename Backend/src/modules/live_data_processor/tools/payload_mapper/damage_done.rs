@@ -12,7 +12,7 @@ impl MapDamageDone for [u8] {
     fn from_melee_damage(&self) -> Result<DamageDone, LiveDataProcessorFailure> {
         let msg_len = self.len();
         if msg_len < 26 || (msg_len - 26) % 13 != 0 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
 
         let mut damage_components = Vec::with_capacity(1);
@@ -39,7 +39,7 @@ impl MapDamageDone for [u8] {
 
     fn from_spell_damage(&self) -> Result<DamageDone, LiveDataProcessorFailure> {
         if self.len() != 44 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(DamageDone {
             attacker: self[0..9].to_unit()?,
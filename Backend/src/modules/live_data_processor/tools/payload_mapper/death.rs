@@ -8,7 +8,7 @@ pub trait MapDeath {
 impl MapDeath for [u8] {
     fn to_death(&self) -> Result<Death, LiveDataProcessorFailure> {
         if self.len() != 18 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         let cause = self[0..9].to_unit()?;
         Ok(Death {
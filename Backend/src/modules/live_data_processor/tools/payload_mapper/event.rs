@@ -8,7 +8,7 @@ pub trait MapEvent {
 impl MapEvent for [u8] {
     fn to_event(&self) -> Result<Event, LiveDataProcessorFailure> {
         if self.len() != 10 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(Event {
             unit: self[0..9].to_unit()?,
@@ -9,7 +9,7 @@ pub trait MapHealDone {
 impl MapHealDone for [u8] {
     fn to_heal_done(&self) -> Result<HealDone, LiveDataProcessorFailure> {
         if self.len() != 38 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(HealDone {
             caster: self[0..9].to_unit()?,
@@ -8,7 +8,7 @@ pub trait MapInstanceArena {
 impl MapInstanceArena for [u8] {
     fn to_instance_arena(&self) -> Result<InstanceArena, LiveDataProcessorFailure> {
         if self.len() != 33 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(InstanceArena {
             map_id: byte_reader::read_u32(&self[0..4])?,
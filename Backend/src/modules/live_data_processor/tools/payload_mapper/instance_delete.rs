@@ -8,7 +8,7 @@ pub trait MapInstanceDelete {
 impl MapInstanceDelete for [u8] {
     fn to_instance_delete(&self) -> Result<u32, LiveDataProcessorFailure> {
         if self.len() != 4 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         byte_reader::read_u32(&self[0..4])
     }
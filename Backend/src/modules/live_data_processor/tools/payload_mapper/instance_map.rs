@@ -9,7 +9,7 @@ pub trait MapInstanceMap {
 impl MapInstanceMap for [u8] {
     fn to_instance_map(&self) -> Result<InstanceMap, LiveDataProcessorFailure> {
         if self.len() != 18 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(InstanceMap {
             map_id: byte_reader::read_u32(&self[0..4])?,
@@ -8,7 +8,7 @@ pub trait MapInstanceStart {
 impl MapInstanceStart for [u8] {
     fn to_instance_start(&self) -> Result<InstanceStart, LiveDataProcessorFailure> {
         if self.len() != 8 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(InstanceStart {
             map_id: byte_reader::read_u32(&self[0..4])?,
@@ -8,7 +8,7 @@ pub trait MapInstanceStartRatedArena {
 impl MapInstanceStartRatedArena for [u8] {
     fn to_instance_start_rated_arena(&self) -> Result<InstanceStartRatedArena, LiveDataProcessorFailure> {
         if self.len() != 24 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(InstanceStartRatedArena {
             map_id: byte_reader::read_u32(&self[0..4])?,
@@ -8,7 +8,7 @@ pub trait MapInstanceUnratedArena {
 impl MapInstanceUnratedArena for [u8] {
     fn to_instance_unrated_arena(&self) -> Result<InstanceUnratedArena, LiveDataProcessorFailure> {
         if self.len() != 9 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(InstanceUnratedArena {
             map_id: byte_reader::read_u32(&self[0..4])?,
@@ -9,7 +9,7 @@ pub trait MapInterrupt {
 impl MapInterrupt for [u8] {
     fn to_interrupt(&self) -> Result<Interrupt, LiveDataProcessorFailure> {
         if self.len() != 13 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(Interrupt {
             target: self[0..9].to_unit()?,
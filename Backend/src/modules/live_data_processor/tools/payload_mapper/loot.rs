@@ -9,7 +9,7 @@ pub trait MapLoot {
 impl MapLoot for [u8] {
     fn to_loot(&self) -> Result<Loot, LiveDataProcessorFailure> {
         if self.len() != 17 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(Loot {
             unit: self[0..9].to_unit()?,
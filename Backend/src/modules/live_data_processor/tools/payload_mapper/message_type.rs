@@ -27,32 +27,35 @@ pub trait MapMessageType {
 
 impl MapMessageType for u8 {
     fn to_message_type(&self, payload: &[u8]) -> Result<MessageType, LiveDataProcessorFailure> {
+        let malformed = |_: LiveDataProcessorFailure| LiveDataProcessorFailure::MalformedPayload(*self);
         Ok(match self {
-            0 => MessageType::MeleeDamage(payload.from_melee_damage()?),
-            1 => MessageType::SpellDamage(payload.from_spell_damage()?),
-            2 => MessageType::Heal(payload.to_heal_done()?),
-            3 => MessageType::Death(payload.to_death()?),
-            4 => MessageType::AuraApplication(payload.to_aura_application()?),
-            5 => MessageType::Dispel(payload.to_un_aura()?),
-            6 => MessageType::SpellSteal(payload.to_un_aura()?),
-            7 => MessageType::Interrupt(payload.to_interrupt()?),
-            8 => MessageType::Position(payload.to_position()?),
-            9 => MessageType::CombatState(payload.to_combat_state()?),
-            10 => MessageType::Power(payload.to_power()?),
-            11 => MessageType::Loot(payload.to_loot()?),
-            12 => MessageType::SpellCast(payload.to_spell_cast()?),
-            13 => MessageType::Threat(payload.to_threat()?),
-            14 => MessageType::Event(payload.to_event()?),
-            15 => MessageType::Summon(payload.to_summon()?),
-            16 => MessageType::InstancePvPStartUnratedArena(payload.to_instance_start()?),
-            17 => MessageType::InstancePvPStartRatedArena(payload.to_instance_start_rated_arena()?),
-            18 => MessageType::InstancePvPStartBattleground(payload.to_instance_start()?),
-            19 => MessageType::InstancePvPEndUnratedArena(payload.to_instance_unrated_arena()?),
-            20 => MessageType::InstancePvPEndRatedArena(payload.to_instance_arena()?),
-            21 => MessageType::InstancePvPEndBattleground(payload.to_instance_battleground()?),
-            22 => MessageType::InstanceDelete { instance_id: payload.to_instance_delete()? },
-            23 => MessageType::InstanceMap(payload.to_instance_map()?),
-            _ => return Err(LiveDataProcessorFailure::InvalidInput),
+            0 => MessageType::MeleeDamage(payload.from_melee_damage().map_err(malformed)?),
+            1 => MessageType::SpellDamage(payload.from_spell_damage().map_err(malformed)?),
+            2 => MessageType::Heal(payload.to_heal_done().map_err(malformed)?),
+            3 => MessageType::Death(payload.to_death().map_err(malformed)?),
+            4 => MessageType::AuraApplication(payload.to_aura_application().map_err(malformed)?),
+            5 => MessageType::Dispel(payload.to_un_aura().map_err(malformed)?),
+            6 => MessageType::SpellSteal(payload.to_un_aura().map_err(malformed)?),
+            7 => MessageType::Interrupt(payload.to_interrupt().map_err(malformed)?),
+            8 => MessageType::Position(payload.to_position().map_err(malformed)?),
+            9 => MessageType::CombatState(payload.to_combat_state().map_err(malformed)?),
+            10 => MessageType::Power(payload.to_power().map_err(malformed)?),
+            11 => MessageType::Loot(payload.to_loot().map_err(malformed)?),
+            12 => MessageType::SpellCast(payload.to_spell_cast().map_err(malformed)?),
+            13 => MessageType::Threat(payload.to_threat().map_err(malformed)?),
+            14 => MessageType::Event(payload.to_event().map_err(malformed)?),
+            15 => MessageType::Summon(payload.to_summon().map_err(malformed)?),
+            16 => MessageType::InstancePvPStartUnratedArena(payload.to_instance_start().map_err(malformed)?),
+            17 => MessageType::InstancePvPStartRatedArena(payload.to_instance_start_rated_arena().map_err(malformed)?),
+            18 => MessageType::InstancePvPStartBattleground(payload.to_instance_start().map_err(malformed)?),
+            19 => MessageType::InstancePvPEndUnratedArena(payload.to_instance_unrated_arena().map_err(malformed)?),
+            20 => MessageType::InstancePvPEndRatedArena(payload.to_instance_arena().map_err(malformed)?),
+            21 => MessageType::InstancePvPEndBattleground(payload.to_instance_battleground().map_err(malformed)?),
+            22 => MessageType::InstanceDelete {
+                instance_id: payload.to_instance_delete().map_err(malformed)?,
+            },
+            23 => MessageType::InstanceMap(payload.to_instance_map().map_err(malformed)?),
+            _ => return Err(LiveDataProcessorFailure::UnknownMessageType(*self)),
         })
     }
 }
@@ -9,7 +9,7 @@ pub trait MapPosition {
 impl MapPosition for [u8] {
     fn to_position(&self) -> Result<Position, LiveDataProcessorFailure> {
         if self.len() != 25 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(Position {
             unit: self[0..9].to_unit()?,
@@ -9,7 +9,7 @@ pub trait MapPower {
 impl MapPower for [u8] {
     fn to_power(&self) -> Result<Power, LiveDataProcessorFailure> {
         if self.len() != 18 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(Power {
             unit: self[0..9].to_unit()?,
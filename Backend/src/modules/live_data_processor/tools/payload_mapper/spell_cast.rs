@@ -9,7 +9,7 @@ pub trait MapSpellCast {
 impl MapSpellCast for [u8] {
     fn to_spell_cast(&self) -> Result<SpellCast, LiveDataProcessorFailure> {
         if self.len() != 26 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         let target_id: Unit = self[9..18].to_unit()?;
         Ok(SpellCast {
@@ -8,7 +8,7 @@ pub trait MapSummon {
 impl MapSummon for [u8] {
     fn to_summon(&self) -> Result<Summon, LiveDataProcessorFailure> {
         if self.len() != 18 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(Summon {
             owner: self[0..9].to_unit()?,
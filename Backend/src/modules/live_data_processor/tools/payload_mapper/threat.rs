@@ -9,7 +9,7 @@ pub trait MapThreat {
 impl MapThreat for [u8] {
     fn to_threat(&self) -> Result<Threat, LiveDataProcessorFailure> {
         if self.len() != 26 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         let spell_id = byte_reader::read_u32(&self[18..22])?;
         Ok(Threat {
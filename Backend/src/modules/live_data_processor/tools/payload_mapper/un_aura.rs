@@ -9,7 +9,7 @@ pub trait MapUnAura {
 impl MapUnAura for [u8] {
     fn to_un_aura(&self) -> Result<UnAura, LiveDataProcessorFailure> {
         if self.len() != 36 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         Ok(UnAura {
             un_aura_caster: self[0..9].to_unit()?,
@@ -8,7 +8,7 @@ pub trait MapUnit {
 impl MapUnit for [u8] {
     fn to_unit(&self) -> Result<Unit, LiveDataProcessorFailure> {
         if self.len() != 9 {
-            return Err(LiveDataProcessorFailure::InvalidInput);
+            return Err(LiveDataProcessorFailure::TruncatedMessage);
         }
         let unit_id = byte_reader::read_u64(&self[1..9]).unwrap();
         Ok(Unit { is_player: self[0] == 1, unit_id })
@@ -19,6 +19,7 @@ impl ProcessMessages for LiveDataProcessor {
     fn process_messages(&self, db_main: &mut (impl Select + Execute), server_id: u32, armory: &Armory, data: &Data, msg_vec: Vec<Message>, member_id: u32) -> Result<(), LiveDataProcessorFailure> {
         if !msg_vec.is_empty() {
             self.create_server_if_not_exist(db_main, server_id);
+            self.reset_server_if_poisoned(db_main, server_id);
             let servers = self.servers.read().unwrap();
             let mut server = servers.get(&server_id).expect("Server Id must exist!").write().unwrap();
             return server.parse_events(db_main, armory, data, msg_vec, member_id);
@@ -0,0 +1,63 @@
+use crate::modules::live_data_processor::material::Server;
+use std::collections::BTreeSet;
+
+impl Server {
+    /// Finalized instances are evicted during the post processing, once the events that were committed until then are saved
+    pub fn schedule_eviction(&mut self, instance_key: (u32, u32), instance_meta_id: u32, timestamp: u64) {
+        self.finalized_instances.entry(instance_key).or_insert((instance_meta_id, timestamp));
+    }
+
+    /// Removes the state that belongs to an instance, once it is finalized.
+    /// Units are only evicted if they were last seen in this instance and no other uploader is still in the same instance id.
+    pub fn evict_instance(&mut self, instance_key: (u32, u32), instance_meta_id: u32) {
+        self.instance_participants.remove(&instance_meta_id);
+        self.active_instances.remove(&instance_key);
+        self.finalized_instances.remove(&instance_key);
+        self.active_attempts.remove(&instance_key);
+        self.infight_players.remove(&instance_key);
+        self.committed_events.remove(&instance_key);
+        self.committed_events_count.remove(&instance_key);
+        self.recently_committed_spell_cast_and_aura_applications.remove(&instance_key);
+        self.post_processing_last_precessed_event_id.remove(&instance_meta_id);
+        self.release_merged_instances(instance_meta_id);
+        self.num_evicted_instances += 1;
+
+        let (instance_id, _) = instance_key;
+        if self.active_instances.keys().any(|(active_instance_id, _)| *active_instance_id == instance_id) {
+            return;
+        }
+
+        let evicted_units = self.unit_instance_id.iter().filter(|(_, unit_instance_id)| **unit_instance_id == instance_id).map(|(unit_id, _)| *unit_id).collect::<BTreeSet<u64>>();
+        for unit_id in evicted_units.iter() {
+            self.unit_instance_id.remove(unit_id);
+            self.summons.remove(unit_id);
+            self.cache_unit.remove(unit_id);
+            self.subject_prepend_mode_set.remove(unit_id);
+            self.non_committed_events.remove(unit_id);
+        }
+    }
+
+    /// Number of entries of each collection of the live state, which is what its memory usage grows with
+    pub fn get_state_sizes(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            ("summons", self.summons.len()),
+            ("active_instances", self.active_instances.len()),
+            ("finalized_instances", self.finalized_instances.len()),
+            ("unit_instance_id", self.unit_instance_id.len()),
+            ("instance_participants", self.instance_participants.values().map(|participants| participants.len()).sum()),
+            ("active_attempts", self.active_attempts.values().map(|attempts| attempts.len()).sum()),
+            ("infight_players", self.infight_players.values().map(|players| players.len()).sum()),
+            ("merged_instances", self.merged_instances.len()),
+            ("subject_prepend_mode_set", self.subject_prepend_mode_set.len()),
+            ("post_processing_last_precessed_event_id", self.post_processing_last_precessed_event_id.len()),
+            ("non_committed_events", self.non_committed_events.values().map(|events| events.len()).sum()),
+            ("committed_events", self.committed_events.values().map(|events| events.len()).sum()),
+            ("committed_events_count", self.committed_events_count.len()),
+            (
+                "recently_committed_spell_cast_and_aura_applications",
+                self.recently_committed_spell_cast_and_aura_applications.values().map(|events| events.len()).sum(),
+            ),
+            ("cache_unit", self.cache_unit.len()),
+        ]
+    }
+}
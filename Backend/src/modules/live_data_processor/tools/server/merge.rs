@@ -0,0 +1,252 @@
+use crate::modules::live_data_processor::domain_value::{Event, EventType, UnitInstance};
+use crate::modules::live_data_processor::material::{Attempt, MergedInstance, Server, MERGE_TIMESTAMP_TOLERANCE};
+use crate::modules::live_data_processor::tools::LiveDataDeserializer;
+use crate::mysql::Value;
+use crate::params;
+use crate::util::database::{Execute, Select};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+// Uploads of the same instance id are merged if they are at most this far apart
+static MERGE_WINDOW: u64 = 30 * 60 * 1000;
+// Events are committed roughly in order, hence the saved events before are not matched anymore
+static MERGE_PRUNE_MARGIN: u64 = 5000;
+
+impl Server {
+    /// Key of the instance that the events of the uploader are committed to
+    pub fn instance_key(&self, instance_id: u32, member_id: u32) -> (u32, u32) {
+        self.merged_instances
+            .get(&(instance_id, member_id))
+            .map(|merged_instance| (instance_id, merged_instance.canonical_member_id))
+            .unwrap_or((instance_id, member_id))
+    }
+
+    /// Joins the instance of another uploader, if it has the same instance id and overlaps in time
+    pub fn merge_into_instance(&mut self, db_main: &mut (impl Execute + Select), start_ts: u64, instance_id: u32, map_id: u32, member_id: u32) -> bool {
+        let instance = db_main.select_wparams_value(
+            "SELECT id, start_ts, uploaded_user, last_event_id FROM instance_meta WHERE server_id=:server_id AND instance_id=:instance_id AND map_id=:map_id AND start_ts<=:latest_start_ts AND COALESCE(end_ts, start_ts)>=:earliest_end_ts ORDER BY start_ts LIMIT 1",
+            |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<u64, usize>(1).unwrap(), row.take::<u32, usize>(2).unwrap(), row.take::<u32, usize>(3).unwrap()),
+            params!(
+                "server_id" => self.server_id,
+                "instance_id" => instance_id,
+                "map_id" => map_id as u16,
+                "latest_start_ts" => start_ts + MERGE_WINDOW,
+                "earliest_end_ts" => start_ts.saturating_sub(MERGE_WINDOW)
+            ),
+        );
+        let (instance_meta_id, entered, canonical_member_id, last_event_id) = match instance {
+            Some(instance) => instance,
+            None => return false,
+        };
+
+        // E.g. if the instance already expired or the server was restarted since
+        let canonical_key = (instance_id, canonical_member_id);
+        if !self.active_instances.contains_key(&canonical_key) {
+            self.active_instances.insert(
+                canonical_key,
+                UnitInstance {
+                    instance_meta_id,
+                    entered,
+                    map_id: map_id as u16,
+                    instance_id,
+                    uploaded_user: canonical_member_id,
+                },
+            );
+            self.committed_events_count.insert(canonical_key, last_event_id);
+            let participants = db_main.select_wparams(
+                "SELECT character_id FROM instance_participants WHERE instance_meta_id=:instance_meta_id",
+                |mut row| row.take::<u32, usize>(0).unwrap(),
+                params!("instance_meta_id" => instance_meta_id),
+            );
+            self.instance_participants.insert(instance_meta_id, participants.into_iter().collect());
+        }
+
+        let mut merged_instance = MergedInstance::new(instance_meta_id, canonical_member_id);
+        load_saved_events(&mut merged_instance, self.server_id);
+        self.merged_instances.insert((instance_id, member_id), merged_instance);
+
+        db_main.execute_wparams(
+            "UPDATE instance_meta SET start_ts=LEAST(start_ts, :start_ts) WHERE id=:instance_meta_id",
+            params!("start_ts" => start_ts, "instance_meta_id" => instance_meta_id),
+        );
+        db_main.execute_wparams(
+            "INSERT IGNORE INTO instance_uploader (`instance_meta_id`, `member_id`) VALUES (:instance_meta_id, :member_id)",
+            params!("instance_meta_id" => instance_meta_id, "member_id" => member_id),
+        );
+        true
+    }
+
+    pub fn release_merged_instances(&mut self, instance_meta_id: u32) {
+        self.merged_instances.retain(|_, merged_instance| merged_instance.instance_meta_id != instance_meta_id);
+    }
+
+    /// Drops the events of the uploader that were already saved by another uploader of the instance.
+    /// The remaining events are saved along, which fills the gaps of the other uploads, e.g. due to a smaller combat log range.
+    pub fn deduplicate_merged_events(&mut self, now: u64, member_id: u32) {
+        for ((instance_id, _), merged_instance) in self.merged_instances.iter_mut().filter(|((_, merged_member_id), _)| *merged_member_id == member_id) {
+            if let Some(committed_events) = self.committed_events.get_mut(&(*instance_id, merged_instance.canonical_member_id)) {
+                // Same events that are about to be saved
+                if let Some(extraction_index) = committed_events.iter().rposition(|event| event.timestamp + 2000 < now) {
+                    let mut last_timestamp = 0;
+                    let mut remaining_events = Vec::with_capacity(extraction_index + 1);
+                    for mut event in committed_events.drain(..(extraction_index + 1)) {
+                        last_timestamp = last_timestamp.max(event.timestamp);
+                        remap_event_ids(&mut event, merged_instance);
+                        index_saved_events(merged_instance, &event.event);
+                        if let Some((_, timestamp, fingerprint)) = event_fingerprint(&event.event, &event.deserialize()) {
+                            if let Some(saved_event_id) = merged_instance.take_saved_event(fingerprint, timestamp) {
+                                merged_instance.map_event_id(event.id, saved_event_id, timestamp);
+                                continue;
+                            }
+                        }
+                        remaining_events.push(event);
+                    }
+                    // Subjects are committed one after another, hence the events are not necessarily in order
+                    remaining_events.sort_by_key(|event| event.timestamp);
+                    for event in remaining_events.into_iter().rev() {
+                        committed_events.push_front(event);
+                    }
+                    merged_instance.prune(last_timestamp.saturating_sub(MERGE_PRUNE_MARGIN));
+                }
+            }
+        }
+    }
+}
+
+/// Another uploader may have committed the same attempt already, which is then completed by this one.
+/// Returns false if there is no such attempt.
+/// The spec and role of new ranking rows are looked up in the given specs.
+pub fn merge_attempt(db_main: &mut (impl Execute + Select), instance_meta_id: u32, encounter_id: u32, is_kill: bool, attempt: &Attempt, specs: &HashMap<u32, (Option<u8>, Option<u8>)>) -> bool {
+    let saved_attempt = db_main.select_wparams_value(
+        "SELECT id, is_kill FROM `instance_attempt` WHERE instance_meta_id=:instance_meta_id AND encounter_id=:encounter_id AND start_ts<=:end_ts AND end_ts>=:start_ts LIMIT 1",
+        |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<bool, usize>(1).unwrap()),
+        params!(
+            "instance_meta_id" => instance_meta_id,
+            "encounter_id" => encounter_id,
+            "start_ts" => attempt.start_ts.saturating_sub(MERGE_TIMESTAMP_TOLERANCE),
+            "end_ts" => attempt.end_ts + MERGE_TIMESTAMP_TOLERANCE
+        ),
+    );
+    let (attempt_id, was_kill) = match saved_attempt {
+        Some(saved_attempt) => saved_attempt,
+        None => return false,
+    };
+
+    db_main.execute_wparams(
+        "UPDATE `instance_attempt` SET start_ts=LEAST(start_ts, :start_ts), end_ts=GREATEST(end_ts, :end_ts), is_kill=:is_kill WHERE id=:attempt_id",
+        params!("start_ts" => attempt.start_ts, "end_ts" => attempt.end_ts, "is_kill" => was_kill || is_kill, "attempt_id" => attempt_id),
+    );
+
+    // Rankings are only collected for kills
+    if is_kill {
+        merge_ranking(db_main, "instance_ranking_damage", "damage", attempt_id, &attempt.ranking_damage, Some(specs));
+        merge_ranking(db_main, "instance_ranking_heal", "heal", attempt_id, &attempt.ranking_heal, Some(specs));
+        merge_ranking(db_main, "instance_ranking_threat", "threat", attempt_id, &attempt.ranking_threat, Some(specs));
+        merge_ranking(db_main, "instance_ranking_active_time", "active_time", attempt_id, &attempt.ranking_active_time, None);
+    }
+    true
+}
+
+fn merge_ranking<T>(db_main: &mut (impl Execute + Select), table: &str, column: &str, attempt_id: u32, ranking: &HashMap<u32, T>, specs: Option<&HashMap<u32, (Option<u8>, Option<u8>)>>)
+where
+    T: Copy,
+    Value: From<T>,
+{
+    let ranked_characters = db_main
+        .select_wparams(
+            &format!("SELECT character_id FROM `{}` WHERE attempt_id=:attempt_id", table),
+            |mut row| row.take::<u32, usize>(0).unwrap(),
+            params!("attempt_id" => attempt_id),
+        )
+        .into_iter()
+        .collect::<BTreeSet<u32>>();
+
+    for (character_id, amount) in ranking.iter().map(|(character_id, amount)| (*character_id, *amount)) {
+        // Each upload only sees a part of the attempt, hence the higher amount is the more complete one
+        if ranked_characters.contains(&character_id) {
+            db_main.execute_wparams(
+                &format!("UPDATE `{}` SET `{}`=GREATEST(`{}`, :amount) WHERE attempt_id=:attempt_id AND character_id=:character_id", table, column, column),
+                params!("amount" => amount, "attempt_id" => attempt_id, "character_id" => character_id),
+            );
+        } else if let Some(specs) = specs {
+            let (spec_id, role) = specs.get(&character_id).cloned().unwrap_or((None, None));
+            db_main.execute_wparams(
+                &format!(
+                    "INSERT INTO `{}` (`character_id`, `attempt_id`, `{}`, `spec_id`, `role`) VALUES (:character_id, :attempt_id, :amount, :spec_id, :role)",
+                    table, column
+                ),
+                params!("character_id" => character_id, "attempt_id" => attempt_id, "amount" => amount, "spec_id" => spec_id, "role" => role),
+            );
+        } else {
+            db_main.execute_wparams(
+                &format!("INSERT INTO `{}` (`character_id`, `attempt_id`, `{}`) VALUES (:character_id, :attempt_id, :amount)", table, column),
+                params!("character_id" => character_id, "attempt_id" => attempt_id, "amount" => amount),
+            );
+        }
+    }
+}
+
+/// Identifies a saved event by its content, regardless of the event ids that were assigned by the upload.
+/// Returns the event id, the timestamp and the fingerprint.
+pub fn event_fingerprint(event_type: &EventType, line: &str) -> Option<(u32, u64, u64)> {
+    if !line.starts_with('[') || !line.ends_with(']') {
+        return None;
+    }
+    let mut segments = line[1..line.len() - 1].splitn(3, ',');
+    let event_id = u32::from_str(segments.next()?).ok()?;
+    let timestamp = u64::from_str(segments.next()?).ok()?;
+    let mut content = segments.next()?;
+
+    // These start with the ids of the events that they refer to
+    let num_referenced_event_ids = match event_type {
+        EventType::Interrupt { .. } | EventType::Threat { .. } | EventType::SpellDamage { .. } | EventType::Heal { .. } => 1,
+        EventType::SpellSteal { .. } | EventType::Dispel { .. } => 2,
+        _ => 0,
+    };
+    for _ in 0..num_referenced_event_ids {
+        content = &content[(content.find(',')? + 1)..];
+    }
+
+    let mut hasher = DefaultHasher::new();
+    event_type.to_u8().hash(&mut hasher);
+    content.hash(&mut hasher);
+    Some((event_id, timestamp, hasher.finish()))
+}
+
+fn remap_event_ids(event: &mut Event, merged_instance: &MergedInstance) {
+    let remap = |referenced_event: &mut Box<Event>| {
+        if let Some(saved_event_id) = merged_instance.get_mapped_event_id(referenced_event.id) {
+            referenced_event.id = saved_event_id;
+        }
+    };
+    match &mut event.event {
+        EventType::Interrupt { cause_event, .. } | EventType::Threat { cause_event, .. } | EventType::SpellDamage { spell_cause: cause_event, .. } | EventType::Heal { spell_cause: cause_event, .. } => remap(cause_event),
+        EventType::SpellSteal { cause_event, target_event } | EventType::Dispel { cause_event, target_event } => {
+            remap(cause_event);
+            remap(target_event);
+        },
+        _ => {},
+    }
+}
+
+fn load_saved_events(merged_instance: &mut MergedInstance, server_id: u32) {
+    let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set!");
+    let instance_path = format!("{}/{}/{}", storage_path, server_id, merged_instance.instance_meta_id);
+    for event_type in 0..16 {
+        let lines = event_storage::read_events(&instance_path, event_type).unwrap_or_default().into_iter().map(|(_, line)| line).collect::<Vec<String>>();
+        if !lines.is_empty() {
+            merged_instance.insert_saved_lines(event_type, lines);
+        }
+    }
+}
+
+/// Saved events are only known by their type id, hence they are fingerprinted once an event of that type is merged
+fn index_saved_events(merged_instance: &mut MergedInstance, event_type: &EventType) {
+    for line in merged_instance.take_saved_lines(event_type.to_u8()) {
+        if let Some((event_id, timestamp, fingerprint)) = event_fingerprint(event_type, &line) {
+            merged_instance.insert_saved_event(fingerprint, timestamp, event_id);
+        }
+    }
+}
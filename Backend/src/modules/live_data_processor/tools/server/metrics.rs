@@ -0,0 +1,29 @@
+use crate::modules::live_data_processor::LiveDataProcessor;
+use std::time::Duration;
+
+static METRICS_INTERVAL: u64 = 15;
+
+impl LiveDataProcessor {
+    pub fn init_server_metrics(self) -> Self {
+        let me = self.clone();
+        std::thread::spawn(move || loop {
+            me.update_server_metrics();
+            std::thread::sleep(Duration::from_secs(METRICS_INTERVAL));
+        });
+        self
+    }
+
+    pub fn update_server_metrics(&self) {
+        let servers = self.servers.read().unwrap();
+        // Poisoned servers are skipped until they are rebuilt
+        for server in servers.values().filter_map(|server| server.read().ok()) {
+            let server_id = server.server_id.to_string();
+            for (collection, num_entries) in server.get_state_sizes() {
+                self.metrics.state_entries.with_label_values(&[&server_id, collection]).set(num_entries as i64);
+            }
+
+            let evicted_instances = self.metrics.evicted_instances.with_label_values(&[&server_id]);
+            evicted_instances.inc_by(server.num_evicted_instances.saturating_sub(evicted_instances.get()));
+        }
+    }
+}
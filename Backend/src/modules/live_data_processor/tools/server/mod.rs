@@ -1,11 +1,16 @@
 pub use self::dispel::try_parse_dispel;
 pub use self::instance_reset::HandleInstanceReset;
 pub use self::interrupt::try_parse_interrupt;
+pub use self::merge::{event_fingerprint, merge_attempt};
 pub use self::spell_steal::try_parse_spell_steal;
 
 mod dispel;
+mod eviction;
 mod instance_reset;
 mod interrupt;
+mod merge;
+mod metrics;
 pub mod server;
 pub mod server_post_processing;
+mod snapshot;
 mod spell_steal;
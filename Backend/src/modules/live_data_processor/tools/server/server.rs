@@ -2,17 +2,22 @@ use crate::modules::armory::tools::GetArenaTeam;
 use crate::modules::armory::Armory;
 use crate::modules::data::tools::RetrieveSpell;
 use crate::modules::data::Data;
-use crate::modules::live_data_processor::domain_value::{hit_mask_from_u32, school_mask_from_u8, AuraApplication, Event, EventParseFailureAction, EventType, Mitigation, Position, Power, PowerType, School, SpellComponent, Unit, UnitInstance};
-use crate::modules::live_data_processor::dto::{get_damage_components_total, CombatState, Death, Loot, Summon};
+use crate::modules::live_data_processor::domain_value::{
+    hit_mask_from_u32, school_mask_from_u8, AuraApplication, Event, EventParseFailureAction, EventType, Mitigation, NonCommittedEvent, Position, Power, PowerType, School, SpellComponent, Unit, UnitInstance,
+};
+use crate::modules::live_data_processor::dto::{get_damage_components_total, CombatState, Death, EventCommitOutcomes, Loot, Summon};
 use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, Message, MessageType};
 use crate::modules::live_data_processor::material::Server;
 use crate::modules::live_data_processor::tools::server::{try_parse_dispel, try_parse_interrupt, try_parse_spell_steal};
-use crate::modules::live_data_processor::tools::MapUnit;
+use crate::modules::live_data_processor::tools::{LookupCharacter, MapUnit, ResolveCharacter};
 use crate::modules::live_data_processor::{domain_value, dto};
 use crate::params;
 use crate::util::database::{Execute, Select};
 use std::collections::{BTreeSet, VecDeque};
 
+// How long the events of a subject may wait to be committed
+const NON_COMMITTED_EVENT_TIMEOUT: u64 = 2000;
+
 impl Server {
     pub fn parse_events(&mut self, db_main: &mut (impl Select + Execute), armory: &Armory, data: &Data, messages: Vec<Message>, member_id: u32) -> Result<(), LiveDataProcessorFailure> {
         let mut next_reset = 0;
@@ -23,15 +28,54 @@ impl Server {
             self.test_for_committable_events(db_main, data, armory, member_id);
             self.cleanup(msg.timestamp);
             if next_reset < msg.timestamp || next_reset == u64::MAX {
-                next_reset = self.reset_instances(db_main, msg.timestamp, member_id);
+                next_reset = self.reset_instances(db_main, msg.timestamp);
             }
             self.push_non_committed_event(msg);
         }
-        self.perform_post_processing(db_main, u64::MAX, data);
+        self.perform_post_processing(db_main, u64::MAX, data, member_id)?;
         println!("Done");
         Ok(())
     }
 
+    /// Runs the messages through the commit step. Players are only looked up in the armory and nothing is written to the database.
+    pub fn dry_run_events(&mut self, armory: &Armory, data: &Data, messages: Vec<Message>) -> EventCommitOutcomes {
+        // Nothing is attributed to an uploader
+        const MEMBER_ID: u32 = 0;
+
+        let mut commit_outcomes = EventCommitOutcomes::default();
+        for msg in messages {
+            match &msg.message_type {
+                MessageType::Summon(summon) => self.extract_summon(&mut LookupCharacter, armory, summon),
+                MessageType::InstanceMap(dto::InstanceMap { map_id, instance_id, unit, .. }) => {
+                    if is_raid_map(*map_id) {
+                        self.unit_instance_id.insert(unit.unit_id, *instance_id);
+                        self.unit_instance_id.insert(0, *instance_id);
+                    } else {
+                        self.unit_instance_id.remove(&unit.unit_id);
+                    }
+                },
+                _ => {},
+            }
+            // Waiting subjects are tried again with every message, hence only the events that time out while waiting are counted
+            let mut outcomes = self.test_for_committable_events(&mut LookupCharacter, data, armory, MEMBER_ID);
+            outcomes.wait = self.count_waiting_events(msg.timestamp);
+            commit_outcomes.merge(outcomes);
+            self.committed_events.clear();
+            self.cleanup(msg.timestamp);
+            self.push_non_committed_event(msg);
+        }
+        commit_outcomes
+    }
+
+    /// Events of subjects within an instance that are still not committed once they time out at the given timestamp
+    pub fn count_waiting_events(&self, current_timestamp: u64) -> u64 {
+        self.non_committed_events
+            .iter()
+            .filter(|(subject_id, events)| self.unit_instance_id.contains_key(*subject_id) && is_timed_out(events, current_timestamp))
+            .map(|(_, events)| events.len() as u64)
+            .sum()
+    }
+
     fn push_non_committed_event(&mut self, message: Message) {
         if let Some(unit_dto) = message.message_type.extract_subject() {
             let non_committed_events = self.non_committed_events.entry(unit_dto.unit_id).or_insert_with(|| VecDeque::with_capacity(1));
@@ -44,16 +88,19 @@ impl Server {
         }
     }
 
-    fn test_for_committable_events(&mut self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, member_id: u32) {
+    fn test_for_committable_events(&mut self, characters: &mut impl ResolveCharacter, data: &Data, armory: &Armory, member_id: u32) -> EventCommitOutcomes {
+        let mut commit_outcomes = EventCommitOutcomes::default();
         let mut remove_first_non_committed_event = Vec::new();
         for (subject_id, first_message) in self.non_committed_events.iter().map(|(subject_id, nce)| (*subject_id, nce.front().unwrap().clone())).collect::<Vec<(u64, Message)>>() {
             if let Some(unit_instance_id) = self.unit_instance_id.get(&subject_id).cloned() {
-                match self.commit_event(db_main, data, armory, first_message, member_id) {
+                let instance_key = self.instance_key(unit_instance_id, member_id);
+                match self.commit_event(characters, data, armory, first_message, member_id) {
                     Ok(mut committable_event) => {
                         // For all except Spell we want to only remove the first event
                         remove_first_non_committed_event.push(subject_id);
+                        commit_outcomes.committed += 1;
 
-                        let committed_event_count = self.committed_events_count.entry((unit_instance_id, member_id)).or_insert(1);
+                        let committed_event_count = self.committed_events_count.entry(instance_key).or_insert(1);
                         committable_event.id = *committed_event_count;
                         *committed_event_count += 1;
 
@@ -61,28 +108,32 @@ impl Server {
                             EventType::SpellCast(_spell_cast) => {
                                 // if !spell_cast.hit_mask.contains(&domain_value::HitType::Miss) && !spell_cast.hit_mask.contains(&domain_value::HitType::FullResist) {
                                 self.recently_committed_spell_cast_and_aura_applications
-                                    .entry((unit_instance_id, member_id))
+                                    .entry(instance_key)
                                     .or_insert_with(|| VecDeque::with_capacity(1))
                                     .push_back(committable_event.clone())
                                 // }
                             }
                             EventType::AuraApplication(_) => self
                                 .recently_committed_spell_cast_and_aura_applications
-                                .entry((unit_instance_id, member_id))
+                                .entry(instance_key)
                                 .or_insert_with(|| VecDeque::with_capacity(1))
                                 .push_back(committable_event.clone()),
                             _ => {}
                         };
 
-                        self.committed_events.entry((unit_instance_id, member_id)).or_insert_with(|| VecDeque::with_capacity(1)).push_back(committable_event);
+                        self.committed_events.entry(instance_key).or_insert_with(|| VecDeque::with_capacity(1)).push_back(committable_event);
                     }
                     Err(EventParseFailureAction::DiscardFirst) => {
                         remove_first_non_committed_event.push(subject_id);
+                        commit_outcomes.discard_first += 1;
                     }
                     Err(EventParseFailureAction::PrependNext) => {
                         self.subject_prepend_mode_set.insert(subject_id);
+                        commit_outcomes.prepend_next += 1;
+                    }
+                    Err(EventParseFailureAction::Wait) => {
+                        commit_outcomes.wait += 1;
                     }
-                    Err(EventParseFailureAction::Wait) => {}
                 };
             } else {
                 remove_first_non_committed_event.push(subject_id);
@@ -96,13 +147,14 @@ impl Server {
                 self.non_committed_events.remove(&subject_id);
             }
         }
+        commit_outcomes
     }
 
     fn cleanup(&mut self, current_timestamp: u64) {
         for subject_id in self
             .non_committed_events
             .iter()
-            .filter(|(_subject_id, event)| event.front().expect("Should be initialized with at least one element").timestamp + 2000 < current_timestamp)
+            .filter(|(_subject_id, events)| is_timed_out(events, current_timestamp))
             .map(|(subject_id, _event)| *subject_id)
             .collect::<Vec<u64>>()
         {
@@ -127,18 +179,20 @@ impl Server {
 
     // So based on the next event for the current users in the system
     // we are going to decide whether or not to commit it.
-    fn commit_event(&mut self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, first_message: Message, member_id: u32) -> Result<Event, EventParseFailureAction> {
+    fn commit_event(&mut self, characters: &mut impl ResolveCharacter, data: &Data, armory: &Armory, first_message: Message, member_id: u32) -> Result<Event, EventParseFailureAction> {
         match first_message.message_type {
             // Events that are just of size 1
             MessageType::CombatState(CombatState { unit: unit_dto, in_combat }) => {
-                let subject = unit_dto.to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons).map_err(|_| EventParseFailureAction::DiscardFirst);
+                let subject = unit_dto
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
+                    .map_err(|_| EventParseFailureAction::DiscardFirst);
                 Ok(Event::new(first_message.message_count, first_message.timestamp, subject?, EventType::CombatState { in_combat }))
             }
             MessageType::Loot(Loot { unit: unit_dto, item_id, count }) => Ok(Event::new(
                 first_message.message_count,
                 first_message.timestamp,
                 unit_dto
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?,
                 EventType::Loot { item_id, amount: count },
             )),
@@ -147,7 +201,7 @@ impl Server {
                 first_message.timestamp,
                 position
                     .unit
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?,
                 EventType::Position(Position {
                     x: position.x,
@@ -161,7 +215,7 @@ impl Server {
                 first_message.timestamp,
                 power
                     .unit
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?,
                 EventType::Power(Power {
                     power_type: PowerType::from_u8(power.power_type).ok_or(EventParseFailureAction::DiscardFirst)?,
@@ -174,12 +228,12 @@ impl Server {
                 first_message.timestamp,
                 aura_application
                     .target
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?,
                 EventType::AuraApplication(AuraApplication {
                     caster: aura_application
                         .caster
-                        .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                        .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                         .map_err(|_| EventParseFailureAction::DiscardFirst)?,
                     stack_amount: aura_application.stack_amount,
                     spell_id: aura_application.spell_id,
@@ -192,14 +246,16 @@ impl Server {
             MessageType::Death(Death { cause, victim }) => Ok(Event::new(
                 first_message.message_count,
                 first_message.timestamp,
-                victim.to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons).map_err(|_| EventParseFailureAction::DiscardFirst)?,
+                victim
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
+                    .map_err(|_| EventParseFailureAction::DiscardFirst)?,
                 EventType::Death {
-                    murder: cause.as_ref().and_then(|cause| cause.to_unit_add_implicit(&mut self.cache_unit, db_main, &armory, self.server_id, &self.summons).ok()),
+                    murder: cause.as_ref().and_then(|cause| cause.to_unit_add_implicit(&mut self.cache_unit, characters, &armory, self.server_id, &self.summons).ok()),
                 },
             )),
             MessageType::Event(event_dto) => {
                 if event_dto.event_type == 0 {
-                    if let Ok(creature @ domain_value::Unit::Creature(_)) = event_dto.unit.to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons) {
+                    if let Ok(creature @ domain_value::Unit::Creature(_)) = event_dto.unit.to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons) {
                         // TODO: Is the creature really the unit that we want to return here?
                         return Ok(Event::new(first_message.message_count, first_message.timestamp, creature, EventType::ThreatWipe));
                     }
@@ -209,25 +265,28 @@ impl Server {
             MessageType::Summon(summon) => {
                 let summoner = summon
                     .owner
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let summoned = summon
                     .unit
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 Ok(Event::new(first_message.message_count, first_message.timestamp, summoner, EventType::Summon { summoned }))
             }
             MessageType::SpellCast(spell_cast) => {
                 let subject = spell_cast
                     .caster
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 Ok(Event::new(
                     first_message.message_count,
                     first_message.timestamp,
                     subject,
                     EventType::SpellCast(domain_value::SpellCast {
-                        victim: spell_cast.target.as_ref().and_then(|victim| victim.to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons).ok()),
+                        victim: spell_cast
+                            .target
+                            .as_ref()
+                            .and_then(|victim| victim.to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons).ok()),
                         hit_mask: hit_mask_from_u32(spell_cast.hit_mask),
                         spell_id: spell_cast.spell_id,
                         school_mask: data
@@ -240,11 +299,11 @@ impl Server {
             MessageType::MeleeDamage(melee_damage) => {
                 let subject = melee_damage
                     .attacker
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let victim = melee_damage
                     .victim
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let total_damage = get_damage_components_total(&melee_damage.damage_components) as f64;
                 Ok(Event::new(
@@ -282,11 +341,11 @@ impl Server {
             MessageType::SpellDamage(spell_damage) => {
                 let subject = spell_damage
                     .attacker
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let victim = spell_damage
                     .victim
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let spell_cause = self.find_matching_spell_cause(spell_damage.spell_id.unwrap(), spell_damage.attacker.unit_id, &subject, &victim, first_message.message_count, Some(spell_damage.damage_over_time), member_id)?;
                 let total_damage = get_damage_components_total(&spell_damage.damage_components) as f64;
@@ -328,11 +387,11 @@ impl Server {
             MessageType::Heal(heal_done) => {
                 let subject = heal_done
                     .caster
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let target = heal_done
                     .target
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let spell_cause = self.find_matching_spell_cause(heal_done.spell_id, heal_done.caster.unit_id, &subject, &target, first_message.message_count, None, member_id)?;
                 Ok(Event::new(
@@ -356,11 +415,11 @@ impl Server {
             MessageType::Threat(threat) => {
                 let subject = threat
                     .threater
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let threatened = threat
                     .threatened
-                    .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                    .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                     .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                 let cause_event = self.find_matching_threat_cause(threat.spell_id, threat.threater.unit_id, &subject, &threatened, member_id)?;
                 Ok(Event::new(
@@ -377,10 +436,10 @@ impl Server {
             MessageType::Interrupt(interrupt) => {
                 // If we dont find any committable events for this interrupt, we need to discard
                 if let Some(unit_instance_id) = self.unit_instance_id.get(&interrupt.target.unit_id) {
-                    if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&(*unit_instance_id, member_id)) {
+                    if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&self.instance_key(*unit_instance_id, member_id)) {
                         let subject = interrupt
                             .target
-                            .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                            .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                             .map_err(|_| EventParseFailureAction::DiscardFirst)?;
                         return match try_parse_interrupt(committed_events, &subject) {
                             Ok(cause_event) => Ok(Event::new(
@@ -402,12 +461,12 @@ impl Server {
             MessageType::Dispel(dispel) => {
                 // If we dont find any committable events for this interrupt, we need to discard
                 if let Some(unit_instance_id) = self.unit_instance_id.get(&dispel.un_aura_caster.unit_id) {
-                    if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&(*unit_instance_id, member_id)) {
+                    if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&self.instance_key(*unit_instance_id, member_id)) {
                         let subject = dispel
                             .un_aura_caster
-                            .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                            .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                             .map_err(|_| EventParseFailureAction::DiscardFirst)?;
-                        return match try_parse_dispel(db_main, &dispel, committed_events, armory, self.server_id, &self.summons, &mut self.cache_unit) {
+                        return match try_parse_dispel(characters, &dispel, committed_events, armory, self.server_id, &self.summons, &mut self.cache_unit) {
                             Ok((cause_event, target_event)) => Ok(Event::new(
                                 first_message.message_count,
                                 first_message.timestamp,
@@ -427,12 +486,12 @@ impl Server {
             MessageType::SpellSteal(spell_steal) => {
                 // If we dont find any committable events for this interrupt, we need to discard
                 if let Some(unit_instance_id) = self.unit_instance_id.get(&spell_steal.un_aura_caster.unit_id) {
-                    if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&(*unit_instance_id, member_id)) {
+                    if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&self.instance_key(*unit_instance_id, member_id)) {
                         let subject = spell_steal
                             .un_aura_caster
-                            .to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons)
+                            .to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons)
                             .map_err(|_| EventParseFailureAction::DiscardFirst)?;
-                        return match try_parse_spell_steal(db_main, &spell_steal, committed_events, first_message.timestamp, armory, self.server_id, &self.summons, &mut self.cache_unit) {
+                        return match try_parse_spell_steal(characters, &spell_steal, committed_events, first_message.timestamp, armory, self.server_id, &self.summons, &mut self.cache_unit) {
                             Ok((cause_event, target_event)) => Ok(Event::new(
                                 first_message.message_count,
                                 first_message.timestamp,
@@ -454,15 +513,10 @@ impl Server {
 
     fn extract_meta_information(&mut self, db_main: &mut (impl Select + Execute), armory: &Armory, message: &Message, member_id: u32) {
         match &message.message_type {
-            MessageType::Summon(Summon { owner, unit }) => {
-                let summoner = owner.to_unit_add_implicit(&mut self.cache_unit, db_main, armory, self.server_id, &self.summons);
-                if let Ok(summoner_unit) = summoner {
-                    self.summons.insert(unit.unit_id, summoner_unit);
-                }
-            }
+            MessageType::Summon(summon) => self.extract_summon(db_main, armory, summon),
             MessageType::InstanceMap(dto::InstanceMap { map_id, instance_id, map_difficulty, unit }) => {
                 // These are raids
-                if let 249 | 309 | 409 | 469 | 509 | 531 | 532 | 533 | 534 | 544 | 548 | 550 | 564 | 565 | 568 | 580 | 603 | 615 | 616 | 624 | 631 | 649 | 724 = *map_id {
+                if is_raid_map(*map_id) {
                     if let Some(instance_meta_id) = self.create_instance_meta(db_main, message.timestamp, *instance_id, *map_id, member_id) {
                         // Vanilla does usually not set difficulty for raids correctly
                         // Nor does TBC
@@ -494,7 +548,7 @@ impl Server {
                 }
 
                 // Insert participants
-                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&(*instance_id, member_id)) {
+                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&self.instance_key(*instance_id, member_id)) {
                     if !self.instance_participants.contains_key(instance_meta_id) {
                         self.instance_participants.insert(*instance_meta_id, BTreeSet::new());
                     }
@@ -544,7 +598,8 @@ impl Server {
                                                         score_horde,
                                                         ..
                                                     }) => {
-                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&(*instance_id, member_id)) {
+                let instance_key = self.instance_key(*instance_id, member_id);
+                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&instance_key) {
                     self.finalize_instance_meta(db_main, message.timestamp, *instance_meta_id);
                     db_main.execute_wparams(
                         "UPDATE instance_battleground SET `winner`=:winner, `score_alliance`=:score_alliance, `score_horde`=:score_horde WHERE instance_meta_id=:instance_meta_id",
@@ -555,9 +610,8 @@ impl Server {
                             "score_horde" => *score_horde
                         ),
                     );
-                    self.instance_participants.remove(instance_meta_id);
-                    self.active_instances.remove(&(*instance_id, member_id));
-                    self.active_attempts.remove(&(*instance_id, member_id));
+                    let instance_meta_id = *instance_meta_id;
+                    self.schedule_eviction(instance_key, instance_meta_id, message.timestamp);
                 }
             }
             MessageType::InstancePvPEndRatedArena(dto::InstanceArena {
@@ -567,7 +621,8 @@ impl Server {
                                                       team_change2,
                                                       ..
                                                   }) => {
-                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&(*instance_id, member_id)) {
+                let instance_key = self.instance_key(*instance_id, member_id);
+                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&instance_key) {
                     self.finalize_instance_meta(db_main, message.timestamp, *instance_meta_id);
                     db_main.execute_wparams(
                         "UPDATE instance_rated_arena SET `winner`=:winner, `team_change1`=:team_change1, `team_change2`=:team_change2 WHERE instance_meta_id=:instance_meta_id",
@@ -578,13 +633,13 @@ impl Server {
                             "team_change2" => *team_change2
                         ),
                     );
-                    self.instance_participants.remove(instance_meta_id);
-                    self.active_instances.remove(&(*instance_id, member_id));
-                    self.active_attempts.remove(&(*instance_id, member_id));
+                    let instance_meta_id = *instance_meta_id;
+                    self.schedule_eviction(instance_key, instance_meta_id, message.timestamp);
                 }
             }
             MessageType::InstancePvPEndUnratedArena(dto::InstanceUnratedArena { instance_id, winner, .. }) => {
-                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&(*instance_id, member_id)) {
+                let instance_key = self.instance_key(*instance_id, member_id);
+                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&instance_key) {
                     self.finalize_instance_meta(db_main, message.timestamp, *instance_meta_id);
                     db_main.execute_wparams(
                         "UPDATE instance_skirmish `winner`=:winner WHERE instance_meta_id=:instance_meta_id",
@@ -593,17 +648,16 @@ impl Server {
                             "winner" => *winner
                         ),
                     );
-                    self.instance_participants.remove(instance_meta_id);
-                    self.active_instances.remove(&(*instance_id, member_id));
-                    self.active_attempts.remove(&(*instance_id, member_id));
+                    let instance_meta_id = *instance_meta_id;
+                    self.schedule_eviction(instance_key, instance_meta_id, message.timestamp);
                 }
             }
             MessageType::InstanceDelete { instance_id } => {
-                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&(*instance_id, member_id)) {
-                    if self.finalize_instance_meta(db_main, message.timestamp, *instance_meta_id) {
-                        self.instance_participants.remove(instance_meta_id);
-                        self.active_instances.remove(&(*instance_id, member_id));
-                        self.active_attempts.remove(&(*instance_id, member_id));
+                let instance_key = self.instance_key(*instance_id, member_id);
+                if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&instance_key) {
+                    let instance_meta_id = *instance_meta_id;
+                    if self.finalize_instance_meta(db_main, message.timestamp, instance_meta_id) {
+                        self.schedule_eviction(instance_key, instance_meta_id, message.timestamp);
                     }
                 }
             }
@@ -611,9 +665,20 @@ impl Server {
         }
     }
 
+    fn extract_summon(&mut self, characters: &mut impl ResolveCharacter, armory: &Armory, Summon { owner, unit }: &Summon) {
+        let summoner = owner.to_unit_add_implicit(&mut self.cache_unit, characters, armory, self.server_id, &self.summons);
+        if let Ok(summoner_unit) = summoner {
+            self.summons.insert(unit.unit_id, summoner_unit);
+        }
+    }
+
     fn create_instance_meta(&mut self, db_main: &mut (impl Execute + Select), start_ts: u64, instance_id: u32, map_id: u32, member_id: u32) -> Option<u32> {
-        if !self.active_instances.contains_key(&(instance_id, member_id)) {
-            // Maybe sanity check, if active instance already exists, before?
+        if !self.active_instances.contains_key(&self.instance_key(instance_id, member_id)) {
+            // Another uploader may have logged the same instance already
+            if self.merge_into_instance(db_main, start_ts, instance_id, map_id, member_id) {
+                return None;
+            }
+
             if db_main.execute_wparams(
                 "INSERT INTO instance_meta (`server_id`, `start_ts`, `instance_id`, `map_id`, `uploaded_user`) VALUES (:server_id, :start_ts, :instance_id, :map_id, :member_id)",
                 params!(
@@ -656,7 +721,7 @@ impl Server {
 
     fn finalize_instance_meta(&self, db_main: &mut impl Execute, end_ts: u64, instance_meta_id: u32) -> bool {
         db_main.execute_wparams(
-            "UPDATE instance_meta SET end_ts=IF(end_ts IS NULL, :end_ts, end_ts), expired=:end_ts WHERE instance_meta_id=:instance_meta_id",
+            "UPDATE instance_meta SET end_ts=IF(end_ts IS NULL, :end_ts, end_ts), expired=:end_ts WHERE id=:instance_meta_id",
             params!(
                 "end_ts" => end_ts,
                 "instance_meta_id" => instance_meta_id
@@ -665,10 +730,11 @@ impl Server {
     }
 
     /// Returns timestamp when the next reset is required
-    pub fn reset_instances(&mut self, db_main: &mut impl Execute, now: u64, member_id: u32) -> u64 {
-        for ((instance_id, _), instance_meta_id) in self
+    pub fn reset_instances(&mut self, db_main: &mut impl Execute, now: u64) -> u64 {
+        for (instance_key, instance_meta_id) in self
             .active_instances
             .iter()
+            .filter(|(instance_key, _)| !self.finalized_instances.contains_key(instance_key))
             .filter(|(_, active_instance)| {
                 if let Some(instance_reset) = self.instance_resets.get(&active_instance.map_id) {
                     return active_instance.entered <= instance_reset.reset_time && now > instance_reset.reset_time;
@@ -679,9 +745,7 @@ impl Server {
             .collect::<Vec<((u32, u32), u32)>>()
         {
             if self.finalize_instance_meta(db_main, now, instance_meta_id) {
-                self.active_instances.remove(&(instance_id, member_id));
-                self.instance_participants.remove(&instance_meta_id);
-                self.active_attempts.remove(&(instance_id, member_id));
+                self.schedule_eviction(instance_key, instance_meta_id, now);
             }
         }
         self.instance_resets
@@ -692,7 +756,7 @@ impl Server {
 
     fn find_matching_spell_cause(&self, spell_id: u32, subject_unit_id: u64, subject: &Unit, victim: &Unit, message_count: u64, is_dot: Option<bool>, member_id: u32) -> Result<Event, EventParseFailureAction> {
         if let Some(unit_instance_id) = self.unit_instance_id.get(&subject_unit_id) {
-            if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&(*unit_instance_id, member_id)) {
+            if let Some(committed_events) = self.recently_committed_spell_cast_and_aura_applications.get(&self.instance_key(*unit_instance_id, member_id)) {
                 if let Some(event_index) = committed_events.iter().rposition(|event| match &event.event {
                     EventType::SpellCast(spell_cast) => {
                         (is_dot.is_none() || is_dot.contains(&false))
@@ -717,7 +781,7 @@ impl Server {
     // Else its a melee_damage event and the threatened must be the victim
     fn find_matching_threat_cause(&self, spell_id: Option<u32>, subject_unit_id: u64, subject: &Unit, threatened: &Unit, member_id: u32) -> Result<Event, EventParseFailureAction> {
         if let Some(unit_instance_id) = self.unit_instance_id.get(&subject_unit_id) {
-            if let Some(committed_events) = self.committed_events.get(&(*unit_instance_id, member_id)) {
+            if let Some(committed_events) = self.committed_events.get(&self.instance_key(*unit_instance_id, member_id)) {
                 if let Some(event_index) = committed_events.iter().rposition(|event| {
                     if let Some(threatening_spell_id) = spell_id {
                         return event.subject == *subject
@@ -751,3 +815,11 @@ impl Server {
         Err(EventParseFailureAction::PrependNext)
     }
 }
+
+fn is_timed_out(events: &NonCommittedEvent, current_timestamp: u64) -> bool {
+    events.front().expect("Should be initialized with at least one element").timestamp + NON_COMMITTED_EVENT_TIMEOUT < current_timestamp
+}
+
+fn is_raid_map(map_id: u32) -> bool {
+    matches!(map_id, 249 | 309 | 409 | 469 | 509 | 531 | 532 | 533 | 534 | 544 | 548 | 550 | 564 | 565 | 568 | 580 | 603 | 615 | 616 | 624 | 631 | 649 | 724)
+}
@@ -1,23 +1,57 @@
 #![allow(clippy::if_same_then_else)]
 
-use crate::modules::data::tools::{RetrieveEncounterNpc, RetrieveItem};
+use crate::modules::armory::util::talent_tree::get_talent_tree;
+use crate::modules::data::domain_value::EncounterSignal;
+use crate::modules::data::tools::{RetrieveEncounterNpc, RetrieveEncounterRule, RetrieveItem};
 use crate::modules::data::Data;
+use crate::modules::instance::material::Role;
 use crate::modules::live_data_processor::domain_value::get_spell_components_total;
 use crate::modules::live_data_processor::domain_value::{Creature, Event, EventType, Player, Power, PowerType, Unit, UnitInstance};
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
 use crate::modules::live_data_processor::material::{Attempt, Server};
+use crate::modules::live_data_processor::tools::server::merge_attempt;
 use crate::modules::live_data_processor::tools::LiveDataDeserializer;
 use crate::params;
 use crate::util::database::{Execute, Select};
-use std::collections::{HashMap, VecDeque};
-use std::io::Write;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::ops::Div;
 
 impl Server {
-    pub fn perform_post_processing(&mut self, db_main: &mut (impl Execute + Select), now: u64, data: &Data) {
+    pub fn perform_post_processing(&mut self, db_main: &mut (impl Execute + Select), now: u64, data: &Data, member_id: u32) -> Result<(), LiveDataProcessorFailure> {
+        // Events that another uploader already saved must not be counted twice
+        self.deduplicate_merged_events(now, member_id);
         self.extract_attempts_and_collect_ranking(db_main, data, now);
         self.extract_loot(db_main, data, now);
         self.save_current_event_id_and_end_ts(db_main);
-        self.save_committed_events_to_disk(now);
+        let saved = self.save_committed_events_to_disk(now);
+        let evicted = self.evict_finalized_instances(db_main, data);
+        saved.and(evicted)
+    }
+
+    fn evict_finalized_instances(&mut self, db_main: &mut (impl Execute + Select), data: &Data) -> Result<(), LiveDataProcessorFailure> {
+        let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set!");
+        let mut result = Ok(());
+        for (instance_key, (instance_meta_id, finalized)) in self.finalized_instances.clone() {
+            // Waits for the events that are not saved yet
+            if self.committed_events.get(&instance_key).map(|committed_events| !committed_events.is_empty()).unwrap_or(false) {
+                continue;
+            }
+
+            // Attempts that were still ongoing end with the instance
+            let is_merged = self.merged_instances.values().any(|merged_instance| merged_instance.instance_meta_id == instance_meta_id);
+            for (_, mut attempt) in self.active_attempts.remove(&instance_key).unwrap_or_default() {
+                attempt.end_ts = finalized;
+                commit_attempt(db_main, data, instance_meta_id, attempt, is_merged);
+            }
+
+            // No more events are appended, hence the small blocks of each batch are merged.
+            // The events are readable either way, so the instance is evicted regardless.
+            if event_storage::compact_instance(&format!("{}/{}/{}", storage_path, self.server_id, instance_meta_id)).is_err() {
+                result = Err(LiveDataProcessorFailure::StorageFailure(String::from("evict_finalized_instances")));
+            }
+            self.evict_instance(instance_key, instance_meta_id);
+        }
+        result
     }
 
     fn extract_loot(&self, db_main: &mut (impl Execute + Select), data: &Data, now: u64) {
@@ -60,6 +94,8 @@ impl Server {
         for (instance_id, committed_events) in self.committed_events.iter() {
             if let Some(UnitInstance { instance_meta_id, .. }) = self.active_instances.get(&instance_id) {
                 let active_attempts = self.active_attempts.entry(*instance_id).or_insert_with(|| HashMap::with_capacity(1));
+                let infight_players = self.infight_players.entry(*instance_id).or_insert_with(BTreeSet::new);
+                let is_merged = self.merged_instances.values().any(|merged_instance| merged_instance.instance_meta_id == *instance_meta_id);
                 for event in committed_events.iter() {
                     if event.timestamp + 2000 > now {
                         break;
@@ -71,7 +107,14 @@ impl Server {
                                 match &event.event {
                                     EventType::CombatState { in_combat } => {
                                         if *in_combat && (active_attempts.contains_key(&encounter_npc.encounter_id) || encounter_npc.can_start_encounter) {
-                                            let attempt = active_attempts.entry(encounter_npc.encounter_id).or_insert_with(|| Attempt::new(encounter_npc.encounter_id, event.timestamp, data.encounter_has_pivot(encounter_npc.encounter_id)));
+                                            let attempt = active_attempts.entry(encounter_npc.encounter_id).or_insert_with(|| {
+                                                // Players that pulled the encounter are in combat before the attempt began
+                                                let mut attempt = Attempt::new(encounter_npc.encounter_id, event.timestamp, data.encounter_has_pivot(encounter_npc.encounter_id));
+                                                for character_id in infight_players.iter() {
+                                                    attempt.set_player_active(*character_id, event.timestamp);
+                                                }
+                                                attempt
+                                            });
                                             if encounter_npc.requires_death {
                                                 attempt.creatures_required_to_die.insert(*creature_id);
                                             }
@@ -94,7 +137,7 @@ impl Server {
                                             if is_committable {
                                                 if let Some(mut attempt) = active_attempts.remove(&encounter_npc.encounter_id) {
                                                     attempt.end_ts = event.timestamp;
-                                                    commit_attempt(db_main, *instance_meta_id, attempt);
+                                                    commit_attempt(db_main, data, *instance_meta_id, attempt, is_merged);
                                                 }
                                             }
                                         }
@@ -102,24 +145,10 @@ impl Server {
                                     EventType::Death { murder: _ } => {
                                         let mut is_committable = false;
                                         if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
-                                            // Ulduar hardmodes
-                                            // If XTs heart dies, hard mode is activated
-                                            if *entry == 33329 {
-                                                attempt.hard_mode_encounter_id = Some(150);
-                                                attempt.hard_mode_npcs_died.insert(*entry);
-                                            }
-
-                                            // Assembly of iron, order determines hard mode
-                                            if *entry == 32857 || *entry == 32867 || *entry == 32927 {
-                                                attempt.hard_mode_npcs_died.insert(*entry);
-                                                if attempt.hard_mode_npcs_died.len() == 2 && !attempt.hard_mode_npcs_died.contains(&32867) {
-                                                    attempt.hard_mode_encounter_id = Some(151);
-                                                }
-                                            }
-
-                                            // Freya
-                                            if *entry == 33168 {
-                                                attempt.hard_mode_npcs_died.insert(*entry);
+                                            // Hard mode tracking, e.g. XT's heart or the order of the Assembly of Iron
+                                            let signal = EncounterSignal::NpcDeath(*entry);
+                                            if data.is_encounter_signal(encounter_npc.encounter_id, &signal) {
+                                                attempt.add_hard_mode_signal(signal);
                                             }
 
                                             // attempt tracking
@@ -129,14 +158,14 @@ impl Server {
                                                 attempt.creatures_required_to_die.clear();
                                             }
                                             is_committable = attempt.creatures_required_to_die.is_empty() && attempt.infight_player.len() <= KILL_MIN_INFIGHT_UNITS && attempt.infight_vehicle.len() <= KILL_MIN_INFIGHT_UNITS
-                                                // Exception for Naxx KT, as this causes problems for vanilla
-                                                && (attempt.encounter_id != 57 || *entry == 15990);
+                                                // E.g. Naxx KT, as this causes problems for vanilla
+                                                && data.can_death_end_encounter(attempt.encounter_id, *entry);
                                         }
 
                                         if is_committable {
                                             if let Some(mut attempt) = active_attempts.remove(&encounter_npc.encounter_id) {
                                                 attempt.end_ts = event.timestamp;
-                                                commit_attempt(db_main, *instance_meta_id, attempt);
+                                                commit_attempt(db_main, data, *instance_meta_id, attempt, is_merged);
                                             }
                                         }
                                     }
@@ -155,39 +184,17 @@ impl Server {
                                             if is_committable {
                                                 if let Some(mut attempt) = active_attempts.remove(&encounter_npc.encounter_id) {
                                                     attempt.end_ts = event.timestamp;
-                                                    commit_attempt(db_main, *instance_meta_id, attempt);
+                                                    commit_attempt(db_main, data, *instance_meta_id, attempt, is_merged);
                                                 }
                                             }
                                         }
                                     }
                                     EventType::AuraApplication(aura_app) => {
-                                        // Ulduar hard mode tracking
-                                        // Flame Leviathan Tower buffs
-                                        if aura_app.spell_id == 65076 || aura_app.spell_id == 64482 || aura_app.spell_id == 65077 || aura_app.spell_id == 65075 {
+                                        // Hard mode tracking, e.g. Flame Leviathan's towers or Mimiron's emergency mode
+                                        let signal = EncounterSignal::AuraApplication(aura_app.spell_id);
+                                        if data.is_encounter_signal(encounter_npc.encounter_id, &signal) {
                                             if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
-                                                attempt.hard_mode_found_buffs.insert(aura_app.spell_id);
-                                            }
-                                        }
-
-                                        // Freya Guardian buffs
-                                        // || aura_app.spell_id == 62713
-                                        if aura_app.spell_id == 65585 || aura_app.spell_id == 65590 {
-                                            if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
-                                                attempt.hard_mode_found_buffs.insert(aura_app.spell_id);
-                                            }
-                                        }
-
-                                        // Mimiron Hard mode - Emergency mode
-                                        if aura_app.spell_id == 64582 || aura_app.spell_id == 65101 {
-                                            if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
-                                                attempt.hard_mode_found_buffs.insert(aura_app.spell_id);
-                                            }
-                                        }
-
-                                        // General Vezax Hard mode - Saronite Barrier
-                                        if aura_app.spell_id == 63364 {
-                                            if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
-                                                attempt.hard_mode_found_buffs.insert(aura_app.spell_id);
+                                                attempt.add_hard_mode_signal(signal);
                                             }
                                         }
 
@@ -221,24 +228,25 @@ impl Server {
                                                 attempt.end_ts = event.timestamp;
                                                 attempt.pivot_is_finished = true;
                                                 attempt.creatures_required_to_die.clear(); // We assume death if it evades!
-                                                commit_attempt(db_main, *instance_meta_id, attempt);
+                                                commit_attempt(db_main, data, *instance_meta_id, attempt, is_merged);
                                             }
                                         }
                                     }
                                     EventType::SpellCast(spell_cast) => {
-                                        // Thorim Hard mode - Sifs casts
-                                        if spell_cast.spell_id == 62583 || spell_cast.spell_id == 62580 || spell_cast.spell_id == 62597 {
+                                        // Hard mode tracking, e.g. Sif's casts at Thorim
+                                        let signal = EncounterSignal::SpellCast(spell_cast.spell_id);
+                                        if data.is_encounter_signal(encounter_npc.encounter_id, &signal) {
                                             if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
-                                                attempt.hard_mode_found_buffs.insert(spell_cast.spell_id);
+                                                attempt.add_hard_mode_signal(signal);
                                             }
                                         }
                                     }
                                     _ => {}
                                 };
                             } else if let EventType::CombatState { in_combat } = &event.event {
-                                for (_encounter_id, attempt) in active_attempts.iter_mut() {
-                                    // Wyrmrest Skytalon
-                                    if *entry == 32535 {
+                                for (encounter_id, attempt) in active_attempts.iter_mut() {
+                                    // E.g. the Wyrmrest Skytalons at Malygos
+                                    if data.is_encounter_vehicle(*encounter_id, *entry) {
                                         if *in_combat {
                                             attempt.infight_vehicle.insert(*creature_id);
                                         } else {
@@ -252,12 +260,16 @@ impl Server {
                             match &event.event {
                                 EventType::CombatState { in_combat } => {
                                     if *in_combat {
+                                        infight_players.insert(player.character_id);
                                         for (_encounter_id, attempt) in active_attempts.iter_mut() {
                                             attempt.infight_player.insert(player.character_id);
+                                            attempt.set_player_active(player.character_id, event.timestamp);
                                         }
                                     } else {
+                                        infight_players.remove(&player.character_id);
                                         for (_encounter_id, attempt) in active_attempts.iter_mut() {
                                             attempt.infight_player.remove(&player.character_id);
+                                            attempt.set_player_inactive(player.character_id, event.timestamp);
                                         }
                                         // If enough player are OOC and Kill requirements are fulfilled
                                         for (encounter_id, attempt) in active_attempts.clone() {
@@ -270,24 +282,32 @@ impl Server {
                                                 if attempt.creatures_required_to_die.is_empty() {
                                                     if let Some(mut attempt) = active_attempts.remove(&encounter_id) {
                                                         attempt.end_ts = event.timestamp;
-                                                        commit_attempt(db_main, *instance_meta_id, attempt);
+                                                        commit_attempt(db_main, data, *instance_meta_id, attempt, is_merged);
                                                     }
                                                 }
                                                 // Commit As Attempt
                                                 else if attempt.creatures_in_combat.is_empty() {
                                                     if let Some(mut attempt) = active_attempts.remove(&encounter_id) {
                                                         attempt.end_ts = event.timestamp;
-                                                        commit_attempt(db_main, *instance_meta_id, attempt);
+                                                        commit_attempt(db_main, data, *instance_meta_id, attempt, is_merged);
                                                     }
                                                 }
                                             }
                                         }
                                     }
                                 }
+                                EventType::Death { .. } => {
+                                    infight_players.remove(&player.character_id);
+                                    for (_encounter_id, attempt) in active_attempts.iter_mut() {
+                                        attempt.set_player_inactive(player.character_id, event.timestamp);
+                                    }
+                                }
                                 EventType::AuraApplication(aura_app) => {
-                                    if aura_app.spell_id == 62670 || aura_app.spell_id == 62650 || aura_app.spell_id == 62671 || aura_app.spell_id == 62702 {
-                                        if let Some((_, attempt)) = active_attempts.iter_mut().find(|(encounter_id, _)| **encounter_id == 126) {
-                                            attempt.hard_mode_found_buffs.insert(aura_app.spell_id);
+                                    // Hard mode tracking, e.g. the blessings of the Keepers that assist at Yogg-Saron
+                                    let signal = EncounterSignal::AuraApplication(aura_app.spell_id);
+                                    for (encounter_id, attempt) in active_attempts.iter_mut() {
+                                        if data.is_encounter_signal(*encounter_id, &signal) {
+                                            attempt.add_hard_mode_signal(signal);
                                         }
                                     }
                                 }
@@ -308,7 +328,7 @@ impl Server {
                 if let Some(committed_events) = self.committed_events.get(&instance_id) {
                     if let Some(last_entry) = committed_events.back() {
                         db_main.execute_wparams(
-                            "UPDATE instance_meta SET last_event_id=:current_event_id, end_ts=:end_ts WHERE id=:instance_meta_id",
+                            "UPDATE instance_meta SET last_event_id=:current_event_id, end_ts=GREATEST(COALESCE(end_ts, 0), :end_ts) WHERE id=:instance_meta_id",
                             params!("current_event_id" => *current_event_id, "end_ts" => last_entry.timestamp, "instance_meta_id" => instance_meta_id),
                         );
                     }
@@ -317,45 +337,42 @@ impl Server {
         }
     }
 
-    fn save_committed_events_to_disk(&mut self, now: u64) {
+    fn save_committed_events_to_disk(&mut self, now: u64) -> Result<(), LiveDataProcessorFailure> {
         let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set!");
-        let mut open_options = std::fs::File::with_options();
-        open_options.append(true);
-        open_options.create(true);
-
+        let mut result = Ok(());
         for (instance_id, active_instance) in self.active_instances.iter() {
             if let Some(committable_events) = self.committed_events.get_mut(&instance_id) {
-                let _result = std::fs::create_dir_all(format!("{}/{}/{}", storage_path, self.server_id, active_instance.instance_meta_id));
-
                 // Find first event that is committable from the back
                 if let Some(extraction_index) = committable_events.iter().rposition(|event| event.timestamp + 2000 < now) {
+                    let instance_path = format!("{}/{}/{}", storage_path, self.server_id, active_instance.instance_meta_id);
                     let mut drained_events = committable_events.drain(..(extraction_index + 1)).collect::<Vec<Event>>();
-                    drained_events.sort_by(|left, right| {
-                        let by_event_type = left.event.to_u8().cmp(&right.event.to_u8());
-                        if let std::cmp::Ordering::Equal = by_event_type {
-                            return left.timestamp.cmp(&right.timestamp);
-                        }
-                        by_event_type
-                    });
+                    drained_events.sort_by_key(|event| event.timestamp);
 
-                    let mut last_opened_event_type_index = 0;
-                    let mut opened_file = open_options.open(format!("{}/{}/{}/{}", storage_path, self.server_id, active_instance.instance_meta_id, 0));
+                    let mut events_by_event_type: BTreeMap<u8, Vec<Event>> = BTreeMap::new();
                     for event in drained_events {
-                        if event.event.to_u8() != last_opened_event_type_index {
-                            last_opened_event_type_index = event.event.to_u8();
-                            opened_file = opened_file.and_then(|file| {
-                                drop(file);
-                                open_options.open(format!("{}/{}/{}/{}", storage_path, self.server_id, active_instance.instance_meta_id, event.event.to_u8()))
-                            })
+                        events_by_event_type.entry(event.event.to_u8()).or_insert_with(Vec::new).push(event);
+                    }
+
+                    // Events that could not be saved are kept, so they are saved with the next batch
+                    let mut unsaved_events = Vec::new();
+                    let created = std::fs::create_dir_all(&instance_path).is_ok();
+                    for (event_type, events) in events_by_event_type {
+                        let lines = events.iter().map(|event| event.deserialize()).collect::<Vec<String>>();
+                        if !created || event_storage::append_events(&instance_path, event_type, &lines).is_err() {
+                            unsaved_events.extend(events);
                         }
-                        if let Ok(file) = &mut opened_file {
-                            let _ = file.write(event.deserialize().as_bytes());
-                            let _ = file.write(&[10]);
+                    }
+                    if !unsaved_events.is_empty() {
+                        unsaved_events.sort_by_key(|event| event.timestamp);
+                        for event in unsaved_events.into_iter().rev() {
+                            committable_events.push_front(event);
                         }
+                        result = Err(LiveDataProcessorFailure::StorageFailure(String::from("save_committed_events_to_disk")));
                     }
                 }
             }
         }
+        result
     }
 }
 
@@ -366,6 +383,7 @@ fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mu
                 if let Unit::Creature(Creature { entry, .. }) = damage.victim {
                     if let Some(encounter_npc) = data.get_encounter_npc(entry) {
                         if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
+                            attempt.set_player_active(character_id, event.timestamp);
                             if let Some(player_damage) = attempt.ranking_damage.get_mut(&character_id) {
                                 *player_damage += get_spell_components_total(&damage.components);
                             } else {
@@ -380,6 +398,7 @@ fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mu
                 // For now attribute heal to every attempt, though its just one in 99.9% of the cases anyway.
                 // And in some cases its not even wrong, e.g. BWL where the dragons are cleaved
                 for (_, attempt) in active_attempts.iter_mut() {
+                    attempt.set_player_active(character_id, event.timestamp);
                     if let Some(player_heal) = attempt.ranking_heal.get_mut(&character_id) {
                         *player_heal += heal.effective;
                     } else {
@@ -391,6 +410,7 @@ fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mu
                 if let Unit::Creature(Creature { entry, .. }) = threat.threatened {
                     if let Some(encounter_npc) = data.get_encounter_npc(entry) {
                         if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
+                            attempt.set_player_active(character_id, event.timestamp);
                             if let Some(player_threat) = attempt.ranking_threat.get_mut(&character_id) {
                                 *player_threat += threat.amount;
                             } else {
@@ -405,76 +425,32 @@ fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mu
     }
 }
 
-fn commit_attempt(db_main: &mut (impl Execute + Select), instance_meta_id: u32, mut attempt: Attempt) {
+// Milliseconds, shorter results would rank with an inflated amount per active second
+static RANKING_MIN_ACTIVE_TIME: u64 = 10000;
+
+fn commit_attempt(db_main: &mut (impl Execute + Select), data: &Data, instance_meta_id: u32, mut attempt: Attempt, is_merged: bool) {
     // Likely a false positive
     if attempt.end_ts - attempt.start_ts <= 1000 {
         return;
     }
 
-    // Ulduar hard modes
-    // Flame Leviathan
-    if attempt.encounter_id == 114 {
-        if attempt.hard_mode_found_buffs.len() == 1 {
-            attempt.hard_mode_encounter_id = Some(146);
-        } else if attempt.hard_mode_found_buffs.len() == 2 {
-            attempt.hard_mode_encounter_id = Some(147);
-        } else if attempt.hard_mode_found_buffs.len() == 3 {
-            attempt.hard_mode_encounter_id = Some(148);
-        } else if attempt.hard_mode_found_buffs.len() == 4 {
-            attempt.hard_mode_encounter_id = Some(149);
-        }
-    }
-    // Freya
-    else if attempt.encounter_id == 122 {
-        let knocks = attempt.hard_mode_found_buffs.len() + attempt.hard_mode_npcs_died.len();
-        if knocks == 1 {
-            attempt.hard_mode_encounter_id = Some(152);
-        } else if knocks == 2 {
-            attempt.hard_mode_encounter_id = Some(153);
-        } else if knocks == 3 {
-            attempt.hard_mode_encounter_id = Some(154);
-        }
-    }
-    // Mimiron
-    else if attempt.encounter_id == 121 {
-        if !attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(156);
-        }
-    }
-    // Thorim
-    else if attempt.encounter_id == 123 {
-        if !attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(155);
-        }
-    }
-    // Hodir
-    else if attempt.encounter_id == 124 {
-        if attempt.end_ts - attempt.start_ts - 2000 <= 120000 {
-            attempt.hard_mode_encounter_id = Some(162);
-        }
-    }
-    // General Vezax
-    else if attempt.encounter_id == 125 {
-        if !attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(157);
-        }
-    }
-    // Yogg-Saron
-    else if attempt.encounter_id == 126 {
-        if attempt.hard_mode_found_buffs.len() == 3 {
-            attempt.hard_mode_encounter_id = Some(158);
-        } else if attempt.hard_mode_found_buffs.len() == 2 {
-            attempt.hard_mode_encounter_id = Some(159);
-        } else if attempt.hard_mode_found_buffs.len() == 1 {
-            attempt.hard_mode_encounter_id = Some(160);
-        } else if attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(161);
-        }
-    }
-
+    // E.g. Ulduar hard modes, see data_encounter_rule
+    let difficulty_id = db_main.select_wparams_value(
+        "SELECT map_difficulty FROM instance_raid WHERE instance_meta_id=:instance_meta_id",
+        |mut row| row.take::<u8, usize>(0).unwrap(),
+        params!("instance_meta_id" => instance_meta_id),
+    );
+    let hard_mode_encounter_id = data.get_hard_mode_encounter_id(attempt.encounter_id, difficulty_id, &attempt.hard_mode_signals, attempt.end_ts - attempt.start_ts);
 
-    let encounter_id = attempt.hard_mode_encounter_id.unwrap_or(attempt.encounter_id);
+    let encounter_id = hard_mode_encounter_id.unwrap_or(attempt.encounter_id);
+    attempt.finish_active_time();
+    attempt.retain_ranked_characters(RANKING_MIN_ACTIVE_TIME);
     let is_kill = attempt.creatures_required_to_die.is_empty() && (!attempt.encounter_has_pivot || attempt.pivot_is_finished);
+    let specs = if is_kill { get_ranking_specs(db_main, &attempt) } else { HashMap::new() };
+    if is_merged && merge_attempt(db_main, instance_meta_id, encounter_id, is_kill, &attempt, &specs) {
+        return;
+    }
+
     let params = params!("instance_meta_id" => instance_meta_id, "encounter_id" => encounter_id,
         "start_ts" => attempt.start_ts, "end_ts" => attempt.end_ts, "is_kill" => is_kill);
     db_main.execute_wparams(
@@ -493,43 +469,91 @@ fn commit_attempt(db_main: &mut (impl Execute + Select), instance_meta_id: u32,
     ) {
         let ranking_damage = std::mem::replace(&mut attempt.ranking_damage, HashMap::new());
         db_main.execute_batch_wparams(
-            "INSERT INTO `instance_ranking_damage` (`character_id`, `attempt_id`, `damage`) VALUES (:character_id, :attempt_id, :damage)",
-            ranking_damage.into_iter().collect(),
-            move |(character_id, damage)| {
+            "INSERT INTO `instance_ranking_damage` (`character_id`, `attempt_id`, `damage`, `spec_id`, `role`) VALUES (:character_id, :attempt_id, :damage, :spec_id, :role)",
+            ranking_damage.into_iter().map(|(character_id, damage)| (character_id, damage, specs[&character_id])).collect(),
+            move |(character_id, damage, (spec_id, role))| {
                 params! {
                     "character_id" => character_id,
                     "attempt_id" => attempt_id,
-                    "damage" => damage
+                    "damage" => damage,
+                    "spec_id" => spec_id,
+                    "role" => role
                 }
             },
         );
 
         let ranking_heal = std::mem::replace(&mut attempt.ranking_heal, HashMap::new());
         db_main.execute_batch_wparams(
-            "INSERT INTO `instance_ranking_heal` (`character_id`, `attempt_id`, `heal`) VALUES (:character_id, :attempt_id, :heal)",
-            ranking_heal.into_iter().collect(),
-            move |(character_id, heal)| {
+            "INSERT INTO `instance_ranking_heal` (`character_id`, `attempt_id`, `heal`, `spec_id`, `role`) VALUES (:character_id, :attempt_id, :heal, :spec_id, :role)",
+            ranking_heal.into_iter().map(|(character_id, heal)| (character_id, heal, specs[&character_id])).collect(),
+            move |(character_id, heal, (spec_id, role))| {
                 params! {
                     "character_id" => character_id,
                     "attempt_id" => attempt_id,
-                    "heal" => heal
+                    "heal" => heal,
+                    "spec_id" => spec_id,
+                    "role" => role
                 }
             },
         );
 
         let ranking_threat = std::mem::replace(&mut attempt.ranking_threat, HashMap::new());
         db_main.execute_batch_wparams(
-            "INSERT INTO `instance_ranking_threat` (`character_id`, `attempt_id`, `threat`) VALUES (:character_id, :attempt_id, :threat)",
-            ranking_threat.into_iter().collect(),
-            move |(character_id, threat)| {
+            "INSERT INTO `instance_ranking_threat` (`character_id`, `attempt_id`, `threat`, `spec_id`, `role`) VALUES (:character_id, :attempt_id, :threat, :spec_id, :role)",
+            ranking_threat.into_iter().map(|(character_id, threat)| (character_id, threat, specs[&character_id])).collect(),
+            move |(character_id, threat, (spec_id, role))| {
                 params! {
                     "character_id" => character_id,
                     "attempt_id" => attempt_id,
-                    "threat" => threat
+                    "threat" => threat,
+                    "spec_id" => spec_id,
+                    "role" => role
                 }
             },
         );
+
+        let ranking_active_time = std::mem::replace(&mut attempt.ranking_active_time, HashMap::new());
+        db_main.execute_batch_wparams(
+            "INSERT INTO `instance_ranking_active_time` (`character_id`, `attempt_id`, `active_time`) VALUES (:character_id, :attempt_id, :active_time)",
+            ranking_active_time.into_iter().collect(),
+            move |(character_id, active_time)| {
+                params! {
+                    "character_id" => character_id,
+                    "attempt_id" => attempt_id,
+                    "active_time" => active_time
+                }
+            },
+        );
+    }
+}
+
+/// Spec and role of each ranked character, by the talents of its newest character history,
+/// i.e. those of the log that the attempt is committed from
+fn get_ranking_specs(db_main: &mut impl Select, attempt: &Attempt) -> HashMap<u32, (Option<u8>, Option<u8>)> {
+    let mut specs = HashMap::new();
+    for character_id in attempt.ranking_damage.keys().chain(attempt.ranking_heal.keys()).chain(attempt.ranking_threat.keys()) {
+        if !specs.contains_key(character_id) {
+            specs.insert(*character_id, get_ranking_spec(db_main, *character_id));
+        }
     }
+    specs
+}
+
+fn get_ranking_spec(db_main: &mut impl Select, character_id: u32) -> (Option<u8>, Option<u8>) {
+    db_main
+        .select_wparams_value(
+            "SELECT B.hero_class_id, B.talent_specialization FROM armory_character_history A JOIN armory_character_info B ON A.character_info_id = B.id WHERE A.character_id=:character_id ORDER BY A.id DESC LIMIT 1",
+            |mut row| {
+                let hero_class_id: u8 = row.take(0).unwrap();
+                let talent_specialization: Option<String> = row.take_opt(1).unwrap().ok();
+                (
+                    talent_specialization.as_ref().map(|talents| get_talent_tree(talents)),
+                    Some(Role::from_class_talent_string(hero_class_id, talent_specialization.as_deref().unwrap_or("")).to_u8()),
+                )
+            },
+            params!("character_id" => character_id),
+        )
+        .unwrap_or((None, None))
 }
 
 fn look_ahead_death(committed_events: &VecDeque<Event>, event: &Event, creature_id: u64) -> bool {
@@ -0,0 +1,73 @@
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::material::{Server, ServerSnapshot};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use std::time::Duration;
+
+// A restart loses at most this much of the live state
+static SNAPSHOT_INTERVAL: u64 = 30;
+
+impl Server {
+    /// Has to be called after init, as only the instances that are still active are restored
+    pub fn restore_snapshot(mut self) -> Self {
+        if let Some(snapshot) = std::fs::read(get_snapshot_path(self.server_id)).ok().and_then(|content| ServerSnapshot::decode(&content)) {
+            snapshot.restore(&mut self);
+        }
+        self
+    }
+}
+
+impl LiveDataProcessor {
+    pub fn init_server_snapshots(self) -> Self {
+        let me = self.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(Duration::from_secs(SNAPSHOT_INTERVAL));
+            me.save_server_snapshots();
+        });
+        self
+    }
+
+    /// Failures are counted per server, the previous snapshot is kept in that case
+    pub fn save_server_snapshots(&self) {
+        let servers = self.servers.read().unwrap();
+        for (server_id, server) in servers.iter() {
+            // The server is only locked while its state is serialized.
+            // A poisoned server keeps its previous snapshot, it is restored from it once it is rebuilt.
+            let server = match server.read() {
+                Ok(server) => server,
+                Err(_) => continue,
+            };
+            let serialized = ServerSnapshot::new(&server, time_util::now()).serialize();
+            drop(server);
+            let label = server_id.to_string();
+            match serialized
+                .ok_or_else(|| LiveDataProcessorFailure::StorageFailure(String::from("save_server_snapshots")))
+                .and_then(|serialized| save_snapshot(*server_id, &serialized))
+            {
+                Ok(snapshot_bytes) => self.metrics.snapshot_bytes.with_label_values(&[&label]).set(snapshot_bytes as i64),
+                Err(_) => self.metrics.snapshot_failures.with_label_values(&[&label]).inc(),
+            }
+        }
+    }
+}
+
+/// The snapshot is written to a temporary file first, so a crash while writing keeps the previous one.
+/// Returns the size of the snapshot in bytes.
+fn save_snapshot(server_id: u32, serialized: &[u8]) -> Result<usize, LiveDataProcessorFailure> {
+    let storage_failure = |_| LiveDataProcessorFailure::StorageFailure(String::from("save_snapshot"));
+    let snapshot_path = get_snapshot_path(server_id);
+    let content = ServerSnapshot::compress(serialized).ok_or_else(|| LiveDataProcessorFailure::StorageFailure(String::from("save_snapshot")))?;
+    let temporary_path = format!("{}.tmp", snapshot_path);
+    std::fs::create_dir_all(get_server_storage_path(server_id)).map_err(storage_failure)?;
+    std::fs::write(&temporary_path, &content).map_err(storage_failure)?;
+    std::fs::rename(&temporary_path, &snapshot_path).map_err(storage_failure)?;
+    Ok(content.len())
+}
+
+fn get_server_storage_path(server_id: u32) -> String {
+    let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set!");
+    format!("{}/{}", storage_path, server_id)
+}
+
+fn get_snapshot_path(server_id: u32) -> String {
+    format!("{}/live_state", get_server_storage_path(server_id))
+}
@@ -8,24 +8,47 @@ use crate::modules::live_data_processor::tools::GUID;
 use crate::util::database::{Execute, Select};
 use std::collections::HashMap;
 
+/// Maps a player to its character in the armory
+pub trait ResolveCharacter {
+    fn resolve_character(&mut self, armory: &Armory, server_id: u32, unit_id: u64) -> Option<u32>;
+}
+
+// Uploads create the characters that don't exist yet
+impl<T: Select + Execute> ResolveCharacter for T {
+    fn resolve_character(&mut self, armory: &Armory, server_id: u32, unit_id: u64) -> Option<u32> {
+        let mut character = armory.get_character_by_uid(server_id, unit_id);
+        if character.is_none() {
+            character = armory.create_character(self, server_id, unit_id).ok().and_then(|character_id| armory.get_character(character_id));
+        }
+        character.map(|character| character.id)
+    }
+}
+
+/// Validations only look the characters up, those that don't exist yet are mapped to a placeholder
+pub struct LookupCharacter;
+
+impl ResolveCharacter for LookupCharacter {
+    fn resolve_character(&mut self, armory: &Armory, server_id: u32, unit_id: u64) -> Option<u32> {
+        Some(armory.get_character_id_by_uid(server_id, unit_id).unwrap_or(0))
+    }
+}
+
 pub trait MapUnit {
-    fn to_unit_add_implicit(&self, cache_unit: &mut HashMap<u64, domain_value::Unit>, db_main: &mut (impl Execute + Select), armory: &Armory, server_id: u32, summons: &HashMap<u64, Unit>) -> Result<domain_value::Unit, LiveDataProcessorFailure>;
+    fn to_unit_add_implicit(&self, cache_unit: &mut HashMap<u64, domain_value::Unit>, characters: &mut impl ResolveCharacter, armory: &Armory, server_id: u32, summons: &HashMap<u64, Unit>) -> Result<domain_value::Unit, LiveDataProcessorFailure>;
 }
 
 impl MapUnit for dto::Unit {
-    fn to_unit_add_implicit(&self, cache_unit: &mut HashMap<u64, domain_value::Unit>, db_main: &mut (impl Execute + Select), armory: &Armory, server_id: u32, summons: &HashMap<u64, Unit>) -> Result<domain_value::Unit, LiveDataProcessorFailure> {
+    fn to_unit_add_implicit(&self, cache_unit: &mut HashMap<u64, domain_value::Unit>, characters: &mut impl ResolveCharacter, armory: &Armory, server_id: u32, summons: &HashMap<u64, Unit>) -> Result<domain_value::Unit, LiveDataProcessorFailure> {
         if self.is_player {
             if cache_unit.contains_key(&self.unit_id) {
                 return Ok(cache_unit.get(&self.unit_id).unwrap().clone());
             }
 
-            let mut character = armory.get_character_by_uid(server_id, self.unit_id);
-            if character.is_none() {
-                character = armory.create_character(db_main, server_id, self.unit_id).ok().and_then(|character_id| armory.get_character(character_id));
-            }
-            let character = character.ok_or(LiveDataProcessorFailure::InvalidInput)?;
+            let character_id = characters
+                .resolve_character(armory, server_id, self.unit_id)
+                .ok_or_else(|| LiveDataProcessorFailure::DatabaseFailure(String::from("to_unit_add_implicit")))?;
             let unit = domain_value::Unit::Player(domain_value::Player {
-                character_id: character.id,
+                character_id,
                 /* server_uid: self.unit_id,
                  * character: Some(character), */
             });
@@ -35,7 +58,7 @@ impl MapUnit for dto::Unit {
             // Dont cache, because an owner could be found at a later time
             let unit = domain_value::Unit::Creature(domain_value::Creature {
                 creature_id: self.unit_id,
-                entry: self.unit_id.get_entry().ok_or(LiveDataProcessorFailure::InvalidInput)?,
+                entry: self.unit_id.get_entry().ok_or(LiveDataProcessorFailure::InvalidUnit(self.unit_id))?,
                 owner: summons.get(&self.unit_id).cloned().map(Box::new),
             });
             cache_unit.insert(self.unit_id, unit.clone());
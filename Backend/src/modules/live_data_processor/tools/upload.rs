@@ -0,0 +1,85 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::RetrieveServer;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::material::{CombatLogArchive, PreparedCombatLog, UploadMeta, WoWRetailClassicParser, WoWTBCParser, WoWVanillaParser, WoWWOTLKParser};
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use crate::modules::live_data_processor::tools::log_parser::{inspect_cbl, prepare_cbl};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::util::database::{Execute, Select};
+use chrono::NaiveDateTime;
+use std::io::Read;
+use std::path::PathBuf;
+
+pub trait ParseUpload {
+    /// First pass over the uploaded combat log. Its messages are streamed afterwards with stream_cbl_messages.
+    fn prepare_upload(&self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, archive: &CombatLogArchive, meta: UploadMeta) -> Result<PreparedCombatLog, LiveDataProcessorFailure>;
+    /// Like prepare_upload, but nothing is persisted, see inspect_cbl
+    fn inspect_upload(&self, data: &Data, armory: &Armory, archive: &CombatLogArchive, meta: UploadMeta) -> Result<PreparedCombatLog, LiveDataProcessorFailure>;
+}
+
+impl ParseUpload for LiveDataProcessor {
+    fn prepare_upload(&self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, archive: &CombatLogArchive, meta: UploadMeta) -> Result<PreparedCombatLog, LiveDataProcessorFailure> {
+        let time_window = meta.time_window();
+        let (collector, emitter) = create_parsers(data, archive, meta)?;
+        prepare_cbl(collector, emitter, db_main, data, armory, archive, &time_window)
+    }
+
+    fn inspect_upload(&self, data: &Data, armory: &Armory, archive: &CombatLogArchive, meta: UploadMeta) -> Result<PreparedCombatLog, LiveDataProcessorFailure> {
+        let time_window = meta.time_window();
+        let (collector, emitter) = create_parsers(data, archive, meta)?;
+        inspect_cbl(collector, emitter, data, armory, archive, &time_window)
+    }
+}
+
+// The collector and a fresh emitter of the same kind
+fn create_parsers(data: &Data, archive: &CombatLogArchive, meta: UploadMeta) -> Result<(Box<dyn CombatLogParser>, Box<dyn CombatLogParser>), LiveDataProcessorFailure> {
+    let UploadMeta { server_id, armory_content, .. } = meta;
+    if server_id == -1 {
+        return Ok((Box::new(WoWRetailClassicParser::new()), Box::new(WoWRetailClassicParser::new())));
+    }
+
+    let server = data.get_server(server_id as u32).ok_or(LiveDataProcessorFailure::UnknownServer(server_id))?;
+    // The SavedVariables of the armory collector may also be packed along with the combat log
+    let armory_content = match armory_content {
+        Some(armory_content) => Some(armory_content),
+        None if server.expansion_id >= 2 => archive.read_armory()?,
+        None => None,
+    };
+    match server.expansion_id {
+        1 => Ok((Box::new(WoWVanillaParser::new(server_id as u32)), Box::new(WoWVanillaParser::new(server_id as u32)))),
+        2 => Ok((Box::new(WoWTBCParser::new(server_id as u32, armory_content.clone())), Box::new(WoWTBCParser::new(server_id as u32, armory_content)))),
+        3 => Ok((Box::new(WoWWOTLKParser::new(server_id as u32, armory_content.clone())), Box::new(WoWWOTLKParser::new(server_id as u32, armory_content)))),
+        expansion_id => Err(LiveDataProcessorFailure::UnsupportedExpansion(expansion_id)),
+    }
+}
+
+/// Writes the payload to the staging area of the upload storage, without holding it in memory
+pub fn stage_upload_payload(storage_path: &str, mut payload: impl Read) -> Result<PathBuf, LiveDataProcessorFailure> {
+    let staging_path = format!("{}/staging", storage_path);
+    std::fs::create_dir_all(&staging_path).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("stage_upload_payload")))?;
+
+    let payload_path = PathBuf::from(format!("{}/{}", staging_path, str_util::random::alphanumeric(32)));
+    let mut file = std::fs::File::create(&payload_path).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("stage_upload_payload")))?;
+    let written = std::io::copy(&mut payload, &mut file).unwrap_or(0);
+    drop(file);
+    if written == 0 {
+        let _ = std::fs::remove_file(&payload_path);
+        return Err(LiveDataProcessorFailure::EmptyPayload);
+    }
+    Ok(payload_path)
+}
+
+/// Parses the time format that is used by the upload form, e.g. "24.12.20 08:30 PM", and converts it to UTC.
+/// The offset is given in minutes east of UTC, from UTC-12:00 up to UTC+14:00.
+pub fn parse_upload_time(raw: &str, utc_offset: i32) -> Result<u64, LiveDataProcessorFailure> {
+    if utc_offset < -12 * 60 || utc_offset > 14 * 60 {
+        return Err(LiveDataProcessorFailure::InvalidUtcOffset(utc_offset));
+    }
+    NaiveDateTime::parse_from_str(raw, "%d.%m.%y %I:%M %p")
+        .map(|date_time| date_time.timestamp_millis() - utc_offset as i64 * 60000)
+        .ok()
+        .filter(|timestamp| *timestamp >= 0)
+        .map(|timestamp| timestamp as u64)
+        .ok_or_else(|| LiveDataProcessorFailure::InvalidTimeFormat(raw.to_owned()))
+}
@@ -0,0 +1,267 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::UploadJobStatus;
+use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, UploadJob};
+use crate::modules::live_data_processor::material::{CombatLogArchive, UploadMeta};
+use crate::modules::live_data_processor::tools::log_parser::stream_cbl_messages;
+use crate::modules::live_data_processor::tools::ParseUpload;
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::mysql::{Conn, Opts, Row};
+use crate::params;
+use crate::util::database::{Execute, Select};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+const MAX_FAILURE_REASON_LENGTH: usize = 255;
+
+pub trait UploadJobs {
+    /// Takes ownership of the staged payload file
+    fn enqueue_upload_job(&self, db_main: &mut (impl Select + Execute), member_id: u32, payload: &Path, meta: UploadMeta) -> Result<UploadJob, LiveDataProcessorFailure>;
+    fn get_upload_job(&self, db_main: &mut impl Select, member_id: u32, job_id: &str) -> Result<UploadJob, LiveDataProcessorFailure>;
+    fn get_upload_jobs(&self, db_main: &mut impl Select, member_id: u32) -> Vec<UploadJob>;
+}
+
+impl UploadJobs for LiveDataProcessor {
+    fn enqueue_upload_job(&self, db_main: &mut (impl Select + Execute), member_id: u32, payload: &Path, meta: UploadMeta) -> Result<UploadJob, LiveDataProcessorFailure> {
+        let job_id = str_util::random::alphanumeric(32);
+        let job_path = get_job_path(&self.upload_storage_path, &job_id);
+        let moved = std::fs::create_dir_all(&job_path).and_then(|_| std::fs::rename(payload, format!("{}/payload", job_path)));
+        if moved.is_err() {
+            let _ = std::fs::remove_file(payload);
+            let _ = std::fs::remove_dir_all(&job_path);
+            return Err(LiveDataProcessorFailure::StorageFailure(String::from("enqueue_upload_job")));
+        }
+        if let Some(armory_content) = &meta.armory_content {
+            std::fs::write(format!("{}/armory", job_path), armory_content).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("enqueue_upload_job")))?;
+        }
+
+        let now = time_util::now();
+        if !db_main.execute_wparams(
+            "INSERT INTO live_data_processor_upload_job (`id`, `member_id`, `server_id`, `start_ts`, `end_ts`, `utc_offset`, `status`, `created_ts`, `updated_ts`) VALUES (:job_id, :member_id, :server_id, :start_ts, :end_ts, :utc_offset, :status, :now, :now)",
+            params!(
+                "job_id" => job_id.clone(),
+                "member_id" => member_id,
+                "server_id" => meta.server_id,
+                "start_ts" => meta.start_time,
+                "end_ts" => meta.end_time,
+                "utc_offset" => meta.utc_offset,
+                "status" => UploadJobStatus::Queued.to_u8(),
+                "now" => now
+            ),
+        ) {
+            let _ = std::fs::remove_dir_all(&job_path);
+            return Err(LiveDataProcessorFailure::DatabaseFailure(String::from("enqueue_upload_job")));
+        }
+
+        let (queue, condvar) = &*self.upload_queue;
+        queue.lock().unwrap().push_back(job_id.clone());
+        condvar.notify_one();
+
+        Ok(UploadJob {
+            job_id,
+            server_id: meta.server_id,
+            start_time: meta.start_time,
+            end_time: meta.end_time,
+            utc_offset: meta.utc_offset,
+            status: UploadJobStatus::Queued,
+            lines_parsed: 0,
+            events_committed: 0,
+            failure_reason: None,
+            created: now,
+            updated: now,
+        })
+    }
+
+    fn get_upload_job(&self, db_main: &mut impl Select, member_id: u32, job_id: &str) -> Result<UploadJob, LiveDataProcessorFailure> {
+        db_main
+            .select_wparams_value(
+                "SELECT id, server_id, start_ts, end_ts, utc_offset, status, lines_parsed, events_committed, failure_reason, created_ts, updated_ts FROM live_data_processor_upload_job WHERE id=:job_id AND member_id=:member_id",
+                upload_job_from_row,
+                params!("job_id" => job_id, "member_id" => member_id),
+            )
+            .ok_or(LiveDataProcessorFailure::UploadNotFound)
+    }
+
+    fn get_upload_jobs(&self, db_main: &mut impl Select, member_id: u32) -> Vec<UploadJob> {
+        db_main.select_wparams(
+            "SELECT id, server_id, start_ts, end_ts, utc_offset, status, lines_parsed, events_committed, failure_reason, created_ts, updated_ts FROM live_data_processor_upload_job WHERE member_id=:member_id ORDER BY created_ts DESC",
+            upload_job_from_row,
+            params!("member_id" => member_id),
+        )
+    }
+}
+
+impl LiveDataProcessor {
+    pub fn init_upload_workers(self, opts: Opts, data: Arc<Data>, armory: &Armory, num_workers: usize) -> Self {
+        let mut db_main = Conn::new(opts.clone()).unwrap();
+        self.requeue_upload_jobs(&mut db_main);
+
+        for _ in 0..num_workers {
+            let me = self.clone();
+            let data = Arc::clone(&data);
+            let armory = armory.clone();
+            let opts = opts.clone();
+            let mut db_main = Conn::new(opts.clone()).unwrap();
+            std::thread::spawn(move || loop {
+                let job_id = me.next_upload_job();
+                // A panicking job must neither take the worker down nor stay in progress forever
+                if panic::catch_unwind(AssertUnwindSafe(|| me.process_upload_job(&mut db_main, &data, &armory, &job_id))).is_err() {
+                    db_main = Conn::new(opts.clone()).unwrap();
+                    set_upload_job_status(&mut db_main, &job_id, UploadJobStatus::Failed, Some(String::from("Panicked while processing")));
+                    let _ = std::fs::remove_dir_all(get_job_path(&me.upload_storage_path, &job_id));
+                }
+            });
+        }
+        self
+    }
+
+    fn requeue_upload_jobs(&self, db_main: &mut (impl Select + Execute)) {
+        let now = time_util::now();
+        // Events of an interrupted commit are already partially persisted, hence it can't be repeated
+        db_main.execute_wparams(
+            "UPDATE live_data_processor_upload_job SET status=:failed, failure_reason=:failure_reason, updated_ts=:now WHERE status=:committing",
+            params!(
+                "failed" => UploadJobStatus::Failed.to_u8(),
+                "failure_reason" => "Interrupted while committing",
+                "now" => now,
+                "committing" => UploadJobStatus::Committing.to_u8()
+            ),
+        );
+        db_main.execute_wparams(
+            "UPDATE live_data_processor_upload_job SET status=:queued, updated_ts=:now WHERE status=:parsing",
+            params!("queued" => UploadJobStatus::Queued.to_u8(), "now" => now, "parsing" => UploadJobStatus::Parsing.to_u8()),
+        );
+
+        let (queue, _) = &*self.upload_queue;
+        let mut queue = queue.lock().unwrap();
+        db_main
+            .select_wparams(
+                "SELECT id FROM live_data_processor_upload_job WHERE status=:queued ORDER BY created_ts",
+                |mut row| row.take::<String, usize>(0).unwrap(),
+                params!("queued" => UploadJobStatus::Queued.to_u8()),
+            )
+            .into_iter()
+            .for_each(|job_id| queue.push_back(job_id));
+    }
+
+    fn next_upload_job(&self) -> String {
+        let (queue, condvar) = &*self.upload_queue;
+        let mut queue = queue.lock().unwrap();
+        loop {
+            if let Some(job_id) = queue.pop_front() {
+                return job_id;
+            }
+            queue = condvar.wait(queue).unwrap();
+        }
+    }
+
+    fn process_upload_job(&self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, job_id: &str) {
+        let job = db_main.select_wparams_value(
+            "SELECT member_id, server_id, start_ts, end_ts, utc_offset FROM live_data_processor_upload_job WHERE id=:job_id",
+            |mut row| {
+                (
+                    row.take::<u32, usize>(0).unwrap(),
+                    row.take::<i32, usize>(1).unwrap(),
+                    row.take::<u64, usize>(2).unwrap(),
+                    row.take::<u64, usize>(3).unwrap(),
+                    row.take::<i32, usize>(4).unwrap(),
+                )
+            },
+            params!("job_id" => job_id),
+        );
+
+        if let Some((member_id, server_id, start_time, end_time, utc_offset)) = job {
+            let armory_content = std::fs::read_to_string(format!("{}/armory", get_job_path(&self.upload_storage_path, job_id))).ok();
+            let meta = UploadMeta {
+                server_id,
+                start_time,
+                end_time,
+                utc_offset,
+                armory_content,
+            };
+            match self.run_upload_job(db_main, data, armory, job_id, member_id, meta) {
+                Ok(()) => set_upload_job_status(db_main, job_id, UploadJobStatus::Done, None),
+                Err(failure) => set_upload_job_status(db_main, job_id, UploadJobStatus::Failed, Some(to_failure_reason(failure))),
+            }
+        }
+        let _ = std::fs::remove_dir_all(get_job_path(&self.upload_storage_path, job_id));
+    }
+
+    fn run_upload_job(&self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, job_id: &str, member_id: u32, meta: UploadMeta) -> Result<(), LiveDataProcessorFailure> {
+        set_upload_job_status(db_main, job_id, UploadJobStatus::Parsing, None);
+        let archive = CombatLogArchive::new(Path::new(&format!("{}/payload", get_job_path(&self.upload_storage_path, job_id))));
+        let prepared = self.prepare_upload(db_main, data, armory, &archive, meta)?;
+        let server_id = prepared.server_id;
+        let lines_parsed = prepared.num_lines;
+
+        set_upload_job_status(db_main, job_id, UploadJobStatus::Committing, None);
+        set_upload_job_progress(db_main, job_id, lines_parsed, 0);
+
+        // Jobs of the same server are serialized, so that their events don't interleave.
+        // The server itself is only locked per batch, so that it can still be read in between.
+        self.create_server_if_not_exist(db_main, server_id);
+        self.reset_server_if_poisoned(db_main, server_id);
+        let job_lock = self.get_upload_server_lock(server_id);
+        let _job_guard = job_lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut events_committed = 0;
+        // Committed batch by batch while the log is streamed, so that the progress can be followed
+        stream_cbl_messages(prepared, data, &archive, |batch| {
+            events_committed += batch.len() as u64;
+            {
+                let servers = self.servers.read().unwrap();
+                let mut server = servers.get(&server_id).expect("Server Id must exist!").write().unwrap();
+                server.parse_events(db_main, armory, data, batch, member_id)?;
+            }
+            set_upload_job_progress(db_main, job_id, lines_parsed, events_committed);
+            Ok(())
+        })
+    }
+
+    fn get_upload_server_lock(&self, server_id: u32) -> Arc<Mutex<()>> {
+        let mut upload_server_locks = self.upload_server_locks.lock().unwrap();
+        Arc::clone(upload_server_locks.entry(server_id).or_insert_with(|| Arc::new(Mutex::new(()))))
+    }
+}
+
+fn set_upload_job_status(db_main: &mut impl Execute, job_id: &str, status: UploadJobStatus, failure_reason: Option<String>) {
+    // The column is a VARCHAR(255)
+    let failure_reason = failure_reason.map(|reason| reason.chars().take(MAX_FAILURE_REASON_LENGTH).collect::<String>());
+    db_main.execute_wparams(
+        "UPDATE live_data_processor_upload_job SET status=:status, failure_reason=:failure_reason, updated_ts=:now WHERE id=:job_id",
+        params!("status" => status.to_u8(), "failure_reason" => failure_reason, "now" => time_util::now(), "job_id" => job_id),
+    );
+}
+
+// The status code and body of the responder, which don't change along with the variants
+fn to_failure_reason(failure: LiveDataProcessorFailure) -> String {
+    let (status, body) = failure.describe();
+    format!("{} {}: {}", status.code, status.reason, body)
+}
+
+fn set_upload_job_progress(db_main: &mut impl Execute, job_id: &str, lines_parsed: u64, events_committed: u64) {
+    db_main.execute_wparams(
+        "UPDATE live_data_processor_upload_job SET lines_parsed=:lines_parsed, events_committed=:events_committed, updated_ts=:now WHERE id=:job_id",
+        params!("lines_parsed" => lines_parsed, "events_committed" => events_committed, "now" => time_util::now(), "job_id" => job_id),
+    );
+}
+
+fn upload_job_from_row(mut row: Row) -> UploadJob {
+    UploadJob {
+        job_id: row.take(0).unwrap(),
+        server_id: row.take(1).unwrap(),
+        start_time: row.take(2).unwrap(),
+        end_time: row.take(3).unwrap(),
+        utc_offset: row.take(4).unwrap(),
+        status: UploadJobStatus::from_u8(row.take(5).unwrap()).unwrap_or(UploadJobStatus::Failed),
+        lines_parsed: row.take(6).unwrap(),
+        events_committed: row.take(7).unwrap(),
+        failure_reason: row.take_opt(8).unwrap().ok(),
+        created: row.take(9).unwrap(),
+        updated: row.take(10).unwrap(),
+    }
+}
+
+fn get_job_path(storage_path: &str, job_id: &str) -> String {
+    format!("{}/jobs/{}", storage_path, job_id)
+}
@@ -0,0 +1,207 @@
+use crate::modules::live_data_processor::dto::{CreateUploadSession, LiveDataProcessorFailure, UploadJob, UploadSessionProgress};
+use crate::modules::live_data_processor::material::{UploadMeta, UploadSession};
+use crate::modules::live_data_processor::tools::{parse_upload_time, stage_upload_payload, UploadJobs};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::util::database::{Execute, Select};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const CHUNK_SIZE_LIMIT: u64 = 64 * 1024 * 1024;
+const MAX_NUM_CHUNKS: u32 = 1024;
+const SESSION_TIMEOUT: u64 = 24 * 60 * 60;
+// Persisted next to the chunks, so sessions survive a restart
+const SESSION_FILE: &str = "session.json";
+
+pub trait UploadSessions {
+    fn open_upload_session(&self, member_id: u32, session: CreateUploadSession) -> Result<UploadSessionProgress, LiveDataProcessorFailure>;
+    fn store_upload_chunk(&self, member_id: u32, upload_id: &str, chunk_index: u32, chunk: impl Read) -> Result<UploadSessionProgress, LiveDataProcessorFailure>;
+    fn get_upload_session_progress(&self, member_id: u32, upload_id: &str) -> Result<UploadSessionProgress, LiveDataProcessorFailure>;
+    fn finalize_upload_session(&self, db_main: &mut (impl Select + Execute), member_id: u32, upload_id: &str) -> Result<UploadJob, LiveDataProcessorFailure>;
+}
+
+impl UploadSessions for LiveDataProcessor {
+    fn open_upload_session(&self, member_id: u32, session: CreateUploadSession) -> Result<UploadSessionProgress, LiveDataProcessorFailure> {
+        if session.num_chunks == 0 || session.num_chunks > MAX_NUM_CHUNKS {
+            return Err(LiveDataProcessorFailure::InvalidField(String::from("num_chunks")));
+        }
+        let utc_offset = session.utc_offset.unwrap_or(0);
+        let start_time = parse_upload_time(&session.start_time, utc_offset)?;
+        let end_time = parse_upload_time(&session.end_time, utc_offset)?;
+
+        self.evict_stale_upload_sessions();
+
+        let upload_id = str_util::random::alphanumeric(32);
+        std::fs::create_dir_all(get_session_path(&self.upload_storage_path, &upload_id)).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("open_upload_session")))?;
+
+        let upload_session = UploadSession::new(member_id, session.server_id, start_time, end_time, utc_offset, session.num_chunks, session.payload_armory);
+        let persisted = std::fs::File::create(format!("{}/{}", get_session_path(&self.upload_storage_path, &upload_id), SESSION_FILE)).map(|file| serde_json::to_writer(file, &upload_session).is_ok());
+        if persisted.ok() != Some(true) {
+            let _ = std::fs::remove_dir_all(get_session_path(&self.upload_storage_path, &upload_id));
+            return Err(LiveDataProcessorFailure::StorageFailure(String::from("open_upload_session")));
+        }
+        let progress = to_progress(&upload_id, &upload_session);
+        let mut upload_sessions = self.upload_sessions.write().unwrap();
+        upload_sessions.insert(upload_id, upload_session);
+        Ok(progress)
+    }
+
+    fn store_upload_chunk(&self, member_id: u32, upload_id: &str, chunk_index: u32, chunk: impl Read) -> Result<UploadSessionProgress, LiveDataProcessorFailure> {
+        {
+            let upload_sessions = self.upload_sessions.read().unwrap();
+            let upload_session = upload_sessions.get(upload_id).filter(|session| session.member_id == member_id).ok_or(LiveDataProcessorFailure::UploadNotFound)?;
+            if chunk_index >= upload_session.num_chunks {
+                return Err(LiveDataProcessorFailure::InvalidChunk(chunk_index));
+            }
+        }
+
+        // Written to a temporary file first, so an interrupted transfer never leaves a truncated chunk behind.
+        // The temporary file is unique, as the same chunk may be uploaded concurrently.
+        let chunk_path = format!("{}/{}", get_session_path(&self.upload_storage_path, upload_id), chunk_index);
+        let temporary_path = format!("{}.{}.part", chunk_path, str_util::random::alphanumeric(16));
+        let mut file = std::fs::File::create(&temporary_path).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("store_upload_chunk")))?;
+        let written = std::io::copy(&mut chunk.take(CHUNK_SIZE_LIMIT + 1), &mut file).unwrap_or(0);
+        drop(file);
+        if written == 0 || written > CHUNK_SIZE_LIMIT {
+            let _ = std::fs::remove_file(&temporary_path);
+            return Err(LiveDataProcessorFailure::InvalidChunk(chunk_index));
+        }
+        std::fs::rename(&temporary_path, &chunk_path).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("store_upload_chunk")))?;
+
+        let mut upload_sessions = self.upload_sessions.write().unwrap();
+        let upload_session = upload_sessions.get_mut(upload_id).ok_or(LiveDataProcessorFailure::UploadNotFound)?;
+        upload_session.received_chunks.insert(chunk_index);
+        upload_session.last_update = time_util::now();
+        Ok(to_progress(upload_id, upload_session))
+    }
+
+    fn get_upload_session_progress(&self, member_id: u32, upload_id: &str) -> Result<UploadSessionProgress, LiveDataProcessorFailure> {
+        let upload_sessions = self.upload_sessions.read().unwrap();
+        upload_sessions
+            .get(upload_id)
+            .filter(|session| session.member_id == member_id)
+            .map(|session| to_progress(upload_id, session))
+            .ok_or(LiveDataProcessorFailure::UploadNotFound)
+    }
+
+    fn finalize_upload_session(&self, db_main: &mut (impl Select + Execute), member_id: u32, upload_id: &str) -> Result<UploadJob, LiveDataProcessorFailure> {
+        // Taken out of the map, so it can't be finalized twice concurrently. It is put back if the job can't be enqueued.
+        let upload_session = {
+            let mut upload_sessions = self.upload_sessions.write().unwrap();
+            match upload_sessions.get(upload_id) {
+                Some(session) if session.member_id == member_id && session.is_complete() => upload_sessions.remove(upload_id).unwrap(),
+                Some(session) if session.member_id == member_id => return Err(LiveDataProcessorFailure::UploadIncomplete),
+                _ => return Err(LiveDataProcessorFailure::UploadNotFound),
+            }
+        };
+
+        let session_path = get_session_path(&self.upload_storage_path, upload_id);
+        let upload_job = stage_upload_session(&self.upload_storage_path, &session_path, &upload_session).and_then(|payload| {
+            self.enqueue_upload_job(
+                db_main,
+                member_id,
+                &payload,
+                UploadMeta {
+                    server_id: upload_session.server_id,
+                    start_time: upload_session.start_time,
+                    end_time: upload_session.end_time,
+                    utc_offset: upload_session.utc_offset,
+                    armory_content: upload_session.armory_content.clone(),
+                },
+            )
+        });
+
+        match upload_job {
+            Ok(upload_job) => {
+                let _ = std::fs::remove_dir_all(&session_path);
+                Ok(upload_job)
+            },
+            Err(failure) => {
+                // Chunks that got lost have to be uploaded again
+                let mut upload_session = upload_session;
+                upload_session.received_chunks.retain(|chunk_index| Path::new(&format!("{}/{}", session_path, chunk_index)).exists());
+                let mut upload_sessions = self.upload_sessions.write().unwrap();
+                upload_sessions.insert(upload_id.to_owned(), upload_session);
+                Err(failure)
+            },
+        }
+    }
+}
+
+impl LiveDataProcessor {
+    /// Restores the sessions that were persisted before a restart and removes the chunks of sessions that can't be restored
+    pub fn restore_upload_sessions(self) -> Self {
+        let entries = match std::fs::read_dir(format!("{}/sessions", self.upload_storage_path)) {
+            Ok(entries) => entries,
+            Err(_) => return self,
+        };
+
+        {
+            let mut upload_sessions = self.upload_sessions.write().unwrap();
+            for entry in entries.filter_map(Result::ok).filter(|entry| entry.path().is_dir()) {
+                match restore_upload_session(&entry.path()) {
+                    Some(upload_session) => {
+                        upload_sessions.insert(entry.file_name().to_string_lossy().to_string(), upload_session);
+                    },
+                    None => {
+                        let _ = std::fs::remove_dir_all(entry.path());
+                    },
+                }
+            }
+        }
+
+        self.evict_stale_upload_sessions();
+        self
+    }
+
+    fn evict_stale_upload_sessions(&self) {
+        let now = time_util::now();
+        let mut upload_sessions = self.upload_sessions.write().unwrap();
+        for upload_id in upload_sessions
+            .iter()
+            .filter(|(_, session)| session.last_update + SESSION_TIMEOUT < now)
+            .map(|(upload_id, _)| upload_id.clone())
+            .collect::<Vec<String>>()
+        {
+            upload_sessions.remove(&upload_id);
+            let _ = std::fs::remove_dir_all(get_session_path(&self.upload_storage_path, &upload_id));
+        }
+    }
+}
+
+fn stage_upload_session(storage_path: &str, session_path: &str, upload_session: &UploadSession) -> Result<PathBuf, LiveDataProcessorFailure> {
+    let mut payload: Box<dyn Read> = Box::new(std::io::empty());
+    for chunk_index in 0..upload_session.num_chunks {
+        let file = std::fs::File::open(format!("{}/{}", session_path, chunk_index)).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("finalize_upload_session")))?;
+        payload = Box::new(payload.chain(file));
+    }
+    stage_upload_payload(storage_path, payload)
+}
+
+fn restore_upload_session(session_path: &Path) -> Option<UploadSession> {
+    let file = std::fs::File::open(session_path.join(SESSION_FILE)).ok()?;
+    let mut upload_session: UploadSession = serde_json::from_reader(file).ok()?;
+    // Chunks are only renamed to their index once they are completely written
+    for entry in std::fs::read_dir(session_path).ok()?.filter_map(Result::ok) {
+        let chunk_index = match entry.file_name().to_str().and_then(|file_name| file_name.parse::<u32>().ok()) {
+            Some(chunk_index) if chunk_index < upload_session.num_chunks => chunk_index,
+            _ => continue,
+        };
+        upload_session.received_chunks.insert(chunk_index);
+        if let Some(modified) = entry.metadata().and_then(|metadata| metadata.modified()).ok().and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok()) {
+            upload_session.last_update = upload_session.last_update.max(modified.as_secs());
+        }
+    }
+    Some(upload_session)
+}
+
+fn get_session_path(storage_path: &str, upload_id: &str) -> String {
+    format!("{}/sessions/{}", storage_path, upload_id)
+}
+
+fn to_progress(upload_id: &str, upload_session: &UploadSession) -> UploadSessionProgress {
+    UploadSessionProgress {
+        upload_id: upload_id.to_owned(),
+        num_chunks: upload_session.num_chunks,
+        received_chunks: upload_session.received_chunks.iter().cloned().collect(),
+    }
+}
@@ -0,0 +1,133 @@
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::RetrieveEncounterNpc;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::{CombatState, Death, DiagnosticActiveMap, DiagnosticAttempt, DiagnosticParticipant, EventCommitOutcomes, LiveDataProcessorFailure, Message, MessageType, UploadDiagnostic};
+use crate::modules::live_data_processor::material::{CombatLogArchive, Server, UploadMeta};
+use crate::modules::live_data_processor::tools::log_parser::stream_cbl_messages;
+use crate::modules::live_data_processor::tools::{ParseUpload, GUID};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use std::collections::{BTreeSet, HashMap};
+
+pub trait ValidateUpload {
+    fn validate_upload(&self, data: &Data, armory: &Armory, archive: &CombatLogArchive, meta: UploadMeta) -> Result<UploadDiagnostic, LiveDataProcessorFailure>;
+}
+
+impl ValidateUpload for LiveDataProcessor {
+    fn validate_upload(&self, data: &Data, armory: &Armory, archive: &CombatLogArchive, meta: UploadMeta) -> Result<UploadDiagnostic, LiveDataProcessorFailure> {
+        let prepared = self.inspect_upload(data, armory, archive, meta)?;
+        let server_id = prepared.server_id;
+        let expansion_id = prepared.collector.get_expansion_id();
+        let num_lines = prepared.num_lines;
+        let unparsable_lines = prepared.unparsable_lines.clone();
+
+        let participants = prepared
+            .collector
+            .get_participants()
+            .into_iter()
+            .filter(|participant| participant.is_player)
+            .map(|participant| DiagnosticParticipant {
+                unit_id: participant.id,
+                name: participant.name,
+                hero_class_id: participant.hero_class_id,
+                active_intervals: participant.active_intervals,
+            })
+            .collect();
+        let active_maps = prepared
+            .collector
+            .get_active_maps()
+            .into_iter()
+            .map(|active_map| DiagnosticActiveMap {
+                map_id: active_map.map_id,
+                intervals: active_map.intervals,
+                difficulties: active_map.active_difficulty,
+            })
+            .collect();
+
+        // A fresh server, so the live state of the actual server is not touched
+        let mut server = Server::new(server_id, expansion_id);
+        let mut attempt_detector = AttemptDetector::default();
+        let mut commit_outcomes = EventCommitOutcomes::default();
+        let mut num_messages = 0;
+        stream_cbl_messages(prepared, data, archive, |batch| {
+            num_messages += batch.len() as u64;
+            attempt_detector.feed(data, &batch);
+            commit_outcomes.merge(server.dry_run_events(armory, data, batch));
+            Ok(())
+        })?;
+        // Events that are still waiting at the end of the log
+        commit_outcomes.wait += server.count_waiting_events(u64::MAX);
+
+        Ok(UploadDiagnostic {
+            server_id,
+            expansion_id,
+            num_lines,
+            num_messages,
+            participants,
+            active_maps,
+            attempts: attempt_detector.finish(),
+            unparsable_lines,
+            commit_outcomes,
+        })
+    }
+}
+
+// An attempt lasts as long as any NPC of the encounter is in combat
+#[derive(Default)]
+struct AttemptDetector {
+    // encounter_id => (start_ts, units in combat, killed)
+    active_attempts: HashMap<u32, (u64, BTreeSet<u64>, bool)>,
+    attempts: Vec<DiagnosticAttempt>,
+    last_timestamp: u64,
+}
+
+impl AttemptDetector {
+    fn feed(&mut self, data: &Data, messages: &[Message]) {
+        for Message { timestamp, message_type, .. } in messages.iter() {
+            match message_type {
+                MessageType::CombatState(CombatState { unit, in_combat }) => {
+                    if let Some(encounter_npc) = unit.unit_id.get_entry().and_then(|entry| data.get_encounter_npc(entry)) {
+                        if *in_combat {
+                            if encounter_npc.can_start_encounter || self.active_attempts.contains_key(&encounter_npc.encounter_id) {
+                                self.active_attempts.entry(encounter_npc.encounter_id).or_insert_with(|| (*timestamp, BTreeSet::new(), false)).1.insert(unit.unit_id);
+                            }
+                        } else if let Some((start_ts, units_in_combat, killed)) = self.active_attempts.get_mut(&encounter_npc.encounter_id) {
+                            units_in_combat.remove(&unit.unit_id);
+                            if units_in_combat.is_empty() {
+                                self.attempts.push(DiagnosticAttempt {
+                                    encounter_id: encounter_npc.encounter_id,
+                                    start_ts: *start_ts,
+                                    end_ts: *timestamp,
+                                    killed: *killed,
+                                });
+                                self.active_attempts.remove(&encounter_npc.encounter_id);
+                            }
+                        }
+                    }
+                },
+                MessageType::Death(Death { victim, .. }) => {
+                    if let Some(encounter_npc) = victim.unit_id.get_entry().and_then(|entry| data.get_encounter_npc(entry)).filter(|encounter_npc| encounter_npc.requires_death) {
+                        if let Some((_, _, killed)) = self.active_attempts.get_mut(&encounter_npc.encounter_id) {
+                            *killed = true;
+                        }
+                    }
+                },
+                _ => {},
+            }
+            self.last_timestamp = *timestamp;
+        }
+    }
+
+    fn finish(mut self) -> Vec<DiagnosticAttempt> {
+        // Attempts that are still ongoing at the end of the log
+        for (encounter_id, (start_ts, _, killed)) in self.active_attempts {
+            self.attempts.push(DiagnosticAttempt {
+                encounter_id,
+                start_ts,
+                end_ts: self.last_timestamp,
+                killed,
+            });
+        }
+        self.attempts.sort_by_key(|attempt| attempt.start_ts);
+        self.attempts
+    }
+}
@@ -1,4 +1,4 @@
-use crate::modules::account::guard::ServerOwner;
+use crate::modules::account::guard::{scope, ServerOwner};
 use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
 use crate::modules::live_data_processor::tools::ProcessMessages;
 use crate::modules::live_data_processor::LiveDataProcessor;
@@ -12,11 +12,11 @@ use rocket_multipart_form_data::{MultipartFormData, MultipartFormDataField, Mult
 
 #[openapi(skip)]
 #[post("/package", format = "multipart/form-data", data = "<data>")]
-pub fn get_package(mut db_main: MainDb, me: State<LiveDataProcessor>, armory: State<Armory>, domain_data: State<DomainData>, owner: ServerOwner, content_type: &ContentType, data: Data) -> Result<(), LiveDataProcessorFailure> {
+pub fn get_package(mut db_main: MainDb, me: State<LiveDataProcessor>, armory: State<Armory>, domain_data: State<DomainData>, owner: ServerOwner<scope::ServerPackage>, content_type: &ContentType, data: Data) -> Result<(), LiveDataProcessorFailure> {
     let mut options = MultipartFormDataOptions::new();
     options.allowed_fields.push(MultipartFormDataField::bytes("payload").size_limit(2 * 1024 * 1024));
 
-    let mut multipart_form_data = MultipartFormData::parse(content_type, data, options).unwrap();
+    let mut multipart_form_data = MultipartFormData::parse(content_type, data, options).map_err(|_| LiveDataProcessorFailure::MalformedForm)?;
 
     let payload = multipart_form_data.raw.get_mut("payload");
 
@@ -24,18 +24,18 @@ pub fn get_package(mut db_main: MainDb, me: State<LiveDataProcessor>, armory: St
         if let Some(raw_field) = raw_fields.get_mut(0) {
             let RawField { content_type: _, file_name: _, raw } = raw_field;
             if raw.is_empty() {
-                return Err(LiveDataProcessorFailure::InvalidInput);
+                return Err(LiveDataProcessorFailure::EmptyPayload);
             }
 
             let mut messages = Vec::new();
             while !raw.is_empty() {
-                if raw[2] == 0 {
-                    return Err(LiveDataProcessorFailure::InvalidInput);
+                if raw.len() < 3 || raw[2] == 0 || raw[2] as usize > raw.len() {
+                    return Err(LiveDataProcessorFailure::InvalidPackage);
                 }
                 messages.push(raw.drain(..(raw[2] as usize)).collect());
             }
             return me.parse_messages(&mut *db_main, owner.0, &armory, &domain_data, messages, owner.0);
         }
     }
-    Err(LiveDataProcessorFailure::InvalidInput)
+    Err(LiveDataProcessorFailure::MissingField(String::from("payload")))
 }
@@ -1,114 +1,117 @@
+use crate::modules::account::guard::{role, scope, HasRole};
 use crate::modules::armory::Armory;
-use crate::modules::data::tools::RetrieveServer;
 use crate::modules::data::Data as DataMaterial;
-use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
-use crate::modules::live_data_processor::material::{WoWRetailClassicParser, WoWTBCParser, WoWWOTLKParser, WoWVanillaParser};
-use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
-use crate::modules::live_data_processor::tools::log_parser::parse_cbl;
-use crate::modules::live_data_processor::tools::ProcessMessages;
+use crate::modules::live_data_processor::dto::{CreateUploadSession, LiveDataProcessorFailure, UploadDiagnostic, UploadJob, UploadSessionProgress};
+use crate::modules::live_data_processor::material::{CombatLogArchive, UploadMeta};
+use crate::modules::live_data_processor::tools::{parse_upload_time, stage_upload_payload, UploadJobs, UploadSessions, ValidateUpload};
 use crate::modules::live_data_processor::LiveDataProcessor;
-use crate::util::database::{Execute, Select};
 use crate::MainDb;
-use chrono::NaiveDateTime;
 use rocket::http::ContentType;
 use rocket::{Data, State};
-use rocket_multipart_form_data::{MultipartFormData, MultipartFormDataField, MultipartFormDataOptions, RawField};
-use std::io::Read;
-use crate::modules::account::guard::Authenticate;
+use rocket_contrib::json::Json;
+use rocket_multipart_form_data::{FileField, MultipartFormData, MultipartFormDataField, MultipartFormDataOptions, RawField};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 #[openapi(skip)]
 #[post("/upload", format = "multipart/form-data", data = "<form_data>")]
-pub fn upload_log(mut db_main: MainDb, auth: Authenticate, me: State<LiveDataProcessor>, data: State<DataMaterial>, armory: State<Armory>, content_type: &ContentType, form_data: Data) -> Result<(), LiveDataProcessorFailure> {
+pub fn upload_log(mut db_main: MainDb, auth: HasRole<role::Uploader, scope::UploadLog>, me: State<LiveDataProcessor>, content_type: &ContentType, form_data: Data) -> Result<Json<UploadJob>, LiveDataProcessorFailure> {
+    let (payload, meta) = parse_upload_form(&me.upload_storage_path, content_type, form_data)?;
+    me.enqueue_upload_job(&mut *db_main, auth.0, &payload, meta).map(Json)
+}
+
+#[openapi(skip)]
+#[post("/upload/validate", format = "multipart/form-data", data = "<form_data>")]
+pub fn validate_log(
+    _auth: HasRole<role::Uploader, scope::UploadLog>, me: State<LiveDataProcessor>, data: State<Arc<DataMaterial>>, armory: State<Armory>, content_type: &ContentType, form_data: Data,
+) -> Result<Json<UploadDiagnostic>, LiveDataProcessorFailure> {
+    let (payload, meta) = parse_upload_form(&me.upload_storage_path, content_type, form_data)?;
+    let diagnostic = me.validate_upload(&data, &armory, &CombatLogArchive::new(&payload), meta);
+    let _ = std::fs::remove_file(&payload);
+    diagnostic.map(Json)
+}
+
+// The payload is staged in the upload storage
+fn parse_upload_form(storage_path: &str, content_type: &ContentType, form_data: Data) -> Result<(PathBuf, UploadMeta), LiveDataProcessorFailure> {
     let mut options = MultipartFormDataOptions::new();
-    options.allowed_fields.push(MultipartFormDataField::bytes("payload").size_limit(40 * 1024 * 1024 * 1024));
+    options.allowed_fields.push(MultipartFormDataField::file("payload").size_limit(40 * 1024 * 1024 * 1024));
     options.allowed_fields.push(MultipartFormDataField::bytes("payload_armory").size_limit(10 * 1024 * 1024 * 1024));
     options.allowed_fields.push(MultipartFormDataField::bytes("server_id").size_limit(1024));
     options.allowed_fields.push(MultipartFormDataField::bytes("start_time").size_limit(1024));
     options.allowed_fields.push(MultipartFormDataField::bytes("end_time").size_limit(1024));
+    options.allowed_fields.push(MultipartFormDataField::bytes("utc_offset").size_limit(1024));
 
-    let mut multipart_form_data = MultipartFormData::parse(content_type, form_data, options).unwrap();
+    let mut multipart_form_data = MultipartFormData::parse(content_type, form_data, options).map_err(|_| LiveDataProcessorFailure::MalformedForm)?;
 
-    let mut start_time_raw_fields = multipart_form_data.raw.remove("start_time").ok_or(LiveDataProcessorFailure::InvalidInput)?;
-    let start_time_raw_field = start_time_raw_fields.remove(0);
-    let start_time_raw = std::str::from_utf8(&start_time_raw_field.raw).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
-    let start_time = NaiveDateTime::parse_from_str(&start_time_raw, "%d.%m.%y %I:%M %p").ok().ok_or(LiveDataProcessorFailure::InvalidInput)?;
+    // Older clients don't send an offset, their times are taken as UTC
+    let utc_offset = match take_text_field(&mut multipart_form_data, "utc_offset") {
+        Some(utc_offset) => i32::from_str_radix(&utc_offset?, 10).map_err(|_| LiveDataProcessorFailure::InvalidField(String::from("utc_offset")))?,
+        None => 0,
+    };
+    let start_time = parse_upload_time(&take_required_text_field(&mut multipart_form_data, "start_time")?, utc_offset)?;
+    let end_time = parse_upload_time(&take_required_text_field(&mut multipart_form_data, "end_time")?, utc_offset)?;
+    let server_id = i32::from_str_radix(&take_required_text_field(&mut multipart_form_data, "server_id")?, 10).map_err(|_| LiveDataProcessorFailure::InvalidField(String::from("server_id")))?;
+    let armory_content = take_text_field(&mut multipart_form_data, "payload_armory").transpose()?;
 
-    let mut end_time_raw_fields = multipart_form_data.raw.remove("end_time").ok_or(LiveDataProcessorFailure::InvalidInput)?;
-    let end_time_raw_field = end_time_raw_fields.remove(0);
-    let end_time_raw = std::str::from_utf8(&end_time_raw_field.raw).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
-    let end_time = NaiveDateTime::parse_from_str(&end_time_raw, "%d.%m.%y %I:%M %p").ok().ok_or(LiveDataProcessorFailure::InvalidInput)?;
+    let mut file_fields = multipart_form_data.files.remove("payload").ok_or_else(|| LiveDataProcessorFailure::MissingField(String::from("payload")))?;
+    let FileField { path, .. } = file_fields.remove(0);
+    let payload = stage_upload_payload(storage_path, std::fs::File::open(path).map_err(|_| LiveDataProcessorFailure::StorageFailure(String::from("parse_upload_form")))?)?;
 
-    let mut server_id_raw_fields = multipart_form_data.raw.remove("server_id").ok_or(LiveDataProcessorFailure::InvalidInput)?;
-    let RawField { raw: server_id_raw, .. } = server_id_raw_fields.remove(0);
-    let server_id = i32::from_str_radix(std::str::from_utf8(&server_id_raw).map_err(|_| LiveDataProcessorFailure::InvalidInput)?, 10).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
+    Ok((
+        payload,
+        UploadMeta {
+            server_id,
+            start_time,
+            end_time,
+            utc_offset,
+            armory_content,
+        },
+    ))
+}
 
-    let mut raw_fields = multipart_form_data.raw.remove("payload").ok_or(LiveDataProcessorFailure::InvalidInput)?;
-    let RawField { content_type: _, file_name: _, raw } = raw_fields.remove(0);
-    if raw.is_empty() {
-        return Err(LiveDataProcessorFailure::InvalidInput);
-    }
-    let reader = std::io::Cursor::new(raw.as_slice());
-    let mut zip = zip::ZipArchive::new(reader).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
+fn take_text_field(multipart_form_data: &mut MultipartFormData, field: &str) -> Option<Result<String, LiveDataProcessorFailure>> {
+    multipart_form_data.raw.remove(field).map(|mut raw_fields| {
+        let RawField { raw, .. } = raw_fields.remove(0);
+        String::from_utf8(raw).map_err(|_| LiveDataProcessorFailure::InvalidField(field.to_owned()))
+    })
+}
 
-    // There should only be the combat log in there
-    let file = zip.by_index(0).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
-    let bytes = file.bytes().filter_map(|byte| byte.ok()).collect::<Vec<u8>>();
-    let content = std::str::from_utf8(&bytes).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
+fn take_required_text_field(multipart_form_data: &mut MultipartFormData, field: &str) -> Result<String, LiveDataProcessorFailure> {
+    take_text_field(multipart_form_data, field).unwrap_or_else(|| Err(LiveDataProcessorFailure::MissingField(field.to_owned())))
+}
 
-    let armory_content = multipart_form_data.raw.remove("payload_armory").and_then(|mut armory_raw_fields| {
-        let RawField { raw, .. } = armory_raw_fields.remove(0);
-        std::str::from_utf8(&raw).ok().map(|str| str.to_string())
-    });
+#[openapi]
+#[post("/upload/session", format = "application/json", data = "<session>")]
+pub fn open_upload_session(me: State<LiveDataProcessor>, auth: HasRole<role::Uploader, scope::UploadLog>, session: Json<CreateUploadSession>) -> Result<Json<UploadSessionProgress>, LiveDataProcessorFailure> {
+    me.open_upload_session(auth.0, session.into_inner()).map(Json)
+}
+
+#[openapi]
+#[get("/upload/session/<upload_id>")]
+pub fn get_upload_session(me: State<LiveDataProcessor>, auth: HasRole<role::Uploader, scope::UploadLog>, upload_id: String) -> Result<Json<UploadSessionProgress>, LiveDataProcessorFailure> {
+    me.get_upload_session_progress(auth.0, &upload_id).map(Json)
+}
 
-    if server_id == -1 {
-        return parse(&me, WoWRetailClassicParser::new(), &mut *db_main, &data, &armory, content, start_time.timestamp_millis() as u64, end_time.timestamp_millis() as u64, auth.0);
-    } else {
-        let server = data.get_server(server_id as u32).unwrap();
-        if server.expansion_id == 1 {
-            return parse(
-                &me,
-                WoWVanillaParser::new(server_id as u32),
-                &mut *db_main,
-                &data,
-                &armory,
-                content,
-                start_time.timestamp_millis() as u64,
-                end_time.timestamp_millis() as u64,
-                auth.0
-            );
-        } else if server.expansion_id == 2 {
-            return parse(
-                &me,
-                WoWTBCParser::new(server_id as u32, armory_content),
-                &mut *db_main,
-                &data,
-                &armory,
-                content,
-                start_time.timestamp_millis() as u64,
-                end_time.timestamp_millis() as u64,
-                auth.0
-            );
-        } else if server.expansion_id == 3 {
-            return parse(
-                &me,
-                WoWWOTLKParser::new(server_id as u32, armory_content),
-                &mut *db_main,
-                &data,
-                &armory,
-                content,
-                start_time.timestamp_millis() as u64,
-                end_time.timestamp_millis() as u64,
-                auth.0
-            );
-        }
-    }
+#[openapi(skip)]
+#[post("/upload/chunk/<upload_id>/<chunk_index>", format = "application/octet-stream", data = "<chunk>")]
+pub fn upload_chunk(me: State<LiveDataProcessor>, auth: HasRole<role::Uploader, scope::UploadLog>, upload_id: String, chunk_index: u32, chunk: Data) -> Result<Json<UploadSessionProgress>, LiveDataProcessorFailure> {
+    me.store_upload_chunk(auth.0, &upload_id, chunk_index, chunk.open()).map(Json)
+}
+
+#[openapi]
+#[post("/upload/finalize/<upload_id>")]
+pub fn finalize_upload_session(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: HasRole<role::Uploader, scope::UploadLog>, upload_id: String) -> Result<Json<UploadJob>, LiveDataProcessorFailure> {
+    me.finalize_upload_session(&mut *db_main, auth.0, &upload_id).map(Json)
+}
 
-    Err(LiveDataProcessorFailure::InvalidInput)
+#[openapi]
+#[get("/upload/job")]
+pub fn get_upload_jobs(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: HasRole<role::Uploader, scope::UploadLog>) -> Json<Vec<UploadJob>> {
+    Json(me.get_upload_jobs(&mut *db_main, auth.0))
 }
 
-fn parse(me: &LiveDataProcessor, mut parser: impl CombatLogParser, db_main: &mut (impl Select + Execute), data: &DataMaterial, armory: &Armory, content: &str, start_time: u64, end_time: u64, member_id: u32) -> Result<(), LiveDataProcessorFailure> {
-    if let Some((server_id, messages)) = parse_cbl(&mut parser, &mut *db_main, data, armory, content, start_time, end_time) {
-        return me.process_messages(&mut *db_main, server_id as u32, &armory, &data, messages, member_id);
-    }
-    Err(LiveDataProcessorFailure::InvalidInput)
+#[openapi]
+#[get("/upload/job/<job_id>")]
+pub fn get_upload_job(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: HasRole<role::Uploader, scope::UploadLog>, job_id: String) -> Result<Json<UploadJob>, LiveDataProcessorFailure> {
+    me.get_upload_job(&mut *db_main, auth.0, &job_id).map(Json)
 }
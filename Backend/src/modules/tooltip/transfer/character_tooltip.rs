@@ -6,9 +6,10 @@ use crate::modules::{
     data::{guard::Language, Data},
     tooltip::{dto::TooltipFailure, material::CharacterTooltip, tools::RetrieveCharacterTooltip, Tooltip},
 };
+use std::sync::Arc;
 
 #[openapi]
 #[get("/character/<id>")]
-pub fn get_character(me: State<Tooltip>, data: State<Data>, armory: State<Armory>, language: Language, id: u32) -> Result<Json<CharacterTooltip>, TooltipFailure> {
+pub fn get_character(me: State<Tooltip>, data: State<Arc<Data>>, armory: State<Armory>, language: Language, id: u32) -> Result<Json<CharacterTooltip>, TooltipFailure> {
     me.get_character(&data, &armory, language.0, id).map(Json)
 }
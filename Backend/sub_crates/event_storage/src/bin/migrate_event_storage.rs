@@ -0,0 +1,24 @@
+extern crate event_storage;
+
+/// Converts the instance event files from the text format to the current event storage format.
+/// It is run while the backend is stopped, as the backend may append to the same files otherwise.
+/// Until then, the backend reads the text format next to the events that it appended since.
+/// Usage: migrate_event_storage [storage path], defaults to INSTANCE_STORAGE_PATH
+fn main() {
+    let storage_path = std::env::args().nth(1).or_else(|| std::env::var("INSTANCE_STORAGE_PATH").ok()).expect("storage path must be set!");
+    match event_storage::migrate_storage(&storage_path) {
+        Ok(result) => {
+            println!("Migrated {} events in {} files", result.migrated_events, result.migrated_files);
+            for failed_file in result.failed_files.iter() {
+                println!("Failed to migrate {}", failed_file);
+            }
+            if !result.failed_files.is_empty() {
+                std::process::exit(1);
+            }
+        },
+        Err(err) => {
+            println!("Failed to migrate {}: {}", storage_path, err);
+            std::process::exit(1);
+        },
+    }
+}
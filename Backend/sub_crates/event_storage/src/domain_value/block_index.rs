@@ -0,0 +1,22 @@
+/// Header of a block of events, which is read without decompressing the block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockIndex {
+    // Offset of the block header in the file
+    pub offset: u64,
+    pub compressed_len: u32,
+    pub num_events: u32,
+    pub min_id: u32,
+    pub max_id: u32,
+    pub min_timestamp: u64,
+    pub max_timestamp: u64,
+}
+
+impl BlockIndex {
+    pub fn has_ids_after(&self, last_event_id: u32) -> bool {
+        self.max_id > last_event_id
+    }
+
+    pub fn overlaps(&self, start_ts: u64, end_ts: u64) -> bool {
+        self.min_timestamp <= end_ts && self.max_timestamp >= start_ts
+    }
+}
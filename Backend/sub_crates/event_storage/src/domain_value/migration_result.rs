@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationResult {
+    pub migrated_files: usize,
+    pub migrated_events: usize,
+    pub failed_files: Vec<String>,
+}
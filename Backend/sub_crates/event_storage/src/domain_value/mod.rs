@@ -0,0 +1,5 @@
+pub use self::block_index::BlockIndex;
+pub use self::migration_result::MigrationResult;
+
+mod block_index;
+mod migration_result;
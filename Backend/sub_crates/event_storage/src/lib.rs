@@ -0,0 +1,13 @@
+extern crate serde_json;
+extern crate zstd;
+
+pub use self::domain_value::{BlockIndex, MigrationResult};
+pub use self::tools::codec::{decode_line, encode_line};
+pub use self::tools::compact::{compact_event_file, compact_instance};
+pub use self::tools::migrate::{migrate_event_file, migrate_instance, migrate_storage};
+pub use self::tools::storage::{append_events, event_file_path, index_file_path, legacy_event_file_path, read_events, read_events_where, read_index, FORMAT_VERSION};
+
+mod domain_value;
+#[cfg(test)]
+mod tests;
+mod tools;
@@ -0,0 +1,53 @@
+extern crate proptest;
+use self::proptest::prelude::*;
+use crate::{decode_line, encode_line};
+
+fn transcode(line: &str) -> (usize, Option<String>) {
+    let mut buffer = Vec::new();
+    encode_line(line, &mut buffer);
+    (buffer.len(), decode_line(&buffer, &mut 0))
+}
+
+proptest! {
+  #[test]
+  fn transcode_numbers(id in 0u32.., timestamp in 0u64.., amount in any::<i32>()) {
+    let line = format!("[{},{},[1,5],[0,{},15990],0,1,1,{}]", id, timestamp, timestamp, amount);
+    prop_assert_eq!(transcode(&line).1, Some(line));
+  }
+
+  #[test]
+  fn transcode_positions(x in any::<f64>(), y in -10000.0f64..10000.0) {
+    let line = format!("[1,2,[1,5],{},{},0,3.14]", x, y);
+    prop_assert_eq!(transcode(&line).1, Some(line));
+  }
+}
+
+#[test]
+fn transcode_event_lines() {
+    let lines = [
+        "[1,1000,[1,5],[0,42,15990],1,[[100,1,20,0,0],[50,4,0,0,0]]]",
+        "[2,1500,7,[0,9,1863,[1,5]],[0,42,15990],133,2,[[300,4,0,0,0]]]",
+        "[3,2000,[1,6],null,48782,1,2]",
+        "[4,2500,[1,5],true]",
+        "[5,3000,1,[1,5],[0,42,15990],0,1,1,-130]",
+        "[6,3500,?!?]",
+        "[7,4000,-0,\"name\"]",
+    ];
+    for line in lines.iter() {
+        assert_eq!(transcode(line).1.as_deref(), Some(*line));
+    }
+}
+
+#[test]
+fn encoding_is_smaller_than_text() {
+    let line = "[123456,1602345678901,13,[0,17379391181095723008,15990],[1,1234],133,2,[[3000,4,0,0,0]]]";
+    assert!(transcode(line).0 < line.len() * 2 / 3);
+}
+
+#[test]
+fn decode_truncated_line() {
+    let mut buffer = Vec::new();
+    encode_line("[1,1000,[1,5],[0,42,15990]]", &mut buffer);
+    buffer.truncate(buffer.len() - 1);
+    assert_eq!(decode_line(&buffer, &mut 0), None);
+}
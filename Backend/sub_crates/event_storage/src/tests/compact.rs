@@ -0,0 +1,39 @@
+use crate::tests::storage::{create_instance_path, event_lines};
+use crate::{append_events, compact_event_file, compact_instance, read_events, read_index};
+
+#[test]
+fn compact_small_blocks() {
+    let instance_path = create_instance_path("compact");
+    for start in (0..5000).step_by(100) {
+        append_events(&instance_path, 12, &event_lines(start..(start + 100))).unwrap();
+    }
+    assert_eq!(read_index(&instance_path, 12).unwrap().len(), 50);
+
+    assert_eq!(compact_event_file(&instance_path, 12).unwrap(), 50);
+    assert_eq!(read_index(&instance_path, 12).unwrap().iter().map(|block| block.num_events).collect::<Vec<u32>>(), vec![4096, 904]);
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..5000));
+
+    // Already compact
+    assert_eq!(compact_event_file(&instance_path, 12).unwrap(), 0);
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn compact_instance_event_files() {
+    let instance_path = create_instance_path("compact_instance");
+    for event_type in vec![12, 14] {
+        append_events(&instance_path, event_type, &event_lines(0..10)).unwrap();
+        append_events(&instance_path, event_type, &event_lines(10..20)).unwrap();
+    }
+
+    compact_instance(&instance_path).unwrap();
+    assert_eq!(read_index(&instance_path, 12).unwrap().len(), 1);
+    assert_eq!(read_index(&instance_path, 14).unwrap().len(), 1);
+    assert_eq!(read_events(&instance_path, 14).unwrap().len(), 20);
+
+    // Appending continues after the compacted block
+    append_events(&instance_path, 12, &event_lines(20..30)).unwrap();
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..30));
+    assert!(compact_instance(&format!("{}/missing", instance_path)).is_ok());
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
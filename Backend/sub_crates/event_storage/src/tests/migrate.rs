@@ -0,0 +1,52 @@
+use crate::tests::storage::{create_instance_path, event_lines};
+use crate::{append_events, event_file_path, legacy_event_file_path, migrate_event_file, migrate_storage, read_events, read_index, MigrationResult};
+use std::path::Path;
+
+#[test]
+fn migrate_text_file() {
+    let instance_path = create_instance_path("migrate");
+    let text = event_lines(0..5000).join("\n") + "\n";
+    std::fs::write(legacy_event_file_path(&instance_path, 12), &text).unwrap();
+
+    assert_eq!(migrate_event_file(&instance_path, 12).unwrap(), 5000);
+    assert!(!Path::new(&legacy_event_file_path(&instance_path, 12)).exists());
+    assert!(std::fs::metadata(event_file_path(&instance_path, 12)).unwrap().len() < text.len() as u64 / 4);
+    assert_eq!(read_index(&instance_path, 12).unwrap().len(), 2);
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..5000));
+    assert_eq!(migrate_event_file(&instance_path, 12).unwrap(), 0);
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn migrate_keeps_appended_events() {
+    let instance_path = create_instance_path("migrate_appended");
+    std::fs::write(legacy_event_file_path(&instance_path, 12), event_lines(0..10).join("\n")).unwrap();
+    append_events(&instance_path, 12, &event_lines(10..20)).unwrap();
+
+    assert_eq!(migrate_event_file(&instance_path, 12).unwrap(), 10);
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..20));
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn migrate_storage_directories() {
+    let storage_path = create_instance_path("migrate_storage");
+    let instance_path = format!("{}/1/42", storage_path);
+    std::fs::create_dir_all(&instance_path).unwrap();
+    std::fs::write(format!("{}/1/live_state", storage_path), b"snapshot").unwrap();
+    std::fs::write(legacy_event_file_path(&instance_path, 12), event_lines(0..10).join("\n")).unwrap();
+    std::fs::write(legacy_event_file_path(&instance_path, 14), event_lines(0..3).join("\n")).unwrap();
+    std::fs::write(format!("{}/notes", instance_path), b"not an event file").unwrap();
+
+    assert_eq!(
+        migrate_storage(&storage_path).unwrap(),
+        MigrationResult {
+            migrated_files: 2,
+            migrated_events: 13,
+            failed_files: Vec::new(),
+        }
+    );
+    assert!(Path::new(&format!("{}/notes", instance_path)).exists());
+    assert!(Path::new(&format!("{}/1/live_state", storage_path)).exists());
+    let _ = std::fs::remove_dir_all(&storage_path);
+}
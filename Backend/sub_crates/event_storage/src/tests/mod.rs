@@ -0,0 +1,4 @@
+mod codec;
+mod compact;
+mod migrate;
+mod storage;
@@ -0,0 +1,123 @@
+use crate::{append_events, event_file_path, index_file_path, legacy_event_file_path, read_events, read_events_where, read_index};
+use std::fs::OpenOptions;
+use std::io::Write;
+
+pub fn create_instance_path(name: &str) -> String {
+    let instance_path = format!("{}/event_storage_{}_{}", std::env::temp_dir().to_string_lossy(), name, std::process::id());
+    let _ = std::fs::remove_dir_all(&instance_path);
+    std::fs::create_dir_all(&instance_path).unwrap();
+    instance_path
+}
+
+pub fn event_lines(ids: std::ops::Range<u32>) -> Vec<String> {
+    ids.map(|id| format!("[{},{},[1,5],[0,42,15990],1,[[100,1,0,0,0]]]", id, 1000 * id as u64)).collect()
+}
+
+#[test]
+fn append_and_read_events() {
+    let instance_path = create_instance_path("append");
+    append_events(&instance_path, 12, &event_lines(0..10)).unwrap();
+    append_events(&instance_path, 12, &[]).unwrap();
+    append_events(&instance_path, 12, &event_lines(10..15)).unwrap();
+
+    let events = read_events(&instance_path, 12).unwrap();
+    assert_eq!(events.iter().map(|(id, _)| *id).collect::<Vec<u32>>(), (0..15).collect::<Vec<u32>>());
+    assert_eq!(events.into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..15));
+
+    let index = read_index(&instance_path, 12).unwrap();
+    assert_eq!(index.len(), 2);
+    assert_eq!((index[1].min_id, index[1].max_id, index[1].num_events), (10, 14, 5));
+    assert_eq!((index[1].min_timestamp, index[1].max_timestamp), (10000, 14000));
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn read_events_by_index() {
+    let instance_path = create_instance_path("index");
+    append_events(&instance_path, 12, &event_lines(0..10)).unwrap();
+    append_events(&instance_path, 12, &event_lines(10..20)).unwrap();
+
+    assert_eq!(read_events_where(&instance_path, 12, |block| block.has_ids_after(9)).unwrap().len(), 10);
+    assert_eq!(read_events_where(&instance_path, 12, |block| block.overlaps(8000, 11000)).unwrap().len(), 20);
+    assert_eq!(read_events_where(&instance_path, 12, |block| block.overlaps(20000, 30000)).unwrap().len(), 0);
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn discard_partially_written_block() {
+    let instance_path = create_instance_path("partial");
+    append_events(&instance_path, 12, &event_lines(0..10)).unwrap();
+    let mut file = OpenOptions::new().append(true).open(event_file_path(&instance_path, 12)).unwrap();
+    file.write_all(&[1, 2, 3, 4, 5]).unwrap();
+    drop(file);
+
+    assert_eq!(read_events(&instance_path, 12).unwrap().len(), 10);
+    append_events(&instance_path, 12, &event_lines(10..12)).unwrap();
+    assert_eq!(read_events(&instance_path, 12).unwrap().len(), 12);
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn reject_unknown_format() {
+    let instance_path = create_instance_path("unknown");
+    std::fs::write(event_file_path(&instance_path, 12), b"RPLLEV\xFF\x0Csomething").unwrap();
+    assert!(append_events(&instance_path, 12, &event_lines(0..1)).is_err());
+    assert!(read_events(&instance_path, 12).is_err());
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn persist_index() {
+    let instance_path = create_instance_path("persist_index");
+    append_events(&instance_path, 12, &event_lines(0..10)).unwrap();
+    append_events(&instance_path, 12, &event_lines(10..20)).unwrap();
+    let index = read_index(&instance_path, 12).unwrap();
+    assert_eq!(std::fs::metadata(index_file_path(&instance_path, 12)).unwrap().len(), 8 + 2 * 40);
+
+    // A partially written entry is ignored and overwritten
+    let mut file = OpenOptions::new().append(true).open(index_file_path(&instance_path, 12)).unwrap();
+    file.write_all(&[1, 2, 3]).unwrap();
+    drop(file);
+    assert_eq!(read_index(&instance_path, 12).unwrap(), index);
+    append_events(&instance_path, 12, &event_lines(20..30)).unwrap();
+    assert_eq!(std::fs::metadata(index_file_path(&instance_path, 12)).unwrap().len(), 8 + 3 * 40);
+    assert_eq!(read_events(&instance_path, 12).unwrap().len(), 30);
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn rebuild_index_that_does_not_match() {
+    let instance_path = create_instance_path("rebuild_index");
+    append_events(&instance_path, 12, &event_lines(0..10)).unwrap();
+    let index = read_index(&instance_path, 12).unwrap();
+
+    std::fs::write(index_file_path(&instance_path, 12), b"garbage").unwrap();
+    assert_eq!(read_index(&instance_path, 12).unwrap(), index);
+    append_events(&instance_path, 12, &event_lines(10..20)).unwrap();
+    assert_eq!(read_index(&instance_path, 12).unwrap().len(), 2);
+
+    // Blocks that are missing in the index, e.g. after a crash
+    std::fs::remove_file(index_file_path(&instance_path, 12)).unwrap();
+    append_events(&instance_path, 12, &event_lines(20..30)).unwrap();
+    assert_eq!(read_index(&instance_path, 12).unwrap().len(), 3);
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..30));
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn read_legacy_text_file() {
+    let instance_path = create_instance_path("legacy");
+    std::fs::write(legacy_event_file_path(&instance_path, 12), event_lines(0..3).join("\n") + "\n").unwrap();
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..3));
+    assert!(read_events(&instance_path, 13).unwrap().is_empty());
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
+
+#[test]
+fn read_legacy_text_file_and_appended_events() {
+    let instance_path = create_instance_path("legacy_appended");
+    std::fs::write(legacy_event_file_path(&instance_path, 12), event_lines(0..3).join("\n") + "\n").unwrap();
+    append_events(&instance_path, 12, &event_lines(3..5)).unwrap();
+    assert_eq!(read_events(&instance_path, 12).unwrap().into_iter().map(|(_, line)| line).collect::<Vec<String>>(), event_lines(0..5));
+    let _ = std::fs::remove_dir_all(&instance_path);
+}
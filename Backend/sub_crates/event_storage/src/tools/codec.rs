@@ -0,0 +1,135 @@
+use serde_json::Value;
+use std::fmt::Write;
+
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_UNSIGNED: u8 = 3;
+const TAG_NEGATIVE: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_ARRAY: u8 = 6;
+const TAG_STRING: u8 = 7;
+const TAG_RAW: u8 = 8;
+// Unsigned numbers below 240 are stored in the tag itself
+const TAG_SMALL_UNSIGNED: u8 = 16;
+
+/// Encodes a line of the text format, i.e. a JSON array of numbers and nested arrays.
+/// Lines that would not be transcoded back to exactly the same text are stored verbatim.
+pub fn encode_line(line: &str, buffer: &mut Vec<u8>) {
+    if let Ok(value) = serde_json::from_str::<Value>(line) {
+        let mut encoded = Vec::new();
+        if encode_value(&value, &mut encoded) && decode_line(&encoded, &mut 0).as_deref() == Some(line) {
+            buffer.append(&mut encoded);
+            return;
+        }
+    }
+    buffer.push(TAG_RAW);
+    write_varint(line.len() as u64, buffer);
+    buffer.extend_from_slice(line.as_bytes());
+}
+
+/// Transcodes an encoded line back to the text format, starting at the cursor
+pub fn decode_line(buffer: &[u8], cursor: &mut usize) -> Option<String> {
+    let mut line = String::new();
+    decode_value(buffer, cursor, &mut line)?;
+    Some(line)
+}
+
+fn encode_value(value: &Value, buffer: &mut Vec<u8>) -> bool {
+    match value {
+        Value::Null => buffer.push(TAG_NULL),
+        Value::Bool(false) => buffer.push(TAG_FALSE),
+        Value::Bool(true) => buffer.push(TAG_TRUE),
+        Value::Number(number) => {
+            if let Some(unsigned) = number.as_u64().filter(|unsigned| *unsigned < (256 - TAG_SMALL_UNSIGNED as u64)) {
+                buffer.push(TAG_SMALL_UNSIGNED + unsigned as u8);
+            } else if let Some(unsigned) = number.as_u64() {
+                buffer.push(TAG_UNSIGNED);
+                write_varint(unsigned, buffer);
+            } else if let Some(negative) = number.as_i64() {
+                buffer.push(TAG_NEGATIVE);
+                write_varint(!(negative as u64), buffer);
+            } else if let Some(float) = number.as_f64() {
+                buffer.push(TAG_FLOAT);
+                buffer.extend_from_slice(&float.to_le_bytes());
+            } else {
+                return false;
+            }
+        },
+        Value::String(string) => {
+            buffer.push(TAG_STRING);
+            write_varint(string.len() as u64, buffer);
+            buffer.extend_from_slice(string.as_bytes());
+        },
+        Value::Array(values) => {
+            buffer.push(TAG_ARRAY);
+            write_varint(values.len() as u64, buffer);
+            return values.iter().all(|value| encode_value(value, buffer));
+        },
+        Value::Object(_) => return false,
+    };
+    true
+}
+
+fn decode_value(buffer: &[u8], cursor: &mut usize, line: &mut String) -> Option<()> {
+    let tag = *buffer.get(*cursor)?;
+    *cursor += 1;
+    match tag {
+        TAG_NULL => line.push_str("null"),
+        TAG_FALSE => line.push_str("false"),
+        TAG_TRUE => line.push_str("true"),
+        TAG_UNSIGNED => write!(line, "{}", read_varint(buffer, cursor)?).ok()?,
+        TAG_NEGATIVE => write!(line, "{}", !read_varint(buffer, cursor)? as i64).ok()?,
+        TAG_FLOAT => {
+            let bytes = buffer.get(*cursor..*cursor + 8)?;
+            *cursor += 8;
+            let mut float = [0; 8];
+            float.copy_from_slice(bytes);
+            write!(line, "{}", f64::from_le_bytes(float)).ok()?
+        },
+        TAG_STRING => line.push_str(&serde_json::to_string(read_str(buffer, cursor)?).ok()?),
+        TAG_ARRAY => {
+            let len = read_varint(buffer, cursor)?;
+            line.push('[');
+            for i in 0..len {
+                if i > 0 {
+                    line.push(',');
+                }
+                decode_value(buffer, cursor, line)?;
+            }
+            line.push(']');
+        },
+        TAG_RAW => line.push_str(read_str(buffer, cursor)?),
+        TAG_SMALL_UNSIGNED..=255 => write!(line, "{}", tag - TAG_SMALL_UNSIGNED).ok()?,
+        _ => return None,
+    };
+    Some(())
+}
+
+fn read_str<'a>(buffer: &'a [u8], cursor: &mut usize) -> Option<&'a str> {
+    let len = read_varint(buffer, cursor)? as usize;
+    let bytes = buffer.get(*cursor..*cursor + len)?;
+    *cursor += len;
+    std::str::from_utf8(bytes).ok()
+}
+
+pub fn write_varint(mut value: u64, buffer: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+pub fn read_varint(buffer: &[u8], cursor: &mut usize) -> Option<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = *buffer.get(*cursor)?;
+        *cursor += 1;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
@@ -0,0 +1,57 @@
+use crate::tools::storage::{encode_block, event_file_path, file_header, index_file_path, read_file_events, read_file_index, replace_event_file, EVENTS_PER_BLOCK};
+use std::fs::File;
+use std::io;
+
+/// Merges the blocks of the event type into full size blocks, if there are more blocks than needed.
+/// Returns the number of blocks that were merged.
+pub fn compact_event_file(instance_path: &str, event_type: u8) -> io::Result<usize> {
+    let path = event_file_path(instance_path, event_type);
+    let index_path = index_file_path(instance_path, event_type);
+    let mut file = match File::open(&path) {
+        Ok(file) => file,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err),
+    };
+    let index = read_file_index(Some(&index_path), &mut file)?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unknown event file format"))?;
+    let num_events = index.iter().map(|block| block.num_events as usize).sum::<usize>();
+    let num_required_blocks = (num_events + EVENTS_PER_BLOCK - 1) / EVENTS_PER_BLOCK;
+    if index.len() <= num_required_blocks {
+        return Ok(0);
+    }
+
+    let lines = read_file_events(&path, Some(&index_path), |_| true)?.into_iter().map(|(_, line)| line).collect::<Vec<String>>();
+    let mut content = file_header(event_type);
+    for block_lines in lines.chunks(EVENTS_PER_BLOCK) {
+        content.append(&mut encode_block(block_lines)?);
+    }
+
+    let temporary_path = format!("{}.tmp", path);
+    std::fs::write(&temporary_path, &content)?;
+    let is_lossless = read_file_events(&temporary_path, None, |_| true)?.into_iter().map(|(_, line)| line).eq(lines.into_iter());
+    if !is_lossless {
+        let _ = std::fs::remove_file(&temporary_path);
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Compacted events differ from the original"));
+    }
+    replace_event_file(instance_path, event_type, &temporary_path)?;
+    Ok(index.len())
+}
+
+/// Compacts all event files of an instance directory, e.g. once the instance is finalized and nothing is appended anymore
+pub fn compact_instance(instance_path: &str) -> io::Result<()> {
+    let entries = match std::fs::read_dir(instance_path) {
+        Ok(entries) => entries,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries.filter_map(Result::ok) {
+        if let Some(event_type) = entry
+            .file_name()
+            .to_str()
+            .filter(|file_name| file_name.ends_with(".events"))
+            .and_then(|file_name| file_name.trim_end_matches(".events").parse::<u8>().ok())
+        {
+            compact_event_file(instance_path, event_type)?;
+        }
+    }
+    Ok(())
+}
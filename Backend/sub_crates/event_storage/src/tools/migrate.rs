@@ -0,0 +1,81 @@
+use crate::domain_value::MigrationResult;
+use crate::tools::storage::{encode_block, event_file_path, file_header, index_file_path, legacy_event_file_path, read_file_events, read_file_index, replace_event_file, BLOCK_HEADER_LEN, EVENTS_PER_BLOCK};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Converts the text file of the event type, if there is one.
+/// Events that were already appended in the new format are kept after the migrated ones.
+/// The text file is only removed once the converted file was read back successfully.
+/// Returns the number of migrated events.
+pub fn migrate_event_file(instance_path: &str, event_type: u8) -> io::Result<usize> {
+    let legacy_path = legacy_event_file_path(instance_path, event_type);
+    if !Path::new(&legacy_path).exists() {
+        return Ok(0);
+    }
+    let legacy_content = std::fs::read_to_string(&legacy_path)?;
+    let lines = legacy_content.lines().map(|line| line.to_owned()).collect::<Vec<String>>();
+
+    let mut content = file_header(event_type);
+    for block_lines in lines.chunks(EVENTS_PER_BLOCK) {
+        content.append(&mut encode_block(block_lines)?);
+    }
+
+    let path = event_file_path(instance_path, event_type);
+    if let Ok(mut file) = File::open(&path) {
+        let index = read_file_index(Some(&index_file_path(instance_path, event_type)), &mut file)?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unknown event file format"))?;
+        if let (Some(first_block), Some(last_block)) = (index.first(), index.last()) {
+            let mut appended_blocks = vec![0; (last_block.offset + BLOCK_HEADER_LEN + last_block.compressed_len as u64 - first_block.offset) as usize];
+            file.seek(SeekFrom::Start(first_block.offset))?;
+            file.read_exact(&mut appended_blocks)?;
+            content.append(&mut appended_blocks);
+        }
+    }
+
+    let temporary_path = format!("{}.tmp", path);
+    std::fs::write(&temporary_path, &content)?;
+    let is_lossless = read_file_events(&temporary_path, None, |_| true)?.iter().zip(lines.iter()).all(|((_, transcoded), line)| transcoded == line);
+    if !is_lossless {
+        let _ = std::fs::remove_file(&temporary_path);
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Transcoded events differ from the original"));
+    }
+    replace_event_file(instance_path, event_type, &temporary_path)?;
+    std::fs::remove_file(&legacy_path)?;
+    Ok(lines.len())
+}
+
+/// Migrates all event files of an instance directory
+pub fn migrate_instance(instance_path: &str, result: &mut MigrationResult) -> io::Result<()> {
+    for entry in std::fs::read_dir(instance_path)?.filter_map(Result::ok) {
+        if let Some(event_type) = entry.file_name().to_str().and_then(|file_name| file_name.parse::<u8>().ok()) {
+            match migrate_event_file(instance_path, event_type) {
+                Ok(migrated_events) => {
+                    result.migrated_files += 1;
+                    result.migrated_events += migrated_events;
+                },
+                Err(_) => result.failed_files.push(legacy_event_file_path(instance_path, event_type)),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Migrates every instance of the storage, which is laid out as: {storage_path}/{server_id}/{instance_meta_id}/{event_type}
+pub fn migrate_storage(storage_path: &str) -> io::Result<MigrationResult> {
+    let mut result = MigrationResult::default();
+    for server_path in numeric_directories(storage_path)? {
+        for instance_path in numeric_directories(&server_path)? {
+            migrate_instance(&instance_path, &mut result)?;
+        }
+    }
+    Ok(result)
+}
+
+fn numeric_directories(path: &str) -> io::Result<Vec<String>> {
+    Ok(std::fs::read_dir(path)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false))
+        .filter(|entry| entry.file_name().to_str().map(|file_name| file_name.parse::<u32>().is_ok()).unwrap_or(false))
+        .map(|entry| format!("{}/{}", path, entry.file_name().to_string_lossy()))
+        .collect())
+}
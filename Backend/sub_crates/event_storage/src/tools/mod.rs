@@ -0,0 +1,4 @@
+pub mod codec;
+pub mod compact;
+pub mod migrate;
+pub mod storage;
@@ -0,0 +1,337 @@
+use crate::domain_value::BlockIndex;
+use crate::tools::codec::{decode_line, encode_line, read_varint, write_varint};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Incremented whenever the layout of the event files changes
+pub static FORMAT_VERSION: u8 = 1;
+
+static MAGIC: &[u8; 6] = b"RPLLEV";
+static INDEX_MAGIC: &[u8; 6] = b"RPLLIX";
+static COMPRESSION_LEVEL: i32 = 3;
+pub(crate) const FILE_HEADER_LEN: u64 = 8;
+pub(crate) const BLOCK_HEADER_LEN: u64 = 32;
+const INDEX_ENTRY_LEN: u64 = 8 + BLOCK_HEADER_LEN;
+// Appended blocks are as small as the batch that was saved, they are merged into blocks of this size by compact_event_file
+pub(crate) const EVENTS_PER_BLOCK: usize = 4096;
+
+/// File layout:
+/// - Header: magic, version, event type
+/// - Blocks: compressed length, number of events, min/max event id, min/max timestamp, zstd compressed records
+/// - Record: event id, timestamp, length of the encoded line, encoded line
+pub fn event_file_path(instance_path: &str, event_type: u8) -> String {
+    format!("{}/{}.events", instance_path, event_type)
+}
+
+/// Index file layout:
+/// - Header: magic, version, event type
+/// - Entries: offset of the block, block header
+pub fn index_file_path(instance_path: &str, event_type: u8) -> String {
+    format!("{}/{}.index", instance_path, event_type)
+}
+
+/// The text format, one line per event
+pub fn legacy_event_file_path(instance_path: &str, event_type: u8) -> String {
+    format!("{}/{}", instance_path, event_type)
+}
+
+/// Appends the lines as one block. A block that was only partially written before is discarded.
+/// Files of another version are not touched.
+pub fn append_events(instance_path: &str, event_type: u8, lines: &[String]) -> io::Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let path = event_file_path(instance_path, event_type);
+    let index_path = index_file_path(instance_path, event_type);
+    let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path)?;
+    let (mut index, num_persisted) = match load_file_index(Some(&index_path), &mut file)? {
+        Some(loaded_index) => loaded_index,
+        None if file.metadata()?.len() >= FILE_HEADER_LEN => return Err(io::Error::new(io::ErrorKind::InvalidData, "Unknown event file format")),
+        None => {
+            file.set_len(0)?;
+            file.write_all(&file_header(event_type))?;
+            (Vec::new(), None)
+        },
+    };
+    let end = index.last().map(block_end).unwrap_or(FILE_HEADER_LEN);
+    let block = encode_block(lines)?;
+    file.set_len(end)?;
+    file.seek(SeekFrom::Start(end))?;
+    file.write_all(&block)?;
+    file.sync_data()?;
+
+    // Only written once the block is, hence the index never refers to a block that does not exist
+    index.push(decode_block_header(end, &block[..BLOCK_HEADER_LEN as usize]));
+    persist_index(&index_path, event_type, &index, num_persisted)
+}
+
+pub fn read_index(instance_path: &str, event_type: u8) -> io::Result<Vec<BlockIndex>> {
+    let mut file = File::open(event_file_path(instance_path, event_type))?;
+    read_file_index(Some(&index_file_path(instance_path, event_type)), &mut file)?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unknown event file format"))
+}
+
+pub fn read_events(instance_path: &str, event_type: u8) -> io::Result<Vec<(u32, String)>> {
+    read_events_where(instance_path, event_type, |_| true)
+}
+
+/// Returns the events of the blocks that satisfy the predicate in the text format.
+/// The index only skips whole blocks, hence the caller still has to filter the events.
+/// Files that were not migrated yet are read in full, followed by the events that were appended since.
+pub fn read_events_where(instance_path: &str, event_type: u8, predicate: impl Fn(&BlockIndex) -> bool) -> io::Result<Vec<(u32, String)>> {
+    let mut events = Vec::new();
+    let legacy_path = legacy_event_file_path(instance_path, event_type);
+    if Path::new(&legacy_path).exists() {
+        events = std::fs::read_to_string(legacy_path)?.lines().map(|line| (parse_id_and_timestamp(line).map(|(id, _)| id).unwrap_or(0), line.to_owned())).collect();
+    }
+
+    let path = event_file_path(instance_path, event_type);
+    if Path::new(&path).exists() {
+        events.append(&mut read_file_events(&path, Some(&index_file_path(instance_path, event_type)), predicate)?);
+    }
+    Ok(events)
+}
+
+pub(crate) fn read_file_events(path: &str, index_path: Option<&str>, predicate: impl Fn(&BlockIndex) -> bool) -> io::Result<Vec<(u32, String)>> {
+    let mut file = File::open(path)?;
+    let index = read_file_index(index_path, &mut file)?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unknown event file format"))?;
+
+    let mut events = Vec::with_capacity(index.iter().filter(|block| predicate(block)).map(|block| block.num_events as usize).sum());
+    for block in index.iter().filter(|block| predicate(block)) {
+        let mut compressed = vec![0; block.compressed_len as usize];
+        file.seek(SeekFrom::Start(block.offset + BLOCK_HEADER_LEN))?;
+        file.read_exact(&mut compressed)?;
+        let records = zstd::decode_all(&compressed[..])?;
+
+        let mut cursor = 0;
+        while cursor < records.len() {
+            let (id, line) = decode_record(&records, &mut cursor).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Corrupt event block"))?;
+            events.push((id, line));
+        }
+    }
+    Ok(events)
+}
+
+/// None if the file is not an event file of this version.
+/// Reading stops at the first block that was not written completely.
+pub(crate) fn read_file_index(index_path: Option<&str>, file: &mut File) -> io::Result<Option<Vec<BlockIndex>>> {
+    Ok(load_file_index(index_path, file)?.map(|(index, _)| index))
+}
+
+/// Starts with the persisted index and only scans the blocks that were appended after it, e.g. if the process crashed in between.
+/// Also returns the number of persisted entries, which is None if the persisted index is missing or does not match the file.
+fn load_file_index(index_path: Option<&str>, file: &mut File) -> io::Result<Option<(Vec<BlockIndex>, Option<usize>)>> {
+    let file_len = file.metadata()?.len();
+    if file_len < FILE_HEADER_LEN {
+        return Ok(None);
+    }
+
+    let mut header = [0; FILE_HEADER_LEN as usize];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut header)?;
+    if &header[..6] != MAGIC || header[6] != FORMAT_VERSION {
+        return Ok(None);
+    }
+
+    let persisted_index = match index_path {
+        Some(index_path) => read_persisted_index(index_path, file, file_len)?,
+        None => None,
+    };
+    let num_persisted = persisted_index.as_ref().map(|index| index.len());
+    let mut index = persisted_index.unwrap_or_default();
+    let mut offset = index.last().map(block_end).unwrap_or(FILE_HEADER_LEN);
+    let mut block_header = [0; BLOCK_HEADER_LEN as usize];
+    while offset + BLOCK_HEADER_LEN <= file_len {
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut block_header)?;
+        let block = decode_block_header(offset, &block_header);
+        let next_offset = block_end(&block);
+        if next_offset > file_len {
+            break;
+        }
+        index.push(block);
+        offset = next_offset;
+    }
+    Ok(Some((index, num_persisted)))
+}
+
+/// None if there is no index or it does not match the event file, which is then scanned instead.
+/// Entries that were only partially written are ignored.
+fn read_persisted_index(index_path: &str, file: &mut File, file_len: u64) -> io::Result<Option<Vec<BlockIndex>>> {
+    let content = match std::fs::read(index_path) {
+        Ok(content) => content,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    if content.len() < FILE_HEADER_LEN as usize || &content[..6] != INDEX_MAGIC || content[6] != FORMAT_VERSION {
+        return Ok(None);
+    }
+
+    let mut index: Vec<BlockIndex> = Vec::new();
+    for entry in content[FILE_HEADER_LEN as usize..].chunks_exact(INDEX_ENTRY_LEN as usize) {
+        let block = decode_block_header(read_u64(&entry[0..8]), &entry[8..]);
+        let expected_offset = index.last().map(block_end).unwrap_or(FILE_HEADER_LEN);
+        if block.offset != expected_offset || block_end(&block) > file_len {
+            return Ok(None);
+        }
+        index.push(block);
+    }
+
+    // The event file may have been rewritten since, e.g. by a migration
+    if let Some(last_block) = index.last() {
+        let mut block_header = [0; BLOCK_HEADER_LEN as usize];
+        file.seek(SeekFrom::Start(last_block.offset))?;
+        file.read_exact(&mut block_header)?;
+        if decode_block_header(last_block.offset, &block_header) != *last_block {
+            return Ok(None);
+        }
+    }
+    Ok(Some(index))
+}
+
+/// Appends the entries that are not persisted yet or, if the persisted index is unusable, replaces it
+pub(crate) fn persist_index(index_path: &str, event_type: u8, index: &[BlockIndex], num_persisted: Option<usize>) -> io::Result<()> {
+    match num_persisted {
+        Some(num_persisted) => {
+            let mut index_file = OpenOptions::new().write(true).open(index_path)?;
+            let persisted_len = FILE_HEADER_LEN + num_persisted as u64 * INDEX_ENTRY_LEN;
+            index_file.set_len(persisted_len)?;
+            index_file.seek(SeekFrom::Start(persisted_len))?;
+            index_file.write_all(&encode_index_entries(&index[num_persisted..]))?;
+            index_file.sync_data()
+        },
+        None => {
+            let mut content = INDEX_MAGIC.to_vec();
+            content.push(FORMAT_VERSION);
+            content.push(event_type);
+            content.append(&mut encode_index_entries(index));
+            let temporary_path = format!("{}.tmp", index_path);
+            std::fs::write(&temporary_path, &content)?;
+            std::fs::rename(&temporary_path, index_path)
+        },
+    }
+}
+
+/// Replaces the event file by a rewritten one, e.g. a migrated or compacted one.
+/// The index is removed first, so it never refers to the blocks of the replaced file.
+pub(crate) fn replace_event_file(instance_path: &str, event_type: u8, temporary_path: &str) -> io::Result<()> {
+    let path = event_file_path(instance_path, event_type);
+    let index_path = index_file_path(instance_path, event_type);
+    if let Err(err) = std::fs::remove_file(&index_path) {
+        if err.kind() != io::ErrorKind::NotFound {
+            return Err(err);
+        }
+    }
+    std::fs::rename(temporary_path, &path)?;
+
+    let mut file = File::open(&path)?;
+    let index = read_file_index(None, &mut file)?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unknown event file format"))?;
+    persist_index(&index_path, event_type, &index, None)
+}
+
+fn encode_index_entries(index: &[BlockIndex]) -> Vec<u8> {
+    let mut entries = Vec::with_capacity(index.len() * INDEX_ENTRY_LEN as usize);
+    for block in index {
+        entries.extend_from_slice(&block.offset.to_le_bytes());
+        entries.extend_from_slice(&encode_block_header(block));
+    }
+    entries
+}
+
+fn block_end(block: &BlockIndex) -> u64 {
+    block.offset + BLOCK_HEADER_LEN + block.compressed_len as u64
+}
+
+pub(crate) fn file_header(event_type: u8) -> Vec<u8> {
+    let mut header = MAGIC.to_vec();
+    header.push(FORMAT_VERSION);
+    header.push(event_type);
+    header
+}
+
+pub(crate) fn encode_block(lines: &[String]) -> io::Result<Vec<u8>> {
+    let (mut min_id, mut max_id, mut min_timestamp, mut max_timestamp) = (u32::MAX, 0, u64::MAX, 0);
+    let mut records = Vec::new();
+    let mut encoded_line = Vec::new();
+    for line in lines {
+        let (id, timestamp) = parse_id_and_timestamp(line).unwrap_or((0, 0));
+        min_id = min_id.min(id);
+        max_id = max_id.max(id);
+        min_timestamp = min_timestamp.min(timestamp);
+        max_timestamp = max_timestamp.max(timestamp);
+
+        encoded_line.clear();
+        encode_line(line, &mut encoded_line);
+        write_varint(id as u64, &mut records);
+        write_varint(timestamp, &mut records);
+        write_varint(encoded_line.len() as u64, &mut records);
+        records.extend_from_slice(&encoded_line);
+    }
+    let compressed = zstd::encode_all(&records[..], COMPRESSION_LEVEL)?;
+
+    let mut block = encode_block_header(&BlockIndex {
+        offset: 0,
+        compressed_len: compressed.len() as u32,
+        num_events: lines.len() as u32,
+        min_id,
+        max_id,
+        min_timestamp,
+        max_timestamp,
+    })
+    .to_vec();
+    block.extend_from_slice(&compressed);
+    Ok(block)
+}
+
+fn encode_block_header(block: &BlockIndex) -> [u8; BLOCK_HEADER_LEN as usize] {
+    let mut header = [0; BLOCK_HEADER_LEN as usize];
+    header[0..4].copy_from_slice(&block.compressed_len.to_le_bytes());
+    header[4..8].copy_from_slice(&block.num_events.to_le_bytes());
+    header[8..12].copy_from_slice(&block.min_id.to_le_bytes());
+    header[12..16].copy_from_slice(&block.max_id.to_le_bytes());
+    header[16..24].copy_from_slice(&block.min_timestamp.to_le_bytes());
+    header[24..32].copy_from_slice(&block.max_timestamp.to_le_bytes());
+    header
+}
+
+fn decode_block_header(offset: u64, header: &[u8]) -> BlockIndex {
+    BlockIndex {
+        offset,
+        compressed_len: read_u32(&header[0..4]),
+        num_events: read_u32(&header[4..8]),
+        min_id: read_u32(&header[8..12]),
+        max_id: read_u32(&header[12..16]),
+        min_timestamp: read_u64(&header[16..24]),
+        max_timestamp: read_u64(&header[24..32]),
+    }
+}
+
+fn decode_record(records: &[u8], cursor: &mut usize) -> Option<(u32, String)> {
+    let id = read_varint(records, cursor)? as u32;
+    let _timestamp = read_varint(records, cursor)?;
+    let len = read_varint(records, cursor)? as usize;
+    let encoded_line = records.get(*cursor..*cursor + len)?;
+    *cursor += len;
+    Some((id, decode_line(encoded_line, &mut 0)?))
+}
+
+// Every line starts with: [id,timestamp,
+fn parse_id_and_timestamp(line: &str) -> Option<(u32, u64)> {
+    let mut segments = line.get(1..)?.splitn(3, ',');
+    let id = segments.next()?.parse().ok()?;
+    let timestamp = segments.next()?.trim_end_matches(']').parse().ok()?;
+    Some((id, timestamp))
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut buffer = [0; 4];
+    buffer.copy_from_slice(bytes);
+    u32::from_le_bytes(buffer)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut buffer = [0; 8];
+    buffer.copy_from_slice(bytes);
+    u64::from_le_bytes(buffer)
+}
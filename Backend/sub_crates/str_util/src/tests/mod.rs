@@ -1,3 +1,4 @@
 mod hash;
+mod password;
 mod random;
 mod strformat;
@@ -0,0 +1,30 @@
+use crate::{password, sha3};
+
+#[test]
+fn hash_and_verify() {
+    let hash = password::hash("Test123!§$%&");
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(password::verify("Test123!§$%&", &hash, ""));
+    assert!(!password::verify("Test123", &hash, ""));
+    assert!(!password::needs_rehash(&hash));
+}
+
+#[test]
+fn hash_is_salted() {
+    assert_ne!(password::hash("Test123"), password::hash("Test123"));
+}
+
+#[test]
+fn verify_legacy_hash() {
+    let hash = sha3::hash(&["Test123", "salt"]);
+    assert!(password::verify("Test123", &hash, "salt"));
+    assert!(!password::verify("Test123", &hash, "other salt"));
+    assert!(password::needs_rehash(&hash));
+}
+
+#[test]
+fn verify_outdated_parameters() {
+    let hash = "$argon2id$v=19$m=4096,t=3,p=1$c29tZXNhbHQ$4ONbr2+oVIxBmJ/hNKoDUwc5Q5mmwD8GGgtTNIq4FWw";
+    assert!(password::needs_rehash(hash));
+    assert!(!password::verify("password", "$argon2id$malformed", ""));
+}
@@ -1,3 +1,4 @@
+pub mod password;
 pub mod random;
 pub mod sha3;
 pub mod strformat;
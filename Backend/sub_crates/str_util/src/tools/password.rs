@@ -0,0 +1,45 @@
+use argon2::{Config, ThreadMode, Variant, Version};
+
+use crate::random;
+use crate::sha3;
+
+// Parameters are part of the encoded hash, e.g. "$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>"
+// Hashes that were created with other parameters are re-hashed on the next login
+static MEMORY_COST: u32 = 19456;
+static TIME_COST: u32 = 2;
+static LANES: u32 = 1;
+
+fn config() -> Config<'static> {
+    Config {
+        variant: Variant::Argon2id,
+        version: Version::Version13,
+        mem_cost: MEMORY_COST,
+        time_cost: TIME_COST,
+        lanes: LANES,
+        thread_mode: ThreadMode::Sequential,
+        secret: &[],
+        ad: &[],
+        hash_length: 32,
+    }
+}
+
+fn prefix() -> String {
+    format!("$argon2id$v=19$m={},t={},p={}$", MEMORY_COST, TIME_COST, LANES)
+}
+
+pub fn hash(password: &str) -> String {
+    let salt = random::alphanumeric(16);
+    argon2::hash_encoded(password.as_bytes(), salt.as_bytes(), &config()).unwrap()
+}
+
+/// Hashes without an algorithm prefix are SHA3 hashes of the password and the legacy salt
+pub fn verify(password: &str, hash: &str, legacy_salt: &str) -> bool {
+    if hash.starts_with("$argon2") {
+        return argon2::verify_encoded(hash, password.as_bytes()).unwrap_or(false);
+    }
+    sha3::hash(&[password, legacy_salt]) == hash
+}
+
+pub fn needs_rehash(hash: &str) -> bool {
+    !hash.starts_with(&prefix())
+}
@@ -1,5 +1,4 @@
 pub enum PasswordFailure {
     TooFewCharacters,
-    InvalidCharacters,
     Pwned(u64),
 }
@@ -19,6 +19,13 @@ mod tests {
         assert!(valid_password(pass).is_err());
     }
 
+    #[test]
+    fn password_with_special_characters() {
+        dotenv().ok();
+        let pass = "§$%&/()=?Password123456Password123456";
+        assert!(valid_password(pass).is_ok());
+    }
+
     #[test]
     fn password_is_secure_enough() {
         dotenv().ok();
@@ -11,10 +11,6 @@ pub fn valid_password(input: &str) -> Result<(), PasswordFailure> {
             .unwrap();
     }
 
-    if !input.chars().all(char::is_alphanumeric) {
-        return Err(PasswordFailure::InvalidCharacters);
-    }
-
     if input.chars().count() < 12 {
         return Err(PasswordFailure::TooFewCharacters);
     }
@@ -0,0 +1,69 @@
+# Generates the patch that backfills the effect index and the aura period of data_spell_effect from the Spell.dbc of an expansion.
+# Usage: python3 spell_effect_backfill.py <expansion_id> <path to Spell.dbc> > ../patches/<number>_data_spell_effect_backfill_<expansion>.sql
+#
+# Stored effects are matched to the effects of the DBC by their upper points, in order of their occurrence.
+# Effects that do not match keep their effect index and aura period.
+import struct
+import sys
+
+# Field offsets of Effect, EffectDieSides, EffectBasePoints and EffectAmplitude per expansion
+FIELD_OFFSETS = {
+    1: (61, 64, 76, 94),
+    2: (65, 68, 80, 98),
+    3: (71, 74, 80, 98),
+}
+MAX_EFFECTS = 3
+BATCH_SIZE = 1000
+
+
+def read_dbc(path):
+    with open(path, "rb") as file:
+        content = file.read()
+    magic, record_count, field_count, record_size, _string_block_size = struct.unpack_from("<4s4I", content, 0)
+    if magic != b"WDBC" or record_size != field_count * 4:
+        raise ValueError("Not a WDBC file with 4 byte fields: " + path)
+    for record_index in range(record_count):
+        yield struct.unpack_from("<%di" % field_count, content, 20 + record_index * record_size)
+
+
+def read_spell_effects(expansion_id, path):
+    effect, die_sides, base_points, amplitude = FIELD_OFFSETS[expansion_id]
+    for record in read_dbc(path):
+        for index in range(MAX_EFFECTS):
+            if record[effect + index] == 0:
+                continue
+            points_upper = record[base_points + index] + record[die_sides + index]
+            aura_period = max(record[amplitude + index], 0)
+            yield record[0], index + 1, points_upper, aura_period
+
+
+def main():
+    if len(sys.argv) != 3 or int(sys.argv[1]) not in FIELD_OFFSETS:
+        sys.exit("Usage: spell_effect_backfill.py <expansion_id 1-3> <path to Spell.dbc>")
+    expansion_id = int(sys.argv[1])
+    spell_effects = list(read_spell_effects(expansion_id, sys.argv[2]))
+
+    print("CREATE TEMPORARY TABLE `main`.`tmp_spell_effect_dbc` (")
+    print("  `spell_id` INT(11) UNSIGNED NOT NULL,")
+    print("  `effect_index` TINYINT(3) UNSIGNED NOT NULL,")
+    print("  `points_upper` INT(11) NOT NULL,")
+    print("  `aura_period` INT(11) UNSIGNED NOT NULL,")
+    print("  PRIMARY KEY (`spell_id`, `effect_index`)")
+    print(");")
+    for start in range(0, len(spell_effects), BATCH_SIZE):
+        values = ",\n".join("(%d, %d, %d, %d)" % spell_effect for spell_effect in spell_effects[start:start + BATCH_SIZE])
+        print("INSERT INTO `main`.`tmp_spell_effect_dbc` (`spell_id`, `effect_index`, `points_upper`, `aura_period`) VALUES\n%s;" % values)
+    print("""
+UPDATE `main`.`data_spell_effect` A
+JOIN (SELECT `id`, ROW_NUMBER() OVER (PARTITION BY `spell_id`, `points_upper` ORDER BY `id`) AS `occurrence` FROM `main`.`data_spell_effect` WHERE `expansion_id` = %d) B
+	ON A.`id` = B.`id`
+JOIN (SELECT `spell_id`, `effect_index`, `points_upper`, `aura_period`, ROW_NUMBER() OVER (PARTITION BY `spell_id`, `points_upper` ORDER BY `effect_index`) AS `occurrence` FROM `main`.`tmp_spell_effect_dbc`) C
+	ON A.`spell_id` = C.`spell_id` AND A.`points_upper` = C.`points_upper` AND B.`occurrence` = C.`occurrence`
+SET A.`effect_index` = C.`effect_index`, A.`aura_period` = C.`aura_period`
+WHERE A.`expansion_id` = %d;
+
+DROP TEMPORARY TABLE `main`.`tmp_spell_effect_dbc`;""" % (expansion_id, expansion_id))
+
+
+if __name__ == "__main__":
+    main()
@@ -1,6 +1,8 @@
 import {Component} from "@angular/core";
 import {ConfirmService} from "../../service/confirm";
 import {ActivatedRoute, Router} from "@angular/router";
+import {FormFailure} from "../../../../material/form_failure";
+import {APIFailure} from "../../../../domain_value/api_failure";
 
 @Component({
     selector: "Confirm",
@@ -8,6 +10,10 @@ import {ActivatedRoute, Router} from "@angular/router";
     styleUrls: ["./confirm.scss"]
 })
 export class ConfirmComponent {
+    forgotId: string;
+    password: string = '';
+    formFailure: FormFailure = FormFailure.empty();
+    disableSubmit: boolean = false;
 
     constructor(private confirmService: ConfirmService,
                 private routerService: Router,
@@ -18,7 +24,7 @@ export class ConfirmComponent {
     private processParams(params): void {
         switch (params.type) {
             case "forgot":
-                this.confirmService.forgot(params.confirm_id);
+                this.forgotId = params.confirm_id;
                 break;
             case "update_mail":
                 this.confirmService.update_mail(params.confirm_id);
@@ -35,4 +41,13 @@ export class ConfirmComponent {
         }
     }
 
+    on_submit(): void {
+        this.disableSubmit = true;
+        this.confirmService.forgot(this.forgotId, this.password, (api_failure) => this.on_failure(api_failure));
+    }
+
+    on_failure(api_failure: APIFailure): void {
+        this.formFailure = FormFailure.from(api_failure, 523, 524);
+        this.disableSubmit = false;
+    }
 }
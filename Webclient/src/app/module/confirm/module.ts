@@ -4,13 +4,21 @@ import {ConfirmComponent} from "./component/confirm/confirm";
 import {CommonModule} from "@angular/common";
 import {ConfirmRouting} from "./routing";
 import {ConfirmService} from "./service/confirm";
+import {PasswordInputModule} from "src/app/template/input/password_input/module";
+import {ConfirmButtonModule} from "src/app/template/button/confirm_button/module";
+import {FormsModule} from "@angular/forms";
+import {FormValidDirectiveModule} from "../../directive/form_valid/module";
 
 @NgModule({
     declarations: [ConfirmComponent],
     imports: [
         CommonModule,
         TranslateModule,
-        ConfirmRouting
+        ConfirmRouting,
+        PasswordInputModule,
+        ConfirmButtonModule,
+        FormsModule,
+        FormValidDirectiveModule
     ],
     exports: [ConfirmComponent],
     providers: [ConfirmService]
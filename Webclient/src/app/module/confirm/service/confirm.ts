@@ -40,11 +40,15 @@ export class ConfirmService {
         }, () => this.routerService.navigate(["/"]));
     }
 
-    forgot(confirm_id: string): void {
-        this.apiService.get(ConfirmService.URL_CONFIRM_FORGOT + "/" + confirm_id, (api_token) => {
+    forgot(confirm_id: string, password: string, on_failure: any): void {
+        this.apiService.post(ConfirmService.URL_CONFIRM_FORGOT + "/" + confirm_id, password, (api_token) => {
             this.settingsService.set("API_TOKEN", api_token);
             this.notificationService.propagate(Severity.Success, "serverResponses.200");
-            this.routerService.navigate(["/account/password"]);
-        }, () => this.routerService.navigate(["/"]));
+            this.routerService.navigate(["/account"]);
+        }, (api_failure) => {
+            if (api_failure.status === 530)
+                this.routerService.navigate(["/"]);
+            else on_failure.call(on_failure, api_failure);
+        });
     }
 }